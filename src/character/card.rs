@@ -1,6 +1,16 @@
+use super::lorebook::{CharacterBook, LorebookEntry};
+use crate::core::macros::{expand, MacroContext};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
 
-/// Character card following the v2 specification
+/// Card spec identifier for v2 cards.
+pub const SPEC_V2: &str = "chara_card_v2";
+
+/// Card spec identifier for v3 cards.
+pub const SPEC_V3: &str = "chara_card_v3";
+
+/// Character card following the v2 or v3 specification
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CharacterCard {
     pub spec: String,
@@ -8,7 +18,7 @@ pub struct CharacterCard {
     pub data: CharacterData,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct CharacterData {
     pub name: String,
     pub description: String,
@@ -32,71 +42,196 @@ pub struct CharacterData {
     pub creator: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub character_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub character_book: Option<CharacterBook>,
+
+    // V3 fields
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_only_greetings: Option<Vec<String>>,
+    /// Creator notes keyed by ISO 639-1 language code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creator_notes_multilingual: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assets: Option<Vec<CardAsset>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<Vec<String>>,
+    /// Unix timestamp (seconds).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creation_date: Option<i64>,
+    /// Unix timestamp (seconds).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modification_date: Option<i64>,
+
+    /// Application data such as SillyTavern's `depth_prompt`,
+    /// `talkativeness`, or regex scripts; kept as-is.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub extensions: Map<String, Value>,
+    /// Keys this version does not know, kept so saving a card doesn't drop
+    /// them.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Image or other file attached to a v3 card.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CardAsset {
+    /// `icon`, `background`, `user_icon`, `emotion`, or a custom type.
+    #[serde(rename = "type")]
+    pub asset_type: String,
+    /// `embeded://<path>` for files inside a CHARX archive,
+    /// `ccdefault:` for the container's default image, or a URL.
+    pub uri: String,
+    pub name: String,
+    pub ext: String,
+}
+
+impl CardAsset {
+    /// Path inside a CHARX archive for `embeded://` URIs.
+    pub fn embedded_path(&self) -> Option<&str> {
+        self.uri
+            .strip_prefix("embeded://")
+            .or_else(|| self.uri.strip_prefix("embedded://"))
+    }
 }
 
 impl CharacterCard {
-    /// Build the system prompt from character data with persona/character substitutions
+    pub fn is_v3(&self) -> bool {
+        self.spec == SPEC_V3
+    }
+
+    /// Name used for `{{char}}`: the v3 `nickname` when set, otherwise `name`.
+    pub fn display_name(&self) -> &str {
+        self.data
+            .nickname
+            .as_deref()
+            .map(str::trim)
+            .filter(|nickname| !nickname.is_empty())
+            .unwrap_or(&self.data.name)
+    }
+
+    /// Build the system prompt from character data with macros expanded
+    ///
+    /// Activated lorebook entries are placed before or after the character
+    /// definition according to their `position`.
     pub fn build_system_prompt_with_substitutions(
         &self,
-        user_name: Option<&str>,
-        char_name: Option<&str>,
+        macros: &MacroContext,
+        lore: &[&LorebookEntry],
     ) -> String {
         let mut prompt = String::new();
 
         if let Some(system_prompt) = &self.data.system_prompt {
-            let substituted = self.apply_substitutions(system_prompt, user_name, char_name);
+            let substituted = self.apply_substitutions(system_prompt, macros);
             prompt.push_str(&substituted);
             prompt.push_str("\n\n");
         }
 
-        let char_display_name = char_name.unwrap_or(&self.data.name);
+        let (before_char, after_char): (Vec<&LorebookEntry>, Vec<&LorebookEntry>) =
+            lore.iter().partition(|entry| entry.is_before_char());
+        if let Some(block) = self.lore_block(&before_char, macros) {
+            prompt.push_str(&block);
+            prompt.push_str("\n\n");
+        }
+
+        let char_display_name = macros.char.unwrap_or(self.display_name());
         prompt.push_str(&format!("Character: {}\n", char_display_name));
         prompt.push_str(&format!("Description: {}\n", self.data.description));
         prompt.push_str(&format!("Personality: {}\n", self.data.personality));
         prompt.push_str(&format!("Scenario: {}\n", self.data.scenario));
 
         if !self.data.mes_example.is_empty() {
-            let substituted_example =
-                self.apply_substitutions(&self.data.mes_example, user_name, char_name);
+            let substituted_example = self.apply_substitutions(&self.data.mes_example, macros);
             prompt.push_str(&format!("\nExample dialogue:\n{}\n", substituted_example));
         }
 
+        if let Some(block) = self.lore_block(&after_char, macros) {
+            prompt.push('\n');
+            prompt.push_str(&block);
+            prompt.push('\n');
+        }
+
         prompt
     }
 
-    /// Get the first greeting message with persona/character substitutions
-    pub fn get_greeting_with_substitutions(
+    /// Lorebook entries activated by the most recent messages (oldest first).
+    pub fn active_lore_entries(&self, recent_messages: &[&str]) -> Vec<&LorebookEntry> {
+        self.data
+            .character_book
+            .as_ref()
+            .map(|book| book.activate(recent_messages))
+            .unwrap_or_default()
+    }
+
+    fn lore_block(&self, entries: &[&LorebookEntry], macros: &MacroContext) -> Option<String> {
+        let contents: Vec<String> = entries
+            .iter()
+            .map(|entry| entry.body())
+            .filter(|content| !content.is_empty())
+            .map(|content| self.apply_substitutions(content, macros))
+            .collect();
+        if contents.is_empty() {
+            return None;
+        }
+        Some(format!("World info:\n{}", contents.join("\n")))
+    }
+
+    /// Opening greetings: `first_mes` followed by the alternates, skipping
+    /// blank ones.
+    pub fn greetings(&self) -> Vec<&str> {
+        std::iter::once(self.data.first_mes.as_str())
+            .chain(
+                self.data
+                    .alternate_greetings
+                    .iter()
+                    .flatten()
+                    .map(String::as_str),
+            )
+            .filter(|greeting| !greeting.trim().is_empty())
+            .collect()
+    }
+
+    /// Whether [`CharacterCard::greetings`] starts with `first_mes`.
+    pub fn has_first_message(&self) -> bool {
+        !self.data.first_mes.trim().is_empty()
+    }
+
+    /// Get the first greeting message with macros expanded
+    pub fn get_greeting_with_substitutions(&self, macros: &MacroContext) -> String {
+        self.apply_substitutions(&self.data.first_mes, macros)
+    }
+
+    /// Get the greeting at `index` in [`CharacterCard::greetings`] with
+    /// macros expanded
+    pub fn get_greeting_at_with_substitutions(
         &self,
-        user_name: Option<&str>,
-        char_name: Option<&str>,
-    ) -> String {
-        self.apply_substitutions(&self.data.first_mes, user_name, char_name)
+        index: usize,
+        macros: &MacroContext,
+    ) -> Option<String> {
+        self.greetings()
+            .get(index)
+            .map(|greeting| self.apply_substitutions(greeting, macros))
     }
 
-    /// Get post-history instructions with persona/character substitutions
+    /// Get post-history instructions with macros expanded
     pub fn get_post_history_instructions_with_substitutions(
         &self,
-        user_name: Option<&str>,
-        char_name: Option<&str>,
+        macros: &MacroContext,
     ) -> Option<String> {
         self.data
             .post_history_instructions
             .as_ref()
-            .map(|instructions| self.apply_substitutions(instructions, user_name, char_name))
+            .map(|instructions| self.apply_substitutions(instructions, macros))
     }
 
-    /// Apply {{user}} and {{char}} substitutions to text
-    fn apply_substitutions(
-        &self,
-        text: &str,
-        user_name: Option<&str>,
-        char_name: Option<&str>,
-    ) -> String {
-        let char_replacement = char_name.unwrap_or(&self.data.name);
-        let user_replacement = user_name.unwrap_or("Anon");
-
-        text.replace("{{char}}", char_replacement)
-            .replace("{{user}}", user_replacement)
+    /// Expand macros in card text; `{{user}}` defaults to "Anon" and
+    /// `{{char}}` to the card's display name.
+    fn apply_substitutions(&self, text: &str, macros: &MacroContext) -> String {
+        expand(
+            text,
+            &macros.with_default_names("Anon", self.display_name()),
+        )
     }
 }
 
@@ -122,6 +257,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         }
     }
@@ -160,6 +296,32 @@ mod tests {
         assert_eq!(card, deserialized);
     }
 
+    #[test]
+    fn test_extensions_and_unknown_keys_round_trip() {
+        let json = serde_json::json!({
+            "spec": "chara_card_v2",
+            "spec_version": "2.0",
+            "data": {
+                "name": "Seraphina",
+                "description": "",
+                "personality": "",
+                "scenario": "",
+                "first_mes": "Hi",
+                "mes_example": "",
+                "extensions": {
+                    "talkativeness": "0.5",
+                    "depth_prompt": { "prompt": "Stay calm.", "depth": 4 }
+                },
+                "avatar": "none"
+            }
+        });
+        let card: CharacterCard = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(card.data.extensions["talkativeness"], "0.5");
+        assert_eq!(card.data.other["avatar"], "none");
+
+        assert_eq!(serde_json::to_value(&card).unwrap(), json);
+    }
+
     #[test]
     fn test_optional_fields_not_serialized_when_none() {
         let card = create_test_card();
@@ -181,24 +343,62 @@ mod tests {
         card.data.first_mes = "Hello {{user}}! I'm {{char}}, nice to meet you!".to_string();
 
         // Test with no substitutions (defaults)
-        let greeting_default = card.get_greeting_with_substitutions(None, None);
+        let greeting_default = card.get_greeting_with_substitutions(&MacroContext::default());
         assert_eq!(greeting_default, "Hello Anon! I'm Alice, nice to meet you!");
 
         // Test with custom user and character names
-        let greeting_custom = card.get_greeting_with_substitutions(Some("Bob"), Some("Assistant"));
+        let greeting_custom = card
+            .get_greeting_with_substitutions(&MacroContext::names(Some("Bob"), Some("Assistant")));
         assert_eq!(
             greeting_custom,
             "Hello Bob! I'm Assistant, nice to meet you!"
         );
     }
 
+    #[test]
+    fn test_greetings_include_non_empty_alternates() {
+        let mut card = create_test_card();
+        card.data.alternate_greetings = Some(vec![
+            "Hi {{user}}, {{char}} here.".to_string(),
+            "   ".to_string(),
+            "Welcome back.".to_string(),
+        ]);
+
+        assert_eq!(
+            card.greetings(),
+            vec![
+                "Hello! How can I help you today?",
+                "Hi {{user}}, {{char}} here.",
+                "Welcome back."
+            ]
+        );
+        assert_eq!(
+            card.get_greeting_at_with_substitutions(1, &MacroContext::names(Some("Bob"), None)),
+            Some("Hi Bob, Alice here.".to_string())
+        );
+        assert_eq!(
+            card.get_greeting_at_with_substitutions(3, &MacroContext::default()),
+            None
+        );
+
+        card.data.first_mes = " ".to_string();
+        assert!(!card.has_first_message());
+        assert_eq!(
+            card.greetings(),
+            vec!["Hi {{user}}, {{char}} here.", "Welcome back."]
+        );
+    }
+
     #[test]
     fn test_system_prompt_with_substitutions() {
         let mut card = create_test_card();
         card.data.system_prompt = Some("You are {{char}} talking to {{user}}.".to_string());
         card.data.mes_example = "{{user}}: Hi\n{{char}}: Hello {{user}}!".to_string();
 
-        let prompt = card.build_system_prompt_with_substitutions(Some("Alice"), Some("Bot"));
+        let prompt = card.build_system_prompt_with_substitutions(
+            &MacroContext::names(Some("Alice"), Some("Bot")),
+            &[],
+        );
 
         assert!(prompt.contains("You are Bot talking to Alice."));
         assert!(prompt.contains("Character: Bot"));
@@ -211,8 +411,9 @@ mod tests {
         card.data.post_history_instructions =
             Some("Remember that {{user}} is talking to {{char}}.".to_string());
 
-        let instructions =
-            card.get_post_history_instructions_with_substitutions(Some("John"), Some("AI"));
+        let instructions = card.get_post_history_instructions_with_substitutions(
+            &MacroContext::names(Some("John"), Some("AI")),
+        );
         assert_eq!(
             instructions,
             Some("Remember that John is talking to AI.".to_string())
@@ -220,8 +421,9 @@ mod tests {
 
         // Test with None
         card.data.post_history_instructions = None;
-        let instructions_none =
-            card.get_post_history_instructions_with_substitutions(Some("John"), Some("AI"));
+        let instructions_none = card.get_post_history_instructions_with_substitutions(
+            &MacroContext::names(Some("John"), Some("AI")),
+        );
         assert_eq!(instructions_none, None);
     }
 }
@@ -0,0 +1,256 @@
+//! Group chats: several character cards sharing one conversation.
+//!
+//! Each reply comes from one member, chosen by the group's [`TurnOrder`].
+//! The chosen member's card drives the request exactly as a single active
+//! character would (system prompt, lorebook, `{{char}}`), and its replies
+//! are stored with a `Name: ` prefix so both the transcript and the model
+//! can tell the speakers apart.
+
+use crate::character::card::CharacterCard;
+
+/// How the next speaker is chosen after a user message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TurnOrder {
+    /// Members take turns in the order they joined.
+    #[default]
+    RoundRobin,
+    /// The member named in the user's message replies; round-robin otherwise.
+    Mention,
+    /// The member last picked with `/speak` keeps replying.
+    Manual,
+}
+
+impl TurnOrder {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "round-robin" | "roundrobin" | "rr" => Some(TurnOrder::RoundRobin),
+            "mention" | "mentions" => Some(TurnOrder::Mention),
+            "manual" => Some(TurnOrder::Manual),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TurnOrder::RoundRobin => "round-robin",
+            TurnOrder::Mention => "mention",
+            TurnOrder::Manual => "manual",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GroupChat {
+    members: Vec<CharacterCard>,
+    pub turn_order: TurnOrder,
+    last_speaker: Option<usize>,
+}
+
+impl GroupChat {
+    pub fn new(members: Vec<CharacterCard>) -> Self {
+        Self {
+            members,
+            turn_order: TurnOrder::default(),
+            last_speaker: None,
+        }
+    }
+
+    pub fn members(&self) -> &[CharacterCard] {
+        &self.members
+    }
+
+    pub fn member(&self, index: usize) -> Option<&CharacterCard> {
+        self.members.get(index)
+    }
+
+    /// Index of the member whose name or nickname matches, ignoring case.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        let name = name.trim();
+        self.members.iter().position(|card| {
+            card.data.name.eq_ignore_ascii_case(name)
+                || card.display_name().eq_ignore_ascii_case(name)
+        })
+    }
+
+    pub fn add(&mut self, card: CharacterCard) -> Result<(), String> {
+        if self.position(&card.data.name).is_some() {
+            return Err(format!("{} is already in the group.", card.data.name));
+        }
+        self.members.push(card);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<CharacterCard, String> {
+        let index = self
+            .position(name)
+            .ok_or_else(|| format!("{} is not in the group.", name.trim()))?;
+        self.last_speaker = match self.last_speaker {
+            Some(last) if last == index => None,
+            Some(last) if last > index => Some(last - 1),
+            other => other,
+        };
+        Ok(self.members.remove(index))
+    }
+
+    pub fn last_speaker(&self) -> Option<usize> {
+        self.last_speaker
+    }
+
+    pub fn record_speaker(&mut self, index: usize) {
+        if index < self.members.len() {
+            self.last_speaker = Some(index);
+        }
+    }
+
+    /// Picks who replies to `user_message`. Returns `None` for an empty group.
+    pub fn next_speaker(&self, user_message: Option<&str>) -> Option<usize> {
+        if self.members.is_empty() {
+            return None;
+        }
+        let round_robin = self
+            .last_speaker
+            .map(|last| (last + 1) % self.members.len())
+            .unwrap_or(0);
+        match self.turn_order {
+            TurnOrder::RoundRobin => Some(round_robin),
+            TurnOrder::Mention => Some(
+                user_message
+                    .and_then(|message| self.first_mentioned(message))
+                    .unwrap_or(round_robin),
+            ),
+            TurnOrder::Manual => Some(self.last_speaker.unwrap_or(0)),
+        }
+    }
+
+    /// The member mentioned earliest in `message`, matched as whole words.
+    fn first_mentioned(&self, message: &str) -> Option<usize> {
+        let lower = message.to_lowercase();
+        self.members
+            .iter()
+            .enumerate()
+            .filter_map(|(index, card)| {
+                [card.data.name.as_str(), card.display_name()]
+                    .iter()
+                    .filter_map(|name| find_word(&lower, &name.to_lowercase()))
+                    .min()
+                    .map(|offset| (offset, index))
+            })
+            .min()
+            .map(|(_, index)| index)
+    }
+
+    /// Member display names, in the order they joined.
+    pub fn names(&self) -> Vec<&str> {
+        self.members
+            .iter()
+            .map(|card| card.display_name())
+            .collect()
+    }
+
+    /// Index of the member whose prefix starts `content`, if any.
+    pub fn speaker_of(&self, content: &str) -> Option<usize> {
+        self.members
+            .iter()
+            .position(|card| content.starts_with(&speaker_prefix(card.display_name())))
+    }
+
+    /// Instruction appended to the system prompt when `speaker` replies.
+    pub fn turn_instruction(&self, speaker: usize, user_name: &str) -> Option<String> {
+        let name = self.members.get(speaker)?.display_name();
+        let others: Vec<&str> = self
+            .names()
+            .into_iter()
+            .filter(|other| *other != name)
+            .collect();
+        let participants = if others.is_empty() {
+            format!("{user_name} and {name}")
+        } else {
+            format!("{user_name}, {name}, and {}", others.join(", "))
+        };
+        Some(format!(
+            "This is a group chat between {participants}. \
+             Earlier replies are labelled with the speaker's name. \
+             Write only {name}'s next reply, without a name label, and do not speak for anyone else."
+        ))
+    }
+}
+
+/// Label placed before a member's reply in the transcript.
+pub fn speaker_prefix(name: &str) -> String {
+    format!("{name}: ")
+}
+
+/// Byte offset of `word` in `text` where it is not part of a longer word.
+fn find_word(text: &str, word: &str) -> Option<usize> {
+    if word.is_empty() {
+        return None;
+    }
+    text.match_indices(word)
+        .map(|(offset, _)| offset)
+        .find(|&offset| {
+            let before = text[..offset].chars().next_back();
+            let after = text[offset + word.len()..].chars().next();
+            !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character::test_helpers::helpers::create_test_character;
+
+    fn group() -> GroupChat {
+        GroupChat::new(vec![
+            create_test_character("Alice", "Hi"),
+            create_test_character("Bob", "Hey"),
+            create_test_character("Carol", "Hello"),
+        ])
+    }
+
+    #[test]
+    fn round_robin_cycles_after_last_speaker() {
+        let mut group = group();
+        assert_eq!(group.next_speaker(Some("hello")), Some(0));
+        group.record_speaker(0);
+        assert_eq!(group.next_speaker(None), Some(1));
+        group.record_speaker(2);
+        assert_eq!(group.next_speaker(None), Some(0));
+        assert_eq!(GroupChat::new(Vec::new()).next_speaker(None), None);
+    }
+
+    #[test]
+    fn mention_picks_earliest_named_member_or_falls_back() {
+        let mut group = group();
+        group.turn_order = TurnOrder::Mention;
+        group.record_speaker(0);
+        assert_eq!(group.next_speaker(Some("Carol, then bob?")), Some(2));
+        assert_eq!(group.next_speaker(Some("what does BOB think")), Some(1));
+        assert_eq!(group.next_speaker(Some("Bobby and Alicent")), Some(1));
+        assert_eq!(group.next_speaker(Some("anyone?")), Some(1));
+    }
+
+    #[test]
+    fn manual_keeps_the_last_speaker() {
+        let mut group = group();
+        group.turn_order = TurnOrder::parse("Manual").unwrap();
+        assert_eq!(group.next_speaker(Some("Bob?")), Some(0));
+        group.record_speaker(2);
+        assert_eq!(group.next_speaker(None), Some(2));
+    }
+
+    #[test]
+    fn add_remove_and_speaker_lookup() {
+        let mut group = group();
+        assert!(group.add(create_test_character("alice", "Hi")).is_err());
+        group.record_speaker(2);
+        assert_eq!(group.remove("bob").unwrap().data.name, "Bob");
+        assert_eq!(group.last_speaker(), Some(1));
+        assert!(group.remove("Bob").is_err());
+
+        assert_eq!(group.speaker_of("Carol: sure thing"), Some(1));
+        assert_eq!(group.speaker_of("Carolyn: hi"), None);
+        let instruction = group.turn_instruction(1, "Sam").unwrap();
+        assert!(instruction.contains("between Sam, Carol, and Alice"));
+        assert!(instruction.contains("Write only Carol's next reply"));
+    }
+}
@@ -47,7 +47,7 @@ impl std::error::Error for ImportError {}
 /// one place while leaving this public API focused on its high-level behavior.
 ///
 /// # Arguments
-/// * `source_path` - Path to the source card file (JSON or PNG)
+/// * `source_path` - Path to the source card file (JSON, PNG, or CHARX)
 /// * `force_overwrite` - If true, overwrite existing files without prompting
 ///
 /// # Returns
@@ -221,7 +221,7 @@ mod tests {
 
         match result.unwrap_err() {
             ImportError::ValidationFailed(msg) => {
-                assert!(msg.contains("must be .json or .png"));
+                assert!(msg.contains("must be .json, .png, or .charx"));
             }
             _ => panic!("Expected ValidationFailed error for wrong extension"),
         }
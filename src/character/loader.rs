@@ -1,7 +1,9 @@
 use std::fmt;
 use std::fs;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 
+use crate::character::card::{SPEC_V2, SPEC_V3};
 use crate::character::{png_text, CharacterCard};
 use crate::core::config::data::Config;
 use base64::Engine;
@@ -21,6 +23,9 @@ pub enum CardLoadError {
     /// Required PNG metadata chunk is missing from character card image.
     MissingMetadata(String),
 
+    /// CHARX zip archive could not be read or lacks `card.json`.
+    InvalidArchive(String),
+
     /// Character card validation failed (invalid or missing required fields).
     ValidationFailed(Vec<String>),
 }
@@ -40,6 +45,9 @@ impl fmt::Display for CardLoadError {
             CardLoadError::MissingMetadata(msg) => {
                 write!(f, "Missing metadata: {}", msg)
             }
+            CardLoadError::InvalidArchive(msg) => {
+                write!(f, "Invalid CHARX archive: {}", msg)
+            }
             CardLoadError::ValidationFailed(errors) => {
                 writeln!(f, "Card validation failed:")?;
                 for error in errors {
@@ -83,8 +91,11 @@ pub fn list_available_cards() -> Result<Vec<(String, PathBuf)>, Box<dyn std::err
                 .and_then(|e| e.to_str())
                 .map(|s| s.to_lowercase());
 
-            // Check if it's a JSON or PNG file
-            if matches!(extension.as_deref(), Some("json") | Some("png")) {
+            // Check if it's a JSON, PNG, or CHARX file
+            if matches!(
+                extension.as_deref(),
+                Some("json") | Some("png") | Some("charx")
+            ) {
                 // Try to load the card to get its name
                 // If loading fails, skip this file (it will be logged elsewhere)
                 match load_card(&path) {
@@ -105,7 +116,7 @@ pub fn list_available_cards() -> Result<Vec<(String, PathBuf)>, Box<dyn std::err
     Ok(cards)
 }
 
-/// Load a character card from a file (JSON or PNG)
+/// Load a character card from a file (JSON, PNG, or CHARX)
 /// Automatically detects the file type based on extension
 pub fn load_card<P: AsRef<Path>>(path: P) -> Result<CharacterCard, CardLoadError> {
     let path = path.as_ref();
@@ -117,8 +128,9 @@ pub fn load_card<P: AsRef<Path>>(path: P) -> Result<CharacterCard, CardLoadError
     match extension.as_deref() {
         Some("json") => load_json_card(path),
         Some("png") => load_png_card(path),
+        Some("charx") => load_charx_card(path),
         _ => Err(CardLoadError::InvalidJson(format!(
-            "{}: File must be .json or .png",
+            "{}: File must be .json, .png, or .charx",
             path.display()
         ))),
     }
@@ -143,27 +155,30 @@ pub fn load_json_card<P: AsRef<Path>>(path: P) -> Result<CharacterCard, CardLoad
 }
 
 /// Load a character card from a PNG file with embedded metadata
+///
+/// A v3 `ccv3` tEXt chunk takes precedence over the v2 `chara` chunk.
 pub fn load_png_card<P: AsRef<Path>>(path: P) -> Result<CharacterCard, CardLoadError> {
     let path = path.as_ref();
 
     let data = fs::read(path)
         .map_err(|e| CardLoadError::FileNotFound(format!("{}: {}", path.display(), e)))?;
 
-    let chara_text = match png_text::extract_text(&data, "chara") {
+    let invalid_png = |err| CardLoadError::InvalidPng(format!("{}: {}", path.display(), err));
+    let chara_text = match png_text::extract_text(&data, "ccv3") {
         Ok(text) => text,
         Err(png_text::PngTextError::MissingKeyword(_)) => {
-            return Err(CardLoadError::MissingMetadata(format!(
-                "{}: PNG does not contain 'chara' metadata in tEXt chunk",
-                path.display()
-            )))
-        }
-        Err(err) => {
-            return Err(CardLoadError::InvalidPng(format!(
-                "{}: {}",
-                path.display(),
-                err
-            )))
+            match png_text::extract_text(&data, "chara") {
+                Ok(text) => text,
+                Err(png_text::PngTextError::MissingKeyword(_)) => {
+                    return Err(CardLoadError::MissingMetadata(format!(
+                        "{}: PNG does not contain 'ccv3' or 'chara' metadata in tEXt chunk",
+                        path.display()
+                    )))
+                }
+                Err(err) => return Err(invalid_png(err)),
+            }
         }
+        Err(err) => return Err(invalid_png(err)),
     };
 
     // Base64 decode the chara data
@@ -188,15 +203,67 @@ pub fn load_png_card<P: AsRef<Path>>(path: P) -> Result<CharacterCard, CardLoadE
     Ok(card)
 }
 
-/// Validate a character card against the v2 specification
+/// Largest `card.json` read from a CHARX archive, so a small archive cannot
+/// expand into an unbounded allocation.
+const MAX_CHARX_CARD_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Load a character card from a CHARX archive
+///
+/// CHARX files are zip archives with the v3 card in `card.json` at the root
+/// and the files referenced by `embeded://` asset URIs stored alongside it.
+pub fn load_charx_card<P: AsRef<Path>>(path: P) -> Result<CharacterCard, CardLoadError> {
+    let path = path.as_ref();
+
+    let file = fs::File::open(path)
+        .map_err(|e| CardLoadError::FileNotFound(format!("{}: {}", path.display(), e)))?;
+    let invalid_archive =
+        |msg: String| CardLoadError::InvalidArchive(format!("{}: {}", path.display(), msg));
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| invalid_archive(e.to_string()))?;
+
+    let mut json_str = String::new();
+    archive
+        .by_name("card.json")
+        .map_err(|_| invalid_archive("archive does not contain card.json".to_string()))?
+        .take(MAX_CHARX_CARD_BYTES + 1)
+        .read_to_string(&mut json_str)
+        .map_err(|e| invalid_archive(format!("failed to read card.json: {}", e)))?;
+    if json_str.len() as u64 > MAX_CHARX_CARD_BYTES {
+        return Err(invalid_archive(format!(
+            "card.json is larger than {} MiB",
+            MAX_CHARX_CARD_BYTES / (1024 * 1024)
+        )));
+    }
+
+    let card: CharacterCard = serde_json::from_str(&json_str)
+        .map_err(|e| CardLoadError::InvalidJson(format!("{}: {}", path.display(), e)))?;
+
+    validate_card(&card)?;
+
+    let missing: Vec<String> = card
+        .data
+        .assets
+        .iter()
+        .flatten()
+        .filter_map(|asset| asset.embedded_path())
+        .filter(|inner| archive.index_for_name(inner).is_none())
+        .map(|inner| format!("Embedded asset '{}' is missing from the archive", inner))
+        .collect();
+    if !missing.is_empty() {
+        return Err(CardLoadError::ValidationFailed(missing));
+    }
+
+    Ok(card)
+}
+
+/// Validate a character card against the v2 or v3 specification
 pub fn validate_card(card: &CharacterCard) -> Result<(), CardLoadError> {
     let mut errors = Vec::new();
 
     // Check spec field
-    if card.spec != "chara_card_v2" {
+    if card.spec != SPEC_V2 && card.spec != SPEC_V3 {
         errors.push(format!(
-            "Invalid spec field: expected 'chara_card_v2', got '{}'",
-            card.spec
+            "Invalid spec field: expected '{}' or '{}', got '{}'",
+            SPEC_V2, SPEC_V3, card.spec
         ));
     }
 
@@ -310,12 +377,19 @@ mod tests {
     }
 
     fn assemble_png(chara_payload: Option<&[u8]>) -> Vec<u8> {
+        match chara_payload {
+            Some(payload) => assemble_png_with_texts(&[("chara", payload)]),
+            None => assemble_png_with_texts(&[]),
+        }
+    }
+
+    fn assemble_png_with_texts(texts: &[(&str, &[u8])]) -> Vec<u8> {
         let mut png = Vec::new();
         png.extend_from_slice(&png_text::PNG_SIGNATURE);
         png.extend_from_slice(&png_chunk(*b"IHDR", &IHDR_DATA));
-        if let Some(payload) = chara_payload {
-            let mut text_data = Vec::with_capacity("chara".len() + 1 + payload.len());
-            text_data.extend_from_slice(b"chara");
+        for (keyword, payload) in texts {
+            let mut text_data = Vec::with_capacity(keyword.len() + 1 + payload.len());
+            text_data.extend_from_slice(keyword.as_bytes());
             text_data.push(0);
             text_data.extend_from_slice(payload);
             png.extend_from_slice(&png_chunk(*b"tEXt", &text_data));
@@ -325,6 +399,36 @@ mod tests {
         png
     }
 
+    fn create_v3_card() -> CharacterCard {
+        let mut card = create_test_character("Seraphina", "Welcome.");
+        card.spec = "chara_card_v3".to_string();
+        card.spec_version = "3.0".to_string();
+        card.data.nickname = Some("Sera".to_string());
+        card.data.group_only_greetings = Some(vec!["Hello, everyone.".to_string()]);
+        card.data.source = Some(vec!["https://example.com/sera".to_string()]);
+        card.data.creation_date = Some(1_700_000_000);
+        card
+    }
+
+    fn build_charx(card_json: Option<&str>, files: &[(&str, &[u8])]) -> NamedTempFile {
+        let temp_file = Builder::new()
+            .suffix(".charx")
+            .tempfile()
+            .expect("failed to create temp charx file");
+        let mut writer = zip::ZipWriter::new(temp_file.reopen().unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        if let Some(json) = card_json {
+            writer.start_file("card.json", options).unwrap();
+            writer.write_all(json.as_bytes()).unwrap();
+        }
+        for (name, bytes) in files {
+            writer.start_file(*name, options).unwrap();
+            writer.write_all(bytes).unwrap();
+        }
+        writer.finish().unwrap();
+        temp_file
+    }
+
     fn build_png_with_text(text: &[u8]) -> Vec<u8> {
         assemble_png(Some(text))
     }
@@ -629,6 +733,66 @@ mod tests {
         assert_eq!(card.data.description, "A test character for unit tests");
     }
 
+    #[test]
+    fn test_load_png_card_prefers_ccv3_chunk() {
+        let v2_json = create_valid_card_json();
+        let v3_json = serde_json::to_string(&create_v3_card()).unwrap();
+        let encode = |json: &str| base64::prelude::BASE64_STANDARD.encode(json.as_bytes());
+        let png_bytes = assemble_png_with_texts(&[
+            ("chara", encode(&v2_json).as_bytes()),
+            ("ccv3", encode(&v3_json).as_bytes()),
+        ]);
+
+        let temp_file = NamedTempFile::new().unwrap();
+        temp_file.as_file().write_all(&png_bytes).unwrap();
+
+        let card = load_png_card(temp_file.path()).unwrap();
+        assert!(card.is_v3());
+        assert_eq!(card.data.name, "Seraphina");
+        assert_eq!(card.display_name(), "Sera");
+        assert_eq!(card.data.creation_date, Some(1_700_000_000));
+    }
+
+    #[test]
+    fn test_load_charx_card_with_embedded_assets() {
+        let mut card = create_v3_card();
+        card.data.assets = Some(vec![crate::character::card::CardAsset {
+            asset_type: "icon".to_string(),
+            uri: "embeded://assets/icon/images/main.png".to_string(),
+            name: "main".to_string(),
+            ext: "png".to_string(),
+        }]);
+        let json = serde_json::to_string(&card).unwrap();
+
+        let archive = build_charx(
+            Some(&json),
+            &[("assets/icon/images/main.png", &build_png_without_text())],
+        );
+        let loaded = load_card(archive.path()).unwrap();
+        assert_eq!(loaded, card);
+
+        let missing_asset = build_charx(Some(&json), &[]);
+        match load_charx_card(missing_asset.path()).unwrap_err() {
+            CardLoadError::ValidationFailed(errors) => {
+                assert!(errors[0].contains("assets/icon/images/main.png"));
+            }
+            other => panic!("Expected ValidationFailed error, got {other:?}"),
+        }
+
+        let no_card = build_charx(None, &[("readme.txt", b"hi")]);
+        assert!(matches!(
+            load_charx_card(no_card.path()),
+            Err(CardLoadError::InvalidArchive(msg)) if msg.contains("card.json")
+        ));
+
+        let oversized = " ".repeat(MAX_CHARX_CARD_BYTES as usize + 1);
+        let too_large = build_charx(Some(&oversized), &[]);
+        assert!(matches!(
+            load_charx_card(too_large.path()),
+            Err(CardLoadError::InvalidArchive(msg)) if msg.contains("larger than")
+        ));
+    }
+
     #[test]
     fn test_load_png_card_without_metadata() {
         // Create a PNG without chara metadata
@@ -900,7 +1064,7 @@ mod tests {
 
         match result.unwrap_err() {
             CardLoadError::InvalidJson(msg) => {
-                assert!(msg.contains("must be .json or .png"));
+                assert!(msg.contains("must be .json, .png, or .charx"));
             }
             _ => panic!("Expected InvalidJson error for invalid extension"),
         }
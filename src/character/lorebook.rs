@@ -0,0 +1,551 @@
+//! Character Book (lorebook / world info) support for v2 character cards.
+//!
+//! A card's `character_book` holds entries whose content is added to the
+//! system prompt only when the conversation mentions one of their keys.
+//! Activation scans the most recent messages (`scan_depth`), honors
+//! `selective` secondary keys, `/regex/` keys, and always-on `constant`
+//! entries, optionally re-scans activated content (`recursive_scanning`),
+//! and trims the result to `token_budget` by `priority` before ordering it by
+//! `insertion_order`.
+//!
+//! V3 books may also start an entry's content with `@@decorator` lines (for
+//! example `@@activate`, `@@scan_depth 4`, or `@@exclude_keys a,b`). They
+//! adjust activation for that entry and are stripped before injection;
+//! unsupported decorators are ignored.
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Messages scanned for keys when the book does not set `scan_depth`.
+pub const DEFAULT_LORE_SCAN_DEPTH: usize = 2;
+
+/// Estimated tokens of entry content allowed when the book sets no budget.
+pub const DEFAULT_LORE_TOKEN_BUDGET: usize = 2048;
+
+/// Upper bound on recursive scanning passes, so entries that mention each
+/// other cannot loop.
+const MAX_RECURSION_PASSES: usize = 8;
+
+/// A book's key regexes by `(pattern, case_insensitive)`, compiled on its
+/// first activation rather than on every one. Invalid patterns are kept as
+/// `None`.
+#[derive(Clone, Default)]
+struct KeyRegexes(OnceLock<HashMap<(String, bool), Option<Regex>>>);
+
+impl std::fmt::Debug for KeyRegexes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyRegexes").finish_non_exhaustive()
+    }
+}
+
+/// Compiled patterns are derived from the entries, so they never make two
+/// books differ.
+impl PartialEq for KeyRegexes {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct CharacterBook {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scan_depth: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_budget: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recursive_scanning: Option<bool>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub extensions: Map<String, Value>,
+    #[serde(default)]
+    pub entries: Vec<LorebookEntry>,
+    #[serde(skip)]
+    key_regexes: KeyRegexes,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LorebookEntry {
+    #[serde(default)]
+    pub keys: Vec<String>,
+    #[serde(default)]
+    pub content: String,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub extensions: Map<String, Value>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub insertion_order: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub case_sensitive: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selective: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secondary_keys: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constant: Option<bool>,
+    /// `before_char` or `after_char`; anything else is treated as `after_char`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<String>,
+    /// V3: treat every key as a regular expression.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub use_regex: Option<bool>,
+}
+
+/// Activation settings parsed from the leading `@@` lines of a v3 entry.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Decorators {
+    activate: bool,
+    dont_activate: bool,
+    scan_depth: Option<usize>,
+    position: Option<String>,
+    additional_keys: Vec<String>,
+    exclude_keys: Vec<String>,
+    use_regex: bool,
+    match_full_word: bool,
+}
+
+/// Splits `content` into its decorators and the text that gets injected.
+/// `@@@` fallback lines are skipped, since every decorator is either
+/// handled or safely ignored here.
+fn parse_decorators(content: &str) -> (Decorators, &str) {
+    let mut decorators = Decorators::default();
+    let mut rest = content.trim_start();
+    while let Some(line) = rest.strip_prefix("@@") {
+        let (line, remainder) = line.split_once('\n').unwrap_or((line, ""));
+        rest = remainder;
+        if line.starts_with('@') {
+            continue;
+        }
+        let (name, value) = line
+            .trim()
+            .split_once(char::is_whitespace)
+            .map(|(name, value)| (name, value.trim()))
+            .unwrap_or((line.trim(), ""));
+        let list = || {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+        match name {
+            "activate" => decorators.activate = true,
+            "dont_activate" => decorators.dont_activate = true,
+            "scan_depth" => decorators.scan_depth = value.parse().ok(),
+            "position" => decorators.position = Some(value.to_string()),
+            "additional_keys" => decorators.additional_keys.extend(list()),
+            "exclude_keys" => decorators.exclude_keys.extend(list()),
+            "use_regex" => decorators.use_regex = true,
+            "match_full_word" => decorators.match_full_word = true,
+            _ => {}
+        }
+    }
+    (decorators, rest)
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl LorebookEntry {
+    pub fn is_constant(&self) -> bool {
+        self.constant.unwrap_or(false)
+    }
+
+    /// True for `before_char` entries and v3 `@@position before_desc`.
+    pub fn is_before_char(&self) -> bool {
+        match parse_decorators(&self.content).0.position.as_deref() {
+            Some(position) => position == "before_desc",
+            None => self.position.as_deref() == Some("before_char"),
+        }
+    }
+
+    /// Content to inject, without any leading v3 decorators.
+    pub fn body(&self) -> &str {
+        parse_decorators(&self.content).1.trim()
+    }
+
+    /// Short label for inspect views: the name, comment, or first key.
+    pub fn label(&self) -> String {
+        [self.name.as_deref(), self.comment.as_deref()]
+            .into_iter()
+            .flatten()
+            .map(str::trim)
+            .find(|label| !label.is_empty())
+            .map(str::to_string)
+            .or_else(|| self.keys.first().map(|key| key.trim().to_string()))
+            .unwrap_or_else(|| "(unnamed entry)".to_string())
+    }
+
+    fn matches(&self, decorators: &Decorators, regexes: &KeyRegexes, text: &str) -> bool {
+        let any = |keys: &[String]| {
+            keys.iter()
+                .any(|key| self.key_matches(decorators, regexes, key, text))
+        };
+        if decorators.dont_activate || !any(&self.keys) || any(&decorators.exclude_keys) {
+            return false;
+        }
+        if self.selective.unwrap_or(false)
+            && !self.secondary_keys.is_empty()
+            && !any(&self.secondary_keys)
+        {
+            return false;
+        }
+        decorators.additional_keys.is_empty() || any(&decorators.additional_keys)
+    }
+
+    /// Keys written as `/pattern/flags` are regular expressions whose flags
+    /// alone decide case (`i` ignores it), as are all keys when `use_regex`
+    /// is set; other keys are substring matches, or whole-word matches under
+    /// `@@match_full_word`.
+    fn key_matches(
+        &self,
+        decorators: &Decorators,
+        regexes: &KeyRegexes,
+        key: &str,
+        text: &str,
+    ) -> bool {
+        let key = key.trim();
+        if key.is_empty() {
+            return false;
+        }
+        if let Some(pattern) = self.key_pattern(decorators, key) {
+            return match regexes.0.get().and_then(|compiled| compiled.get(&pattern)) {
+                Some(regex) => regex.as_ref().is_some_and(|regex| regex.is_match(text)),
+                None => compile_key_regex(&pattern).is_some_and(|regex| regex.is_match(text)),
+            };
+        }
+        if self.case_sensitive.unwrap_or(false) {
+            text.contains(key)
+        } else {
+            text.to_lowercase().contains(&key.to_lowercase())
+        }
+    }
+
+    /// The `(pattern, case_insensitive)` regex `key` is matched with, or
+    /// `None` for a plain substring key.
+    fn key_pattern(&self, decorators: &Decorators, key: &str) -> Option<(String, bool)> {
+        let case_sensitive = self.case_sensitive.unwrap_or(false);
+        match parse_regex_key(key) {
+            Some((pattern, flags)) => Some((pattern.to_string(), flags.contains('i'))),
+            None if self.use_regex.unwrap_or(false) || decorators.use_regex => {
+                Some((key.to_string(), !case_sensitive))
+            }
+            None if decorators.match_full_word => {
+                Some((format!(r"\b{}\b", regex::escape(key)), !case_sensitive))
+            }
+            None => None,
+        }
+    }
+
+    /// Every key this entry matches against, decorator keys included.
+    fn all_keys<'a>(&'a self, decorators: &'a Decorators) -> impl Iterator<Item = &'a str> {
+        self.keys
+            .iter()
+            .chain(&self.secondary_keys)
+            .chain(&decorators.additional_keys)
+            .chain(&decorators.exclude_keys)
+            .map(|key| key.trim())
+            .filter(|key| !key.is_empty())
+    }
+
+    fn estimated_tokens(&self) -> usize {
+        self.body().chars().count().div_ceil(4)
+    }
+}
+
+fn compile_key_regex((pattern, ignore_case): &(String, bool)) -> Option<Regex> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(*ignore_case)
+        .build()
+        .ok()
+}
+
+fn parse_regex_key(key: &str) -> Option<(&str, &str)> {
+    let rest = key.strip_prefix('/')?;
+    let end = rest.rfind('/')?;
+    let (pattern, flags) = (&rest[..end], &rest[end + 1..]);
+    if pattern.is_empty() || !flags.chars().all(|flag| "gimsuy".contains(flag)) {
+        return None;
+    }
+    Some((pattern, flags))
+}
+
+impl CharacterBook {
+    pub fn scan_depth(&self) -> usize {
+        self.scan_depth.unwrap_or(DEFAULT_LORE_SCAN_DEPTH)
+    }
+
+    pub fn token_budget(&self) -> usize {
+        self.token_budget
+            .filter(|budget| *budget > 0)
+            .unwrap_or(DEFAULT_LORE_TOKEN_BUDGET)
+    }
+
+    /// Returns the entries activated by `recent_messages` (oldest first), in
+    /// insertion order and within the token budget.
+    pub fn activate(&self, recent_messages: &[&str]) -> Vec<&LorebookEntry> {
+        let decorators: Vec<Decorators> = self
+            .entries
+            .iter()
+            .map(|entry| parse_decorators(&entry.content).0)
+            .collect();
+        // Entries edited after this first build fall back to compiling
+        // their new patterns on each match.
+        self.key_regexes.0.get_or_init(|| {
+            self.entries
+                .iter()
+                .zip(&decorators)
+                .flat_map(|(entry, decorators)| {
+                    entry
+                        .all_keys(decorators)
+                        .filter_map(|key| entry.key_pattern(decorators, key))
+                })
+                .map(|pattern| {
+                    let regex = compile_key_regex(&pattern);
+                    (pattern, regex)
+                })
+                .collect()
+        });
+        let always_on =
+            |index: usize| self.entries[index].is_constant() || decorators[index].activate;
+        let window = |depth: usize| {
+            let depth = depth.min(recent_messages.len());
+            recent_messages[recent_messages.len() - depth..].join("\n")
+        };
+        let book_window = window(self.scan_depth());
+        let mut recursed_text = String::new();
+        let mut active = vec![false; self.entries.len()];
+
+        for pass in 0..MAX_RECURSION_PASSES {
+            let mut activated = Vec::new();
+            for (index, entry) in self.entries.iter().enumerate() {
+                if active[index] || !entry.enabled {
+                    continue;
+                }
+                let matched = (pass == 0 && always_on(index)) || {
+                    let mut text = match decorators[index].scan_depth {
+                        Some(depth) => window(depth),
+                        None => book_window.clone(),
+                    };
+                    text.push_str(&recursed_text);
+                    entry.matches(&decorators[index], &self.key_regexes, &text)
+                };
+                if matched {
+                    active[index] = true;
+                    activated.push(index);
+                }
+            }
+            if activated.is_empty() || !self.recursive_scanning.unwrap_or(false) {
+                break;
+            }
+            for index in activated {
+                recursed_text.push('\n');
+                recursed_text.push_str(self.entries[index].body());
+            }
+        }
+
+        // Always-on entries first, then higher priority; lower priority
+        // entries are dropped first once the budget runs out.
+        let mut candidates: Vec<usize> = (0..self.entries.len())
+            .filter(|index| active[*index])
+            .collect();
+        candidates.sort_by_key(|index| {
+            (
+                !always_on(*index),
+                std::cmp::Reverse(self.entries[*index].priority.unwrap_or(0)),
+            )
+        });
+        let mut remaining = self.token_budget();
+        let mut kept = Vec::new();
+        for index in candidates {
+            let cost = self.entries[index].estimated_tokens();
+            if cost <= remaining {
+                remaining -= cost;
+                kept.push(index);
+            }
+        }
+
+        kept.sort_by_key(|index| (self.entries[*index].insertion_order, *index));
+        kept.into_iter().map(|index| &self.entries[index]).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(keys: &[&str], content: &str) -> LorebookEntry {
+        LorebookEntry {
+            keys: keys.iter().map(|key| key.to_string()).collect(),
+            content: content.to_string(),
+            extensions: Map::new(),
+            enabled: true,
+            insertion_order: 0,
+            case_sensitive: None,
+            name: None,
+            priority: None,
+            id: None,
+            comment: None,
+            selective: None,
+            secondary_keys: Vec::new(),
+            constant: None,
+            position: None,
+            use_regex: None,
+        }
+    }
+
+    fn contents<'a>(entries: &[&'a LorebookEntry]) -> Vec<&'a str> {
+        entries.iter().map(|entry| entry.content.as_str()).collect()
+    }
+
+    #[test]
+    fn deserializes_spec_book_with_defaults() {
+        let book: CharacterBook = serde_json::from_value(serde_json::json!({
+            "scan_depth": 3,
+            "extensions": {},
+            "entries": [
+                { "keys": ["castle"], "content": "The castle is old.", "extensions": {},
+                  "enabled": true, "insertion_order": 10, "position": "before_char" },
+                { "keys": ["moat"], "content": "Eels live in the moat." }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(book.scan_depth(), 3);
+        assert_eq!(book.token_budget(), DEFAULT_LORE_TOKEN_BUDGET);
+        assert!(book.entries[0].is_before_char());
+        assert!(book.entries[1].enabled);
+        assert_eq!(book.entries[1].label(), "moat");
+    }
+
+    #[test]
+    fn activation_respects_scan_depth_case_and_disabled_entries() {
+        let mut disabled = entry(&["castle"], "disabled");
+        disabled.enabled = false;
+        let mut sensitive = entry(&["Moat"], "moat");
+        sensitive.case_sensitive = Some(true);
+        let book = CharacterBook {
+            scan_depth: Some(1),
+            entries: vec![entry(&["CASTLE"], "castle"), disabled, sensitive],
+            ..Default::default()
+        };
+
+        assert!(book.activate(&["the castle", "hello"]).is_empty());
+        assert_eq!(contents(&book.activate(&["the castle"])), vec!["castle"]);
+        assert!(book.activate(&["a moat"]).is_empty());
+        assert_eq!(contents(&book.activate(&["a Moat"])), vec!["moat"]);
+    }
+
+    #[test]
+    fn activation_handles_secondary_keys_regex_and_constants() {
+        let mut selective = entry(&["dragon"], "selective");
+        selective.selective = Some(true);
+        selective.secondary_keys = vec!["fire".to_string()];
+        let mut constant = entry(&[], "constant");
+        constant.constant = Some(true);
+        let book = CharacterBook {
+            entries: vec![selective, entry(&["/sw(or|ea)d/i"], "regex"), constant],
+            ..Default::default()
+        };
+
+        assert_eq!(contents(&book.activate(&["a dragon"])), vec!["constant"]);
+        assert_eq!(
+            contents(&book.activate(&["a dragon breathes FIRE", "a SWORD"])),
+            vec!["selective", "regex", "constant"]
+        );
+    }
+
+    #[test]
+    fn regex_key_flags_decide_case_sensitivity() {
+        let book = CharacterBook {
+            entries: vec![entry(&["/Sword/"], "exact"), entry(&["/Shield/i"], "any")],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            contents(&book.activate(&["a sword and a SHIELD"])),
+            vec!["any"]
+        );
+        assert_eq!(
+            contents(&book.activate(&["a Sword and a shield"])),
+            vec!["exact", "any"]
+        );
+    }
+
+    #[test]
+    fn activation_recurses_orders_and_trims_to_budget() {
+        let mut king = entry(&["king"], "The king rules the castle.");
+        king.insertion_order = 5;
+        let mut castle = entry(&["castle"], "The castle has a moat.");
+        castle.insertion_order = 1;
+        let mut low = entry(&["king"], &"x".repeat(40));
+        low.priority = Some(-1);
+        let book = CharacterBook {
+            recursive_scanning: Some(true),
+            token_budget: Some(20),
+            entries: vec![king, castle, low],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            contents(&book.activate(&["Meet the king"])),
+            vec!["The castle has a moat.", "The king rules the castle."]
+        );
+    }
+
+    #[test]
+    fn v3_decorators_adjust_activation_and_are_stripped() {
+        let forced = entry(
+            &["never"],
+            "@@activate\n@@position before_desc\nAlways here.",
+        );
+        let excluded = entry(&["tower"], "@@exclude_keys ruin\nThe tower stands.");
+        let mut deep = entry(&["harbor"], "@@scan_depth 3\n@@@unknown\nShips dock.");
+        deep.insertion_order = 1;
+        let mut words = entry(&["cat"], "@@match_full_word\nCats purr.");
+        words.insertion_order = 2;
+        let mut regex = entry(&["gr[ae]y"], "Grey skies.");
+        regex.use_regex = Some(true);
+        regex.insertion_order = 3;
+        let book = CharacterBook {
+            scan_depth: Some(1),
+            entries: vec![forced, excluded, deep, words, regex],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            contents(&book.activate(&["a", "the tower ruin"])),
+            vec!["@@activate\n@@position before_desc\nAlways here."]
+        );
+        let active = book.activate(&["the harbor", "a", "the tower, a cat, gray"]);
+        let bodies: Vec<&str> = active.iter().map(|entry| entry.body()).collect();
+        assert_eq!(
+            bodies,
+            vec![
+                "Always here.",
+                "The tower stands.",
+                "Ships dock.",
+                "Cats purr.",
+                "Grey skies."
+            ]
+        );
+        assert!(active[0].is_before_char());
+        assert_eq!(book.activate(&["concatenate"]).len(), 1);
+    }
+}
@@ -1,9 +1,12 @@
 pub mod cache;
 pub mod card;
+pub mod group;
 pub mod import;
 pub mod loader;
+pub mod lorebook;
 pub mod png_text;
 pub mod service;
+pub mod writer;
 
 #[cfg(test)]
 mod test_helpers;
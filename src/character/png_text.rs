@@ -97,6 +97,88 @@ pub fn extract_text(data: &[u8], keyword: &str) -> Result<String, PngTextError>
     Err(PngTextError::MissingKeyword(keyword.to_string()))
 }
 
+/// Returns a copy of the PNG with `keyword`'s tEXt chunk set to `value`.
+///
+/// Existing chunks for the keyword are dropped and the new chunk is written
+/// just before `IEND`, so the last occurrence is the only one left.
+pub fn write_text(data: &[u8], keyword: &str, value: &str) -> Result<Vec<u8>, PngTextError> {
+    let mut text_data = latin1_bytes(keyword)?;
+    text_data.push(0);
+    text_data.extend(latin1_bytes(value)?);
+    rewrite_text_chunks(data, keyword, Some(&text_data))
+}
+
+/// Returns a copy of the PNG without any tEXt chunks for `keyword`.
+pub fn remove_text(data: &[u8], keyword: &str) -> Result<Vec<u8>, PngTextError> {
+    rewrite_text_chunks(data, keyword, None)
+}
+
+fn rewrite_text_chunks(
+    data: &[u8],
+    keyword: &str,
+    replacement: Option<&[u8]>,
+) -> Result<Vec<u8>, PngTextError> {
+    if data.len() < PNG_SIGNATURE.len() || data[..PNG_SIGNATURE.len()] != PNG_SIGNATURE {
+        return Err(PngTextError::InvalidSignature);
+    }
+
+    let mut out = Vec::with_capacity(data.len() + replacement.map_or(0, |r| r.len() + 12));
+    out.extend_from_slice(&PNG_SIGNATURE);
+    let mut offset = PNG_SIGNATURE.len();
+    while offset + 12 <= data.len() {
+        let length = u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap()) as usize;
+        let chunk_type: [u8; 4] = data[offset + 4..offset + 8]
+            .try_into()
+            .expect("slice of length 4");
+        let data_end = (offset + 8)
+            .checked_add(length)
+            .ok_or(PngTextError::InvalidChunkLength)?;
+        if data_end + 4 > data.len() {
+            return Err(PngTextError::TruncatedChunk);
+        }
+        let chunk_data = &data[offset + 8..data_end];
+
+        let is_replaced = &chunk_type == b"tEXt"
+            && chunk_data
+                .iter()
+                .position(|&b| b == 0)
+                .is_some_and(|null_pos| chunk_data[..null_pos] == *keyword.as_bytes());
+        if &chunk_type == b"IEND" {
+            if let Some(text_data) = replacement {
+                out.extend_from_slice(&encode_chunk(b"tEXt", text_data));
+            }
+            out.extend_from_slice(&data[offset..data_end + 4]);
+            return Ok(out);
+        }
+        if !is_replaced {
+            out.extend_from_slice(&data[offset..data_end + 4]);
+        }
+        offset = data_end + 4;
+    }
+
+    Err(PngTextError::TruncatedChunk)
+}
+
+fn encode_chunk(chunk_type: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(12 + data.len());
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(chunk_type);
+    out.extend_from_slice(data);
+    let mut hasher = Hasher::new();
+    hasher.update(chunk_type);
+    hasher.update(data);
+    out.extend_from_slice(&hasher.finalize().to_be_bytes());
+    out
+}
+
+/// tEXt chunks are Latin-1; card payloads are base64 and always fit.
+fn latin1_bytes(text: &str) -> Result<Vec<u8>, PngTextError> {
+    text.chars()
+        .map(|ch| u8::try_from(u32::from(ch)).ok().filter(|&b| b != 0))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or(PngTextError::MalformedText("text is not Latin-1"))
+}
+
 fn display_chunk_type(chunk_type: &[u8; 4]) -> String {
     chunk_type
         .iter()
@@ -141,6 +223,37 @@ mod tests {
         assert!(matches!(result, Err(PngTextError::InvalidCrc { .. })));
     }
 
+    #[test]
+    fn write_text_replaces_existing_chunk_before_iend() {
+        let png = build_png(Some(b"old"), true);
+        let updated = write_text(&png, "chara", "new").unwrap();
+        assert_eq!(extract_text(&updated, "chara").unwrap(), "new");
+        assert!(updated.ends_with(&chunk(*b"IEND", &[], true)));
+        assert_eq!(
+            updated
+                .windows(4)
+                .filter(|window| window == b"tEXt")
+                .count(),
+            1
+        );
+
+        let added = write_text(&build_png(None, true), "ccv3", "v3").unwrap();
+        assert_eq!(extract_text(&added, "ccv3").unwrap(), "v3");
+        assert!(write_text(&png, "chara", "caf\u{e9} \u{1F600}").is_err());
+    }
+
+    #[test]
+    fn remove_text_drops_only_the_keyword() {
+        let png = write_text(&build_png(Some(b"value"), true), "ccv3", "v3").unwrap();
+        let stripped = remove_text(&png, "ccv3").unwrap();
+        assert!(matches!(
+            extract_text(&stripped, "ccv3"),
+            Err(PngTextError::MissingKeyword(_))
+        ));
+        assert_eq!(extract_text(&stripped, "chara").unwrap(), "value");
+        assert_eq!(remove_text(&stripped, "ccv3").unwrap(), stripped);
+    }
+
     const TEST_IHDR: [u8; 13] = [
         0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x00, 0x00, 0x00,
     ];
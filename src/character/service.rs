@@ -208,6 +208,20 @@ impl CharacterService {
         Err(CharacterServiceError::NotFound(name.to_string()))
     }
 
+    /// Resolves a file path or character name to the card file on disk.
+    ///
+    /// Uses the same lookup as [`CharacterService::resolve`] without loading
+    /// the card, for commands that rewrite the file in place.
+    pub fn resolve_path(&mut self, input: &str) -> Result<PathBuf, CharacterServiceError> {
+        let path = Path::new(input);
+        if path.is_file() {
+            return Ok(path.to_path_buf());
+        }
+
+        self.try_find_card_path(input)?
+            .ok_or_else(|| CharacterServiceError::NotFound(input.to_string()))
+    }
+
     /// Loads the default character for a specific provider and model.
     ///
     /// This method consults the user configuration to find a default character
@@ -270,7 +284,7 @@ impl CharacterService {
 
         let normalized_lookup = Self::normalize_lookup_key(name);
 
-        for ext in ["json", "png"] {
+        for ext in ["json", "png", "charx"] {
             let candidate = cards_dir.join(format!("{name}.{ext}"));
             if candidate.is_file() {
                 return Ok(Some(candidate));
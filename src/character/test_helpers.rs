@@ -24,6 +24,7 @@ pub(crate) mod helpers {
             tags: None,
             creator: None,
             character_version: None,
+            ..Default::default()
         }
     }
 
@@ -59,6 +60,33 @@ pub(crate) mod helpers {
         (temp_dir, cards_dir)
     }
 
+    /// Build a 1x1 PNG without any tEXt chunks
+    pub fn build_blank_png() -> Vec<u8> {
+        const IHDR: [u8; 13] = [
+            0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x00, 0x00, 0x00,
+        ];
+        const IDAT: [u8; 12] = [
+            0x78, 0xDA, 0x63, 0x60, 0x60, 0x60, 0x00, 0x00, 0x00, 0x04, 0x00, 0x01,
+        ];
+        let chunk = |chunk_type: &[u8; 4], data: &[u8]| {
+            let mut out = Vec::with_capacity(12 + data.len());
+            out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            out.extend_from_slice(chunk_type);
+            out.extend_from_slice(data);
+            let mut hasher = crc32fast::Hasher::new();
+            hasher.update(chunk_type);
+            hasher.update(data);
+            out.extend_from_slice(&hasher.finalize().to_be_bytes());
+            out
+        };
+
+        let mut png = crate::character::png_text::PNG_SIGNATURE.to_vec();
+        png.extend(chunk(b"IHDR", &IHDR));
+        png.extend(chunk(b"IDAT", &IDAT));
+        png.extend(chunk(b"IEND", &[]));
+        png
+    }
+
     /// Helper to create a minimal valid character card JSON string
     pub fn create_valid_card_json() -> String {
         let card = CharacterCard {
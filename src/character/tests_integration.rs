@@ -473,6 +473,7 @@ mod integration_tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
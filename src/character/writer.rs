@@ -0,0 +1,201 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use tempfile::NamedTempFile;
+
+use base64::Engine;
+
+use crate::character::card::{CharacterCard, SPEC_V2};
+use crate::character::png_text;
+
+/// File format for exported cards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum CardFormat {
+    Json,
+    Png,
+}
+
+impl CardFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            CardFormat::Json => "json",
+            CardFormat::Png => "png",
+        }
+    }
+}
+
+/// Encode a card in `format`. PNG exports embed the card into `image`,
+/// which is required for that format.
+pub fn export_card(
+    card: &CharacterCard,
+    format: CardFormat,
+    image: Option<&[u8]>,
+) -> Result<Vec<u8>, String> {
+    match format {
+        CardFormat::Json => Ok(card_to_json(card)?.into_bytes()),
+        CardFormat::Png => {
+            let image = image.ok_or("A PNG image is required to export a PNG card")?;
+            embed_card_in_png(image, card)
+        }
+    }
+}
+
+/// Serialize a card as pretty-printed JSON.
+pub fn card_to_json(card: &CharacterCard) -> Result<String, String> {
+    serde_json::to_string_pretty(card).map_err(|e| format!("Failed to encode card: {}", e))
+}
+
+/// Embed a card into PNG image data, replacing any existing card metadata.
+///
+/// The `chara` chunk always holds a v2-labelled copy so older readers can load
+/// it; v3 cards additionally get a `ccv3` chunk, which `load_png_card` prefers.
+/// A stale `ccv3` chunk is removed when writing a v2 card.
+pub fn embed_card_in_png(png: &[u8], card: &CharacterCard) -> Result<Vec<u8>, String> {
+    let encode = |card: &CharacterCard| -> Result<String, String> {
+        let json =
+            serde_json::to_string(card).map_err(|e| format!("Failed to encode card: {}", e))?;
+        Ok(base64::prelude::BASE64_STANDARD.encode(json.as_bytes()))
+    };
+    let invalid_png = |e: png_text::PngTextError| format!("Invalid PNG: {}", e);
+
+    let mut v2_card = card.clone();
+    v2_card.spec = SPEC_V2.to_string();
+    v2_card.spec_version = "2.0".to_string();
+    let png = png_text::write_text(png, "chara", &encode(&v2_card)?).map_err(invalid_png)?;
+
+    if card.is_v3() {
+        png_text::write_text(&png, "ccv3", &encode(card)?).map_err(invalid_png)
+    } else {
+        png_text::remove_text(&png, "ccv3").map_err(invalid_png)
+    }
+}
+
+/// Write a card back to its file, choosing the format from the extension.
+///
+/// JSON cards are rewritten in full; PNG cards keep their image and only have
+/// the card metadata chunks replaced.
+pub fn save_card(card: &CharacterCard, path: &Path) -> Result<(), String> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|s| s.to_lowercase());
+
+    let bytes = match extension.as_deref() {
+        Some("json") => card_to_json(card)?.into_bytes(),
+        Some("png") => {
+            let png =
+                fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            embed_card_in_png(&png, card)?
+        }
+        Some("charx") => {
+            return Err(format!(
+                "{}: CHARX archives cannot be rewritten; save the card as JSON or PNG instead",
+                path.display()
+            ))
+        }
+        _ => return Err(format!("{}: File must be .json or .png", path.display())),
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    write_atomically(path, &bytes)
+}
+
+/// Writes to a temporary file next to `path` and renames it over `path`, so
+/// a failed write never leaves the user's card half-written. An existing
+/// card keeps its permissions.
+fn write_atomically(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    let write = || -> std::io::Result<()> {
+        let mut temp_file = match path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            Some(dir) => NamedTempFile::new_in(dir)?,
+            None => NamedTempFile::new_in(".")?,
+        };
+        temp_file.write_all(bytes)?;
+        temp_file.as_file_mut().sync_all()?;
+        if let Ok(metadata) = fs::metadata(path) {
+            fs::set_permissions(temp_file.path(), metadata.permissions())?;
+        }
+        temp_file.persist(path)?;
+        Ok(())
+    };
+    write().map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character::loader::{load_card, load_png_card};
+    use crate::character::test_helpers::helpers::{build_blank_png, create_test_character};
+    use tempfile::TempDir;
+
+    #[test]
+    fn save_card_round_trips_json_and_png() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut card = create_test_character("Alice", "Hi there!");
+        card.data
+            .extensions
+            .insert("talkativeness".to_string(), "0.5".into());
+
+        let json_path = temp_dir.path().join("alice.json");
+        save_card(&card, &json_path).unwrap();
+        assert_eq!(load_card(&json_path).unwrap(), card);
+
+        let png_path = temp_dir.path().join("alice.png");
+        fs::write(&png_path, build_blank_png()).unwrap();
+        save_card(&card, &png_path).unwrap();
+        card.data.description = "Edited".to_string();
+        save_card(&card, &png_path).unwrap();
+        assert_eq!(load_png_card(&png_path).unwrap(), card);
+
+        let charx_path = temp_dir.path().join("alice.charx");
+        assert!(save_card(&card, &charx_path).is_err());
+        assert!(!charx_path.exists());
+    }
+
+    #[test]
+    fn export_card_to_png_round_trips_through_loader() {
+        let temp_dir = TempDir::new().unwrap();
+        let card = create_test_character("Alice", "Hi there!");
+
+        assert!(export_card(&card, CardFormat::Png, None).is_err());
+        assert!(export_card(&card, CardFormat::Png, Some(b"not a png")).is_err());
+
+        let png_path = temp_dir.path().join("alice.png");
+        let bytes = export_card(&card, CardFormat::Png, Some(&build_blank_png())).unwrap();
+        fs::write(&png_path, &bytes).unwrap();
+        assert_eq!(load_png_card(&png_path).unwrap(), card);
+
+        let json = export_card(&card, CardFormat::Json, None).unwrap();
+        assert_eq!(
+            serde_json::from_slice::<CharacterCard>(&json).unwrap(),
+            card
+        );
+    }
+
+    #[test]
+    fn embed_card_in_png_writes_ccv3_only_for_v3_cards() {
+        let mut card = create_test_character("Sera", "Welcome.");
+        card.spec = "chara_card_v3".to_string();
+        card.spec_version = "3.0".to_string();
+        card.data.nickname = Some("Sera".to_string());
+
+        let v3_png = embed_card_in_png(&build_blank_png(), &card).unwrap();
+        let decode = |png: &[u8], keyword: &str| -> CharacterCard {
+            let text = png_text::extract_text(png, keyword).unwrap();
+            let json = base64::prelude::BASE64_STANDARD.decode(text).unwrap();
+            serde_json::from_slice(&json).unwrap()
+        };
+        assert_eq!(decode(&v3_png, "ccv3"), card);
+        let chara = decode(&v3_png, "chara");
+        assert_eq!(chara.spec, "chara_card_v2");
+        assert_eq!(chara.data.nickname.as_deref(), Some("Sera"));
+
+        let v2_card = create_test_character("Sera", "Welcome.");
+        let v2_png = embed_card_in_png(&v3_png, &v2_card).unwrap();
+        assert!(png_text::extract_text(&v2_png, "ccv3").is_err());
+        assert_eq!(decode(&v2_png, "chara"), v2_card);
+    }
+}
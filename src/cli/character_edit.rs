@@ -0,0 +1,295 @@
+//! `chabeau character new/edit`: author character cards from the terminal
+//!
+//! Short fields are edited inline with the line editor, pre-filled with the
+//! current value. Long fields open in `$EDITOR` when it is set and fall back
+//! to the line editor otherwise. The card is validated before it is written.
+
+use std::error::Error;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+use crate::character::card::{CharacterCard, CharacterData, SPEC_V2};
+use crate::character::loader::{get_cards_dir, load_card, validate_card};
+use crate::character::service::CharacterServiceError;
+use crate::character::writer::save_card;
+use crate::character::CharacterService;
+use crate::cli::prompt_bool_with_default;
+use crate::utils::editor::edit_text_in_editor;
+use crate::utils::line_editor::{prompt_line_editor, LineEditorOptions, MaskMode};
+
+/// Line separating alternate greetings when they are edited in `$EDITOR`.
+const GREETING_SEPARATOR: &str = "---";
+
+pub fn create_card(name: &str) -> Result<(), Box<dyn Error>> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Character name cannot be empty.".into());
+    }
+    match CharacterService::new().resolve_path(name) {
+        Ok(existing) => {
+            return Err(format!(
+                "Character '{}' already exists at {}. Use `chabeau character edit` to change it.",
+                name,
+                existing.display()
+            )
+            .into())
+        }
+        Err(CharacterServiceError::NotFound(_)) => {}
+        Err(err) => return Err(err.into()),
+    }
+    let path = new_card_path(&get_cards_dir(), name);
+    if path.exists() {
+        return Err(format!("{} already exists.", path.display()).into());
+    }
+
+    let mut card = CharacterCard {
+        spec: SPEC_V2.to_string(),
+        spec_version: "2.0".to_string(),
+        data: CharacterData {
+            name: name.to_string(),
+            ..Default::default()
+        },
+    };
+    println!("Creating character '{}' (Esc or Ctrl+C cancels).", name);
+    edit_fields(&mut card, true)?;
+    write_card(&card, &path)
+}
+
+pub fn edit_card(input: &str) -> Result<(), Box<dyn Error>> {
+    let path = CharacterService::new().resolve_path(input)?;
+    if has_extension(&path, "charx") {
+        return Err(format!(
+            "{}: CHARX archives cannot be edited in place. Import the card as JSON or PNG first.",
+            path.display()
+        )
+        .into());
+    }
+
+    let mut card = load_card(&path)?;
+    println!(
+        "Editing '{}' in {} (Esc or Ctrl+C cancels; nothing is saved until the end).",
+        card.data.name,
+        path.display()
+    );
+    edit_fields(&mut card, false)?;
+    if card.is_v3() {
+        card.data.modification_date = Some(chrono::Utc::now().timestamp());
+    }
+    write_card(&card, &path)
+}
+
+fn write_card(card: &CharacterCard, path: &Path) -> Result<(), Box<dyn Error>> {
+    validate_card(card)?;
+    save_card(card, path)?;
+    println!(
+        "✅ Saved character '{}' to {}",
+        card.data.name,
+        path.display()
+    );
+    Ok(())
+}
+
+/// Cards created here are named like `CharacterService` normalizes lookups,
+/// so `-c "Name"` finds them without scanning the directory.
+fn new_card_path(cards_dir: &Path, name: &str) -> PathBuf {
+    cards_dir.join(format!("{}.json", card_file_stem(name)))
+}
+
+/// Lowercased file stem for a character name, safe on every platform.
+pub(super) fn card_file_stem(name: &str) -> String {
+    name.trim()
+        .to_lowercase()
+        .chars()
+        .map(|ch| match ch {
+            ' ' => '_',
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            other => other,
+        })
+        .collect()
+}
+
+pub(super) fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case(extension))
+}
+
+fn edit_fields(card: &mut CharacterCard, creating: bool) -> Result<(), Box<dyn Error>> {
+    let is_v3 = card.is_v3();
+    let data = &mut card.data;
+
+    loop {
+        data.name = prompt_line("Name", &data.name)?;
+        if !data.name.is_empty() {
+            break;
+        }
+        println!("Name cannot be empty.");
+    }
+    if is_v3 {
+        data.nickname = non_empty(prompt_line(
+            "Nickname",
+            data.nickname.as_deref().unwrap_or(""),
+        )?);
+    }
+    data.description = prompt_long("Description", &data.description, creating)?;
+    data.personality = prompt_long("Personality", &data.personality, false)?;
+    data.scenario = prompt_long("Scenario", &data.scenario, false)?;
+    data.first_mes = prompt_long("First message", &data.first_mes, creating)?;
+    data.alternate_greetings = prompt_greetings(data.alternate_greetings.as_deref())?;
+    data.mes_example = prompt_long("Example messages", &data.mes_example, false)?;
+    data.system_prompt = non_empty(prompt_long(
+        "System prompt",
+        data.system_prompt.as_deref().unwrap_or(""),
+        false,
+    )?);
+    data.post_history_instructions = non_empty(prompt_long(
+        "Post-history instructions",
+        data.post_history_instructions.as_deref().unwrap_or(""),
+        false,
+    )?);
+    data.creator_notes = non_empty(prompt_long(
+        "Creator notes",
+        data.creator_notes.as_deref().unwrap_or(""),
+        false,
+    )?);
+    data.creator = non_empty(prompt_line(
+        "Creator",
+        data.creator.as_deref().unwrap_or(""),
+    )?);
+    data.character_version = non_empty(prompt_line(
+        "Version",
+        data.character_version.as_deref().unwrap_or(""),
+    )?);
+    let tags = prompt_line(
+        "Tags (comma-separated)",
+        &data.tags.as_deref().unwrap_or_default().join(", "),
+    )?;
+    data.tags = parse_list(&tags, ",");
+    Ok(())
+}
+
+fn non_empty(value: String) -> Option<String> {
+    (!value.is_empty()).then_some(value)
+}
+
+/// Splits `input` on `separator`, dropping blank items; `None` when nothing
+/// is left so the field is omitted from the card.
+fn parse_list(input: &str, separator: &str) -> Option<Vec<String>> {
+    let items: Vec<String> = input
+        .split(separator)
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect();
+    (!items.is_empty()).then_some(items)
+}
+
+fn interactive() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+fn editor_available() -> bool {
+    std::env::var("EDITOR").is_ok_and(|editor| !editor.trim().is_empty())
+}
+
+/// Prompts with the current value pre-filled. Without a terminal, an empty
+/// line keeps the current value.
+fn prompt_line(label: &str, current: &str) -> Result<String, Box<dyn Error>> {
+    let prompt = format!("{label}: ");
+    if interactive() {
+        let options = LineEditorOptions {
+            initial_text: current.to_string(),
+            allow_cancel: true,
+            mask_mode: MaskMode::None,
+        };
+        return prompt_line_editor(&prompt, &options)
+            .map(|value| value.trim().to_string())
+            .map_err(|err| Box::new(err) as Box<dyn Error>);
+    }
+
+    print!("{prompt}");
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let input = input.trim();
+    Ok(if input.is_empty() { current } else { input }.to_string())
+}
+
+fn prompt_long(label: &str, current: &str, suggest: bool) -> Result<String, Box<dyn Error>> {
+    if interactive() && editor_available() {
+        println!("{label}: {}", preview(current));
+        let question = format!("Edit {} in $EDITOR?", label.to_lowercase());
+        if !prompt_bool_with_default(&question, suggest)? {
+            return Ok(current.to_string());
+        }
+        if let Some(text) = edit_text_in_editor(current)? {
+            return Ok(text);
+        }
+    }
+    if current.contains('\n') {
+        println!("{label}: {}", preview(current));
+        println!("  Spans several lines; set $EDITOR to change it.");
+        return Ok(current.to_string());
+    }
+    prompt_line(label, current)
+}
+
+fn prompt_greetings(current: Option<&[String]>) -> Result<Option<Vec<String>>, Box<dyn Error>> {
+    let current = current.unwrap_or_default();
+    let count = current.len();
+    if !(interactive() && editor_available()) {
+        if count > 0 {
+            println!("Alternate greetings: {count} (set $EDITOR to change them)");
+        }
+        return Ok((count > 0).then(|| current.to_vec()));
+    }
+
+    println!("Alternate greetings: {count}");
+    if !prompt_bool_with_default("Edit alternate greetings in $EDITOR?", false)? {
+        return Ok((count > 0).then(|| current.to_vec()));
+    }
+    let joined = current.join(&format!("\n{GREETING_SEPARATOR}\n"));
+    let edited = edit_text_in_editor(&joined)?.unwrap_or(joined);
+    Ok(parse_list(&edited, &format!("\n{GREETING_SEPARATOR}\n")))
+}
+
+fn preview(text: &str) -> String {
+    const PREVIEW_CHARS: usize = 60;
+    let flattened = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flattened.is_empty() {
+        "(empty)".to_string()
+    } else if flattened.chars().count() > PREVIEW_CHARS {
+        let cut: String = flattened.chars().take(PREVIEW_CHARS).collect();
+        format!("{cut}…")
+    } else {
+        flattened
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_card_path_matches_normalized_lookup() {
+        let dir = Path::new("/cards");
+        assert_eq!(
+            new_card_path(dir, "  Ada Lovelace "),
+            dir.join("ada_lovelace.json")
+        );
+        assert_eq!(new_card_path(dir, "A/B?"), dir.join("a-b-.json"));
+    }
+
+    #[test]
+    fn parse_list_drops_blank_items() {
+        assert_eq!(
+            parse_list("fantasy, , elf ,", ","),
+            Some(vec!["fantasy".to_string(), "elf".to_string()])
+        );
+        assert_eq!(parse_list(" , ", ","), None);
+        assert_eq!(
+            parse_list("Hello!\n---\n\n---\nWelcome\nback.", "\n---\n"),
+            Some(vec!["Hello!".to_string(), "Welcome\nback.".to_string()])
+        );
+    }
+}
@@ -0,0 +1,73 @@
+//! `chabeau character export`: write a stored card out as JSON or PNG
+//!
+//! PNG exports embed the card into `--image`, or into the card's own image
+//! when the source is already a PNG card. Existing `chara`/`ccv3` metadata in
+//! that image is replaced.
+
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::character::loader::load_card;
+use crate::character::writer::{export_card, CardFormat};
+use crate::character::CharacterService;
+use crate::cli::character_edit::{card_file_stem, has_extension};
+
+pub struct ExportOptions {
+    pub name: String,
+    pub format: CardFormat,
+    pub image: Option<PathBuf>,
+    pub output: Option<PathBuf>,
+    pub force: bool,
+}
+
+pub fn export(options: ExportOptions) -> Result<(), Box<dyn Error>> {
+    let format = options.format;
+    let source = CharacterService::new().resolve_path(&options.name)?;
+    let card = load_card(&source)?;
+
+    let image = match format {
+        CardFormat::Json => None,
+        CardFormat::Png => Some(read_image(options.image.as_deref(), &source)?),
+    };
+    let bytes = export_card(&card, format, image.as_deref())?;
+
+    let output = options.output.unwrap_or_else(|| {
+        PathBuf::from(format!(
+            "{}.{}",
+            card_file_stem(&card.data.name),
+            format.extension()
+        ))
+    });
+    if output.exists() && !options.force {
+        return Err(format!(
+            "{} already exists. Use --force to overwrite.",
+            output.display()
+        )
+        .into());
+    }
+    fs::write(&output, bytes)
+        .map_err(|e| format!("Failed to write {}: {}", output.display(), e))?;
+
+    println!(
+        "✅ Exported character '{}' to {}",
+        card.data.name,
+        output.display()
+    );
+    Ok(())
+}
+
+fn read_image(image: Option<&Path>, source: &Path) -> Result<Vec<u8>, Box<dyn Error>> {
+    let path = match image {
+        Some(path) => path,
+        None if has_extension(source, "png") => source,
+        None => {
+            return Err(format!(
+                "{} has no image to embed the card in. Pass --image <avatar.png>.",
+                source.display()
+            )
+            .into())
+        }
+    };
+    fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e).into())
+}
@@ -0,0 +1,218 @@
+//! `chabeau mcp audit`: view and replay the MCP tool-call audit log
+//!
+//! Entries are numbered by their position in the log, so a number printed by
+//! the filtered listing can be passed straight to `chabeau mcp audit replay`.
+
+use crate::cli::mcp_inspect;
+use crate::core::config::data::Config;
+use crate::mcp::audit::{AuditFilter, AuditStatus, ToolAuditEntry, ToolAuditLog};
+use chrono::Local;
+use serde::Serialize;
+use serde_json::Value;
+use std::error::Error;
+
+/// Characters of arguments and payload shown per entry in the text listing.
+const PREVIEW_CHARS: usize = 120;
+
+pub fn list_entries(
+    server: Option<String>,
+    tool: Option<String>,
+    status: Option<&str>,
+    limit: usize,
+    json: bool,
+) -> Result<(), Box<dyn Error>> {
+    let status = match status {
+        Some(value) => Some(AuditStatus::parse(value).ok_or_else(|| {
+            format!("Unknown status '{value}'. Expected success, error, denied, or blocked.")
+        })?),
+        None => None,
+    };
+    let filter = AuditFilter {
+        server_id: server,
+        tool,
+        status,
+    };
+
+    let log = ToolAuditLog::new(ToolAuditLog::default_path());
+    let entries = log.read_entries()?;
+    let selected = select_entries(&entries, &filter, limit);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&selected)?);
+        return Ok(());
+    }
+    if Config::load()?.mcp_audit_log != Some(true) {
+        eprintln!("ℹ️  Audit logging is off (set `mcp_audit_log = true` in config.toml).");
+    }
+    if entries.is_empty() {
+        println!("No tool calls recorded in {}.", log.path().display());
+        return Ok(());
+    }
+    if selected.is_empty() {
+        println!("No tool calls match.");
+        return Ok(());
+    }
+    println!("MCP tool calls ({}):", log.path().display());
+    for numbered in &selected {
+        print_entry(numbered);
+    }
+    Ok(())
+}
+
+pub async fn replay(entry_number: usize, json: bool) -> Result<(), Box<dyn Error>> {
+    let log = ToolAuditLog::new(ToolAuditLog::default_path());
+    let entries = log.read_entries()?;
+    let Some(entry) = entry_number
+        .checked_sub(1)
+        .and_then(|index| entries.get(index))
+    else {
+        return Err(format!(
+            "Audit entry {entry_number} not found ({} recorded).",
+            entries.len()
+        )
+        .into());
+    };
+    let arguments = replay_arguments(entry)
+        .ok_or_else(|| format!("Audit entry {entry_number} has no JSON object arguments."))?;
+
+    eprintln!(
+        "↻ Replaying #{entry_number}: {} on {} (originally {}, {})",
+        entry.tool,
+        entry.server_id,
+        entry.status.label(),
+        entry.decision.label()
+    );
+    mcp_inspect::call_tool(&entry.server_id, &entry.tool, Some(&arguments), json).await
+}
+
+#[derive(Debug, Serialize)]
+struct NumberedEntry<'a> {
+    entry: usize,
+    #[serde(flatten)]
+    record: &'a ToolAuditEntry,
+}
+
+/// Keeps matching entries with their 1-based log position, limited to the
+/// most recent `limit` (0 keeps all).
+fn select_entries<'a>(
+    entries: &'a [ToolAuditEntry],
+    filter: &AuditFilter,
+    limit: usize,
+) -> Vec<NumberedEntry<'a>> {
+    let mut selected: Vec<NumberedEntry<'a>> = entries
+        .iter()
+        .enumerate()
+        .filter(|(_, record)| filter.matches(record))
+        .map(|(index, record)| NumberedEntry {
+            entry: index + 1,
+            record,
+        })
+        .collect();
+    if limit > 0 && selected.len() > limit {
+        selected.drain(..selected.len() - limit);
+    }
+    selected
+}
+
+/// Returns the logged arguments as a JSON object string, as `mcp call` takes.
+fn replay_arguments(entry: &ToolAuditEntry) -> Option<String> {
+    match &entry.arguments {
+        Value::Object(_) => Some(entry.arguments.to_string()),
+        Value::String(raw) if raw.trim().is_empty() => Some("{}".to_string()),
+        _ => None,
+    }
+}
+
+fn print_entry(numbered: &NumberedEntry<'_>) {
+    let record = numbered.record;
+    let latency = record
+        .latency_ms
+        .map(|ms| format!("  {ms} ms"))
+        .unwrap_or_default();
+    println!(
+        "  {}. {}  {} on {}  {}{}  [{}]",
+        numbered.entry,
+        record
+            .timestamp
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S"),
+        record.tool,
+        record.server_id,
+        record.status.label(),
+        latency,
+        record.decision.label()
+    );
+    println!("      args: {}", preview(&record.arguments.to_string()));
+    if !record.payload.trim().is_empty() {
+        println!("      result: {}", preview(&record.payload));
+    }
+}
+
+fn preview(text: &str) -> String {
+    let flattened = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flattened.chars().count() > PREVIEW_CHARS {
+        let cut: String = flattened.chars().take(PREVIEW_CHARS).collect();
+        format!("{cut}…")
+    } else {
+        flattened
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::audit::AuditDecision;
+
+    fn entry(server: &str, status: AuditStatus, arguments: &str) -> ToolAuditEntry {
+        ToolAuditEntry::new(
+            server,
+            "search",
+            arguments,
+            AuditDecision::Yolo,
+            status,
+            None,
+            "done",
+        )
+    }
+
+    #[test]
+    fn select_entries_keeps_log_numbers_and_most_recent_matches() {
+        let entries = vec![
+            entry("alpha", AuditStatus::Success, "{}"),
+            entry("beta", AuditStatus::Error, "{}"),
+            entry("alpha", AuditStatus::Error, "{}"),
+            entry("alpha", AuditStatus::Success, "{}"),
+        ];
+        let filter = AuditFilter {
+            server_id: Some("alpha".to_string()),
+            ..Default::default()
+        };
+
+        let numbers = |limit| {
+            select_entries(&entries, &filter, limit)
+                .iter()
+                .map(|numbered| numbered.entry)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(0), vec![1, 3, 4]);
+        assert_eq!(numbers(2), vec![3, 4]);
+
+        let json = serde_json::to_value(select_entries(&entries, &filter, 1)).unwrap();
+        assert_eq!(json[0]["entry"], 4);
+        assert_eq!(json[0]["server_id"], "alpha");
+    }
+
+    #[test]
+    fn replay_arguments_requires_an_object() {
+        assert_eq!(
+            replay_arguments(&entry("alpha", AuditStatus::Success, r#"{"q":"rust"}"#)).as_deref(),
+            Some(r#"{"q":"rust"}"#)
+        );
+        assert_eq!(
+            replay_arguments(&entry("alpha", AuditStatus::Success, "")).as_deref(),
+            Some("{}")
+        );
+        assert!(replay_arguments(&entry("alpha", AuditStatus::Success, "[1]")).is_none());
+        assert!(replay_arguments(&entry("alpha", AuditStatus::Success, "oops")).is_none());
+    }
+}
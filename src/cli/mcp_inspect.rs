@@ -0,0 +1,371 @@
+//! One-shot MCP server inspection commands
+//!
+//! These connect to a single configured server outside the TUI, run one
+//! request, print the result, and exit. `--json` prints the raw MCP result for
+//! scripting.
+
+use crate::core::app::session::{McpPromptRequest, ToolCallRequest};
+use crate::core::config::data::Config;
+use crate::core::mcp_auth::McpTokenStore;
+use crate::mcp::client::{
+    execute_prompt, execute_resource_read, execute_tool_call, McpClientManager,
+};
+use crate::mcp::subscriptions::resource_contents_text;
+use crate::mcp::tool_output::{render_content_block, McpToolOutput};
+use rust_mcp_schema::Role;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::error::Error;
+
+pub async fn list_tools(server: &str, json: bool) -> Result<(), Box<dyn Error>> {
+    let (mut manager, server_id) = connect(server).await?;
+    manager.refresh_tools(&server_id).await;
+    let state = server_state(&manager, &server_id)?;
+    let Some(tools) = state.cached_tools.as_ref() else {
+        return Err(listing_error(state.last_error.as_deref(), "tools"));
+    };
+
+    if json {
+        return print_json(tools);
+    }
+    if tools.tools.is_empty() {
+        println!("No tools reported by {}.", state.config.display_name);
+        return Ok(());
+    }
+    println!("Tools on {} ({}):", state.config.display_name, server_id);
+    for tool in &tools.tools {
+        match tool.description.as_deref().map(first_line) {
+            Some(description) if !description.is_empty() => {
+                println!("  - {}: {}", tool.name, description)
+            }
+            _ => println!("  - {}", tool.name),
+        }
+        if let Some(properties) = tool.input_schema.properties.as_ref() {
+            let mut names: Vec<String> = properties
+                .keys()
+                .map(|name| {
+                    if tool.input_schema.required.contains(name) {
+                        format!("{name}*")
+                    } else {
+                        name.clone()
+                    }
+                })
+                .collect();
+            names.sort();
+            if !names.is_empty() {
+                println!("      args: {}", names.join(", "));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub async fn call_tool(
+    server: &str,
+    tool: &str,
+    args: Option<&str>,
+    json: bool,
+) -> Result<(), Box<dyn Error>> {
+    let arguments = parse_tool_args(args)?;
+    let (mut manager, server_id) = connect(server).await?;
+    manager.refresh_tools(&server_id).await;
+
+    let state = server_state(&manager, &server_id)?;
+    let output_schema = state.tool_output_schema(tool).cloned();
+    if let Some(validator) = state
+        .tool_validator(tool)
+        .and_then(|cached| cached.validator.as_ref())
+    {
+        let instance = Value::Object(arguments.clone());
+        let violations = argument_violations(validator.iter_errors(&instance));
+        if !violations.is_empty() {
+            if json {
+                print_json(&serde_json::json!({
+                    "isError": true,
+                    "error": {
+                        "kind": "invalid_arguments",
+                        "tool": tool,
+                        "server_id": server_id,
+                        "violations": violations,
+                    }
+                }))?;
+            } else {
+                eprintln!("❌ Arguments for {tool} do not match its input schema:");
+                for violation in &violations {
+                    eprintln!("  - {}: {}", violation.path, violation.issue);
+                }
+            }
+            return Err("Tool arguments did not match the expected schema.".into());
+        }
+    }
+
+    let mut context = manager
+        .tool_call_context(&server_id)
+        .ok_or("MCP server not available.")?;
+    let request = ToolCallRequest {
+        server_id: server_id.clone(),
+        tool_name: tool.to_string(),
+        raw_arguments: Value::Object(arguments.clone()).to_string(),
+        arguments: Some(arguments),
+        tool_call_id: None,
+    };
+    let result = execute_tool_call(&mut context, &request).await?;
+
+    let is_error = result.is_error.unwrap_or(false);
+    if json {
+        print_json(&result)?;
+    } else {
+        let mut output = McpToolOutput::from_result(result);
+        output.validate(output_schema.as_ref());
+        println!("{}", output.render_text());
+    }
+    if is_error {
+        return Err(format!("Tool {tool} reported an error.").into());
+    }
+    Ok(())
+}
+
+pub async fn list_resources(server: &str, json: bool) -> Result<(), Box<dyn Error>> {
+    let (mut manager, server_id) = connect(server).await?;
+    manager.refresh_resources(&server_id).await;
+    manager.refresh_resource_templates(&server_id).await;
+    let state = server_state(&manager, &server_id)?;
+
+    if json {
+        return print_json(&serde_json::json!({
+            "resources": state.cached_resources.as_ref().map(|list| &list.resources),
+            "resourceTemplates": state
+                .cached_resource_templates
+                .as_ref()
+                .map(|list| &list.resource_templates),
+        }));
+    }
+
+    println!(
+        "Resources on {} ({}):",
+        state.config.display_name, server_id
+    );
+    match state.cached_resources.as_ref() {
+        Some(list) if !list.resources.is_empty() => {
+            for resource in &list.resources {
+                println!("  - {} ({})", resource.uri, resource.name);
+            }
+        }
+        Some(_) => println!("  (none)"),
+        None => println!("  (unavailable)"),
+    }
+    if let Some(list) = state.cached_resource_templates.as_ref() {
+        if !list.resource_templates.is_empty() {
+            println!("Resource templates:");
+            for template in &list.resource_templates {
+                println!("  - {} ({})", template.uri_template, template.name);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub async fn read_resource(server: &str, uri: &str, json: bool) -> Result<(), Box<dyn Error>> {
+    let (manager, server_id) = connect(server).await?;
+    let mut context = manager
+        .tool_call_context(&server_id)
+        .ok_or("MCP server not available.")?;
+    let result = execute_resource_read(&mut context, uri).await?;
+
+    if json {
+        return print_json(&result);
+    }
+    println!("{}", resource_contents_text(&result));
+    Ok(())
+}
+
+pub async fn get_prompt(
+    server: &str,
+    name: &str,
+    args: &[String],
+    json: bool,
+) -> Result<(), Box<dyn Error>> {
+    let arguments = parse_prompt_args(args)?;
+    let (manager, server_id) = connect(server).await?;
+    let mut context = manager
+        .prompt_call_context(&server_id)
+        .ok_or("MCP server not available.")?;
+    let request = McpPromptRequest {
+        server_id: server_id.clone(),
+        prompt_name: name.to_string(),
+        arguments,
+    };
+    let result = execute_prompt(&mut context, &request).await?;
+
+    if json {
+        return print_json(&result);
+    }
+    if let Some(description) = result.description.as_deref() {
+        println!("# {description}\n");
+    }
+    for message in &result.messages {
+        let role = match message.role {
+            Role::User => "user",
+            Role::Assistant => "assistant",
+        };
+        println!("[{role}]\n{}\n", render_content_block(&message.content));
+    }
+    Ok(())
+}
+
+/// Connects to one configured server, even if it is disabled for chat.
+async fn connect(server_input: &str) -> Result<(McpClientManager, String), Box<dyn Error>> {
+    let config = Config::load()?;
+    let Some(server) = config.get_mcp_server(server_input) else {
+        return Err(format!("MCP server '{server_input}' not found.").into());
+    };
+    let mut server = server.clone();
+    server.enabled = Some(true);
+    let server_id = server.id.clone();
+
+    let mut manager = McpClientManager::from_config(&Config {
+        mcp_servers: vec![server],
+        ..config
+    });
+    manager
+        .connect_server(&server_id, &McpTokenStore::new())
+        .await;
+
+    let state = server_state(&manager, &server_id)?;
+    if !state.connected {
+        return Err(format!(
+            "Failed to connect to {}: {}",
+            state.config.display_name,
+            state.last_error.as_deref().unwrap_or("unknown error")
+        )
+        .into());
+    }
+    Ok((manager, server_id))
+}
+
+fn server_state<'a>(
+    manager: &'a McpClientManager,
+    server_id: &str,
+) -> Result<&'a crate::mcp::client::McpServerState, Box<dyn Error>> {
+    manager
+        .server(server_id)
+        .ok_or_else(|| format!("MCP server '{server_id}' not found.").into())
+}
+
+fn listing_error(last_error: Option<&str>, what: &str) -> Box<dyn Error> {
+    match last_error {
+        Some(err) => format!("Failed to list {what}: {err}").into(),
+        None => format!("Server did not return {what}.").into(),
+    }
+}
+
+fn parse_tool_args(args: Option<&str>) -> Result<Map<String, Value>, Box<dyn Error>> {
+    let Some(raw) = args.map(str::trim).filter(|raw| !raw.is_empty()) else {
+        return Ok(Map::new());
+    };
+    match serde_json::from_str::<Value>(raw) {
+        Ok(Value::Object(map)) => Ok(map),
+        Ok(_) => Err("--args must be a JSON object.".into()),
+        Err(err) => Err(format!("Invalid --args JSON: {err}").into()),
+    }
+}
+
+/// Parses repeated `--arg KEY=VALUE` flags; values may contain `=` or commas.
+fn parse_prompt_args(args: &[String]) -> Result<HashMap<String, String>, Box<dyn Error>> {
+    let mut arguments = HashMap::new();
+    for arg in args {
+        let Some((key, value)) = arg.split_once('=') else {
+            return Err(format!("Invalid prompt argument '{arg}'. Expected KEY=VALUE.").into());
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            return Err("Prompt argument name cannot be empty.".into());
+        }
+        arguments.insert(key.to_string(), value.to_string());
+    }
+    Ok(arguments)
+}
+
+#[derive(Debug, Serialize)]
+struct ArgumentViolation {
+    path: String,
+    issue: String,
+}
+
+fn argument_violations<'a>(
+    errors: impl Iterator<Item = jsonschema::ValidationError<'a>>,
+) -> Vec<ArgumentViolation> {
+    errors
+        .map(|error| {
+            let path = error.instance_path().to_string();
+            ArgumentViolation {
+                path: if path.is_empty() {
+                    "/".to_string()
+                } else {
+                    path
+                },
+                issue: error.to_string(),
+            }
+        })
+        .collect()
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), Box<dyn Error>> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or_default().trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_tool_args_requires_json_object() {
+        assert!(parse_tool_args(None).unwrap().is_empty());
+        assert_eq!(
+            parse_tool_args(Some(r#"{"q":"rust"}"#)).unwrap()["q"],
+            Value::from("rust")
+        );
+        assert_eq!(
+            parse_tool_args(Some("[1]")).unwrap_err().to_string(),
+            "--args must be a JSON object."
+        );
+        assert!(parse_tool_args(Some("{oops"))
+            .unwrap_err()
+            .to_string()
+            .starts_with("Invalid --args JSON"));
+    }
+
+    #[test]
+    fn parse_prompt_args_keeps_commas_in_values() {
+        let arguments =
+            parse_prompt_args(&["topic=rust, go".to_string(), "q=a=b".to_string()]).unwrap();
+        assert_eq!(arguments["topic"], "rust, go");
+        assert_eq!(arguments["q"], "a=b");
+        assert!(parse_prompt_args(&["novalue".to_string()]).is_err());
+        assert!(parse_prompt_args(&["=x".to_string()]).is_err());
+    }
+
+    #[test]
+    fn argument_violations_report_paths() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": { "limit": { "type": "integer" } },
+            "required": ["q"]
+        });
+        let validator = crate::mcp::client::build_tool_schema_validator(&schema).unwrap();
+        let instance = serde_json::json!({ "limit": "ten" });
+        let mut violations = argument_violations(validator.iter_errors(&instance));
+        violations.sort_by(|a, b| a.path.cmp(&b.path));
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].path, "/");
+        assert!(violations[0].issue.contains("\"q\""));
+        assert_eq!(violations[1].path, "/limit");
+    }
+}
@@ -0,0 +1,730 @@
+//! `chabeau mcp serve`: expose Chabeau itself as a stdio MCP server
+//!
+//! Other MCP clients can reuse the providers, keyring credentials, personas,
+//! presets and character cards configured here. The server offers a `chat`
+//! tool that runs one completion through [`request_chat_completion`], lists
+//! character cards and presets as resources, and exposes presets as prompts.
+//! Messages are newline-delimited JSON-RPC on stdin/stdout; diagnostics go to
+//! stderr so they never corrupt the protocol stream.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::Arc;
+
+use rust_mcp_schema::schema_utils::{
+    ClientMessage, FromMessage, MessageFromServer, RequestFromClient, ResultFromServer,
+    ServerMessage,
+};
+use rust_mcp_schema::{
+    CallToolRequestParams, CallToolResult, ContentBlock, GetPromptRequestParams, GetPromptResult,
+    Implementation, InitializeRequestParams, InitializeResult, ListPromptsResult,
+    ListResourcesResult, ListToolsResult, Prompt, PromptArgument, PromptMessage, ProtocolVersion,
+    ReadResourceResult, RequestId, Resource, Role, RpcError, ServerCapabilities,
+    ServerCapabilitiesPrompts, ServerCapabilitiesResources, ServerCapabilitiesTools, TextContent,
+    TextResourceContents, Tool, ToolInputSchema, LATEST_PROTOCOL_VERSION,
+};
+use serde_json::{Map, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::api::ChatRequest;
+use crate::character::CharacterService;
+use crate::core::app::session::exit_if_env_only_missing_env;
+use crate::core::app::{self, AppInitConfig};
+use crate::core::chat_stream::request_chat_completion;
+use crate::core::config::data::{Config, Preset};
+use crate::core::macros::{MacroContext, MacroState};
+use crate::core::mcp_resource_mentions::percent_encode;
+use crate::core::persona::PersonaManager;
+use crate::core::preset::PresetManager;
+
+/// Name of the tool that runs a chat completion.
+pub const CHAT_TOOL: &str = "chat";
+
+const CHARACTER_URI_PREFIX: &str = "chabeau://character/";
+const PRESET_URI_PREFIX: &str = "chabeau://preset/";
+const JSON_MIME_TYPE: &str = "application/json";
+
+/// Runs the server until stdin closes.
+pub async fn serve(env_only: bool) -> Result<(), Box<dyn Error>> {
+    exit_if_env_only_missing_env(env_only);
+    let server = Arc::new(McpServe::new(Config::load()?, env_only));
+
+    let (reply_tx, mut reply_rx) = mpsc::unbounded_channel::<String>();
+    let writer = tokio::spawn(async move {
+        let mut stdout = tokio::io::stdout();
+        while let Some(reply) = reply_rx.recv().await {
+            let line = format!("{reply}\n");
+            if stdout.write_all(line.as_bytes()).await.is_err() || stdout.flush().await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        // Requests run concurrently so a slow chat call does not block pings
+        // or listings; replies carry their request id.
+        let server = Arc::clone(&server);
+        let reply_tx = reply_tx.clone();
+        tokio::spawn(async move {
+            if let Some(reply) = server.handle_line(&line).await {
+                let _ = reply_tx.send(reply);
+            }
+        });
+    }
+
+    drop(reply_tx);
+    writer.await?;
+    Ok(())
+}
+
+/// Request handler shared by all in-flight requests.
+pub struct McpServe {
+    config: Config,
+    env_only: bool,
+}
+
+impl McpServe {
+    pub fn new(config: Config, env_only: bool) -> Self {
+        Self { config, env_only }
+    }
+
+    /// Handles one JSON-RPC line and returns the serialized reply, if any.
+    pub async fn handle_line(&self, line: &str) -> Option<String> {
+        let message = match line.parse::<ClientMessage>() {
+            Ok(message) => message,
+            Err(err) => {
+                warn!(error = %err.message, "Ignoring unparseable MCP message");
+                return reply(None, MessageFromServer::Error(err));
+            }
+        };
+
+        let request = match message {
+            ClientMessage::Request(request) => request,
+            // Notifications (`initialized`, `cancelled`) need no reply, and
+            // this server never sends requests that would produce responses.
+            ClientMessage::Notification(_)
+            | ClientMessage::Response(_)
+            | ClientMessage::Error(_) => return None,
+        };
+
+        let id = request.request_id().clone();
+        let message = match self.dispatch(RequestFromClient::from(request)).await {
+            Ok(result) => MessageFromServer::ResultFromServer(result),
+            Err(err) => MessageFromServer::Error(err),
+        };
+        reply(Some(id), message)
+    }
+
+    async fn dispatch(&self, request: RequestFromClient) -> Result<ResultFromServer, RpcError> {
+        match request {
+            RequestFromClient::InitializeRequest(params) => Ok(initialize(&params).into()),
+            RequestFromClient::PingRequest(_) => Ok(rust_mcp_schema::Result::default().into()),
+            RequestFromClient::ListToolsRequest(_) => Ok(ListToolsResult {
+                meta: None,
+                next_cursor: None,
+                tools: vec![chat_tool()],
+            }
+            .into()),
+            RequestFromClient::CallToolRequest(params) => self.call_tool(params).await,
+            RequestFromClient::ListResourcesRequest(_) => Ok(self.list_resources().into()),
+            RequestFromClient::ReadResourceRequest(params) => {
+                self.read_resource(&params.uri).map(Into::into)
+            }
+            RequestFromClient::ListPromptsRequest(_) => Ok(self.list_prompts()?.into()),
+            RequestFromClient::GetPromptRequest(params) => self.get_prompt(params).map(Into::into),
+            other => Err(RpcError::method_not_found()
+                .with_message(format!("Method not supported: {}", other.method()))),
+        }
+    }
+
+    async fn call_tool(&self, params: CallToolRequestParams) -> Result<ResultFromServer, RpcError> {
+        if params.name != CHAT_TOOL {
+            return Err(
+                RpcError::invalid_params().with_message(format!("Unknown tool: {}", params.name))
+            );
+        }
+        let arguments = match ChatArguments::parse(params.arguments.unwrap_or_default()) {
+            Ok(arguments) => arguments,
+            Err(err) => return Ok(tool_error(err).into()),
+        };
+        let result = match self.chat(arguments).await {
+            Ok(content) => {
+                CallToolResult::text_content(vec![TextContent::new(content, None, None)])
+            }
+            Err(err) => tool_error(err),
+        };
+        Ok(result.into())
+    }
+
+    /// Runs one completion with the requested provider, model, persona,
+    /// preset and character, exactly as a fresh TUI session would.
+    async fn chat(&self, arguments: ChatArguments) -> Result<String, String> {
+        let ChatArguments {
+            message,
+            provider,
+            model,
+            preset,
+            character,
+            persona,
+        } = arguments;
+        let mut app = app::new_with_auth(
+            AppInitConfig {
+                model: model.unwrap_or_else(|| "default".to_string()),
+                log_file: None,
+                provider,
+                env_only: self.env_only,
+                pre_resolved_session: None,
+                character,
+                no_default_character: false,
+                persona,
+                preset,
+                disable_mcp: true,
+            },
+            &self.config,
+            CharacterService::new(),
+        )
+        .await
+        .map_err(|err| err.to_string())?;
+
+        let messages = app.conversation().add_user_message(message);
+        let request = ChatRequest {
+            model: app.session.model.clone(),
+            messages,
+            stream: false,
+            tools: None,
+            max_tokens: None,
+            temperature: None,
+            stop: None,
+        };
+        request_chat_completion(
+            &app.session.client,
+            &app.session.base_url,
+            &app.session.api_key,
+            &app.session.provider_name,
+            request,
+        )
+        .await
+        .map(|result| result.content)
+    }
+
+    fn list_resources(&self) -> ListResourcesResult {
+        let mut resources = Vec::new();
+        match CharacterService::new().list_metadata() {
+            Ok(mut cards) => {
+                cards.sort_by(|a, b| a.name.cmp(&b.name));
+                resources.extend(cards.into_iter().map(|card| {
+                    json_resource(
+                        character_uri(&card.name),
+                        card.name,
+                        Some(card.description).filter(|text| !text.trim().is_empty()),
+                    )
+                }));
+            }
+            Err(err) => warn!(error = %err, "Could not list character cards"),
+        }
+        match PresetManager::load_presets(&self.config) {
+            Ok(presets) => {
+                resources.extend(presets.list_presets().iter().map(|preset| {
+                    json_resource(
+                        preset_uri(&preset.id),
+                        format!("preset: {}", preset.id),
+                        None,
+                    )
+                }));
+            }
+            Err(err) => warn!(error = %err, "Could not load presets"),
+        }
+        ListResourcesResult {
+            meta: None,
+            next_cursor: None,
+            resources,
+        }
+    }
+
+    fn read_resource(&self, uri: &str) -> Result<ReadResourceResult, RpcError> {
+        let text = if uri.starts_with(CHARACTER_URI_PREFIX) {
+            let mut service = CharacterService::new();
+            let name = service
+                .list_metadata()
+                .map_err(|err| RpcError::internal_error().with_message(err.to_string()))?
+                .into_iter()
+                .map(|card| card.name)
+                .find(|name| character_uri(name) == uri)
+                .ok_or_else(|| resource_not_found(uri))?;
+            let card = service
+                .resolve_by_name(&name)
+                .map_err(|err| RpcError::internal_error().with_message(err.to_string()))?;
+            serde_json::to_string_pretty(&card)
+        } else if uri.starts_with(PRESET_URI_PREFIX) {
+            let presets = self.load_presets()?;
+            let preset = presets
+                .list_presets()
+                .iter()
+                .find(|preset| preset_uri(&preset.id) == uri)
+                .ok_or_else(|| resource_not_found(uri))?;
+            serde_json::to_string_pretty(preset)
+        } else {
+            return Err(resource_not_found(uri));
+        }
+        .map_err(|err| RpcError::internal_error().with_message(err.to_string()))?;
+
+        Ok(ReadResourceResult {
+            contents: vec![TextResourceContents {
+                meta: None,
+                mime_type: Some(JSON_MIME_TYPE.to_string()),
+                text,
+                uri: uri.to_string(),
+            }
+            .into()],
+            meta: None,
+        })
+    }
+
+    fn list_prompts(&self) -> Result<ListPromptsResult, RpcError> {
+        let presets = self.load_presets()?;
+        let prompts = presets
+            .list_presets()
+            .iter()
+            .map(|preset| Prompt {
+                arguments: vec![PromptArgument {
+                    description: Some(
+                        "Text placed between the preset's opening and closing instructions."
+                            .to_string(),
+                    ),
+                    name: "message".to_string(),
+                    required: Some(false),
+                    title: None,
+                }],
+                description: Some(format!("Chabeau preset {}", preset.id)),
+                icons: Vec::new(),
+                meta: None,
+                name: preset.id.clone(),
+                title: None,
+            })
+            .collect();
+        Ok(ListPromptsResult {
+            meta: None,
+            next_cursor: None,
+            prompts,
+        })
+    }
+
+    fn get_prompt(&self, params: GetPromptRequestParams) -> Result<GetPromptResult, RpcError> {
+        let presets = self.load_presets()?;
+        let preset = presets.find_preset_by_id(&params.name).ok_or_else(|| {
+            RpcError::invalid_params().with_message(format!("Unknown prompt: {}", params.name))
+        })?;
+        let personas = PersonaManager::load_personas(&self.config)
+            .map_err(|err| RpcError::internal_error().with_message(err.to_string()))?;
+        let arguments = params.arguments.unwrap_or_default();
+        let text = render_preset_prompt(preset, &arguments, &personas, &self.config);
+
+        Ok(GetPromptResult {
+            description: Some(format!("Chabeau preset {}", preset.id)),
+            messages: vec![PromptMessage {
+                content: ContentBlock::from(TextContent::new(text, None, None)),
+                role: Role::User,
+            }],
+            meta: None,
+        })
+    }
+
+    fn load_presets(&self) -> Result<PresetManager, RpcError> {
+        PresetManager::load_presets(&self.config)
+            .map_err(|err| RpcError::internal_error().with_message(err.to_string()))
+    }
+}
+
+/// Arguments accepted by the `chat` tool.
+#[derive(Debug, Default, PartialEq)]
+struct ChatArguments {
+    message: String,
+    provider: Option<String>,
+    model: Option<String>,
+    preset: Option<String>,
+    character: Option<String>,
+    persona: Option<String>,
+}
+
+impl ChatArguments {
+    fn parse(mut arguments: Map<String, Value>) -> Result<Self, String> {
+        let mut take = |name: &str| -> Result<Option<String>, String> {
+            match arguments.remove(name) {
+                None | Some(Value::Null) => Ok(None),
+                Some(Value::String(value)) => {
+                    Ok(Some(value.trim().to_string()).filter(|value| !value.is_empty()))
+                }
+                Some(_) => Err(format!("Argument '{name}' must be a string.")),
+            }
+        };
+        let message = take("message")?.ok_or("Argument 'message' is required.")?;
+        Ok(Self {
+            message,
+            provider: take("provider")?,
+            model: take("model")?,
+            preset: take("preset")?,
+            character: take("character")?,
+            persona: take("persona")?,
+        })
+    }
+}
+
+fn initialize(params: &InitializeRequestParams) -> InitializeResult {
+    // Answer with the client's version when we speak it, as the spec asks.
+    let protocol_version = if ProtocolVersion::supported_versions(false)
+        .iter()
+        .any(|version| version.to_string() == params.protocol_version)
+    {
+        params.protocol_version.clone()
+    } else {
+        LATEST_PROTOCOL_VERSION.to_string()
+    };
+    InitializeResult {
+        capabilities: ServerCapabilities {
+            prompts: Some(ServerCapabilitiesPrompts::default()),
+            resources: Some(ServerCapabilitiesResources::default()),
+            tools: Some(ServerCapabilitiesTools::default()),
+            ..ServerCapabilities::default()
+        },
+        instructions: Some(
+            "Use the chat tool to ask a model configured in Chabeau. Character cards and presets \
+             are available as resources; presets are also available as prompts."
+                .to_string(),
+        ),
+        meta: None,
+        protocol_version,
+        server_info: Implementation {
+            description: Some(
+                "Chabeau's providers, personas, presets and character cards".to_string(),
+            ),
+            icons: Vec::new(),
+            name: "chabeau".to_string(),
+            title: Some("Chabeau".to_string()),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            website_url: None,
+        },
+    }
+}
+
+fn chat_tool() -> Tool {
+    let string_property = |description: &str| {
+        let mut property = Map::new();
+        property.insert("type".to_string(), Value::from("string"));
+        property.insert("description".to_string(), Value::from(description));
+        property
+    };
+    let properties = HashMap::from([
+        (
+            "message".to_string(),
+            string_property("User message to send."),
+        ),
+        (
+            "provider".to_string(),
+            string_property("Provider id; defaults to the configured default provider."),
+        ),
+        (
+            "model".to_string(),
+            string_property("Model id; defaults to the provider's default model."),
+        ),
+        (
+            "preset".to_string(),
+            string_property(
+                "Preset id applied to the conversation; comma-separate ids to stack several.",
+            ),
+        ),
+        (
+            "character".to_string(),
+            string_property("Character card name or path."),
+        ),
+        (
+            "persona".to_string(),
+            string_property("Persona id for the user."),
+        ),
+    ]);
+
+    Tool {
+        annotations: None,
+        description: Some(
+            "Send one message to a model configured in Chabeau and return its reply. Uses the \
+             stored provider credentials and the default persona, preset and character unless \
+             overridden."
+                .to_string(),
+        ),
+        execution: None,
+        icons: Vec::new(),
+        input_schema: ToolInputSchema::new(vec!["message".to_string()], Some(properties), None),
+        meta: None,
+        name: CHAT_TOOL.to_string(),
+        output_schema: None,
+        title: Some("Chat".to_string()),
+    }
+}
+
+/// The preset's instructions around the client's `message`. Macros are
+/// expanded in the instructions only; the message is passed through as is.
+fn render_preset_prompt(
+    preset: &Preset,
+    arguments: &HashMap<String, String>,
+    personas: &PersonaManager,
+    config: &Config,
+) -> String {
+    let message = arguments.get("message").map(String::as_str).unwrap_or("");
+    let model = config
+        .default_provider
+        .as_deref()
+        .and_then(|provider| config.get_default_model(provider))
+        .map(String::as_str);
+    let state = MacroState::default();
+    let macros = MacroContext {
+        model,
+        last_message: Some(message),
+        state: Some(&state),
+        ..MacroContext::default()
+    };
+    let pre = personas.expand_macros(&preset.pre, &macros);
+    let post = personas.expand_macros(&preset.post, &macros);
+    [pre.trim(), message, post.trim()]
+        .into_iter()
+        .filter(|part| !part.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn character_uri(name: &str) -> String {
+    format!("{CHARACTER_URI_PREFIX}{}", percent_encode(name, false))
+}
+
+fn preset_uri(id: &str) -> String {
+    format!("{PRESET_URI_PREFIX}{}", percent_encode(id, false))
+}
+
+fn json_resource(uri: String, name: String, description: Option<String>) -> Resource {
+    Resource {
+        annotations: None,
+        description,
+        icons: Vec::new(),
+        meta: None,
+        mime_type: Some(JSON_MIME_TYPE.to_string()),
+        name,
+        size: None,
+        title: None,
+        uri,
+    }
+}
+
+fn resource_not_found(uri: &str) -> RpcError {
+    RpcError::invalid_params().with_message(format!("Resource not found: {uri}"))
+}
+
+fn tool_error(message: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![TextContent::new(message, None, None).into()],
+        is_error: Some(true),
+        meta: None,
+        structured_content: None,
+    }
+}
+
+fn reply(id: Option<RequestId>, message: MessageFromServer) -> Option<String> {
+    match ServerMessage::from_message(message, id) {
+        Ok(message) => Some(message.to_string()),
+        Err(err) => {
+            warn!(error = %err.message, "Could not serialize MCP reply");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::with_test_config_env;
+
+    fn server() -> McpServe {
+        McpServe::new(
+            Config {
+                builtin_presets: Some(false),
+                presets: vec![Preset {
+                    id: "terse".to_string(),
+                    pre: "Answer {{user}} briefly.".to_string(),
+                    post: "No lists.".to_string(),
+                    ..Default::default()
+                }],
+                ..Config::default()
+            },
+            false,
+        )
+    }
+
+    fn request(server: &McpServe, method: &str, params: Value) -> Value {
+        let line = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": method,
+            "params": params,
+        })
+        .to_string();
+        let reply = tokio::runtime::Runtime::new()
+            .expect("runtime")
+            .block_on(server.handle_line(&line))
+            .expect("reply");
+        let reply: Value = serde_json::from_str(&reply).expect("json reply");
+        assert_eq!(reply["id"], 7);
+        reply
+    }
+
+    #[test]
+    fn initialize_echoes_supported_protocol_version() {
+        let server = server();
+        let reply = request(
+            &server,
+            "initialize",
+            serde_json::json!({
+                "protocolVersion": "2025-06-18",
+                "capabilities": {},
+                "clientInfo": { "name": "test", "version": "1" }
+            }),
+        );
+        assert_eq!(reply["result"]["protocolVersion"], "2025-06-18");
+        assert_eq!(reply["result"]["serverInfo"]["name"], "chabeau");
+        assert!(reply["result"]["capabilities"]["tools"].is_object());
+        assert!(reply["result"]["capabilities"]["prompts"].is_object());
+    }
+
+    #[test]
+    fn lists_chat_tool_and_rejects_calls_without_message() {
+        let server = server();
+        let reply = request(&server, "tools/list", serde_json::json!({}));
+        let tools = reply["result"]["tools"].as_array().expect("tools");
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0]["name"], CHAT_TOOL);
+        assert_eq!(tools[0]["inputSchema"]["required"][0], "message");
+
+        let reply = request(
+            &server,
+            "tools/call",
+            serde_json::json!({ "name": "chat", "arguments": { "model": "gpt" } }),
+        );
+        assert_eq!(reply["result"]["isError"], true);
+        assert_eq!(
+            reply["result"]["content"][0]["text"],
+            "Argument 'message' is required."
+        );
+
+        let reply = request(
+            &server,
+            "tools/call",
+            serde_json::json!({ "name": "other", "arguments": {} }),
+        );
+        assert_eq!(reply["error"]["code"], -32602);
+    }
+
+    #[test]
+    fn presets_are_prompts_and_resources() {
+        with_test_config_env(|_| {
+            let server = server();
+            let reply = request(&server, "prompts/list", serde_json::json!({}));
+            assert_eq!(reply["result"]["prompts"][0]["name"], "terse");
+
+            let reply = request(
+                &server,
+                "prompts/get",
+                serde_json::json!({ "name": "terse", "arguments": { "message": "Why?" } }),
+            );
+            assert_eq!(
+                reply["result"]["messages"][0]["content"]["text"],
+                "Answer Anon briefly.\n\nWhy?\n\nNo lists."
+            );
+
+            let reply = request(&server, "resources/list", serde_json::json!({}));
+            let resources = reply["result"]["resources"].as_array().expect("resources");
+            assert!(resources
+                .iter()
+                .any(|resource| resource["uri"] == "chabeau://preset/terse"));
+
+            let reply = request(
+                &server,
+                "resources/read",
+                serde_json::json!({ "uri": "chabeau://preset/terse" }),
+            );
+            let text = reply["result"]["contents"][0]["text"]
+                .as_str()
+                .expect("text");
+            assert!(text.contains("No lists."));
+
+            let reply = request(
+                &server,
+                "resources/read",
+                serde_json::json!({ "uri": "chabeau://preset/missing" }),
+            );
+            assert!(reply["error"]["message"]
+                .as_str()
+                .is_some_and(|message| message.contains("Resource not found")));
+        });
+    }
+
+    #[test]
+    fn preset_prompts_expand_macros_around_the_verbatim_message() {
+        let preset = Preset {
+            id: "vars".to_string(),
+            pre: "{{setvar::tone::dry}}Use {{model}}.".to_string(),
+            post: "Be {{getvar::tone}} about: {{lastMessage}}".to_string(),
+            ..Default::default()
+        };
+        let config = Config {
+            default_provider: Some("openai".to_string()),
+            default_models: HashMap::from([("openai".to_string(), "gpt-4o".to_string())]),
+            ..Config::default()
+        };
+        let personas = PersonaManager::load_personas(&config).expect("personas");
+        let arguments = HashMap::from([("message".to_string(), " {{user}} asks ".to_string())]);
+
+        assert_eq!(
+            render_preset_prompt(&preset, &arguments, &personas, &config),
+            "Use gpt-4o.\n\n {{user}} asks \n\nBe dry about:  {{user}} asks"
+        );
+    }
+
+    #[test]
+    fn unsupported_methods_return_method_not_found() {
+        let server = server();
+        let reply = request(
+            &server,
+            "logging/setLevel",
+            serde_json::json!({ "level": "info" }),
+        );
+        assert_eq!(reply["error"]["code"], -32601);
+    }
+
+    #[test]
+    fn chat_arguments_trim_and_require_strings() {
+        let arguments = ChatArguments::parse(
+            serde_json::json!({ "message": " hi ", "preset": "", "model": "gpt-4o" })
+                .as_object()
+                .cloned()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            arguments,
+            ChatArguments {
+                message: "hi".to_string(),
+                model: Some("gpt-4o".to_string()),
+                ..ChatArguments::default()
+            }
+        );
+        assert!(ChatArguments::parse(
+            serde_json::json!({ "message": "hi", "provider": 3 })
+                .as_object()
+                .cloned()
+                .unwrap()
+        )
+        .is_err());
+    }
+}
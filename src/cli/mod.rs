@@ -2,11 +2,17 @@
 //!
 //! This module handles parsing command-line arguments and executing the appropriate commands.
 
+pub mod character_edit;
+pub mod character_export;
 pub mod character_list;
+pub mod mcp_audit;
+pub mod mcp_inspect;
+pub mod mcp_serve;
 pub mod model_list;
 pub mod provider_list;
 pub mod say;
 pub mod settings;
+pub mod st_import;
 pub mod theme_list;
 
 use std::error::Error;
@@ -20,6 +26,7 @@ use clap::{Parser, Subcommand};
 // Import specific items we need
 use crate::auth::prompt_provider_token;
 use crate::auth::AuthManager;
+use crate::character::writer::CardFormat;
 use crate::character::CharacterService;
 use crate::cli::character_list::list_characters;
 use crate::cli::model_list::list_models;
@@ -29,6 +36,7 @@ use crate::cli::theme_list::list_themes;
 use crate::core::builtin_providers::{find_builtin_provider, load_builtin_providers};
 use crate::core::config::data::{Config, CustomProvider, McpServerConfig};
 use crate::core::mcp_auth::{McpOAuthGrant, McpTokenStore};
+use crate::core::message::Message;
 use crate::core::oauth::{
     apply_oauth_token_response, build_authorization_url, current_unix_epoch_s, exchange_oauth_code,
     open_in_browser, pkce_s256_challenge, probe_oauth_support, random_urlsafe,
@@ -36,6 +44,7 @@ use crate::core::oauth::{
     AuthorizationUrlParams, OAuthMetadata,
 };
 use crate::core::persona::PersonaManager;
+use crate::mcp::policies::describe_policy;
 use crate::ui::chat_loop::run_chat;
 use crate::utils::line_editor::{prompt_line_editor, LineEditorOptions, MaskMode};
 use crate::utils::url::normalize_base_url;
@@ -119,6 +128,7 @@ To select providers (e.g., Anthropic, OpenAI) and their models:\n\
   • It will then give you a choice of models.\n\n\
 Character cards:\n\
   • Import character cards with 'chabeau import <file.json|file.png>'.\n\
+  • Continue a SillyTavern chat with 'chabeau import --chat <chat.jsonl>'.\n\
   • Use '-c [CHARACTER]' to start a chat with a specific character:\n\
     - By name: '-c alice' (looks in {cards_dir})\n\
     - By path: '-c ./alice.json' or '-c /path/to/alice.json'\n\
@@ -164,8 +174,8 @@ pub struct Args {
     #[arg(long, value_name = "PERSONA")]
     pub persona: Option<String>,
 
-    /// Preset to use for this session
-    #[arg(long, value_name = "PRESET")]
+    /// Preset to use for this session; comma-separate IDs to stack several
+    #[arg(long, value_name = "PRESET[,PRESET...]")]
     pub preset: Option<String>,
 
     /// Print version information
@@ -205,17 +215,45 @@ pub enum Commands {
     },
     /// List available themes (built-in and custom)
     Themes,
-    /// Import and validate a character card
+    /// Import and validate a character card, or import a SillyTavern chat or preset
     Import {
-        /// Path to character card file (JSON or PNG)
-        #[arg(value_name = "CARD")]
-        card: String,
-        /// Force overwrite if card already exists
+        /// Path to character card file (JSON, PNG, or CHARX)
+        #[arg(
+            value_name = "CARD",
+            required_unless_present_any = ["chat", "st_preset"],
+            conflicts_with_all = ["chat", "st_preset"]
+        )]
+        card: Option<String>,
+        /// SillyTavern chat (JSONL) to open with its history and character
+        #[arg(long, value_name = "FILE", conflicts_with = "st_preset")]
+        chat: Option<PathBuf>,
+        /// SillyTavern prompt-manager preset (JSON) to add to config.toml
+        #[arg(long = "preset", value_name = "FILE")]
+        st_preset: Option<PathBuf>,
+        /// ID for the imported preset (defaults to the file name)
+        #[arg(long, value_name = "ID", conflicts_with_all = ["card", "chat"])]
+        id: Option<String>,
+        /// Force overwrite if the card or preset already exists
         #[arg(short = 'f', long)]
         force: bool,
     },
-    /// Send a single-turn message to a model without launching the TUI (MCP is disabled in this mode)
+    /// Create, edit, or export character cards
+    Character {
+        #[command(subcommand)]
+        command: CharacterCommands,
+    },
+    /// Send a single-turn message to a model without launching the TUI
+    ///
+    /// MCP is off unless --mcp is given. Tools then run only when allowed by
+    /// YOLO mode, an allow policy, or --allow-tool; a call that would need
+    /// approval stops with an error.
     Say {
+        /// Connect MCP servers (all enabled, or a comma-separated list with --mcp=a,b)
+        #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "", value_name = "SERVERS")]
+        mcp: Option<String>,
+        /// Let a tool run without approval (server:tool or tool; repeatable)
+        #[arg(long = "allow-tool", value_name = "TOOL", requires = "mcp")]
+        allow_tool: Vec<String>,
         /// The prompt to send to the model
         prompt: Vec<String>,
     },
@@ -226,6 +264,37 @@ pub enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+pub enum CharacterCommands {
+    /// Create a new JSON card in the cards directory
+    New {
+        /// Character name
+        name: String,
+    },
+    /// Edit an existing card (JSON or PNG) in place
+    Edit {
+        /// Character name or path to a card file
+        name: String,
+    },
+    /// Export a card as JSON or as a PNG with embedded metadata
+    Export {
+        /// Character name or path to a card file
+        name: String,
+        /// Output format
+        #[arg(long, value_enum, ignore_case = true, default_value_t = CardFormat::Json)]
+        format: CardFormat,
+        /// Image to embed the card in (defaults to the card's own PNG)
+        #[arg(long, value_name = "PNG")]
+        image: Option<PathBuf>,
+        /// Output file (defaults to <name>.<format> in the current directory)
+        #[arg(short = 'o', long, value_name = "PATH")]
+        output: Option<PathBuf>,
+        /// Overwrite the output file if it exists
+        #[arg(short = 'f', long)]
+        force: bool,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum ProviderCommands {
     /// List configured providers and token status
@@ -307,6 +376,114 @@ pub enum McpCommands {
         #[command(subcommand)]
         command: McpOauthCommands,
     },
+    /// List the tools a server exposes
+    Tools {
+        /// MCP server id from config.toml
+        server: String,
+        /// Print the raw MCP result as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Call a tool and print its result
+    Call {
+        /// MCP server id from config.toml
+        server: String,
+        /// Tool name
+        tool: String,
+        /// Tool arguments as a JSON object
+        #[arg(long)]
+        args: Option<String>,
+        /// Print the raw MCP result as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// List the resources and resource templates a server exposes
+    Resources {
+        /// MCP server id from config.toml
+        server: String,
+        /// Print the raw MCP result as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Read a resource and print its contents
+    Read {
+        /// MCP server id from config.toml
+        server: String,
+        /// Resource URI
+        uri: String,
+        /// Print the raw MCP result as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Fetch a prompt and print its messages
+    Prompt {
+        /// MCP server id from config.toml
+        server: String,
+        /// Prompt name
+        name: String,
+        /// Prompt arguments as KEY=VALUE (repeatable)
+        #[arg(long = "arg", value_name = "KEY=VALUE")]
+        args: Vec<String>,
+        /// Print the raw MCP result as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Manage persistent tool permission policies
+    Policy {
+        #[command(subcommand)]
+        command: McpPolicyCommands,
+    },
+    /// Show the audit log of tool calls made during chats, oldest first
+    #[command(args_conflicts_with_subcommands = true)]
+    Audit {
+        #[command(subcommand)]
+        command: Option<McpAuditCommands>,
+        /// Only show calls to this MCP server id
+        #[arg(long)]
+        server: Option<String>,
+        /// Only show calls to this tool
+        #[arg(long)]
+        tool: Option<String>,
+        /// Only show calls with this status (success, error, denied, blocked)
+        #[arg(long)]
+        status: Option<String>,
+        /// Show at most this many of the most recent matching calls
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+        /// Print matching entries as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Run Chabeau as a stdio MCP server exposing its providers, presets, and characters
+    Serve,
+}
+
+#[derive(Subcommand)]
+pub enum McpAuditCommands {
+    /// Re-run a logged tool call against its server and print the result
+    Replay {
+        /// Entry number from `chabeau mcp audit`
+        entry: usize,
+        /// Print the raw MCP result as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum McpPolicyCommands {
+    /// Show policy rules for one or all MCP servers
+    List {
+        /// MCP server id from config.toml
+        server: Option<String>,
+    },
+    /// Remove a policy rule by its number from `chabeau mcp policy list`
+    Remove {
+        /// MCP server id from config.toml
+        server: String,
+        /// Rule number (1-based)
+        index: usize,
+    },
 }
 
 #[derive(Subcommand)]
@@ -408,11 +585,15 @@ fn resolve_theme_id(config: &Config, input: &str) -> Option<String> {
     config.get_custom_theme(input).map(|theme| theme.id.clone())
 }
 
-/// Validate preset argument against available presets in config
-fn validate_preset(preset_id: &str, config: &Config) -> Result<(), Box<dyn Error>> {
+/// Validate preset argument (one ID or a comma-separated list) against available presets in config
+fn validate_preset(preset_ids: &str, config: &Config) -> Result<(), Box<dyn Error>> {
     let preset_manager = crate::core::preset::PresetManager::load_presets(config)?;
 
-    if preset_manager.find_preset_by_id(preset_id).is_none() {
+    let preset_ids = crate::core::preset::parse_preset_ids(preset_ids);
+    if let Some(preset_id) = preset_ids
+        .iter()
+        .find(|id| preset_manager.find_preset_by_id(id).is_none())
+    {
         let available_presets: Vec<String> = preset_manager
             .list_presets()
             .iter()
@@ -497,7 +678,7 @@ fn init_mcp_debugging(enabled: bool) {
         .try_init();
 }
 
-async fn handle_args(args: Args) -> Result<(), Box<dyn Error>> {
+async fn handle_args(mut args: Args) -> Result<(), Box<dyn Error>> {
     // Handle version flag
     if args.version {
         print_version_info();
@@ -506,7 +687,7 @@ async fn handle_args(args: Args) -> Result<(), Box<dyn Error>> {
 
     let mut character_service = CharacterService::new();
 
-    match args.command {
+    match args.command.take() {
         Some(Commands::Provider { command }) => handle_provider_command(command).await,
         Some(Commands::Set { key, value }) => {
             let registry = SettingRegistry::new();
@@ -568,96 +749,62 @@ async fn handle_args(args: Args) -> Result<(), Box<dyn Error>> {
             }
             Ok(())
         }
-        None => {
-            // Check if -c was provided without a character name (empty string)
-            if args.character.as_deref() == Some("") {
-                // -c was provided without a value, list available characters
-                return list_characters(&mut character_service).await;
-            }
-
-            if args.persona.is_some() || args.preset.is_some() {
-                let config = Config::load()?;
-                if let Some(persona_id) = &args.persona {
-                    validate_persona(persona_id, &config)?;
-                }
-                if let Some(preset_id) = &args.preset {
-                    validate_preset(preset_id, &config)?;
-                }
-            }
-
-            // Check if -p was provided without a provider name (empty string)
-            match args.provider.as_deref() {
-                Some("") => {
-                    // -p was provided without a value, list available providers
-                    list_providers().await
-                }
-                _ => {
-                    // Normal flow: check -m flag behavior
-                    let provider_for_operations = if args.provider.as_deref() == Some("") {
-                        None // Don't pass empty string provider to other operations
-                    } else {
-                        args.provider
-                    };
-
-                    let character_for_operations = if args.character.as_deref() == Some("") {
-                        None // Don't pass empty string character to other operations
-                    } else {
-                        args.character
-                    };
-                    let preset_for_operations = args.preset.clone();
-
-                    let mut service_for_run = Some(character_service);
-
-                    match args.model.as_deref() {
-                        Some("") => {
-                            // -m was provided without a value, list available models
-                            let result = list_models(provider_for_operations).await;
-                            drop(service_for_run.take());
-                            result
-                        }
-                        Some(model) => {
-                            // -m was provided with a value, use it for chat
-                            run_chat(crate::ui::chat_loop::RunChatOptions {
-                                model: model.to_string(),
-                                log: args.log,
-                                provider: provider_for_operations,
-                                env_only: args.env_only,
-                                character: character_for_operations,
-                                persona: args.persona,
-                                preset: preset_for_operations.clone(),
-                                disable_mcp: args.disable_mcp,
-                                character_service: service_for_run
-                                    .take()
-                                    .expect("character service available for run_chat"),
-                            })
-                            .await
-                        }
-                        None => {
-                            // -m was not provided, use default model for chat
-                            run_chat(crate::ui::chat_loop::RunChatOptions {
-                                model: "default".to_string(),
-                                log: args.log,
-                                provider: provider_for_operations,
-                                env_only: args.env_only,
-                                character: character_for_operations,
-                                persona: args.persona,
-                                preset: preset_for_operations,
-                                disable_mcp: args.disable_mcp,
-                                character_service: service_for_run
-                                    .take()
-                                    .expect("character service available for run_chat"),
-                            })
-                            .await
-                        }
-                    }
-                }
-            }
-        }
+        None => run_interactive_chat(args, character_service, Vec::new(), false).await,
         Some(Commands::Themes) => {
             list_themes().await?;
             Ok(())
         }
-        Some(Commands::Import { card, force }) => {
+        Some(Commands::Import {
+            chat: Some(chat), ..
+        }) => {
+            let imported = match st_import::read_chat(&chat) {
+                Ok(imported) => imported,
+                Err(e) => {
+                    eprintln!("❌ Import failed: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            let character = match args.character.filter(|name| !name.is_empty()) {
+                Some(character) => Some(character),
+                None => imported.character_name.filter(|name| {
+                    let found = character_service.resolve_by_name(name).is_ok();
+                    if !found {
+                        eprintln!(
+                            "⚠️  Character '{}' is not imported; opening the chat without it.",
+                            name
+                        );
+                        eprintln!("   Import the card first with 'chabeau import <file>'.");
+                    }
+                    found
+                }),
+            };
+            let no_default_character = character.is_none();
+            args.character = character;
+            run_interactive_chat(
+                args,
+                character_service,
+                imported.messages,
+                no_default_character,
+            )
+            .await
+        }
+        Some(Commands::Import {
+            st_preset: Some(preset),
+            id,
+            force,
+            ..
+        }) => match st_import::import_preset(&preset, id.as_deref(), force) {
+            Ok(message) => {
+                println!("{}", message);
+                Ok(())
+            }
+            Err(e) => {
+                eprintln!("❌ Import failed: {}", e);
+                std::process::exit(1);
+            }
+        },
+        Some(Commands::Import { card, force, .. }) => {
+            let card = card.unwrap_or_default();
             match crate::character::import::import_card(&card, force) {
                 Ok(message) => {
                     println!("{}", message);
@@ -669,7 +816,28 @@ async fn handle_args(args: Args) -> Result<(), Box<dyn Error>> {
                 }
             }
         }
-        Some(Commands::Say { prompt }) => {
+        Some(Commands::Character { command }) => match command {
+            CharacterCommands::New { name } => character_edit::create_card(&name),
+            CharacterCommands::Edit { name } => character_edit::edit_card(&name),
+            CharacterCommands::Export {
+                name,
+                format,
+                image,
+                output,
+                force,
+            } => character_export::export(character_export::ExportOptions {
+                name,
+                format,
+                image,
+                output,
+                force,
+            }),
+        },
+        Some(Commands::Say {
+            prompt,
+            mcp,
+            allow_tool,
+        }) => {
             say::run_say(say::RunSayOptions {
                 prompt,
                 model: args.model,
@@ -678,6 +846,15 @@ async fn handle_args(args: Args) -> Result<(), Box<dyn Error>> {
                 character: args.character,
                 persona: args.persona,
                 preset: args.preset,
+                mcp_servers: mcp.map(|servers| {
+                    servers
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|id| !id.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                }),
+                allowed_tools: allow_tool,
             })
             .await
         }
@@ -693,6 +870,103 @@ struct ProviderStatusRow {
     kind: &'static str,
 }
 
+/// Start the interactive chat from the top-level flags, listing characters,
+/// providers, or models when their flag was given without a value.
+async fn run_interactive_chat(
+    args: Args,
+    mut character_service: CharacterService,
+    history: Vec<Message>,
+    no_default_character: bool,
+) -> Result<(), Box<dyn Error>> {
+    // Check if -c was provided without a character name (empty string)
+    if args.character.as_deref() == Some("") {
+        // -c was provided without a value, list available characters
+        return list_characters(&mut character_service).await;
+    }
+
+    if args.persona.is_some() || args.preset.is_some() {
+        let config = Config::load()?;
+        if let Some(persona_id) = &args.persona {
+            validate_persona(persona_id, &config)?;
+        }
+        if let Some(preset_id) = &args.preset {
+            validate_preset(preset_id, &config)?;
+        }
+    }
+
+    // Check if -p was provided without a provider name (empty string)
+    match args.provider.as_deref() {
+        Some("") => {
+            // -p was provided without a value, list available providers
+            list_providers().await
+        }
+        _ => {
+            // Normal flow: check -m flag behavior
+            let provider_for_operations = if args.provider.as_deref() == Some("") {
+                None // Don't pass empty string provider to other operations
+            } else {
+                args.provider
+            };
+
+            let character_for_operations = if args.character.as_deref() == Some("") {
+                None // Don't pass empty string character to other operations
+            } else {
+                args.character
+            };
+            let preset_for_operations = args.preset.clone();
+
+            let mut service_for_run = Some(character_service);
+
+            match args.model.as_deref() {
+                Some("") => {
+                    // -m was provided without a value, list available models
+                    let result = list_models(provider_for_operations).await;
+                    drop(service_for_run.take());
+                    result
+                }
+                Some(model) => {
+                    // -m was provided with a value, use it for chat
+                    run_chat(crate::ui::chat_loop::RunChatOptions {
+                        model: model.to_string(),
+                        log: args.log,
+                        provider: provider_for_operations,
+                        env_only: args.env_only,
+                        character: character_for_operations,
+                        no_default_character,
+                        persona: args.persona,
+                        preset: preset_for_operations.clone(),
+                        disable_mcp: args.disable_mcp,
+                        character_service: service_for_run
+                            .take()
+                            .expect("character service available for run_chat"),
+                        history,
+                    })
+                    .await
+                }
+                None => {
+                    // -m was not provided, use default model for chat
+                    run_chat(crate::ui::chat_loop::RunChatOptions {
+                        model: "default".to_string(),
+                        log: args.log,
+                        provider: provider_for_operations,
+                        env_only: args.env_only,
+                        character: character_for_operations,
+                        no_default_character,
+                        persona: args.persona,
+                        preset: preset_for_operations,
+                        disable_mcp: args.disable_mcp,
+                        character_service: service_for_run
+                            .take()
+                            .expect("character service available for run_chat"),
+                        history,
+                    })
+                    .await
+                }
+            }
+        }
+    }
+}
+
 fn collect_provider_status_rows(
     auth_manager: &AuthManager,
     config: &Config,
@@ -1148,7 +1422,7 @@ fn handle_provider_token(command: ProviderTokenCommands) -> Result<(), Box<dyn E
     Ok(())
 }
 
-async fn handle_mcp_command(command: McpCommands, _env_only: bool) -> Result<(), Box<dyn Error>> {
+async fn handle_mcp_command(command: McpCommands, env_only: bool) -> Result<(), Box<dyn Error>> {
     match command {
         McpCommands::List => handle_mcp_list(),
         McpCommands::Add { advanced } => handle_mcp_add(advanced).await,
@@ -1156,6 +1430,37 @@ async fn handle_mcp_command(command: McpCommands, _env_only: bool) -> Result<(),
         McpCommands::Remove { server } => handle_mcp_remove(&server),
         McpCommands::Token { command } => handle_mcp_token(command),
         McpCommands::Oauth { command } => handle_mcp_oauth(command).await,
+        McpCommands::Policy { command } => handle_mcp_policy(command),
+        McpCommands::Tools { server, json } => mcp_inspect::list_tools(&server, json).await,
+        McpCommands::Call {
+            server,
+            tool,
+            args,
+            json,
+        } => mcp_inspect::call_tool(&server, &tool, args.as_deref(), json).await,
+        McpCommands::Resources { server, json } => mcp_inspect::list_resources(&server, json).await,
+        McpCommands::Read { server, uri, json } => {
+            mcp_inspect::read_resource(&server, &uri, json).await
+        }
+        McpCommands::Prompt {
+            server,
+            name,
+            args,
+            json,
+        } => mcp_inspect::get_prompt(&server, &name, &args, json).await,
+        McpCommands::Audit {
+            command: Some(McpAuditCommands::Replay { entry, json }),
+            ..
+        } => mcp_audit::replay(entry, json).await,
+        McpCommands::Audit {
+            command: None,
+            server,
+            tool,
+            status,
+            limit,
+            json,
+        } => mcp_audit::list_entries(server, tool, status.as_deref(), limit, json),
+        McpCommands::Serve => mcp_serve::serve(env_only).await,
     }
 }
 
@@ -1215,18 +1520,10 @@ async fn handle_mcp_add(advanced: bool) -> Result<(), Box<dyn Error>> {
     let mut server = McpServerConfig {
         id: server_id,
         display_name,
-        base_url: None,
-        command: None,
-        args: None,
-        env: None,
-        headers: None,
         transport: Some(prompt_transport(None)?.to_string()),
-        allowed_tools: None,
-        protocol_version: None,
         enabled: Some(true),
-        tool_payloads: None,
-        tool_payload_window: None,
         yolo: Some(false),
+        ..Default::default()
     };
     configure_mcp_transport_fields(&mut server, false, advanced)?;
     if advanced {
@@ -1235,6 +1532,7 @@ async fn handle_mcp_add(advanced: bool) -> Result<(), Box<dyn Error>> {
             "YOLO auto-approve",
             server.is_yolo(),
         )?);
+        configure_mcp_limit_fields(&mut server)?;
     }
 
     config.mcp_servers.push(server.clone());
@@ -1304,6 +1602,7 @@ fn handle_mcp_edit(server_input: &str, advanced: bool) -> Result<(), Box<dyn Err
             "YOLO auto-approve",
             current.is_yolo(),
         )?);
+        configure_mcp_limit_fields(&mut server)?;
     }
 
     if let Some(existing) = config
@@ -1366,6 +1665,64 @@ fn handle_mcp_remove(server_input: &str) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+fn handle_mcp_policy(command: McpPolicyCommands) -> Result<(), Box<dyn Error>> {
+    let mut config = Config::load()?;
+    match command {
+        McpPolicyCommands::List { server } => {
+            let servers: Vec<&McpServerConfig> = match server {
+                Some(server) => vec![resolve_mcp_server(&config, &server)?],
+                None => config.list_mcp_servers(),
+            };
+            if servers.is_empty() {
+                println!("No MCP servers configured.");
+                return Ok(());
+            }
+            for server in servers {
+                let policies = server.policies.as_deref().unwrap_or_default();
+                println!("{} ({}):", server.display_name, server.id);
+                if policies.is_empty() {
+                    println!("  (no policies)");
+                }
+                for (index, policy) in policies.iter().enumerate() {
+                    println!("  {}. {}", index + 1, describe_policy(policy));
+                }
+            }
+        }
+        McpPolicyCommands::Remove { server, index } => {
+            let server_id = resolve_mcp_server(&config, &server)?.id.clone();
+            let Some(server_config) = config
+                .mcp_servers
+                .iter_mut()
+                .find(|candidate| candidate.id == server_id)
+            else {
+                return Err(format!("MCP server '{}' not found", server).into());
+            };
+            let policies = server_config.policies.get_or_insert_with(Vec::new);
+            if index == 0 || index > policies.len() {
+                return Err(format!(
+                    "Policy {} not found for {} ({} configured)",
+                    index,
+                    server_config.id,
+                    policies.len()
+                )
+                .into());
+            }
+            let removed = policies.remove(index - 1);
+            if policies.is_empty() {
+                server_config.policies = None;
+            }
+            let display_name = server_config.display_name.clone();
+            config.save()?;
+            println!(
+                "✅ Removed policy `{}` from {}",
+                describe_policy(&removed),
+                display_name
+            );
+        }
+    }
+    Ok(())
+}
+
 fn handle_mcp_token(command: McpTokenCommands) -> Result<(), Box<dyn Error>> {
     let config = Config::load()?;
     let store = McpTokenStore::new();
@@ -1744,7 +2101,9 @@ fn prompt_bool_with_default(label: &str, default: bool) -> Result<bool, Box<dyn
 fn prompt_transport(current: Option<&str>) -> Result<&'static str, Box<dyn Error>> {
     let default = current.unwrap_or("streamable-http");
     loop {
-        let input = prompt_optional(&format!("Transport [streamable-http|stdio] [{default}]: "))?;
+        let input = prompt_optional(&format!(
+            "Transport [streamable-http|sse|stdio] [{default}]: "
+        ))?;
         let normalized = if input.is_empty() {
             default.to_ascii_lowercase()
         } else {
@@ -1752,8 +2111,9 @@ fn prompt_transport(current: Option<&str>) -> Result<&'static str, Box<dyn Error
         };
         match normalized.as_str() {
             "streamable-http" | "streamable_http" | "http" => return Ok("streamable-http"),
+            "sse" => return Ok("sse"),
             "stdio" => return Ok("stdio"),
-            _ => println!("Unsupported transport. Enter streamable-http or stdio."),
+            _ => println!("Unsupported transport. Enter streamable-http, sse, or stdio."),
         }
     }
 }
@@ -1920,6 +2280,52 @@ fn configure_mcp_transport_fields(
     Ok(())
 }
 
+/// Prompts for per-server timeouts and the tool cap; `default` clears a value
+/// so the global setting applies.
+fn configure_mcp_limit_fields(server: &mut McpServerConfig) -> Result<(), Box<dyn Error>> {
+    server.request_timeout_seconds =
+        prompt_optional_number("Request timeout in seconds", server.request_timeout_seconds)?;
+    server.sampling_timeout_seconds = prompt_optional_number(
+        "Sampling timeout in seconds",
+        server.sampling_timeout_seconds,
+    )?;
+    server.max_tools = prompt_optional_number("Maximum tools to load", server.max_tools)?;
+    Ok(())
+}
+
+fn prompt_optional_number<T>(label: &str, current: Option<T>) -> Result<Option<T>, Box<dyn Error>>
+where
+    T: std::str::FromStr + std::fmt::Display + PartialOrd + Default + Copy,
+{
+    let current_hint = current
+        .map(|value| value.to_string())
+        .unwrap_or_else(|| "default".to_string());
+    loop {
+        let input = prompt_optional(&format!("{label} [{current_hint}]: "))?;
+        match parse_optional_number(&input, current) {
+            Ok(value) => return Ok(value),
+            Err(message) => println!("{message}"),
+        }
+    }
+}
+
+fn parse_optional_number<T>(input: &str, current: Option<T>) -> Result<Option<T>, String>
+where
+    T: std::str::FromStr + PartialOrd + Default + Copy,
+{
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(current);
+    }
+    if input.eq_ignore_ascii_case("default") {
+        return Ok(None);
+    }
+    match input.parse::<T>() {
+        Ok(value) if value > T::default() => Ok(Some(value)),
+        _ => Err("Enter a positive whole number, or `default`.".to_string()),
+    }
+}
+
 fn parse_key_value_pairs(
     input: &str,
     entry_label: &str,
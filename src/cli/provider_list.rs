@@ -61,6 +61,7 @@ pub async fn list_providers() -> Result<(), Box<dyn Error>> {
         &Message {
             role: TranscriptRole::Assistant,
             content,
+            id: None,
         },
         &monochrome_theme,
         MessageRenderConfig::markdown(true, true)
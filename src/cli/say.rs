@@ -1,19 +1,26 @@
 //! TUI-less "say" command
 
+use std::collections::VecDeque;
 use std::error::Error;
 use std::io::{self, IsTerminal, Read, Write};
 
 use crate::auth::{AuthManager, ProviderAuthStatus};
 use crate::character::CharacterService;
+use crate::core::app::session::ToolCallRequest;
 use crate::core::app::session::{
     exit_if_env_only_missing_env, exit_with_provider_resolution_error,
 };
-use crate::core::app::{self};
+use crate::core::app::{self, AppActionContext, AppActionEnvelope, AppCommand, StreamingAction};
 use crate::core::chat_stream::{ChatStreamService, StreamMessage};
 use crate::core::config::data::Config;
+use crate::core::mcp_auth::McpTokenStore;
 use crate::core::message::AppMessageKind;
 use crate::core::providers::ProviderResolutionError;
+use crate::mcp::client::McpClientManager;
+use crate::mcp::permissions::ToolPermissionDecision;
+use crate::ui::chat_loop::executors::mcp_tools::execute_tool_request;
 use crate::ui::osc;
+use futures_util::future::join_all;
 use ratatui::crossterm::cursor::{MoveToColumn, MoveUp};
 use ratatui::crossterm::execute;
 use ratatui::crossterm::terminal::{self, Clear, ClearType};
@@ -213,6 +220,24 @@ impl OutputMode {
         }
     }
 
+    // Tool rounds are reported on stderr, so treat the transcript so far
+    // (including tool call blocks) as already shown before the follow-up
+    // response streams in.
+    fn on_tool_round(
+        &mut self,
+        app: &mut app::App,
+        term_width: u16,
+        stdout: &mut io::Stdout,
+    ) -> io::Result<()> {
+        match self {
+            OutputMode::Terminal { previous_lines } => {
+                *previous_lines = encoded_terminal_lines(app, term_width);
+                Ok(())
+            }
+            OutputMode::Plain { state } => state.ensure_trailing_newline(stdout),
+        }
+    }
+
     fn finish(&mut self, stdout: &mut io::Stdout) -> io::Result<()> {
         match self {
             OutputMode::Terminal { .. } => Ok(()),
@@ -243,6 +268,10 @@ pub struct RunSayOptions {
     pub character: Option<String>,
     pub persona: Option<String>,
     pub preset: Option<String>,
+    /// MCP servers to connect; `Some(vec![])` connects every enabled server.
+    pub mcp_servers: Option<Vec<String>>,
+    /// Tools that may run without approval, as `server:tool` or `tool`.
+    pub allowed_tools: Vec<String>,
 }
 
 fn resolve_prompt_from_args(
@@ -275,6 +304,8 @@ pub async fn run_say(options: RunSayOptions) -> Result<(), Box<dyn Error>> {
         character,
         persona,
         preset,
+        mcp_servers,
+        allowed_tools,
     } = options;
     let mut stdin = io::stdin();
     let stdin_is_terminal = stdin.is_terminal();
@@ -318,9 +349,10 @@ pub async fn run_say(options: RunSayOptions) -> Result<(), Box<dyn Error>> {
             env_only,
             pre_resolved_session: None,
             character,
+            no_default_character: false,
             persona,
             preset,
-            disable_mcp: true,
+            disable_mcp: mcp_servers.is_none(),
         },
         &config,
         character_service,
@@ -332,6 +364,13 @@ pub async fn run_say(options: RunSayOptions) -> Result<(), Box<dyn Error>> {
         }
     })?;
 
+    if let Some(selection) = mcp_servers.as_deref() {
+        if let Err(err) = connect_mcp_servers(&mut app, selection, &allowed_tools, env_only).await {
+            eprintln!("❌ {err}");
+            std::process::exit(1);
+        }
+    }
+
     // The first render needs an initial width to prewrap conversation lines.
     let (term_width, _) = terminal::size().unwrap_or((80, 24));
 
@@ -355,10 +394,11 @@ pub async fn run_say(options: RunSayOptions) -> Result<(), Box<dyn Error>> {
     output_mode.render_prefix(&mut app, term_width, &mut stdout)?;
 
     // Drive the stream until completion, flushing chunks to stdout as they
-    // arrive.
+    // arrive. Tool calls requested by the model run through the same reducer
+    // as the TUI and start a follow-up stream with their results.
     loop {
         match rx.recv().await {
-            Some((StreamMessage::Chunk(content), _)) => {
+            Some((StreamMessage::Chunk(content), id)) if app.is_current_stream(id) => {
                 let (term_width, term_height) = terminal::size().unwrap_or((80, 24));
                 {
                     let mut conversation = app.conversation();
@@ -368,6 +408,15 @@ pub async fn run_say(options: RunSayOptions) -> Result<(), Box<dyn Error>> {
 
                 output_mode.on_chunk(&content, &mut app, term_width, &mut stdout)?;
             }
+            Some((StreamMessage::ToolCallDelta(delta), id)) => {
+                apply_say_action(
+                    &mut app,
+                    StreamingAction::StreamToolCallDelta {
+                        delta,
+                        stream_id: id,
+                    },
+                );
+            }
             Some((StreamMessage::Error(err), _)) => {
                 let trimmed = err.trim();
                 if trimmed.is_empty() {
@@ -386,11 +435,16 @@ pub async fn run_say(options: RunSayOptions) -> Result<(), Box<dyn Error>> {
                 output_mode.on_error(trimmed, &mut app, term_width, &mut stdout)?;
                 std::process::exit(1);
             }
-            Some((StreamMessage::End, _)) => {
-                let mut conversation = app.conversation();
-                conversation.finalize_response();
-                output_mode.finish(&mut stdout)?;
-                break;
+            Some((StreamMessage::End, id)) => {
+                let commands =
+                    apply_say_action(&mut app, StreamingAction::StreamCompleted { stream_id: id });
+                let Some(params) = run_tool_commands(&mut app, commands).await else {
+                    output_mode.finish(&mut stdout)?;
+                    break;
+                };
+                let (term_width, _) = terminal::size().unwrap_or((80, 24));
+                output_mode.on_tool_round(&mut app, term_width, &mut stdout)?;
+                stream_service.spawn_stream(params);
             }
             None => break,
             _ => {}
@@ -400,9 +454,213 @@ pub async fn run_say(options: RunSayOptions) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+fn say_action_context() -> AppActionContext {
+    let (term_width, term_height) = terminal::size().unwrap_or((80, 24));
+    AppActionContext {
+        term_width,
+        term_height,
+    }
+}
+
+fn apply_say_action(app: &mut app::App, action: StreamingAction) -> Vec<AppCommand> {
+    app::apply_actions(
+        app,
+        [AppActionEnvelope {
+            action: action.into(),
+            context: say_action_context(),
+        }],
+    )
+}
+
+/// Connects the requested MCP servers and pre-approves `--allow-tool` entries.
+async fn connect_mcp_servers(
+    app: &mut app::App,
+    selection: &[String],
+    allowed_tools: &[String],
+    env_only: bool,
+) -> Result<(), String> {
+    let mut config = app.config.clone();
+    for id in selection {
+        if config.get_mcp_server(id).is_none() {
+            return Err(format!("MCP server '{id}' not found."));
+        }
+    }
+    if !selection.is_empty() {
+        for server in &mut config.mcp_servers {
+            let selected = selection
+                .iter()
+                .any(|id| id.eq_ignore_ascii_case(&server.id));
+            server.enabled = Some(selected);
+        }
+    }
+
+    let mut manager = McpClientManager::from_config(&config);
+    manager
+        .connect_all(&McpTokenStore::new_with_keyring(!env_only))
+        .await;
+    let server_ids: Vec<String> = manager
+        .servers()
+        .filter(|server| server.config.is_enabled())
+        .map(|server| server.config.id.clone())
+        .collect();
+    for server_id in &server_ids {
+        manager
+            .refresh_server_metadata_concurrently(server_id)
+            .await;
+        let Some(server) = manager.server(server_id) else {
+            continue;
+        };
+        if server.connected {
+            continue;
+        }
+        let error = server.last_error.as_deref().unwrap_or("unknown error");
+        if selection.is_empty() {
+            eprintln!(
+                "⚠️  Skipping MCP server {}: {error}",
+                server.config.display_name
+            );
+        } else {
+            return Err(format!(
+                "Failed to connect to {}: {error}",
+                server.config.display_name
+            ));
+        }
+    }
+
+    for entry in allowed_tools {
+        let (servers, tool) = match entry.split_once(':') {
+            Some((server, tool)) => {
+                let Some(server) = manager.server(server) else {
+                    return Err(format!("MCP server '{server}' in --allow-tool not found."));
+                };
+                if !server.connected {
+                    return Err(format!(
+                        "MCP server '{}' in --allow-tool is not connected.",
+                        server.config.id
+                    ));
+                }
+                (vec![server.config.id.clone()], tool)
+            }
+            None => (server_ids.clone(), entry.as_str()),
+        };
+        for server_id in servers {
+            app.mcp_permissions
+                .record(&server_id, tool, ToolPermissionDecision::AllowSession);
+        }
+    }
+
+    app.mcp = manager;
+    app.session.mcp_disabled = false;
+    app.session.mcp_init.in_progress = false;
+    app.session.mcp_init.complete = true;
+    Ok(())
+}
+
+/// Runs the tool calls queued by the reducer and returns the follow-up
+/// stream, if any. Exits when a tool would need interactive approval or a
+/// server asks for sampling.
+async fn run_tool_commands(
+    app: &mut app::App,
+    commands: Vec<AppCommand>,
+) -> Option<crate::core::chat_stream::StreamParams> {
+    let mut queue: VecDeque<AppCommand> = commands.into();
+    loop {
+        if let Some(prompt) = app.ui.tool_prompt() {
+            eprintln!(
+                "❌ {} on {} needs approval. Enable YOLO for the server or pass --allow-tool {}:{}.",
+                prompt.tool_name, prompt.server_name, prompt.server_id, prompt.tool_name
+            );
+            std::process::exit(1);
+        }
+        match queue.pop_front()? {
+            AppCommand::SpawnStream(params) => return Some(params),
+            AppCommand::RunMcpTools(requests) => {
+                let commands = run_tools(app, requests).await;
+                queue.extend(commands);
+            }
+            AppCommand::RunMcpSampling(request) => {
+                eprintln!(
+                    "❌ MCP server {} requested sampling, which is not supported in say.",
+                    request.server_id
+                );
+                std::process::exit(1);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Runs a batch of approved tool calls concurrently and feeds their results
+/// back to the reducer in request order.
+async fn run_tools(app: &mut app::App, requests: Vec<ToolCallRequest>) -> Vec<AppCommand> {
+    let calls = requests.into_iter().map(|request| {
+        eprintln!(
+            "🔧 {}:{} {}",
+            request.server_id,
+            request.tool_name,
+            summarize_arguments(&request.raw_arguments)
+        );
+        let context = app.mcp.tool_call_context(&request.server_id);
+        async move {
+            let Some(mut context) = context else {
+                return (
+                    request,
+                    None,
+                    (Err("MCP server not available.".to_string()), None),
+                );
+            };
+            let outcome = execute_tool_request(&mut context, &request, None).await;
+            (request, Some(context), outcome)
+        }
+    });
+    let finished = join_all(calls).await;
+
+    let mut commands = Vec::new();
+    for (request, context, (result, output)) in finished {
+        if let Some(context) = context {
+            let error = result.as_ref().err().cloned();
+            app.mcp
+                .update_tool_call_session(&context.server_id, context.session_id, error);
+        }
+        let label = format!("{}:{}", request.server_id, request.tool_name);
+        match &result {
+            Ok(_) if output.as_ref().is_some_and(|output| output.is_error) => {
+                eprintln!("   {label} reported an error")
+            }
+            Ok(payload) => eprintln!("   {label} returned {} bytes", payload.len()),
+            Err(err) => eprintln!("   {label} failed: {err}"),
+        }
+        commands.extend(apply_say_action(
+            app,
+            StreamingAction::ToolCallCompleted {
+                tool_name: request.tool_name,
+                tool_call_id: request.tool_call_id,
+                result,
+                output,
+            },
+        ));
+    }
+    commands
+}
+
+fn summarize_arguments(raw: &str) -> String {
+    const MAX_CHARS: usize = 80;
+    let compact = serde_json::from_str::<serde_json::Value>(raw)
+        .map(|value| value.to_string())
+        .unwrap_or_else(|_| raw.trim().to_string());
+    if compact.chars().count() <= MAX_CHARS {
+        compact
+    } else {
+        let truncated: String = compact.chars().take(MAX_CHARS).collect();
+        format!("{truncated}…")
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{plain_text_lines, resolve_prompt_from_args, PlainStreamState};
+    use super::{
+        plain_text_lines, resolve_prompt_from_args, summarize_arguments, PlainStreamState,
+    };
     use crate::ui::osc;
     use crate::ui::span::SpanKind;
     use ratatui::text::{Line, Span};
@@ -476,4 +734,16 @@ mod tests {
         let captured = String::from_utf8(buffer).expect("valid utf8");
         assert_eq!(captured, "partial response\noops\n");
     }
+
+    #[test]
+    fn tool_argument_summaries_are_compact_and_bounded() {
+        assert_eq!(
+            summarize_arguments("{\n  \"q\": \"rust\"\n}"),
+            "{\"q\":\"rust\"}"
+        );
+        let long = format!("{{\"q\":\"{}\"}}", "x".repeat(200));
+        let summary = summarize_arguments(&long);
+        assert!(summary.ends_with('…'));
+        assert_eq!(summary.chars().count(), 81);
+    }
 }
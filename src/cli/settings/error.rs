@@ -19,6 +19,8 @@ pub enum SettingError {
     },
     /// The provided value could not be parsed as a boolean.
     InvalidBoolean(String),
+    /// The provided value could not be parsed as a positive whole number.
+    InvalidNumber(String),
     /// Required arguments are missing.
     MissingArgs {
         hint: &'static str,
@@ -55,6 +57,10 @@ impl SettingError {
                 eprintln!("❌ Invalid boolean value: {input}");
                 eprintln!("   Use 'on' or 'off' (also accepts true/false, yes/no)");
             }
+            SettingError::InvalidNumber(input) => {
+                eprintln!("❌ Invalid number: {input}");
+                eprintln!("   Use a positive whole number");
+            }
             SettingError::MissingArgs { hint, example } => {
                 eprintln!("⚠️  {hint}");
                 eprintln!("Example: {example}");
@@ -81,6 +87,7 @@ impl fmt::Display for SettingError {
                 write!(f, "{kind} '{input}' not found")
             }
             SettingError::InvalidBoolean(input) => write!(f, "Invalid boolean value: {input}"),
+            SettingError::InvalidNumber(input) => write!(f, "Invalid number: {input}"),
             SettingError::MissingArgs { hint, .. } => write!(f, "{hint}"),
             SettingError::ConfigError(msg) => write!(f, "Config error: {msg}"),
         }
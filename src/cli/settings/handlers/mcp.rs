@@ -3,15 +3,78 @@
 use crate::cli::settings::error::SettingError;
 use crate::cli::settings::helpers::{format_bool, mutate_config, parse_bool};
 use crate::cli::settings::{SetContext, SettingHandler};
-use crate::core::config::data::Config;
+use crate::core::config::data::{Config, McpServerConfig};
 
-/// Handler for the `mcp` setting (enable/disable MCP servers and yolo mode).
+/// Handler for the `mcp` setting (enable/disable MCP servers, yolo mode, and
+/// per-server limits).
 ///
 /// Supports:
 /// - `chabeau set mcp <server> on/off` - enable/disable server
 /// - `chabeau set mcp <server> yolo on/off` - enable/disable yolo mode
+/// - `chabeau set mcp <server> timeout <seconds>` - per-request timeout
+/// - `chabeau set mcp <server> sampling-timeout <seconds>` - sampling timeout
+/// - `chabeau set mcp <server> max-tools <count>` - cap on loaded tools
 pub struct McpHandler;
 
+/// Per-server numeric settings addressable as `mcp <server> <key> <value>`.
+#[derive(Clone, Copy)]
+enum McpLimit {
+    Timeout,
+    SamplingTimeout,
+    MaxTools,
+}
+
+impl McpLimit {
+    fn parse(key: &str) -> Option<Self> {
+        match key.to_ascii_lowercase().as_str() {
+            "timeout" => Some(Self::Timeout),
+            "sampling-timeout" | "sampling_timeout" => Some(Self::SamplingTimeout),
+            "max-tools" | "max_tools" => Some(Self::MaxTools),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Timeout => "request timeout",
+            Self::SamplingTimeout => "sampling timeout",
+            Self::MaxTools => "max tools",
+        }
+    }
+
+    fn apply(self, server: &mut McpServerConfig, value: Option<u64>) {
+        match self {
+            Self::Timeout => server.request_timeout_seconds = value,
+            Self::SamplingTimeout => server.sampling_timeout_seconds = value,
+            Self::MaxTools => server.max_tools = value.map(|count| count as usize),
+        }
+    }
+
+    fn display(self, value: u64) -> String {
+        match self {
+            Self::MaxTools => value.to_string(),
+            _ => format!("{value}s"),
+        }
+    }
+}
+
+fn update_server(
+    server_id: &str,
+    update: impl FnOnce(&mut McpServerConfig),
+) -> Result<(), SettingError> {
+    let server_id = server_id.to_string();
+    mutate_config(move |config| {
+        if let Some(server) = config
+            .mcp_servers
+            .iter_mut()
+            .find(|s| s.id.eq_ignore_ascii_case(&server_id))
+        {
+            update(server);
+        }
+        Ok(())
+    })
+}
+
 impl McpHandler {
     fn validate_server(ctx: &SetContext<'_>, server_id: &str) -> Result<(), SettingError> {
         if ctx.config.get_mcp_server(server_id).is_some() {
@@ -48,13 +111,31 @@ impl SettingHandler for McpHandler {
         if args.len() < 2 {
             return Err(SettingError::MissingArgs {
                 hint: "To configure an MCP server:",
-                example: "chabeau set mcp <server> on/off\nchabeau set mcp <server> yolo on/off",
+                example: "chabeau set mcp <server> on/off\nchabeau set mcp <server> yolo on/off\nchabeau set mcp <server> timeout <seconds>",
             });
         }
 
         let server_id = &args[0];
         Self::validate_server(ctx, server_id)?;
 
+        // Numeric limits: `mcp <server> timeout 300`
+        if let Some(limit) = McpLimit::parse(&args[1]) {
+            let value_input = args[2..].join(" ");
+            let value = value_input
+                .trim()
+                .parse::<u64>()
+                .ok()
+                .filter(|value| *value > 0)
+                .ok_or(SettingError::InvalidNumber(value_input))?;
+            update_server(server_id, move |server| limit.apply(server, Some(value)))?;
+            return Ok(format!(
+                "✅ Set MCP server '{}' {} to: {}",
+                server_id,
+                limit.label(),
+                limit.display(value)
+            ));
+        }
+
         // Check if this is a yolo setting: `mcp <server> yolo on/off`
         if args.len() >= 3 && args[1].eq_ignore_ascii_case("yolo") {
             let value_input = args[2..].join(" ");
@@ -102,7 +183,7 @@ impl SettingHandler for McpHandler {
     fn unset(&self, args: Option<&str>, ctx: &mut SetContext<'_>) -> Result<String, SettingError> {
         let value = args.ok_or(SettingError::MissingArgs {
             hint: "To reset an MCP server setting:",
-            example: "chabeau unset mcp <server>\nchabeau unset mcp \"<server> yolo\"",
+            example: "chabeau unset mcp <server>\nchabeau unset mcp \"<server> yolo\"\nchabeau unset mcp \"<server> timeout\"",
         })?;
 
         let parts: Vec<&str> = value.split_whitespace().collect();
@@ -116,6 +197,15 @@ impl SettingHandler for McpHandler {
         let server_id = parts[0];
         Self::validate_server(ctx, server_id)?;
 
+        if let Some(limit) = parts.get(1).and_then(|key| McpLimit::parse(key)) {
+            update_server(server_id, move |server| limit.apply(server, None))?;
+            return Ok(format!(
+                "✅ Unset MCP server '{}' {} (will use the global default)",
+                server_id,
+                limit.label()
+            ));
+        }
+
         // Check if unsetting yolo: `unset mcp "<server> yolo"`
         if parts.len() >= 2 && parts[1].eq_ignore_ascii_case("yolo") {
             let server_id_owned = server_id.to_string();
@@ -166,7 +256,25 @@ impl SettingHandler for McpHandler {
             for server in servers {
                 let enabled = format_bool(server.is_enabled());
                 let yolo = if server.is_yolo() { " [yolo]" } else { "" };
-                output.push_str(&format!("    {}: {}{}\n", server.id, enabled, yolo));
+                let mut limits = Vec::new();
+                if let Some(seconds) = server.request_timeout_seconds {
+                    limits.push(format!("timeout {seconds}s"));
+                }
+                if let Some(seconds) = server.sampling_timeout_seconds {
+                    limits.push(format!("sampling-timeout {seconds}s"));
+                }
+                if let Some(count) = server.max_tools {
+                    limits.push(format!("max-tools {count}"));
+                }
+                let limits = if limits.is_empty() {
+                    String::new()
+                } else {
+                    format!(" [{}]", limits.join(", "))
+                };
+                output.push_str(&format!(
+                    "    {}: {}{}{}\n",
+                    server.id, enabled, yolo, limits
+                ));
             }
             output.pop(); // Remove trailing newline
             output
@@ -9,7 +9,7 @@ use crate::cli::settings::helpers::{
 use crate::cli::settings::{SetContext, SettingHandler};
 use crate::core::config::data::Config;
 use crate::core::persona::PersonaManager;
-use crate::core::preset::PresetManager;
+use crate::core::preset::{parse_preset_ids, PresetManager};
 
 fn set_provider_model_value<V, M>(
     args: &[String],
@@ -194,13 +194,17 @@ impl SettingHandler for DefaultPresetHandler {
             args,
             ctx,
             "default preset",
-            "To set a default preset, specify provider, model, and preset:",
-            "chabeau set default-preset openai gpt-4o short",
-            |ctx, preset_id| {
+            "To set a default preset, specify provider, model, and preset (comma-separate IDs to stack several):",
+            "chabeau set default-preset openai gpt-4o short,roleplay",
+            |ctx, preset_ids| {
                 let preset_manager = PresetManager::load_presets(ctx.config)
                     .map_err(|e| SettingError::ConfigError(e.to_string()))?;
 
-                if preset_manager.find_preset_by_id(&preset_id).is_none() {
+                let preset_ids = parse_preset_ids(&preset_ids);
+                if let Some(preset_id) = preset_ids
+                    .iter()
+                    .find(|id| preset_manager.find_preset_by_id(id).is_none())
+                {
                     let available: Vec<_> = preset_manager
                         .list_presets()
                         .iter()
@@ -218,15 +222,15 @@ impl SettingHandler for DefaultPresetHandler {
 
                     return Err(SettingError::UnknownItem {
                         kind: "Preset",
-                        input: preset_id,
+                        input: preset_id.clone(),
                         hint,
                     });
                 }
 
-                Ok(preset_id)
+                Ok(preset_ids.join(", "))
             },
-            |config, provider, model, preset_id| {
-                config.set_default_preset(provider, model, preset_id);
+            |config, provider, model, preset_ids| {
+                config.set_default_preset(provider, model, parse_preset_ids(&preset_ids));
             },
         )
     }
@@ -245,6 +249,17 @@ impl SettingHandler for DefaultPresetHandler {
     }
 
     fn format(&self, config: &Config) -> String {
-        format_provider_model_map("default-presets", &config.default_presets)
+        let joined = config
+            .default_presets
+            .iter()
+            .map(|(provider, models)| {
+                let models = models
+                    .iter()
+                    .map(|(model, ids)| (model.clone(), ids.join(", ")))
+                    .collect();
+                (provider.clone(), models)
+            })
+            .collect();
+        format_provider_model_map("default-presets", &joined)
     }
 }
@@ -0,0 +1,481 @@
+//! `chabeau import --chat` and `chabeau import --preset`: bring SillyTavern
+//! chats and prompt-manager presets into Chabeau
+//!
+//! Chats are JSONL files: an optional header line naming the user and
+//! character, then one message per line. Each message contributes its
+//! selected swipe; hidden system notes are dropped. In group chats every
+//! character reply is labelled with its speaker, as Chabeau's own group
+//! chats do.
+//!
+//! Presets map the enabled prompts of the prompt manager onto one Chabeau
+//! preset. System prompts before the chat history become `pre` text and those
+//! after it `post` text; other prompts become blocks. SillyTavern markers
+//! such as the character description have no Chabeau equivalent and are
+//! skipped, since Chabeau adds the character card itself.
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::core::config::data::{
+    Config, Preset, PresetBlock, PresetCondition, PresetPosition, PresetRole,
+};
+use crate::core::message::{Message, TranscriptRole};
+
+/// Prompt-manager identifier of the chat history marker.
+const CHAT_HISTORY_MARKER: &str = "chatHistory";
+/// `character_id` SillyTavern uses for the prompt order shared by all characters.
+const GLOBAL_PROMPT_ORDER_ID: i64 = 100_001;
+/// `injection_position` of prompts inserted into the chat at a depth.
+const ABSOLUTE_INJECTION: u8 = 1;
+
+/// A SillyTavern chat converted to Chabeau transcript messages.
+#[derive(Debug, Default)]
+pub struct ImportedChat {
+    /// Character named by the chat, when it has exactly one.
+    pub character_name: Option<String>,
+    pub messages: Vec<Message>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ChatLine {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    is_user: bool,
+    #[serde(default)]
+    is_system: bool,
+    #[serde(default)]
+    mes: Option<String>,
+    #[serde(default)]
+    swipes: Option<Vec<String>>,
+    #[serde(default)]
+    swipe_id: Option<usize>,
+    #[serde(default)]
+    character_name: Option<String>,
+}
+
+impl ChatLine {
+    /// Text of the selected swipe, falling back to `mes`.
+    fn selected_text(&self) -> Option<&str> {
+        self.swipe_id
+            .and_then(|index| self.swipes.as_ref()?.get(index))
+            .or(self.mes.as_ref())
+            .map(String::as_str)
+    }
+}
+
+/// Read a SillyTavern chat JSONL file.
+pub fn read_chat(path: &Path) -> Result<ImportedChat, Box<dyn Error>> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_chat(&text).map_err(|e| format!("{}: {}", path.display(), e).into())
+}
+
+fn parse_chat(text: &str) -> Result<ImportedChat, String> {
+    let mut header_character = None;
+    let mut replies: Vec<(bool, Option<String>, String)> = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line: ChatLine = serde_json::from_str(line)
+            .map_err(|e| format!("line {} is not valid JSON: {}", number + 1, e))?;
+        if line.mes.is_none() {
+            header_character = header_character.or(line.character_name);
+            continue;
+        }
+        if line.is_system {
+            continue;
+        }
+        let Some(content) = line.selected_text().filter(|text| !text.trim().is_empty()) else {
+            continue;
+        };
+        replies.push((line.is_user, line.name.clone(), content.to_string()));
+    }
+
+    if replies.is_empty() {
+        return Err("no messages found".to_string());
+    }
+
+    let speakers: HashSet<&str> = replies
+        .iter()
+        .filter(|(is_user, _, _)| !is_user)
+        .filter_map(|(_, name, _)| name.as_deref())
+        .collect();
+    let is_group = speakers.len() > 1;
+    let character_name = if is_group {
+        None
+    } else {
+        header_character
+            .filter(|name| !name.trim().is_empty())
+            .or_else(|| speakers.iter().next().map(|name| name.to_string()))
+    };
+
+    let messages = replies
+        .into_iter()
+        .map(|(is_user, name, content)| {
+            if is_user {
+                return Message::new(TranscriptRole::User, content);
+            }
+            let content = match name.filter(|_| is_group) {
+                Some(name) => format!("{name}: {content}"),
+                None => content,
+            };
+            Message::new(TranscriptRole::Assistant, content)
+        })
+        .collect();
+
+    Ok(ImportedChat {
+        character_name,
+        messages,
+    })
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PresetFile {
+    #[serde(default)]
+    prompts: Vec<Prompt>,
+    #[serde(default)]
+    prompt_order: Vec<PromptOrder>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Prompt {
+    identifier: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    role: Option<String>,
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    marker: bool,
+    #[serde(default)]
+    enabled: Option<bool>,
+    #[serde(default)]
+    injection_position: Option<u8>,
+    #[serde(default)]
+    injection_depth: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PromptOrder {
+    #[serde(default)]
+    character_id: Option<i64>,
+    #[serde(default)]
+    order: Vec<PromptOrderEntry>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PromptOrderEntry {
+    identifier: String,
+    #[serde(default = "enabled_by_default")]
+    enabled: bool,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+/// Read a SillyTavern prompt-manager preset and add it to config.toml as
+/// preset `id` (the file name when `None`).
+pub fn import_preset(path: &Path, id: Option<&str>, force: bool) -> Result<String, Box<dyn Error>> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let id = match id {
+        Some(id) => id.trim().to_string(),
+        None => preset_id_from_path(path),
+    };
+    if id.is_empty() {
+        return Err("Preset ID cannot be empty. Pass --id <ID>.".into());
+    }
+    let preset = parse_preset(&text, &id).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let summary = describe_preset(&preset);
+
+    Config::mutate(move |config| {
+        add_preset(config, preset, force)?;
+        Ok(())
+    })?;
+
+    Ok(format!("✅ Imported preset '{}' ({})", id, summary))
+}
+
+fn add_preset(config: &mut Config, preset: Preset, force: bool) -> Result<(), String> {
+    match config
+        .presets
+        .iter_mut()
+        .find(|existing| existing.id == preset.id)
+    {
+        Some(_) if !force => Err(format!(
+            "Preset '{}' already exists. Use --force to overwrite.",
+            preset.id
+        )),
+        Some(existing) => {
+            *existing = preset;
+            Ok(())
+        }
+        None => {
+            config.presets.push(preset);
+            Ok(())
+        }
+    }
+}
+
+fn preset_id_from_path(path: &Path) -> String {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    stem.split(|ch: char| !ch.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn parse_preset(text: &str, id: &str) -> Result<Preset, String> {
+    let file: PresetFile =
+        serde_json::from_str(text).map_err(|e| format!("not a valid preset: {}", e))?;
+    if file.prompts.is_empty() {
+        return Err("no prompts found; expected a prompt-manager preset".to_string());
+    }
+
+    let order = file
+        .prompt_order
+        .iter()
+        .find(|order| order.character_id == Some(GLOBAL_PROMPT_ORDER_ID))
+        .or_else(|| file.prompt_order.first());
+    // Disabled entries are kept so the chat history marker splits the prompts
+    // even when it is switched off.
+    let ordered: Vec<(&Prompt, bool)> = match order {
+        Some(order) => order
+            .order
+            .iter()
+            .filter_map(|entry| {
+                file.prompts
+                    .iter()
+                    .find(|prompt| prompt.identifier == entry.identifier)
+                    .map(|prompt| (prompt, entry.enabled))
+            })
+            .collect(),
+        None => file
+            .prompts
+            .iter()
+            .map(|prompt| (prompt, prompt.enabled.unwrap_or(true)))
+            .collect(),
+    };
+
+    let mut pre = Vec::new();
+    let mut post = Vec::new();
+    let mut blocks = Vec::new();
+    let mut after_history = false;
+    for (prompt, enabled) in ordered {
+        if prompt.identifier == CHAT_HISTORY_MARKER {
+            after_history = true;
+            continue;
+        }
+        if !enabled {
+            continue;
+        }
+        let content = prompt.content.as_deref().unwrap_or_default().trim();
+        if prompt.marker || content.is_empty() {
+            continue;
+        }
+        let role = match prompt.role.as_deref() {
+            Some("user") => PresetRole::User,
+            Some("assistant") => PresetRole::Assistant,
+            _ => PresetRole::System,
+        };
+        let (position, depth) = if prompt.injection_position == Some(ABSOLUTE_INJECTION) {
+            (PresetPosition::Depth, prompt.injection_depth.unwrap_or(0))
+        } else {
+            match (after_history, role) {
+                (false, PresetRole::System) => {
+                    pre.push(content.to_string());
+                    continue;
+                }
+                (true, PresetRole::System) => {
+                    post.push(content.to_string());
+                    continue;
+                }
+                (false, _) => (PresetPosition::SystemStart, 0),
+                (true, PresetRole::Assistant) => (PresetPosition::Prefill, 0),
+                (true, _) => (PresetPosition::Depth, 0),
+            }
+        };
+        blocks.push(PresetBlock {
+            name: prompt.name.clone().filter(|name| !name.trim().is_empty()),
+            content: content.to_string(),
+            position,
+            depth,
+            role,
+            when: PresetCondition::Always,
+        });
+    }
+
+    if pre.is_empty() && post.is_empty() && blocks.is_empty() {
+        return Err("no enabled prompts with text".to_string());
+    }
+
+    Ok(Preset {
+        id: id.to_string(),
+        pre: pre.join("\n\n"),
+        post: post.join("\n\n"),
+        blocks,
+    })
+}
+
+fn describe_preset(preset: &Preset) -> String {
+    let mut parts = Vec::new();
+    if !preset.pre.is_empty() {
+        parts.push("pre".to_string());
+    }
+    if !preset.post.is_empty() {
+        parts.push("post".to_string());
+    }
+    match preset.blocks.len() {
+        0 => {}
+        1 => parts.push("1 block".to_string()),
+        count => parts.push(format!("{count} blocks")),
+    }
+    parts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_chat_takes_selected_swipes_and_skips_system_notes() {
+        let text = r#"{"user_name":"You","character_name":"Seraphina","create_date":"2024-01-01"}
+{"name":"Seraphina","is_user":false,"mes":"Welcome.","swipes":["Welcome.","Greetings, traveller."],"swipe_id":1}
+{"name":"You","is_user":true,"mes":"Hi!"}
+{"name":"System","is_user":false,"is_system":true,"mes":"Hidden note"}
+
+{"name":"Seraphina","is_user":false,"mes":"How are you?"}
+"#;
+        let chat = parse_chat(text).expect("parse chat");
+
+        assert_eq!(chat.character_name.as_deref(), Some("Seraphina"));
+        let transcript: Vec<(&str, &str)> = chat
+            .messages
+            .iter()
+            .map(|msg| (msg.role.as_str(), msg.content.as_str()))
+            .collect();
+        assert_eq!(
+            transcript,
+            vec![
+                ("assistant", "Greetings, traveller."),
+                ("user", "Hi!"),
+                ("assistant", "How are you?"),
+            ]
+        );
+
+        let error = parse_chat("{\"mes\": oops}").expect_err("invalid JSON");
+        assert!(error.contains("line 1"));
+    }
+
+    #[test]
+    fn parse_chat_labels_group_speakers() {
+        let text = r#"{"name":"Ada","mes":"Hello."}
+{"name":"You","is_user":true,"mes":"Hi both."}
+{"name":"Grace","mes":"Hey."}
+"#;
+        let chat = parse_chat(text).expect("parse chat");
+
+        assert_eq!(chat.character_name, None);
+        assert_eq!(chat.messages[0].content, "Ada: Hello.");
+        assert_eq!(chat.messages[2].content, "Grace: Hey.");
+    }
+
+    #[test]
+    fn parse_preset_follows_prompt_order_around_chat_history() {
+        let text = r#"{
+  "temperature": 1,
+  "prompts": [
+    {"identifier": "main", "name": "Main Prompt", "system_prompt": true, "role": "system", "content": "Write {{char}}'s next reply."},
+    {"identifier": "charDescription", "name": "Char Description", "marker": true},
+    {"identifier": "chatHistory", "name": "Chat History", "marker": true},
+    {"identifier": "jailbreak", "name": "Post-History Instructions", "role": "system", "content": "Stay in character."},
+    {"identifier": "nsfw", "name": "Auxiliary Prompt", "role": "system", "content": "Unused."},
+    {"identifier": "style", "name": "Style", "role": "user", "content": "[Be vivid]", "injection_position": 1, "injection_depth": 2},
+    {"identifier": "prefill", "name": "Prefill", "role": "assistant", "content": "Sure,"}
+  ],
+  "prompt_order": [
+    {"character_id": 100000, "order": [{"identifier": "main", "enabled": false}]},
+    {"character_id": 100001, "order": [
+      {"identifier": "main", "enabled": true},
+      {"identifier": "charDescription", "enabled": true},
+      {"identifier": "nsfw", "enabled": false},
+      {"identifier": "style", "enabled": true},
+      {"identifier": "chatHistory", "enabled": true},
+      {"identifier": "jailbreak", "enabled": true},
+      {"identifier": "prefill", "enabled": true}
+    ]}
+  ]
+}"#;
+        let preset = parse_preset(text, "st-default").expect("parse preset");
+
+        assert_eq!(preset.id, "st-default");
+        assert_eq!(preset.pre, "Write {{char}}'s next reply.");
+        assert_eq!(preset.post, "Stay in character.");
+        let blocks: Vec<(&str, PresetPosition, usize, PresetRole)> = preset
+            .blocks
+            .iter()
+            .map(|block| {
+                (
+                    block.content.as_str(),
+                    block.position,
+                    block.depth,
+                    block.role,
+                )
+            })
+            .collect();
+        assert_eq!(
+            blocks,
+            vec![
+                ("[Be vivid]", PresetPosition::Depth, 2, PresetRole::User),
+                ("Sure,", PresetPosition::Prefill, 0, PresetRole::Assistant),
+            ]
+        );
+        assert_eq!(describe_preset(&preset), "pre, post, 2 blocks");
+
+        // A disabled chat history marker still separates pre and post.
+        let disabled_history = text.replace(
+            r#"{"identifier": "chatHistory", "enabled": true}"#,
+            r#"{"identifier": "chatHistory", "enabled": false}"#,
+        );
+        let preset = parse_preset(&disabled_history, "st-default").expect("parse preset");
+        assert_eq!(preset.pre, "Write {{char}}'s next reply.");
+        assert_eq!(preset.post, "Stay in character.");
+        assert_eq!(preset.blocks[1].position, PresetPosition::Prefill);
+
+        assert!(parse_preset(r#"{"prompts": []}"#, "empty").is_err());
+    }
+
+    #[test]
+    fn add_preset_requires_force_to_replace() {
+        let mut config = Config::default();
+        let preset = |pre: &str| Preset {
+            id: "imported".to_string(),
+            pre: pre.to_string(),
+            post: String::new(),
+            ..Default::default()
+        };
+
+        add_preset(&mut config, preset("first"), false).expect("add preset");
+        let error = add_preset(&mut config, preset("second"), false).expect_err("duplicate");
+        assert!(error.contains("--force"));
+        add_preset(&mut config, preset("second"), true).expect("replace preset");
+
+        assert_eq!(config.presets.len(), 1);
+        assert_eq!(config.presets[0].pre, "second");
+        assert_eq!(
+            preset_id_from_path(Path::new("/tmp/My Preset (v2).json")),
+            "my-preset-v2"
+        );
+    }
+}
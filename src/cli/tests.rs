@@ -189,6 +189,79 @@ fn test_provider_token_list_command_parsing() {
     }
 }
 
+#[test]
+fn test_say_mcp_flags_do_not_consume_prompt() {
+    let args = Args::try_parse_from(["chabeau", "say", "--mcp", "hello", "there"]).unwrap();
+    match args.command {
+        Some(Commands::Say { prompt, mcp, .. }) => {
+            assert_eq!(mcp.as_deref(), Some(""));
+            assert_eq!(prompt, vec!["hello", "there"]);
+        }
+        _ => panic!("Expected say command"),
+    }
+
+    let args = Args::try_parse_from([
+        "chabeau",
+        "say",
+        "--mcp=alpha,beta",
+        "--allow-tool",
+        "alpha:search",
+        "hi",
+    ])
+    .unwrap();
+    match args.command {
+        Some(Commands::Say {
+            prompt,
+            mcp,
+            allow_tool,
+        }) => {
+            assert_eq!(mcp.as_deref(), Some("alpha,beta"));
+            assert_eq!(allow_tool, vec!["alpha:search"]);
+            assert_eq!(prompt, vec!["hi"]);
+        }
+        _ => panic!("Expected say command"),
+    }
+
+    assert!(Args::try_parse_from(["chabeau", "say", "--allow-tool", "search", "hi"]).is_err());
+}
+
+#[test]
+fn test_import_accepts_card_chat_or_sillytavern_preset() {
+    let args = Args::try_parse_from(["chabeau", "import", "alice.png", "--force"]).unwrap();
+    match args.command {
+        Some(Commands::Import { card, force, .. }) => {
+            assert_eq!(card.as_deref(), Some("alice.png"));
+            assert!(force);
+        }
+        _ => panic!("Expected import command"),
+    }
+
+    let args =
+        Args::try_parse_from(["chabeau", "-m", "gpt-4o", "import", "--chat", "a.jsonl"]).unwrap();
+    assert_eq!(args.model.as_deref(), Some("gpt-4o"));
+    match args.command {
+        Some(Commands::Import { card, chat, .. }) => {
+            assert_eq!(card, None);
+            assert_eq!(chat, Some(PathBuf::from("a.jsonl")));
+        }
+        _ => panic!("Expected import command"),
+    }
+
+    let args =
+        Args::try_parse_from(["chabeau", "import", "--preset", "st.json", "--id", "st"]).unwrap();
+    match args.command {
+        Some(Commands::Import { st_preset, id, .. }) => {
+            assert_eq!(st_preset, Some(PathBuf::from("st.json")));
+            assert_eq!(id.as_deref(), Some("st"));
+        }
+        _ => panic!("Expected import command"),
+    }
+
+    assert!(Args::try_parse_from(["chabeau", "import"]).is_err());
+    assert!(Args::try_parse_from(["chabeau", "import", "a.png", "--chat", "a.jsonl"]).is_err());
+    assert!(Args::try_parse_from(["chabeau", "import", "a.png", "--id", "x"]).is_err());
+}
+
 #[test]
 fn test_mcp_token_add_command_parsing() {
     let args = Args::try_parse_from(["chabeau", "mcp", "token", "add", "agpedia"]).unwrap();
@@ -221,6 +294,186 @@ fn test_mcp_token_list_command_parsing() {
     }
 }
 
+#[test]
+fn test_mcp_call_command_parsing() {
+    let args = Args::try_parse_from([
+        "chabeau",
+        "mcp",
+        "call",
+        "agpedia",
+        "search",
+        "--args",
+        r#"{"q":"rust"}"#,
+        "--json",
+    ])
+    .unwrap();
+    match args.command {
+        Some(Commands::Mcp {
+            command:
+                McpCommands::Call {
+                    server,
+                    tool,
+                    args,
+                    json,
+                },
+        }) => {
+            assert_eq!(server, "agpedia");
+            assert_eq!(tool, "search");
+            assert_eq!(args.as_deref(), Some(r#"{"q":"rust"}"#));
+            assert!(json);
+        }
+        _ => panic!("Expected mcp call subcommand"),
+    }
+}
+
+#[test]
+fn test_character_new_and_edit_parsing() {
+    let args = parse_args(&["chabeau", "character", "new", "Ada Lovelace"]);
+    match args.command {
+        Some(Commands::Character {
+            command: CharacterCommands::New { name },
+        }) => assert_eq!(name, "Ada Lovelace"),
+        _ => panic!("Expected character new subcommand"),
+    }
+
+    let args = parse_args(&["chabeau", "character", "edit", "cards/ada.png"]);
+    match args.command {
+        Some(Commands::Character {
+            command: CharacterCommands::Edit { name },
+        }) => assert_eq!(name, "cards/ada.png"),
+        _ => panic!("Expected character edit subcommand"),
+    }
+
+    assert!(Args::try_parse_from(["chabeau", "character", "edit"]).is_err());
+}
+
+#[test]
+fn test_character_export_parsing() {
+    let args = parse_args(&[
+        "chabeau",
+        "character",
+        "export",
+        "ada",
+        "--format",
+        "png",
+        "--image",
+        "avatar.png",
+        "-o",
+        "out/ada.png",
+    ]);
+    match args.command {
+        Some(Commands::Character {
+            command:
+                CharacterCommands::Export {
+                    name,
+                    format,
+                    image,
+                    output,
+                    force,
+                },
+        }) => {
+            assert_eq!(name, "ada");
+            assert_eq!(format, CardFormat::Png);
+            assert_eq!(image, Some(PathBuf::from("avatar.png")));
+            assert_eq!(output, Some(PathBuf::from("out/ada.png")));
+            assert!(!force);
+        }
+        _ => panic!("Expected character export subcommand"),
+    }
+
+    let args = parse_args(&["chabeau", "character", "export", "ada"]);
+    match args.command {
+        Some(Commands::Character {
+            command: CharacterCommands::Export { format, image, .. },
+        }) => {
+            assert_eq!(format, CardFormat::Json);
+            assert!(image.is_none());
+        }
+        _ => panic!("Expected character export subcommand"),
+    }
+
+    assert!(
+        Args::try_parse_from(["chabeau", "character", "export", "ada", "--format", "charx"])
+            .is_err()
+    );
+}
+
+#[test]
+fn test_mcp_audit_command_parsing() {
+    let args = Args::try_parse_from([
+        "chabeau", "mcp", "audit", "--server", "agpedia", "--status", "error", "-n", "5",
+    ])
+    .unwrap();
+    match args.command {
+        Some(Commands::Mcp {
+            command:
+                McpCommands::Audit {
+                    command: None,
+                    server,
+                    tool,
+                    status,
+                    limit,
+                    json,
+                },
+        }) => {
+            assert_eq!(server.as_deref(), Some("agpedia"));
+            assert!(tool.is_none());
+            assert_eq!(status.as_deref(), Some("error"));
+            assert_eq!(limit, 5);
+            assert!(!json);
+        }
+        _ => panic!("Expected mcp audit listing"),
+    }
+
+    let args = Args::try_parse_from(["chabeau", "mcp", "audit", "replay", "3", "--json"]).unwrap();
+    match args.command {
+        Some(Commands::Mcp {
+            command:
+                McpCommands::Audit {
+                    command: Some(McpAuditCommands::Replay { entry, json }),
+                    ..
+                },
+        }) => {
+            assert_eq!(entry, 3);
+            assert!(json);
+        }
+        _ => panic!("Expected mcp audit replay subcommand"),
+    }
+}
+
+#[test]
+fn test_mcp_prompt_command_parsing() {
+    let args = Args::try_parse_from([
+        "chabeau",
+        "mcp",
+        "prompt",
+        "agpedia",
+        "summarize",
+        "--arg",
+        "topic=rust",
+        "--arg",
+        "length=short",
+    ])
+    .unwrap();
+    match args.command {
+        Some(Commands::Mcp {
+            command:
+                McpCommands::Prompt {
+                    server,
+                    name,
+                    args,
+                    json,
+                },
+        }) => {
+            assert_eq!(server, "agpedia");
+            assert_eq!(name, "summarize");
+            assert_eq!(args, vec!["topic=rust", "length=short"]);
+            assert!(!json);
+        }
+        _ => panic!("Expected mcp prompt subcommand"),
+    }
+}
+
 #[test]
 fn test_mcp_edit_command_parsing() {
     let args = Args::try_parse_from(["chabeau", "mcp", "edit", "agpedia"]).unwrap();
@@ -309,6 +562,15 @@ fn test_parse_key_value_pairs_rejects_invalid_entry() {
     );
 }
 
+#[test]
+fn test_parse_optional_number_keeps_or_resets_limits() {
+    assert_eq!(parse_optional_number::<u64>("", Some(90)), Ok(Some(90)));
+    assert_eq!(parse_optional_number::<u64>("default", Some(90)), Ok(None));
+    assert_eq!(parse_optional_number::<u64>(" 300 ", None), Ok(Some(300)));
+    assert!(parse_optional_number::<usize>("0", None).is_err());
+    assert!(parse_optional_number::<usize>("lots", None).is_err());
+}
+
 #[test]
 fn test_mcp_add_advanced_flag_parsing() {
     let args = Args::try_parse_from(["chabeau", "mcp", "add", "--advanced"]).unwrap();
@@ -495,6 +757,48 @@ fn test_cli_set_default_model_with_mixed_case_provider() {
     });
 }
 
+#[test]
+fn test_cli_set_and_unset_mcp_timeout() {
+    with_test_config_env(|_| {
+        Config::mutate(|config| {
+            config.mcp_servers.push(McpServerConfig {
+                id: "research".to_string(),
+                display_name: "Research".to_string(),
+                command: Some("research-mcp".to_string()),
+                transport: Some("stdio".to_string()),
+                enabled: Some(true),
+                ..Default::default()
+            });
+            Ok(())
+        })
+        .unwrap();
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        for argv in [
+            &["chabeau", "set", "mcp", "research", "timeout", "600"][..],
+            &["chabeau", "set", "mcp", "research", "max-tools", "400"][..],
+        ] {
+            runtime
+                .block_on(handle_args(Args::try_parse_from(argv).unwrap()))
+                .expect("CLI command should succeed");
+        }
+        let config = Config::load().expect("config should load");
+        let server = config.get_mcp_server("research").expect("server");
+        assert_eq!(server.request_timeout_seconds, Some(600));
+        assert_eq!(server.max_tools, Some(400));
+
+        runtime
+            .block_on(handle_args(
+                Args::try_parse_from(["chabeau", "unset", "mcp", "research timeout"]).unwrap(),
+            ))
+            .expect("CLI command should succeed");
+        let config = Config::load().expect("config should load");
+        let server = config.get_mcp_server("research").expect("server");
+        assert_eq!(server.request_timeout_seconds, None);
+        assert_eq!(server.max_tools, Some(400));
+    });
+}
+
 #[test]
 fn test_cli_set_default_character_with_cached_service() {
     with_test_config_env(|config_root| {
@@ -1,10 +1,14 @@
-use super::{required_arg, usage_status};
+use super::{add_info_and_focus, required_arg, usage_status};
+use crate::character::group::{GroupChat, TurnOrder};
 use crate::commands::registry::CommandInvocation;
 use crate::commands::CommandResult;
 use crate::core::app::App;
+use crate::core::preset::parse_preset_ids;
 
 const USAGE_MARKDOWN: &str = "Usage: /markdown [on|off|toggle]";
 const USAGE_SYNTAX: &str = "Usage: /syntax [on|off|toggle]";
+const USAGE_GROUP: &str =
+    "Usage: /group [add <name>|remove <name>|order <round-robin|mention|manual>|off]";
 
 pub(crate) fn handle_theme(app: &mut App, invocation: CommandInvocation<'_>) -> CommandResult {
     if invocation.args_len() == 0 {
@@ -132,6 +136,181 @@ pub(crate) fn handle_character(app: &mut App, invocation: CommandInvocation<'_>)
     }
 }
 
+pub(crate) fn handle_greeting(app: &mut App, invocation: CommandInvocation<'_>) -> CommandResult {
+    if app.session.get_character().is_none() {
+        app.conversation()
+            .set_status("No character is active.".to_string());
+        return CommandResult::Continue;
+    }
+    if invocation.args_len() == 0 {
+        return CommandResult::OpenGreetingPicker;
+    }
+
+    match invocation.arg(0).and_then(|arg| arg.parse::<usize>().ok()) {
+        Some(number) if number > 0 && invocation.args_len() == 1 => {
+            app.select_greeting(number - 1);
+        }
+        _ => {
+            app.conversation()
+                .set_status("Usage: /greeting [n]".to_string());
+        }
+    }
+    CommandResult::Continue
+}
+
+pub(crate) fn handle_group(app: &mut App, invocation: CommandInvocation<'_>) -> CommandResult {
+    let args = invocation.args_text().trim();
+    let (action, rest) = args
+        .split_once(char::is_whitespace)
+        .map(|(action, rest)| (action, rest.trim()))
+        .unwrap_or((args, ""));
+
+    let outcome = match action.to_ascii_lowercase().as_str() {
+        "" => match group_summary(app) {
+            Some(summary) => return add_info_and_focus(app, summary),
+            None => Err("No group chat. Start one with /group add <name>.".to_string()),
+        },
+        "add" if !rest.is_empty() => add_group_member(app, rest),
+        "remove" if !rest.is_empty() => remove_group_member(app, rest),
+        "order" => match (TurnOrder::parse(rest), app.session.group.as_mut()) {
+            (_, None) => Err("No group chat is active.".to_string()),
+            (None, Some(_)) => {
+                Err("Turn order must be round-robin, mention, or manual.".to_string())
+            }
+            (Some(order), Some(group)) => {
+                group.turn_order = order;
+                Ok(format!("Group turn order: {}", order.label()))
+            }
+        },
+        "off" => match app.session.group.take() {
+            Some(_) => Ok(match app.session.get_character() {
+                Some(character) => {
+                    format!("Group chat ended; {} stays active", character.data.name)
+                }
+                None => "Group chat ended".to_string(),
+            }),
+            None => Err("No group chat is active.".to_string()),
+        },
+        _ => return usage_status(app, USAGE_GROUP),
+    };
+
+    let (Ok(status) | Err(status)) = outcome;
+    app.conversation().set_status(status);
+    CommandResult::Continue
+}
+
+/// Adds a card to the group, starting one with the active character (if any)
+/// as its first member.
+fn add_group_member(app: &mut App, name: &str) -> Result<String, String> {
+    let card = app
+        .character_service
+        .resolve(name)
+        .map_err(|e| format!("Character error: {}", e))?;
+    let added = card.data.name.clone();
+
+    if let Some(group) = app.session.group.as_mut() {
+        group.add(card)?;
+        return Ok(format!(
+            "Added {} to the group ({})",
+            added,
+            member_count(group.members().len())
+        ));
+    }
+
+    let mut group = GroupChat::new(app.session.get_character().cloned().into_iter().collect());
+    group.add(card.clone())?;
+    let count = group.members().len();
+    app.session.group = Some(group);
+    if app.session.get_character().is_none() {
+        app.session.set_character(card);
+    }
+    Ok(format!(
+        "Group chat started with {} ({})",
+        added,
+        member_count(count)
+    ))
+}
+
+fn remove_group_member(app: &mut App, name: &str) -> Result<String, String> {
+    let group = app
+        .session
+        .group
+        .as_mut()
+        .ok_or_else(|| "No group chat is active.".to_string())?;
+    let removed = group.remove(name)?;
+    let remaining = group.members().len();
+    let next = group.last_speaker().unwrap_or(0);
+
+    if remaining == 0 {
+        app.session.clear_character();
+        return Ok(format!(
+            "Removed {}; the group is now empty",
+            removed.data.name
+        ));
+    }
+    let was_speaking = app
+        .session
+        .get_character()
+        .is_some_and(|character| character.data.name == removed.data.name);
+    if was_speaking {
+        app.session.set_group_speaker(next);
+    }
+    Ok(format!(
+        "Removed {} from the group ({})",
+        removed.data.name,
+        member_count(remaining)
+    ))
+}
+
+fn member_count(count: usize) -> String {
+    if count == 1 {
+        "1 member".to_string()
+    } else {
+        format!("{} members", count)
+    }
+}
+
+fn group_summary(app: &App) -> Option<String> {
+    let group = app.session.group.as_ref()?;
+    let next = group.next_speaker(None);
+    let mut lines = vec![format!(
+        "Group chat ({} turn order):",
+        group.turn_order.label()
+    )];
+    for (index, card) in group.members().iter().enumerate() {
+        let marker = if Some(index) == next { "  (next)" } else { "" };
+        lines.push(format!("{}. {}{}", index + 1, card.data.name, marker));
+    }
+    Some(lines.join("\n"))
+}
+
+pub(crate) fn handle_speak(app: &mut App, invocation: CommandInvocation<'_>) -> CommandResult {
+    let name = invocation.args_text().trim();
+    if name.is_empty() {
+        return usage_status(app, "Usage: /speak <name>");
+    }
+    let Some(group) = app.session.group.as_ref() else {
+        return usage_status(
+            app,
+            "No group chat is active. Start one with /group add <name>.",
+        );
+    };
+    if app.has_interruptible_activity() {
+        return usage_status(
+            app,
+            "Wait for the current reply to finish (or press Esc) before /speak.",
+        );
+    }
+    match group.position(name) {
+        Some(speaker) => CommandResult::GroupReply(speaker),
+        None => {
+            app.conversation()
+                .set_status(format!("{} is not in the group.", name));
+            CommandResult::Continue
+        }
+    }
+}
+
 pub(crate) fn handle_persona(app: &mut App, invocation: CommandInvocation<'_>) -> CommandResult {
     if invocation.args_len() == 0 {
         return CommandResult::OpenPersonaPicker;
@@ -184,10 +363,14 @@ pub(crate) fn handle_preset(app: &mut App, invocation: CommandInvocation<'_>) ->
             .set_status("Preset deactivated".to_string());
         CommandResult::Continue
     } else {
-        match app.preset_manager.set_active_preset(preset_id) {
+        let preset_ids = parse_preset_ids(invocation.args_text());
+        if preset_ids.is_empty() {
+            return usage_status(app, "Usage: /preset <id>");
+        }
+        match app.preset_manager.set_active_presets(&preset_ids) {
             Ok(()) => {
                 app.conversation()
-                    .set_status(format!("Preset activated: {}", preset_id));
+                    .set_status(format!("Preset activated: {}", preset_ids.join(", ")));
                 CommandResult::Continue
             }
             Err(e) => {
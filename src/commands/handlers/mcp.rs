@@ -6,9 +6,13 @@ use crate::core::app::session::McpPromptRequest;
 use crate::core::app::App;
 use crate::core::mcp_auth::McpTokenStore;
 use crate::core::message::AppMessageKind;
+use crate::mcp::health::McpServerHealth;
+use crate::mcp::logging::{parse_logging_level, McpLogEntry};
 
-const USAGE_MCP: &str = "Usage: /mcp <server-id> [on|off|forget]";
+const USAGE_MCP: &str =
+    "Usage: /mcp <server-id> [on|off|forget|logs [level]|watch [<uri> [inject]]|unwatch <uri>]";
 const USAGE_YOLO: &str = "Usage: /yolo <server-id> [on|off]";
+const USAGE_TOOLS: &str = "Usage: /tools";
 
 pub(crate) fn handle_prompt_invocation(app: &mut App, input: &str) -> Option<CommandResult> {
     let trimmed = input.trim();
@@ -95,6 +99,7 @@ pub(crate) fn handle_prompt_invocation(app: &mut App, input: &str) -> Option<Com
                 pending_args: missing,
                 collected,
                 next_index: 0,
+                target: Default::default(),
             });
         return Some(CommandResult::Continue);
     }
@@ -121,6 +126,22 @@ pub(crate) fn handle_mcp(app: &mut App, invocation: CommandInvocation<'_>) -> Co
                 "on" => handle_mcp_toggle(app, server_id, true),
                 "off" => handle_mcp_toggle(app, server_id, false),
                 "forget" => handle_mcp_forget(app, server_id),
+                "logs" => handle_mcp_logs(app, server_id, None),
+                "watch" => handle_mcp_watch_list(app, server_id),
+                _ => usage_status(app, USAGE_MCP),
+            }
+        }
+        3 | 4 => {
+            let subcommand = invocation.arg(1).unwrap_or_default().to_ascii_lowercase();
+            let value = invocation.arg(2).unwrap_or_default();
+            let flag = invocation.arg(3);
+            match (subcommand.as_str(), flag) {
+                ("logs", None) => handle_mcp_logs(app, server_id, Some(value)),
+                ("watch", None) => handle_mcp_watch(app, server_id, value, false),
+                ("watch", Some(flag)) if flag.eq_ignore_ascii_case("inject") => {
+                    handle_mcp_watch(app, server_id, value, true)
+                }
+                ("unwatch", None) => handle_mcp_unwatch(app, server_id, value),
                 _ => usage_status(app, USAGE_MCP),
             }
         }
@@ -128,6 +149,33 @@ pub(crate) fn handle_mcp(app: &mut App, invocation: CommandInvocation<'_>) -> Co
     }
 }
 
+pub(crate) fn handle_tools(app: &mut App, invocation: CommandInvocation<'_>) -> CommandResult {
+    if invocation.args_len() > 0 {
+        return usage_status(app, USAGE_TOOLS);
+    }
+    if app.session.mcp_disabled {
+        app.conversation()
+            .set_status("MCP: **disabled for this session**".to_string());
+        return CommandResult::Continue;
+    }
+
+    let history = &app.session.tool_pipeline.tool_routing_history;
+    if history.is_empty() {
+        app.conversation()
+            .set_status("No MCP tools have been offered yet.".to_string());
+        return CommandResult::Continue;
+    }
+    let content = history
+        .iter()
+        .map(|turn| turn.format_lines().join("\n"))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    app.open_inspect("MCP tool routing".to_string(), content);
+    app.scroll_inspect_to_end();
+    CommandResult::Continue
+}
+
 pub(crate) fn handle_yolo(app: &mut App, invocation: CommandInvocation<'_>) -> CommandResult {
     if invocation.args_len() == 0 || invocation.args_len() > 2 {
         return usage_status(app, USAGE_YOLO);
@@ -229,11 +277,17 @@ fn handle_mcp_list(app: &mut App) -> CommandResult {
         return CommandResult::ContinueWithTranscriptFocus;
     }
 
+    let show_health = !app.session.mcp_disabled;
     for server in servers {
-        let disabled_marker = if server.config.is_enabled() {
-            ""
+        let disabled_marker = if !server.config.is_enabled() {
+            " — **disabled**".to_string()
+        } else if !show_health {
+            String::new()
         } else {
-            " — **disabled**"
+            match &server.health {
+                McpServerHealth::Failed { .. } => " — **failed**".to_string(),
+                health => format!(" — {}", health.describe()),
+            }
         };
         let yolo_marker = if server.config.is_yolo() {
             " — **YOLO**"
@@ -408,6 +462,9 @@ fn handle_mcp_forget(app: &mut App, server_id: &str) -> CommandResult {
         Err(_) => false,
     };
 
+    if let Some(server) = app.mcp.server_mut(server_id) {
+        server.log_buffer.clear();
+    }
     app.mcp_permissions.clear_server(server_id);
     app.session.tool_pipeline.clear_server_records(server_id);
 
@@ -423,6 +480,204 @@ fn handle_mcp_forget(app: &mut App, server_id: &str) -> CommandResult {
     CommandResult::Continue
 }
 
+fn handle_mcp_logs(app: &mut App, server_id: &str, level_arg: Option<&str>) -> CommandResult {
+    let Some(server) = app.mcp.server(server_id) else {
+        app.conversation()
+            .set_status(format!("Unknown MCP server: {}", server_id));
+        return CommandResult::Continue;
+    };
+
+    let level = match level_arg {
+        Some(arg) => match parse_logging_level(arg) {
+            Some(level) => Some(level),
+            None => {
+                app.conversation().set_status(format!(
+                    "Unknown log level: {} (use debug, info, notice, warning, error, critical, alert, or emergency)",
+                    arg
+                ));
+                return CommandResult::Continue;
+            }
+        },
+        None => None,
+    };
+
+    let server_label = server.config.id.clone();
+    let title = match level {
+        Some(level) => format!("MCP logs: {} (≥ {})", server.config.display_name, level),
+        None => format!("MCP logs: {}", server.config.display_name),
+    };
+    let content = build_mcp_logs_output(server, level);
+    let request_level = level.is_some_and(|_| {
+        !app.session.mcp_disabled && server.connected && server.supports_logging()
+    });
+
+    app.open_inspect(title, content);
+    app.scroll_inspect_to_end();
+
+    match level {
+        Some(level) if request_level => CommandResult::SetMcpLogLevel {
+            server_id: server_label,
+            level,
+        },
+        _ => CommandResult::Continue,
+    }
+}
+
+fn handle_mcp_watch(app: &mut App, server_id: &str, uri: &str, inject: bool) -> CommandResult {
+    let Some(server) = app.mcp.server(server_id) else {
+        app.conversation()
+            .set_status(format!("Unknown MCP server: {}", server_id));
+        return CommandResult::Continue;
+    };
+    let server_label = server.config.id.clone();
+
+    if app.session.mcp_disabled || !server.config.is_enabled() {
+        app.conversation()
+            .set_status(format!("MCP server {} is disabled.", server_label));
+        return CommandResult::Continue;
+    }
+    if !server.connected {
+        app.conversation().set_status(format!(
+            "MCP server {} is not connected. Run `/mcp {}` first.",
+            server_label, server_label
+        ));
+        return CommandResult::Continue;
+    }
+    if !server.supports_resource_subscribe() {
+        app.conversation().set_status(format!(
+            "MCP server {} does not support resource subscriptions.",
+            server_label
+        ));
+        return CommandResult::Continue;
+    }
+    if let Some(existing) = server.resource_watches.get(uri) {
+        if existing.inject == inject {
+            app.conversation()
+                .set_status(format!("Already watching {}", uri));
+            return CommandResult::Continue;
+        }
+        // Subscriptions are idempotent; only the local injection flag changes.
+        if let Some(watch) = app
+            .mcp
+            .server_mut(server_id)
+            .and_then(|server| server.resource_watches.get_mut(uri))
+        {
+            watch.inject = inject;
+            watch.pending_injection &= inject;
+        }
+        let mode = if inject { "on" } else { "off" };
+        app.conversation()
+            .set_status(format!("Update injection {} for {}", mode, uri));
+        return CommandResult::Continue;
+    }
+
+    app.conversation()
+        .set_status(format!("Subscribing to {}...", uri));
+    CommandResult::WatchMcpResource {
+        server_id: server_label,
+        uri: uri.to_string(),
+        inject,
+    }
+}
+
+fn handle_mcp_unwatch(app: &mut App, server_id: &str, uri: &str) -> CommandResult {
+    let Some(server) = app.mcp.server(server_id) else {
+        app.conversation()
+            .set_status(format!("Unknown MCP server: {}", server_id));
+        return CommandResult::Continue;
+    };
+    if !server.resource_watches.contains_key(uri) {
+        app.conversation()
+            .set_status(format!("Not watching {}", uri));
+        return CommandResult::Continue;
+    }
+
+    CommandResult::UnwatchMcpResource {
+        server_id: server.config.id.clone(),
+        uri: uri.to_string(),
+    }
+}
+
+fn handle_mcp_watch_list(app: &mut App, server_id: &str) -> CommandResult {
+    let Some(server) = app.mcp.server(server_id) else {
+        app.conversation()
+            .set_status(format!("Unknown MCP server: {}", server_id));
+        return CommandResult::Continue;
+    };
+
+    let title = format!("MCP watches: {}", server.config.display_name);
+    let content = build_mcp_watch_output(server);
+    app.open_inspect(title, content);
+    CommandResult::Continue
+}
+
+fn build_mcp_watch_output(server: &crate::mcp::client::McpServerState) -> String {
+    if server.resource_watches.is_empty() {
+        return format!(
+            "No watched resources. Start one with `/mcp {} watch <uri> [inject]`.",
+            server.config.id
+        );
+    }
+
+    let mut output = String::new();
+    for watch in server.resource_watches.values() {
+        output.push_str(&format!("## {}\n", watch.uri));
+        let updated = watch
+            .updated_at
+            .map(|time| time.format("%H:%M:%S").to_string())
+            .unwrap_or_else(|| "never".to_string());
+        output.push_str(&format!(
+            "Updates: {} (last read {}){}\n",
+            watch.update_count,
+            updated,
+            if watch.inject {
+                "; injected into the next request"
+            } else {
+                ""
+            }
+        ));
+        if let Some(err) = &watch.last_error {
+            output.push_str(&format!("Last error: {}\n", err));
+        }
+        output.push('\n');
+        match &watch.content {
+            Some(content) => output.push_str(content),
+            None => output.push_str("(no content read yet)"),
+        }
+        output.push_str("\n\n");
+    }
+    output.trim_end().to_string()
+}
+
+fn build_mcp_logs_output(
+    server: &crate::mcp::client::McpServerState,
+    level: Option<rust_mcp_schema::LoggingLevel>,
+) -> String {
+    let lines: Vec<String> = server
+        .log_buffer
+        .filtered(level)
+        .map(McpLogEntry::format_line)
+        .collect();
+
+    let mut output = String::new();
+    if server.log_buffer.dropped() > 0 {
+        output.push_str(&format!(
+            "({} older entries discarded)\n",
+            server.log_buffer.dropped()
+        ));
+    }
+    if lines.is_empty() {
+        output.push_str(if server.log_buffer.is_empty() {
+            "No log messages received from this server."
+        } else {
+            "No log messages at or above this level."
+        });
+    } else {
+        output.push_str(&lines.join("\n"));
+    }
+    output
+}
+
 fn clear_mcp_runtime_state(server: &mut crate::mcp::client::McpServerState) {
     server.clear_runtime_state();
 }
@@ -467,6 +722,15 @@ pub(crate) fn build_mcp_server_output(
         "Connected: {}\n",
         if server.connected { "yes" } else { "no" }
     ));
+    if server.config.is_enabled() {
+        output.push_str(&format!("Health: {}\n", server.health.describe()));
+    }
+    output.push_str(&format!(
+        "Limits: request timeout {}s, sampling timeout {}s, max tools {}\n",
+        server.limits.request_timeout.as_secs(),
+        server.limits.sampling_timeout.as_secs(),
+        server.limits.max_tools
+    ));
     match crate::mcp::client::McpTransportKind::from_config(&server.config) {
         Ok(crate::mcp::client::McpTransportKind::Stdio) => {
             output.push_str("Token: not used (stdio)\n");
@@ -576,6 +840,42 @@ pub(crate) fn build_mcp_server_output(
         output.push_str("**Resource templates:** no cached listing yet.\n");
     }
 
+    output.push('\n');
+    if cap_reported && !server.supports_logging() {
+        output.push_str("**Logging:** not supported (per server capabilities).\n");
+    } else {
+        let level = server
+            .log_level
+            .map(|level| level.to_string())
+            .unwrap_or_else(|| "server default".to_string());
+        output.push_str(&format!(
+            "**Logging:** {} buffered message(s), level {}. View with `/mcp {} logs [level]`.\n",
+            server.log_buffer.len(),
+            level,
+            server.config.id
+        ));
+    }
+    if !server.stderr_tail.is_empty() {
+        output.push_str("**Last stderr output:**\n```text\n");
+        for line in &server.stderr_tail {
+            output.push_str(line);
+            output.push('\n');
+        }
+        output.push_str("```\n");
+    }
+    if !server.resource_watches.is_empty() {
+        output.push_str(&format!(
+            "**Watches:** {} resource(s). View with `/mcp {} watch`.\n",
+            server.resource_watches.len(),
+            server.config.id
+        ));
+    } else if !cap_reported || server.supports_resource_subscribe() {
+        output.push_str(&format!(
+            "**Watches:** none. Subscribe with `/mcp {} watch <uri> [inject]`.\n",
+            server.config.id
+        ));
+    }
+
     output.push('\n');
     if let Some(list) = &server.cached_prompts {
         if list.prompts.is_empty() {
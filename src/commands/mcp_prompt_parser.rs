@@ -84,12 +84,36 @@ fn parse_single_prompt_value(input: &str) -> Result<String, String> {
 }
 
 fn tokenize_prompt_args(input: &str) -> Result<Vec<String>, String> {
+    let (tokens, open_quote) = scan_prompt_args(input);
+    if let Some(q) = open_quote {
+        return Err(format!("Unclosed quote ({}) in prompt arguments.", q));
+    }
+    Ok(tokens
+        .into_iter()
+        .map(|token| token.text)
+        .filter(|text| !text.is_empty())
+        .collect())
+}
+
+/// A whitespace-separated prompt argument with its quotes removed.
+pub(crate) struct PromptArgToken {
+    pub(crate) text: String,
+    /// Char offsets of the raw token, quotes included, in the input.
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+/// Splits prompt arguments on unquoted whitespace, also returning the quote
+/// still open at the end of the input so half-typed values can be completed.
+pub(crate) fn scan_prompt_args(input: &str) -> (Vec<PromptArgToken>, Option<char>) {
     let mut tokens = Vec::new();
     let mut current = String::new();
+    let mut start = None;
     let mut in_quote: Option<char> = None;
-    for ch in input.chars() {
+    for (index, ch) in input.chars().enumerate() {
         match ch {
             '"' | '\'' => {
+                start.get_or_insert(index);
                 if let Some(q) = in_quote {
                     if q == ch {
                         in_quote = None;
@@ -101,21 +125,28 @@ fn tokenize_prompt_args(input: &str) -> Result<Vec<String>, String> {
                 }
             }
             c if c.is_whitespace() && in_quote.is_none() => {
-                if !current.is_empty() {
-                    tokens.push(current.clone());
-                    current.clear();
+                if let Some(token_start) = start.take() {
+                    tokens.push(PromptArgToken {
+                        text: std::mem::take(&mut current),
+                        start: token_start,
+                        end: index,
+                    });
                 }
             }
-            _ => current.push(ch),
+            _ => {
+                start.get_or_insert(index);
+                current.push(ch);
+            }
         }
     }
-    if let Some(q) = in_quote {
-        return Err(format!("Unclosed quote ({}) in prompt arguments.", q));
-    }
-    if !current.is_empty() {
-        tokens.push(current);
+    if let Some(token_start) = start {
+        tokens.push(PromptArgToken {
+            text: current,
+            start: token_start,
+            end: input.chars().count(),
+        });
     }
-    Ok(tokens)
+    (tokens, in_quote)
 }
 
 #[cfg(test)]
@@ -7,6 +7,7 @@ mod registry;
 
 pub use handlers::io::dump_conversation_with_overwrite;
 pub(crate) use handlers::mcp::build_mcp_server_output;
+pub(crate) use mcp_prompt_parser::scan_prompt_args;
 pub use registry::{all_commands, matching_commands, CommandInvocation};
 
 use crate::core::app::App;
@@ -23,9 +24,27 @@ pub enum CommandResult {
     OpenCharacterPicker,
     OpenPersonaPicker,
     OpenPresetPicker,
+    OpenGreetingPicker,
     Refine(String),
+    /// Group member at this index replies now.
+    GroupReply(usize),
     RunMcpPrompt(crate::core::app::session::McpPromptRequest),
-    RefreshMcp { server_id: String },
+    RefreshMcp {
+        server_id: String,
+    },
+    SetMcpLogLevel {
+        server_id: String,
+        level: rust_mcp_schema::LoggingLevel,
+    },
+    WatchMcpResource {
+        server_id: String,
+        uri: String,
+        inject: bool,
+    },
+    UnwatchMcpResource {
+        server_id: String,
+        uri: String,
+    },
 }
 
 /// Processes user input and dispatches commands.
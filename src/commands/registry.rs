@@ -265,6 +265,18 @@ const COMMANDS: &[Command] = &[
                 syntax: "/mcp <server-id> forget",
                 description: "Clear cached MCP data for a server.",
             },
+            CommandUsage {
+                syntax: "/mcp <server-id> logs [level]",
+                description: "Show server log messages, optionally requesting a minimum level.",
+            },
+            CommandUsage {
+                syntax: "/mcp <server-id> watch [<uri> [inject]]",
+                description: "List watched resources, or subscribe to updates for a resource.",
+            },
+            CommandUsage {
+                syntax: "/mcp <server-id> unwatch <uri>",
+                description: "Stop watching a resource.",
+            },
         ],
         extra_help: &[],
         handler: super::handlers::mcp::handle_mcp,
@@ -288,6 +300,15 @@ const COMMANDS: &[Command] = &[
         extra_help: &[],
         handler: super::handlers::mcp::handle_yolo,
     },
+    Command {
+        name: "tools",
+        usages: &[CommandUsage {
+            syntax: "/tools",
+            description: "Show which MCP tools were offered to the model on recent requests.",
+        }],
+        extra_help: &[],
+        handler: super::handlers::mcp::handle_tools,
+    },
     Command {
         name: "log",
         usages: &[CommandUsage {
@@ -389,6 +410,60 @@ const COMMANDS: &[Command] = &[
         extra_help: &[],
         handler: super::handlers::config::handle_character,
     },
+    Command {
+        name: "group",
+        usages: &[
+            CommandUsage {
+                syntax: "/group",
+                description: "Show the group chat members and who replies next.",
+            },
+            CommandUsage {
+                syntax: "/group add <name>",
+                description:
+                    "Add a character to the group chat, starting one with the active character.",
+            },
+            CommandUsage {
+                syntax: "/group remove <name>",
+                description: "Remove a character from the group chat.",
+            },
+            CommandUsage {
+                syntax: "/group order <round-robin|mention|manual>",
+                description:
+                    "Choose who replies: take turns, whoever you name, or whoever you last picked with /speak.",
+            },
+            CommandUsage {
+                syntax: "/group off",
+                description: "End the group chat, keeping the current speaker active.",
+            },
+        ],
+        extra_help: &[],
+        handler: super::handlers::config::handle_group,
+    },
+    Command {
+        name: "speak",
+        usages: &[CommandUsage {
+            syntax: "/speak <name>",
+            description: "Have a group chat member reply now.",
+        }],
+        extra_help: &[],
+        handler: super::handlers::config::handle_speak,
+    },
+    Command {
+        name: "greeting",
+        usages: &[
+            CommandUsage {
+                syntax: "/greeting",
+                description: "Pick the active character's opening greeting from its alternates.",
+            },
+            CommandUsage {
+                syntax: "/greeting <n>",
+                description:
+                    "Switch to greeting number n (1 is the card's first message) before your first message.",
+            },
+        ],
+        extra_help: &[],
+        handler: super::handlers::config::handle_greeting,
+    },
     Command {
         name: "persona",
         usages: &[
@@ -412,8 +487,8 @@ const COMMANDS: &[Command] = &[
                 description: "Pick a preset from available presets with filtering and sorting.",
             },
             CommandUsage {
-                syntax: "/preset <id>",
-                description: "Activate the specified preset for this session.",
+                syntax: "/preset <id>[,<id>...]",
+                description: "Activate the specified preset for this session; list several to stack them in order.",
             },
         ],
         extra_help: &[],
@@ -85,6 +85,7 @@ fn clear_command_shows_character_greeting_when_available() {
             tags: None,
             creator: None,
             character_version: None,
+            ..Default::default()
         },
     };
 
@@ -578,6 +579,44 @@ fn character_command_registered_in_help() {
     assert!(character_cmd.usages[1].syntax.contains("<name>"));
 }
 
+#[test]
+fn greeting_command_opens_picker_and_selects_by_number() {
+    let mut app = create_test_app();
+    let res = process_input(&mut app, "/greeting");
+    assert!(matches!(res, CommandResult::Continue));
+    assert_eq!(app.ui.status.as_deref(), Some("No character is active."));
+
+    let character: CharacterCard = serde_json::from_value(serde_json::json!({
+        "spec": "chara_card_v2",
+        "spec_version": "2.0",
+        "data": {
+            "name": "TestBot", "description": "", "personality": "", "scenario": "",
+            "first_mes": "Hello!", "mes_example": "",
+            "alternate_greetings": ["Good evening."]
+        }
+    }))
+    .unwrap();
+    app.session.set_character(character);
+    app.conversation().show_character_greeting_if_needed();
+
+    let res = process_input(&mut app, "/greeting");
+    assert!(matches!(res, CommandResult::OpenGreetingPicker));
+
+    let res = process_input(&mut app, "/greeting 2");
+    assert!(matches!(res, CommandResult::Continue));
+    assert_eq!(app.ui.status.as_deref(), Some("Greeting 2 of 2"));
+    assert_eq!(app.ui.messages[0].content, "Good evening.");
+
+    process_input(&mut app, "/greeting 0");
+    assert_eq!(app.ui.status.as_deref(), Some("Usage: /greeting [n]"));
+
+    app.open_greeting_picker();
+    let items = &app.picker_state().unwrap().items;
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].label, "1. first message");
+    assert_eq!(app.picker_state().unwrap().selected, 1);
+}
+
 #[test]
 fn persona_command_opens_picker() {
     let mut app = create_test_app();
@@ -617,6 +656,14 @@ fn persona_command_with_valid_id_updates_user_display_name() {
     assert_eq!(app.ui.user_display_name, "Alice");
 }
 
+#[test]
+fn preset_command_rejects_empty_id_list() {
+    let mut app = create_test_app();
+    let res = process_input(&mut app, "/preset ,");
+    assert!(matches!(res, CommandResult::Continue));
+    assert_eq!(app.ui.status.as_deref(), Some("Usage: /preset <id>"));
+}
+
 #[test]
 fn mcp_command_lists_empty_config() {
     let mut app = create_test_app();
@@ -644,17 +691,10 @@ fn mcp_command_highlights_yolo_servers() {
         id: "alpha".to_string(),
         display_name: "Alpha".to_string(),
         base_url: Some("https://mcp.example.com".to_string()),
-        command: None,
-        args: None,
-        env: None,
-        headers: None,
         transport: Some("streamable-http".to_string()),
-        allowed_tools: None,
-        protocol_version: None,
         enabled: Some(true),
-        tool_payloads: None,
-        tool_payload_window: None,
         yolo: Some(true),
+        ..Default::default()
     });
     app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
 
@@ -671,17 +711,9 @@ fn mcp_command_highlights_disabled_servers() {
         id: "alpha".to_string(),
         display_name: "Alpha".to_string(),
         base_url: Some("https://mcp.example.com".to_string()),
-        command: None,
-        args: None,
-        env: None,
-        headers: None,
         transport: Some("streamable-http".to_string()),
-        allowed_tools: None,
-        protocol_version: None,
         enabled: Some(false),
-        tool_payloads: None,
-        tool_payload_window: None,
-        yolo: None,
+        ..Default::default()
     });
     app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
 
@@ -691,6 +723,33 @@ fn mcp_command_highlights_disabled_servers() {
     assert!(last.content.contains("**disabled**"));
 }
 
+#[test]
+fn mcp_command_shows_server_health() {
+    let mut app = create_test_app();
+    for id in ["alpha", "beta"] {
+        app.config.mcp_servers.push(McpServerConfig {
+            id: id.to_string(),
+            display_name: id.to_string(),
+            command: Some(format!("{id}-mcp")),
+            transport: Some("stdio".to_string()),
+            enabled: Some(true),
+            ..Default::default()
+        });
+    }
+    app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
+    app.mcp.server_mut("alpha").expect("alpha").health = crate::mcp::health::McpServerHealth::Up;
+    app.mcp.server_mut("beta").expect("beta").health =
+        crate::mcp::health::McpServerHealth::Restarting { attempt: 2 };
+
+    let res = process_input(&mut app, "/mcp");
+    assert!(matches!(res, CommandResult::ContinueWithTranscriptFocus));
+    let last = app.ui.messages.back().expect("app message");
+    assert!(last.content.contains("- **alpha** (alpha) — up"));
+    assert!(last
+        .content
+        .contains("- **beta** (beta) — restarting (attempt 2/5)"));
+}
+
 #[test]
 fn mcp_command_skips_refresh_for_disabled_server() {
     let mut app = create_test_app();
@@ -700,17 +759,9 @@ fn mcp_command_skips_refresh_for_disabled_server() {
             id: "alpha".to_string(),
             display_name: "Alpha".to_string(),
             base_url: Some("https://mcp.example.com".to_string()),
-            command: None,
-            args: None,
-            env: None,
-            headers: None,
             transport: Some("streamable-http".to_string()),
-            allowed_tools: None,
-            protocol_version: None,
             enabled: Some(false),
-            tool_payloads: None,
-            tool_payload_window: None,
-            yolo: None,
+            ..Default::default()
         });
     app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
 
@@ -729,17 +780,11 @@ fn mcp_command_includes_allowed_tools() {
             id: "alpha".to_string(),
             display_name: "Alpha".to_string(),
             base_url: Some("https://mcp.example.com".to_string()),
-            command: None,
-            args: None,
-            env: None,
-            headers: None,
             transport: Some("streamable-http".to_string()),
             allowed_tools: Some(vec!["weather.lookup".to_string(), "time.now".to_string()]),
             protocol_version: Some("2024-11-05".to_string()),
             enabled: Some(true),
-            tool_payloads: None,
-            tool_payload_window: None,
-            yolo: None,
+            ..Default::default()
         });
     app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
 
@@ -764,17 +809,9 @@ fn mcp_command_reports_disabled_client_side_tool_validation() {
         id: "alpha".to_string(),
         display_name: "Alpha".to_string(),
         base_url: Some("https://mcp.example.com".to_string()),
-        command: None,
-        args: None,
-        env: None,
-        headers: None,
         transport: Some("streamable-http".to_string()),
-        allowed_tools: None,
-        protocol_version: None,
         enabled: Some(true),
-        tool_payloads: None,
-        tool_payload_window: None,
-        yolo: None,
+        ..Default::default()
     });
     app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
 
@@ -827,17 +864,9 @@ fn yolo_command_shows_and_persists() {
             id: "alpha".to_string(),
             display_name: "Alpha".to_string(),
             base_url: Some("https://mcp.example.com".to_string()),
-            command: None,
-            args: None,
-            env: None,
-            headers: None,
             transport: Some("streamable-http".to_string()),
-            allowed_tools: None,
-            protocol_version: None,
             enabled: Some(true),
-            tool_payloads: None,
-            tool_payload_window: None,
-            yolo: None,
+            ..Default::default()
         });
         config.save().expect("save config");
 
@@ -876,17 +905,9 @@ fn mcp_command_toggle_enabled_persists() {
             id: "alpha".to_string(),
             display_name: "Alpha".to_string(),
             base_url: Some("https://mcp.example.com".to_string()),
-            command: None,
-            args: None,
-            env: None,
-            headers: None,
             transport: Some("streamable-http".to_string()),
-            allowed_tools: None,
-            protocol_version: None,
             enabled: Some(true),
-            tool_payloads: None,
-            tool_payload_window: None,
-            yolo: None,
+            ..Default::default()
         });
         config.save().expect("save config");
 
@@ -920,17 +941,9 @@ fn mcp_command_toggle_on_triggers_refresh() {
             id: "alpha".to_string(),
             display_name: "Alpha".to_string(),
             base_url: Some("https://mcp.example.com".to_string()),
-            command: None,
-            args: None,
-            env: None,
-            headers: None,
             transport: Some("streamable-http".to_string()),
-            allowed_tools: None,
-            protocol_version: None,
             enabled: Some(false),
-            tool_payloads: None,
-            tool_payload_window: None,
-            yolo: None,
+            ..Default::default()
         });
         config.save().expect("save config");
 
@@ -974,17 +987,9 @@ fn mcp_command_toggle_off_clears_runtime_state() {
         id: "alpha".to_string(),
         display_name: "Alpha".to_string(),
         base_url: Some("https://mcp.example.com".to_string()),
-        command: None,
-        args: None,
-        env: None,
-        headers: None,
         transport: Some("streamable-http".to_string()),
-        allowed_tools: None,
-        protocol_version: None,
         enabled: Some(true),
-        tool_payloads: None,
-        tool_payload_window: None,
-        yolo: None,
+        ..Default::default()
     });
     app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
 
@@ -1060,33 +1065,17 @@ fn mcp_command_forget_clears_permissions_and_history() {
             id: "alpha".to_string(),
             display_name: "Alpha".to_string(),
             base_url: Some("https://mcp.example.com".to_string()),
-            command: None,
-            args: None,
-            env: None,
-            headers: None,
             transport: Some("streamable-http".to_string()),
-            allowed_tools: None,
-            protocol_version: None,
             enabled: Some(true),
-            tool_payloads: None,
-            tool_payload_window: None,
-            yolo: None,
+            ..Default::default()
         });
         config.mcp_servers.push(McpServerConfig {
             id: "beta".to_string(),
             display_name: "Beta".to_string(),
             base_url: Some("https://mcp.example.com".to_string()),
-            command: None,
-            args: None,
-            env: None,
-            headers: None,
             transport: Some("streamable-http".to_string()),
-            allowed_tools: None,
-            protocol_version: None,
             enabled: Some(true),
-            tool_payloads: None,
-            tool_payload_window: None,
-            yolo: None,
+            ..Default::default()
         });
         config.save().expect("save config");
 
@@ -1117,6 +1106,7 @@ fn mcp_command_forget_clears_permissions_and_history() {
                 tool_call_id: None,
                 raw_arguments: None,
                 assistant_message_index: None,
+                output: None,
             },
         );
         app.session.tool_pipeline.tool_result_history.push(
@@ -1131,6 +1121,7 @@ fn mcp_command_forget_clears_permissions_and_history() {
                 tool_call_id: None,
                 raw_arguments: None,
                 assistant_message_index: None,
+                output: None,
             },
         );
         app.session.tool_pipeline.tool_payload_history.push(
@@ -1143,6 +1134,8 @@ fn mcp_command_forget_clears_permissions_and_history() {
                     name: None,
                     tool_call_id: None,
                     tool_calls: None,
+                    images: Vec::new(),
+                    is_prefill: false,
                 },
                 tool_message: crate::api::ChatMessage {
                     role: "tool".to_string(),
@@ -1150,6 +1143,8 @@ fn mcp_command_forget_clears_permissions_and_history() {
                     name: None,
                     tool_call_id: Some("1".to_string()),
                     tool_calls: None,
+                    images: Vec::new(),
+                    is_prefill: false,
                 },
                 assistant_message_index: None,
             },
@@ -1164,6 +1159,8 @@ fn mcp_command_forget_clears_permissions_and_history() {
                     name: None,
                     tool_call_id: None,
                     tool_calls: None,
+                    images: Vec::new(),
+                    is_prefill: false,
                 },
                 tool_message: crate::api::ChatMessage {
                     role: "tool".to_string(),
@@ -1171,6 +1168,8 @@ fn mcp_command_forget_clears_permissions_and_history() {
                     name: None,
                     tool_call_id: Some("2".to_string()),
                     tool_calls: None,
+                    images: Vec::new(),
+                    is_prefill: false,
                 },
                 assistant_message_index: None,
             },
@@ -1213,6 +1212,192 @@ fn mcp_command_forget_clears_permissions_and_history() {
     });
 }
 
+fn logging_test_server() -> McpServerConfig {
+    McpServerConfig {
+        id: "alpha".to_string(),
+        display_name: "Alpha".to_string(),
+        base_url: Some("https://mcp.example.com".to_string()),
+        transport: Some("streamable-http".to_string()),
+        enabled: Some(true),
+        ..Default::default()
+    }
+}
+
+fn push_log_entry(app: &mut App, level: rust_mcp_schema::LoggingLevel, message: &str) {
+    let server = app.mcp.server_mut("alpha").expect("server");
+    server
+        .log_buffer
+        .push(crate::mcp::logging::McpLogEntry::from_params(
+            rust_mcp_schema::LoggingMessageNotificationParams {
+                data: serde_json::json!(message),
+                level,
+                logger: Some("db".to_string()),
+                meta: None,
+            },
+        ));
+}
+
+#[test]
+fn tools_command_shows_routing_history() {
+    let mut app = create_test_app();
+    let res = process_input(&mut app, "/tools");
+    assert!(matches!(res, CommandResult::Continue));
+    assert!(app.inspect_state().is_none());
+
+    app.session.tool_pipeline.tool_routing_history.push_back(
+        crate::mcp::routing::ToolRoutingTurn {
+            at: chrono::Local::now(),
+            candidates: 40,
+            offered: vec![crate::mcp::routing::RoutedTool {
+                server_id: "alpha".to_string(),
+                tool: "search".to_string(),
+                score: 1.5,
+                reason: crate::mcp::routing::RouteReason::Ranked,
+            }],
+            query_terms: vec!["rust".to_string()],
+        },
+    );
+    let res = process_input(&mut app, "/tools");
+    assert!(matches!(res, CommandResult::Continue));
+    let state = app.inspect_state().expect("inspect open");
+    assert_eq!(state.title, "MCP tool routing");
+    assert!(state.content.contains("1 of 40 tools offered"));
+    assert!(state.content.contains("query: rust"));
+    assert!(state.content.contains("- alpha/search  score 1.50"));
+}
+
+#[test]
+fn mcp_logs_command_opens_inspect_with_filtered_entries() {
+    let mut app = create_test_app();
+    app.config.mcp_servers.push(logging_test_server());
+    app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
+    push_log_entry(&mut app, rust_mcp_schema::LoggingLevel::Debug, "noise");
+    push_log_entry(&mut app, rust_mcp_schema::LoggingLevel::Error, "boom");
+
+    let res = process_input(&mut app, "/mcp alpha logs");
+    assert!(matches!(res, CommandResult::Continue));
+    let state = app.inspect_state().expect("inspect open");
+    assert_eq!(state.title, "MCP logs: Alpha");
+    assert!(state.content.contains("[debug] db: noise"));
+    assert!(state.content.contains("[error] db: boom"));
+
+    let res = process_input(&mut app, "/mcp alpha logs warn");
+    // Disconnected servers only filter locally; no setLevel request is issued.
+    assert!(matches!(res, CommandResult::Continue));
+    let state = app.inspect_state().expect("inspect open");
+    assert!(state.title.contains("warning"));
+    assert!(!state.content.contains("noise"));
+    assert!(state.content.contains("boom"));
+}
+
+#[test]
+fn mcp_logs_command_requests_level_from_connected_server() {
+    let mut app = create_test_app();
+    app.config.mcp_servers.push(logging_test_server());
+    app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
+    app.mcp.server_mut("alpha").expect("server").connected = true;
+
+    let res = process_input(&mut app, "/mcp alpha logs error");
+    assert!(matches!(
+        res,
+        CommandResult::SetMcpLogLevel {
+            ref server_id,
+            level: rust_mcp_schema::LoggingLevel::Error,
+        } if server_id == "alpha"
+    ));
+}
+
+#[test]
+fn mcp_logs_command_rejects_unknown_level() {
+    let mut app = create_test_app();
+    app.config.mcp_servers.push(logging_test_server());
+    app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
+
+    let res = process_input(&mut app, "/mcp alpha logs loud");
+    assert!(matches!(res, CommandResult::Continue));
+    assert!(app.inspect_state().is_none());
+    assert!(app
+        .ui
+        .status
+        .as_deref()
+        .is_some_and(|status| status.starts_with("Unknown log level: loud")));
+}
+
+#[test]
+fn mcp_watch_command_requires_connected_server() {
+    let mut app = create_test_app();
+    app.config.mcp_servers.push(logging_test_server());
+    app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
+
+    let res = process_input(&mut app, "/mcp alpha watch db://orders");
+    assert!(matches!(res, CommandResult::Continue));
+    assert!(app
+        .ui
+        .status
+        .as_deref()
+        .is_some_and(|status| status.contains("is not connected")));
+}
+
+#[test]
+fn mcp_watch_command_subscribes_with_inject_flag() {
+    let mut app = create_test_app();
+    app.config.mcp_servers.push(logging_test_server());
+    app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
+    app.mcp.server_mut("alpha").expect("server").connected = true;
+
+    let res = process_input(&mut app, "/mcp alpha watch db://orders inject");
+    assert!(matches!(
+        res,
+        CommandResult::WatchMcpResource {
+            ref server_id,
+            ref uri,
+            inject: true,
+        } if server_id == "alpha" && uri == "db://orders"
+    ));
+
+    let res = process_input(&mut app, "/mcp alpha watch db://orders loudly");
+    assert!(matches!(res, CommandResult::Continue));
+    assert!(app
+        .ui
+        .status
+        .as_deref()
+        .is_some_and(|status| status.starts_with("Usage: /mcp")));
+}
+
+#[test]
+fn mcp_unwatch_and_list_use_local_watches() {
+    let mut app = create_test_app();
+    app.config.mcp_servers.push(logging_test_server());
+    app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
+
+    let res = process_input(&mut app, "/mcp alpha unwatch db://orders");
+    assert!(matches!(res, CommandResult::Continue));
+    assert_eq!(app.ui.status.as_deref(), Some("Not watching db://orders"));
+
+    let mut watch =
+        crate::mcp::subscriptions::McpResourceWatch::new("db://orders".to_string(), false);
+    watch.record_read(Ok("3 open orders".to_string()), false);
+    app.mcp
+        .server_mut("alpha")
+        .expect("server")
+        .resource_watches
+        .insert("db://orders".to_string(), watch);
+
+    let res = process_input(&mut app, "/mcp alpha watch");
+    assert!(matches!(res, CommandResult::Continue));
+    let state = app.inspect_state().expect("inspect open");
+    assert_eq!(state.title, "MCP watches: Alpha");
+    assert!(state.content.contains("## db://orders"));
+    assert!(state.content.contains("3 open orders"));
+
+    let res = process_input(&mut app, "/mcp alpha unwatch db://orders");
+    assert!(matches!(
+        res,
+        CommandResult::UnwatchMcpResource { ref server_id, ref uri }
+            if server_id == "alpha" && uri == "db://orders"
+    ));
+}
+
 #[test]
 fn parse_kv_args_supports_quotes() {
     let args =
@@ -1299,3 +1484,88 @@ fn validate_prompt_args_rejects_unknown_keys() {
     assert!(err.contains("Unknown prompt argument"));
     assert!(err.contains("topic"));
 }
+
+#[test]
+fn group_commands_add_members_and_pick_speakers() {
+    let temp_dir = tempdir().unwrap();
+    let card_path = |name: &str| {
+        let card = CharacterCard {
+            spec: "chara_card_v2".to_string(),
+            spec_version: "2.0".to_string(),
+            data: CharacterData {
+                name: name.to_string(),
+                first_mes: "Hi".to_string(),
+                ..Default::default()
+            },
+        };
+        let path = temp_dir.path().join(format!("{name}.json"));
+        fs::write(&path, serde_json::to_string(&card).unwrap()).unwrap();
+        path.display().to_string()
+    };
+    let alice = card_path("Alice");
+    let bob = card_path("Bob");
+
+    let mut app = create_test_app();
+    process_input(&mut app, "/group");
+    assert!(app
+        .ui
+        .status
+        .as_deref()
+        .unwrap()
+        .starts_with("No group chat"));
+
+    process_input(&mut app, &format!("/group add {alice}"));
+    assert_eq!(
+        app.ui.status.as_deref(),
+        Some("Group chat started with Alice (1 member)")
+    );
+    process_input(&mut app, &format!("/group add {bob}"));
+    assert_eq!(
+        app.ui.status.as_deref(),
+        Some("Added Bob to the group (2 members)")
+    );
+    assert_eq!(app.session.get_character().unwrap().data.name, "Alice");
+    assert_eq!(
+        app.session.group.as_ref().unwrap().names(),
+        vec!["Alice", "Bob"]
+    );
+
+    process_input(&mut app, "/group order mention");
+    assert_eq!(app.ui.status.as_deref(), Some("Group turn order: mention"));
+    process_input(&mut app, "/group order sideways");
+    assert!(app
+        .ui
+        .status
+        .as_deref()
+        .unwrap()
+        .starts_with("Turn order must"));
+
+    assert!(matches!(
+        process_input(&mut app, "/speak bob"),
+        CommandResult::GroupReply(1)
+    ));
+    process_input(&mut app, "/speak Carol");
+    assert!(app.ui.status.as_deref().unwrap().contains("Carol"));
+
+    app.ui.is_streaming = true;
+    assert!(matches!(
+        process_input(&mut app, "/speak bob"),
+        CommandResult::Continue
+    ));
+    assert!(app
+        .ui
+        .status
+        .as_deref()
+        .unwrap()
+        .starts_with("Wait for the current reply"));
+    app.ui.is_streaming = false;
+
+    process_input(&mut app, "/group remove Alice");
+    assert_eq!(
+        app.ui.status.as_deref(),
+        Some("Removed Alice from the group (1 member)")
+    );
+    process_input(&mut app, "/group off");
+    assert!(app.session.group.is_none());
+    assert_eq!(app.session.get_character().unwrap().data.name, "Bob");
+}
@@ -1,7 +1,7 @@
 use super::set_status_message;
 use super::{update_scroll_after_command, App, AppActionContext, AppCommand, CommandAction};
 use crate::commands::{process_input, CommandResult};
-use crate::core::app::actions::streaming;
+use crate::core::app::actions::{mcp_attachments, streaming};
 use crate::core::app::StreamingAction;
 
 pub(super) fn handle_command_action(
@@ -45,8 +45,7 @@ fn handle_process_command(
             None
         }
         CommandResult::ProcessAsMessage(message) => {
-            let action = StreamingAction::SubmitMessage { message };
-            streaming::handle_streaming_action(app, action, ctx)
+            mcp_attachments::submit_message_with_mentions(app, message, ctx)
         }
         CommandResult::OpenModelPicker => match app.prepare_model_picker_request() {
             Ok(request) => Some(AppCommand::LoadModelPicker(request)),
@@ -77,16 +76,39 @@ fn handle_process_command(
             app.open_preset_picker();
             None
         }
+        CommandResult::OpenGreetingPicker => {
+            app.open_greeting_picker();
+            None
+        }
         CommandResult::Refine(prompt) => {
             let action = StreamingAction::RefineLastMessage { prompt };
             streaming::handle_streaming_action(app, action, ctx)
         }
+        CommandResult::GroupReply(speaker) => {
+            let action = StreamingAction::GroupReply { speaker };
+            streaming::handle_streaming_action(app, action, ctx)
+        }
         CommandResult::RunMcpPrompt(request) => Some(AppCommand::RunMcpPrompt(request)),
         CommandResult::RefreshMcp { server_id } => {
             app.ui.focus_transcript();
             update_scroll_after_command(app, ctx);
             Some(AppCommand::RefreshMcp { server_id })
         }
+        CommandResult::SetMcpLogLevel { server_id, level } => {
+            Some(AppCommand::SetMcpLogLevel { server_id, level })
+        }
+        CommandResult::WatchMcpResource {
+            server_id,
+            uri,
+            inject,
+        } => Some(AppCommand::WatchMcpResource {
+            server_id,
+            uri,
+            inject,
+        }),
+        CommandResult::UnwatchMcpResource { server_id, uri } => {
+            Some(AppCommand::UnwatchMcpResource { server_id, uri })
+        }
     }
 }
 
@@ -161,18 +183,9 @@ mod tests {
             .push(crate::core::config::data::McpServerConfig {
                 id: "alpha".to_string(),
                 display_name: "Alpha MCP".to_string(),
-                transport: None,
                 base_url: Some("https://mcp.example.com".to_string()),
-                command: None,
-                args: None,
-                env: None,
-                headers: None,
                 enabled: Some(true),
-                allowed_tools: None,
-                protocol_version: None,
-                tool_payloads: None,
-                tool_payload_window: None,
-                yolo: None,
+                ..Default::default()
             });
         app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
 
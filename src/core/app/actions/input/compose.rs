@@ -1,4 +1,5 @@
 use super::{App, AppActionContext, AppCommand, ComposeAction};
+use crate::core::app::ui_state::McpPromptInputTarget;
 
 pub(super) fn handle_compose_action(
     app: &mut App,
@@ -15,7 +16,23 @@ pub(super) fn handle_compose_action(
             None
         }
         ComposeAction::CancelMcpPromptInput => {
+            let pending_message =
+                app.ui
+                    .mcp_prompt_input()
+                    .and_then(|prompt| match &prompt.target {
+                        McpPromptInputTarget::ResourceTemplate(draft) => {
+                            Some(draft.message.clone())
+                        }
+                        McpPromptInputTarget::Prompt => None,
+                    });
             app.ui.cancel_mcp_prompt_input();
+            // Cancelling template variable entry returns the unsent message for editing.
+            if let Some(message) = pending_message {
+                app.ui.set_input_text(message);
+                if ctx.term_width > 0 {
+                    app.recompute_input_layout_after_edit(ctx.term_width);
+                }
+            }
             None
         }
         ComposeAction::CancelInPlaceEdit => {
@@ -63,7 +63,13 @@ impl ToolInspectSnapshot {
 }
 
 fn tool_inspect_snapshot(app: &App) -> ToolInspectSnapshot {
-    let mut pending = Vec::new();
+    let mut pending: Vec<ToolCallRequest> = app
+        .session
+        .tool_pipeline
+        .running_tool_calls
+        .iter()
+        .map(|running| running.request.clone())
+        .collect();
     if let Some(request) = app.session.tool_pipeline.active_tool_request.clone() {
         pending.push(request);
     }
@@ -716,6 +722,7 @@ mod tests {
             tool_call_id: Some("tool-call-1".to_string()),
             raw_arguments: Some("{\"uri\":\"mcp://example/resource\"}".to_string()),
             assistant_message_index: None,
+            output: None,
         };
 
         let content = build_tool_request_content(&record, false);
@@ -736,6 +743,7 @@ mod tests {
             tool_call_id: None,
             raw_arguments: None,
             assistant_message_index: None,
+            output: None,
         };
 
         let content = build_tool_request_content(&record, false);
@@ -760,6 +768,7 @@ mod tests {
                 tool_call_id: Some("call-1".to_string()),
                 raw_arguments: Some("{\"ok\":true}".to_string()),
                 assistant_message_index: None,
+                output: None,
             });
         app.session
             .tool_pipeline
@@ -814,6 +823,7 @@ mod tests {
                 tool_call_id: Some("call-1".to_string()),
                 raw_arguments: Some("{\"ok\":true}".to_string()),
                 assistant_message_index: None,
+                output: None,
             });
 
         let cmd = handle_inspect_action(&mut app, InspectAction::Open, ctx);
@@ -847,6 +857,7 @@ mod tests {
                 tool_call_id: Some("call-1".to_string()),
                 raw_arguments: Some("{\"uri\":\"mcp://alpha/doc\"}".to_string()),
                 assistant_message_index: None,
+                output: None,
             });
         app.open_tool_call_inspect(
             "Inspect".to_string(),
@@ -879,6 +890,7 @@ mod tests {
                 tool_call_id: Some("call-1".to_string()),
                 raw_arguments: Some("{\"uri\":\"mcp://alpha/doc\"}".to_string()),
                 assistant_message_index: None,
+                output: None,
             });
         app.open_tool_call_inspect(
             "Inspect".to_string(),
@@ -912,6 +924,7 @@ mod tests {
                 tool_call_id: Some("call-1".to_string()),
                 raw_arguments: Some("{\"q\":\"nested\"}".to_string()),
                 assistant_message_index: None,
+                output: None,
             });
         app.open_tool_call_inspect(
             "Inspect".to_string(),
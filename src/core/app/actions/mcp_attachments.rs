@@ -0,0 +1,256 @@
+use super::{input, streaming, App, AppActionContext, AppCommand, StreamingAction};
+use crate::core::app::session::McpResourceAttachmentRequest;
+use crate::core::app::ui_state::{
+    McpPromptArgument, McpPromptInput, McpPromptInputTarget, McpResourceAttachmentDraft,
+};
+use crate::core::mcp_resource_mentions::{
+    embed_attachments, expand_template, parse_resource_mentions, template_variables,
+    McpResourceAttachment, McpResourceMention,
+};
+use std::collections::HashMap;
+
+/// Sends a user message, first resolving any `@server:uri` resource mentions.
+pub(super) fn submit_message_with_mentions(
+    app: &mut App,
+    message: String,
+    ctx: AppActionContext,
+) -> Option<AppCommand> {
+    let mentions = if app.session.mcp_disabled {
+        Vec::new()
+    } else {
+        parse_resource_mentions(&message, |id| {
+            app.mcp
+                .server(id)
+                .filter(|server| server.config.is_enabled())
+                .map(|server| server.config.id.clone())
+        })
+    };
+
+    if mentions.is_empty() {
+        return streaming::handle_streaming_action(
+            app,
+            StreamingAction::SubmitMessage { message },
+            ctx,
+        );
+    }
+
+    continue_resource_attachments(
+        app,
+        McpResourceAttachmentDraft {
+            message,
+            mentions,
+            mention_index: 0,
+        },
+        ctx,
+    )
+}
+
+/// Applies collected template variables to the current mention and resumes.
+pub(super) fn complete_resource_template(
+    app: &mut App,
+    mut draft: McpResourceAttachmentDraft,
+    values: &HashMap<String, String>,
+    ctx: AppActionContext,
+) -> Option<AppCommand> {
+    let mention = draft.mentions.get_mut(draft.mention_index)?;
+    let expanded = expand_template(&mention.uri, values);
+    let raw = mention.raw.replacen(&mention.uri, &expanded, 1);
+    draft.message = draft.message.replacen(&mention.raw, &raw, 1);
+    mention.uri = expanded;
+    mention.raw = raw;
+    draft.mention_index += 1;
+    continue_resource_attachments(app, draft, ctx)
+}
+
+fn continue_resource_attachments(
+    app: &mut App,
+    draft: McpResourceAttachmentDraft,
+    ctx: AppActionContext,
+) -> Option<AppCommand> {
+    let next_template = draft
+        .mentions
+        .iter()
+        .enumerate()
+        .skip(draft.mention_index)
+        .find(|(_, mention)| mention.is_template())
+        .map(|(index, mention)| (index, mention.clone()));
+
+    if let Some((index, mention)) = next_template {
+        start_template_input(app, draft, index, mention);
+        return None;
+    }
+
+    let count = draft.mentions.len();
+    input::set_status_message(
+        app,
+        format!(
+            "Reading {} MCP resource{}...",
+            count,
+            if count == 1 { "" } else { "s" }
+        ),
+        ctx,
+    );
+    Some(AppCommand::AttachMcpResources(
+        McpResourceAttachmentRequest {
+            message: draft.message,
+            mentions: draft.mentions,
+        },
+    ))
+}
+
+fn start_template_input(
+    app: &mut App,
+    mut draft: McpResourceAttachmentDraft,
+    index: usize,
+    mention: McpResourceMention,
+) {
+    let server_name = app
+        .mcp
+        .server(&mention.server_id)
+        .map(|server| server.config.display_name.clone())
+        .unwrap_or_else(|| mention.server_id.clone());
+    let pending_args = template_variables(&mention.uri)
+        .into_iter()
+        .map(|name| McpPromptArgument {
+            name,
+            title: None,
+            description: None,
+            required: true,
+        })
+        .collect();
+    draft.mention_index = index;
+
+    app.ui.start_mcp_prompt_input(McpPromptInput {
+        server_id: mention.server_id,
+        server_name,
+        prompt_name: mention.uri,
+        prompt_title: None,
+        pending_args,
+        collected: HashMap::new(),
+        next_index: 0,
+        target: McpPromptInputTarget::ResourceTemplate(draft),
+    });
+}
+
+pub(super) fn handle_resource_attachments_read(
+    app: &mut App,
+    message: String,
+    results: Vec<(McpResourceMention, Result<String, String>)>,
+    ctx: AppActionContext,
+) -> Option<AppCommand> {
+    let mut attachments = Vec::new();
+    for (mention, result) in results {
+        match result {
+            Ok(text) => attachments.push(McpResourceAttachment {
+                server_id: mention.server_id,
+                uri: mention.uri,
+                text,
+            }),
+            Err(err) => {
+                // Hand the draft back so the user can fix the mention and resend.
+                app.ui.set_input_text(message);
+                app.ui.focus_input();
+                app.recompute_input_layout_after_edit(ctx.term_width);
+                input::set_status_message(
+                    app,
+                    format!("Failed to read {}: {}", mention.raw, err),
+                    ctx,
+                );
+                return None;
+            }
+        }
+    }
+
+    app.clear_status();
+    let message = embed_attachments(&message, &attachments);
+    streaming::handle_streaming_action(app, StreamingAction::SubmitMessage { message }, ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::data::McpServerConfig;
+    use crate::utils::test_utils::create_test_app;
+
+    fn default_ctx() -> AppActionContext {
+        AppActionContext {
+            term_width: 80,
+            term_height: 24,
+        }
+    }
+
+    fn app_with_server() -> App {
+        let mut app = create_test_app();
+        app.config.mcp_servers.push(McpServerConfig {
+            id: "alpha".to_string(),
+            display_name: "Alpha".to_string(),
+            base_url: Some("https://mcp.example.com".to_string()),
+            transport: Some("streamable-http".to_string()),
+            enabled: Some(true),
+            ..Default::default()
+        });
+        app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
+        app
+    }
+
+    #[test]
+    fn mentions_are_read_before_sending() {
+        let mut app = app_with_server();
+        let command = submit_message_with_mentions(
+            &mut app,
+            "Summarize @alpha:mem://notes".to_string(),
+            default_ctx(),
+        );
+        let Some(AppCommand::AttachMcpResources(request)) = command else {
+            panic!("expected attachment request");
+        };
+        assert_eq!(request.mentions.len(), 1);
+        assert_eq!(request.mentions[0].uri, "mem://notes");
+    }
+
+    #[test]
+    fn template_mentions_collect_variables_then_rewrite_message() {
+        let mut app = app_with_server();
+        let command = submit_message_with_mentions(
+            &mut app,
+            "Check @alpha:weather://{city}/today".to_string(),
+            default_ctx(),
+        );
+        assert!(command.is_none());
+        let prompt = app.ui.mcp_prompt_input().cloned().expect("template input");
+        assert_eq!(prompt.pending_args[0].name, "city");
+        let McpPromptInputTarget::ResourceTemplate(draft) = prompt.target else {
+            panic!("expected resource template target");
+        };
+
+        let values = HashMap::from([("city".to_string(), "Lyon".to_string())]);
+        let command = complete_resource_template(&mut app, draft, &values, default_ctx());
+        let Some(AppCommand::AttachMcpResources(request)) = command else {
+            panic!("expected attachment request");
+        };
+        assert_eq!(request.message, "Check @alpha:weather://Lyon/today");
+        assert_eq!(request.mentions[0].uri, "weather://Lyon/today");
+    }
+
+    #[test]
+    fn failed_reads_restore_the_draft() {
+        let mut app = app_with_server();
+        let mention = McpResourceMention {
+            server_id: "alpha".to_string(),
+            uri: "mem://gone".to_string(),
+            raw: "@alpha:mem://gone".to_string(),
+        };
+        let command = handle_resource_attachments_read(
+            &mut app,
+            "Read @alpha:mem://gone".to_string(),
+            vec![(mention, Err("not found".to_string()))],
+            default_ctx(),
+        );
+        assert!(command.is_none());
+        assert_eq!(app.ui.get_input_text(), "Read @alpha:mem://gone");
+        assert_eq!(
+            app.ui.status.as_deref(),
+            Some("Failed to read @alpha:mem://gone: not found")
+        );
+    }
+}
@@ -0,0 +1,295 @@
+use super::{App, AppCommand};
+use crate::mcp::client::{McpServerState, McpTransportKind};
+use crate::mcp::events::McpServerExit;
+use crate::mcp::health::{McpServerHealth, MAX_RESTART_ATTEMPTS};
+use tracing::{debug, warn};
+
+/// Reports whether an event about `client_instance` still describes the live
+/// connection; events from replaced or already-restarting clients are stale.
+fn is_current_client(server: &McpServerState, client_instance: Option<u64>) -> bool {
+    server.config.is_enabled()
+        && server.connected
+        && match (server.client_instance(), client_instance) {
+            (Some(live), Some(reported)) => live == reported,
+            (Some(_), None) => false,
+            (None, _) => true,
+        }
+}
+
+pub(super) fn handle_mcp_server_exited(app: &mut App, exit: McpServerExit) -> Option<AppCommand> {
+    if app.session.mcp_disabled {
+        return None;
+    }
+    let McpServerExit {
+        server_id,
+        client_instance,
+        status,
+        stderr_tail,
+    } = exit;
+    let server = app.mcp.server_mut(&server_id)?;
+    if !is_current_client(server, Some(client_instance)) {
+        debug!(server_id = %server_id, "Ignoring exit of stale MCP stdio client");
+        return None;
+    }
+
+    warn!(
+        server_id = %server_id,
+        status = %status,
+        stderr = %stderr_tail.join("\n"),
+        "MCP stdio server exited unexpectedly"
+    );
+    let display_name = server.config.display_name.clone();
+    server.clear_runtime_state();
+    server.stderr_tail = stderr_tail;
+    server.last_error = Some(format!("Server process exited ({status})."));
+    app.conversation()
+        .set_status(format!("MCP server {display_name} exited; restarting…"));
+    schedule_restart(app, server_id, 1)
+}
+
+pub(super) fn handle_mcp_server_restarted(
+    app: &mut App,
+    server_id: String,
+    attempt: u32,
+    state: McpServerState,
+) -> Option<AppCommand> {
+    let current = app.mcp.server_mut(&server_id)?;
+    if current.health != (McpServerHealth::Restarting { attempt }) || !current.config.is_enabled() {
+        // The server was refreshed or forgotten while the restart was running.
+        return None;
+    }
+    let display_name = current.config.display_name.clone();
+
+    if state.connected {
+        let log_buffer = std::mem::take(&mut current.log_buffer);
+        let stderr_tail = std::mem::take(&mut current.stderr_tail);
+        *current = state;
+        current.log_buffer = log_buffer;
+        current.stderr_tail = stderr_tail;
+        current.health = McpServerHealth::Up;
+        app.conversation()
+            .set_status(format!("MCP server {display_name} restarted"));
+        return None;
+    }
+
+    current.last_error = state.last_error;
+    if !state.stderr_tail.is_empty() {
+        current.stderr_tail = state.stderr_tail;
+    }
+    if attempt >= MAX_RESTART_ATTEMPTS {
+        let reason = current
+            .last_error
+            .clone()
+            .unwrap_or_else(|| "restart failed".to_string());
+        current.health = McpServerHealth::Failed { reason };
+        app.conversation().set_status(format!(
+            "MCP server {display_name} failed to restart after {attempt} attempts"
+        ));
+        return None;
+    }
+    schedule_restart(app, server_id, attempt + 1)
+}
+
+pub(super) fn handle_mcp_health_checked(
+    app: &mut App,
+    server_id: String,
+    client_instance: Option<u64>,
+    result: Result<(), String>,
+) -> Option<AppCommand> {
+    if app.session.mcp_disabled {
+        return None;
+    }
+    let server = app.mcp.server_mut(&server_id)?;
+    if !is_current_client(server, client_instance) {
+        return None;
+    }
+
+    let err = match result {
+        Ok(()) => {
+            if matches!(server.health, McpServerHealth::Failed { .. }) {
+                server.health = McpServerHealth::Up;
+                server.last_error = None;
+            }
+            return None;
+        }
+        Err(err) => err,
+    };
+
+    warn!(server_id = %server_id, error = %err, "MCP health check failed");
+    let is_stdio = matches!(
+        McpTransportKind::from_config(&server.config),
+        Ok(McpTransportKind::Stdio)
+    );
+    if !is_stdio {
+        // Remote servers cannot be restarted; the next successful ping marks
+        // them up again.
+        server.health = McpServerHealth::Failed {
+            reason: format!("health check failed: {err}"),
+        };
+        return None;
+    }
+
+    // Dropping the client kills the unresponsive process.
+    let display_name = server.config.display_name.clone();
+    server.clear_runtime_state();
+    server.last_error = Some(format!("Health check failed: {err}"));
+    app.conversation().set_status(format!(
+        "MCP server {display_name} is not responding; restarting…"
+    ));
+    schedule_restart(app, server_id, 1)
+}
+
+fn schedule_restart(app: &mut App, server_id: String, attempt: u32) -> Option<AppCommand> {
+    let server = app.mcp.server_mut(&server_id)?;
+    server.health = McpServerHealth::Restarting { attempt };
+    Some(AppCommand::RestartMcpServer { server_id, attempt })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::data::McpServerConfig;
+    use crate::utils::test_utils::create_test_app;
+
+    fn app_with_server(transport: &str) -> App {
+        let mut app = create_test_app();
+        app.config.mcp_servers.push(McpServerConfig {
+            id: "alpha".to_string(),
+            display_name: "Alpha".to_string(),
+            base_url: Some("https://mcp.example.com".to_string()),
+            command: Some("alpha-mcp".to_string()),
+            transport: Some(transport.to_string()),
+            enabled: Some(true),
+            ..Default::default()
+        });
+        app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
+        let server = app.mcp.server_mut("alpha").expect("server");
+        server.connected = true;
+        server.health = McpServerHealth::Up;
+        app
+    }
+
+    fn exit() -> McpServerExit {
+        McpServerExit {
+            server_id: "alpha".to_string(),
+            client_instance: 1,
+            status: "exit status: 3".to_string(),
+            stderr_tail: vec!["panic: index out of range".to_string()],
+        }
+    }
+
+    #[test]
+    fn exit_schedules_restart_and_keeps_stderr() {
+        let mut app = app_with_server("stdio");
+        let command = handle_mcp_server_exited(&mut app, exit());
+
+        assert!(matches!(
+            command,
+            Some(AppCommand::RestartMcpServer { ref server_id, attempt: 1 }) if server_id == "alpha"
+        ));
+        let server = app.mcp.server("alpha").expect("server");
+        assert!(!server.connected);
+        assert_eq!(server.health, McpServerHealth::Restarting { attempt: 1 });
+        assert_eq!(server.stderr_tail, vec!["panic: index out of range"]);
+        assert_eq!(
+            server.last_error.as_deref(),
+            Some("Server process exited (exit status: 3).")
+        );
+
+        // A second report for the same crash is ignored while restarting.
+        assert!(handle_mcp_server_exited(&mut app, exit()).is_none());
+    }
+
+    #[test]
+    fn failed_restarts_back_off_until_marked_failed() {
+        let mut app = app_with_server("stdio");
+        handle_mcp_server_exited(&mut app, exit());
+
+        for attempt in 1..MAX_RESTART_ATTEMPTS {
+            let mut failed = app.mcp.server("alpha").expect("server").clone();
+            failed.last_error = Some("spawn failed".to_string());
+            let command =
+                handle_mcp_server_restarted(&mut app, "alpha".to_string(), attempt, failed);
+            assert!(matches!(
+                command,
+                Some(AppCommand::RestartMcpServer { attempt: next, .. }) if next == attempt + 1
+            ));
+        }
+
+        let mut failed = app.mcp.server("alpha").expect("server").clone();
+        failed.last_error = Some("spawn failed".to_string());
+        let command = handle_mcp_server_restarted(
+            &mut app,
+            "alpha".to_string(),
+            MAX_RESTART_ATTEMPTS,
+            failed,
+        );
+        assert!(command.is_none());
+        assert_eq!(
+            app.mcp.server("alpha").expect("server").health,
+            McpServerHealth::Failed {
+                reason: "spawn failed".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn successful_restart_replaces_state() {
+        let mut app = app_with_server("stdio");
+        handle_mcp_server_exited(&mut app, exit());
+
+        let mut restarted = app.mcp.server("alpha").expect("server").clone();
+        restarted.connected = true;
+        restarted.last_error = None;
+        restarted.stderr_tail.clear();
+        handle_mcp_server_restarted(&mut app, "alpha".to_string(), 1, restarted.clone());
+
+        let server = app.mcp.server("alpha").expect("server");
+        assert!(server.connected);
+        assert_eq!(server.health, McpServerHealth::Up);
+        assert_eq!(server.stderr_tail, vec!["panic: index out of range"]);
+
+        // Results for a restart that is no longer pending are dropped.
+        restarted.connected = false;
+        handle_mcp_server_restarted(&mut app, "alpha".to_string(), 1, restarted);
+        assert!(app.mcp.server("alpha").expect("server").connected);
+    }
+
+    #[test]
+    fn remote_health_failures_recover_on_next_ping() {
+        let mut app = app_with_server("streamable-http");
+        let command = handle_mcp_health_checked(
+            &mut app,
+            "alpha".to_string(),
+            None,
+            Err("connection refused".to_string()),
+        );
+        assert!(command.is_none());
+        assert_eq!(
+            app.mcp.server("alpha").expect("server").health.label(),
+            "failed"
+        );
+
+        handle_mcp_health_checked(&mut app, "alpha".to_string(), None, Ok(()));
+        assert_eq!(
+            app.mcp.server("alpha").expect("server").health,
+            McpServerHealth::Up
+        );
+    }
+
+    #[test]
+    fn stdio_health_failure_restarts_server() {
+        let mut app = app_with_server("stdio");
+        let command = handle_mcp_health_checked(
+            &mut app,
+            "alpha".to_string(),
+            None,
+            Err("Timed out".to_string()),
+        );
+        assert!(matches!(
+            command,
+            Some(AppCommand::RestartMcpServer { attempt: 1, .. })
+        ));
+        assert!(!app.mcp.server("alpha").expect("server").connected);
+    }
+}
@@ -0,0 +1,186 @@
+use super::{App, AppActionContext, AppCommand};
+use crate::mcp::events::McpServerNotification;
+use crate::mcp::logging::{trace_log_entry, McpLogEntry};
+use crate::mcp::subscriptions::matching_watch_uri;
+use rust_mcp_schema::schema_utils::ServerJsonrpcNotification;
+use tracing::debug;
+
+pub(super) fn handle_mcp_server_notification(
+    app: &mut App,
+    notification: McpServerNotification,
+    _ctx: AppActionContext,
+) -> Option<AppCommand> {
+    let McpServerNotification {
+        server_id,
+        notification,
+    } = notification;
+
+    match notification {
+        ServerJsonrpcNotification::LoggingMessageNotification(message) => {
+            let entry = McpLogEntry::from_params(message.params);
+            trace_log_entry(&server_id, &entry);
+            if let Some(server) = app.mcp.server_mut(&server_id) {
+                server.log_buffer.push(entry);
+            }
+            None
+        }
+        ServerJsonrpcNotification::ResourceUpdatedNotification(updated) => {
+            let uri = app.mcp.server(&server_id).and_then(|server| {
+                matching_watch_uri(&server.resource_watches, &updated.params.uri)
+            });
+            match uri {
+                Some(uri) => Some(AppCommand::RefreshMcpResourceWatch { server_id, uri }),
+                None => {
+                    debug!(
+                        server_id = %server_id,
+                        uri = %updated.params.uri,
+                        "Ignoring update for unwatched MCP resource"
+                    );
+                    None
+                }
+            }
+        }
+        other => {
+            debug!(
+                server_id = %server_id,
+                notification = %other,
+                "Ignoring MCP server notification"
+            );
+            None
+        }
+    }
+}
+
+pub(super) fn handle_mcp_resource_watch_read(
+    app: &mut App,
+    server_id: String,
+    uri: String,
+    result: Result<String, String>,
+    is_update: bool,
+) -> Option<AppCommand> {
+    let status = match &result {
+        Ok(_) if is_update => Some(format!("Resource updated: {}", uri)),
+        Ok(_) => None,
+        Err(err) => Some(format!("Failed to read watched resource {}: {}", uri, err)),
+    };
+    let Some(watch) = app
+        .mcp
+        .server_mut(&server_id)
+        .and_then(|server| server.resource_watches.get_mut(&uri))
+    else {
+        // The watch was removed while the read was in flight.
+        return None;
+    };
+    watch.record_read(result, is_update);
+    if let Some(status) = status {
+        app.conversation().set_status(status);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::data::McpServerConfig;
+    use crate::mcp::subscriptions::McpResourceWatch;
+    use crate::utils::test_utils::create_test_app;
+    use rust_mcp_schema::{
+        LoggingLevel, LoggingMessageNotification, LoggingMessageNotificationParams,
+        ResourceUpdatedNotification, ResourceUpdatedNotificationParams,
+    };
+
+    fn default_ctx() -> AppActionContext {
+        AppActionContext {
+            term_width: 80,
+            term_height: 24,
+        }
+    }
+
+    fn app_with_server() -> App {
+        let mut app = create_test_app();
+        app.config.mcp_servers.push(McpServerConfig {
+            id: "alpha".to_string(),
+            display_name: "Alpha".to_string(),
+            command: Some("alpha-mcp".to_string()),
+            transport: Some("stdio".to_string()),
+            enabled: Some(true),
+            ..Default::default()
+        });
+        app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
+        app
+    }
+
+    #[test]
+    fn logging_notification_is_buffered_for_server() {
+        let mut app = app_with_server();
+        let notification = McpServerNotification {
+            server_id: "alpha".to_string(),
+            notification: ServerJsonrpcNotification::LoggingMessageNotification(
+                LoggingMessageNotification::new(LoggingMessageNotificationParams {
+                    data: serde_json::json!("index rebuilt"),
+                    level: LoggingLevel::Notice,
+                    logger: Some("indexer".to_string()),
+                    meta: None,
+                }),
+            ),
+        };
+        let command = handle_mcp_server_notification(&mut app, notification, default_ctx());
+
+        assert!(command.is_none());
+        let server = app.mcp.server("alpha").expect("server");
+        let entry = server.log_buffer.filtered(None).next().expect("log entry");
+        assert_eq!(entry.level, LoggingLevel::Notice);
+        assert_eq!(entry.logger.as_deref(), Some("indexer"));
+        assert_eq!(entry.message(), "index rebuilt");
+    }
+
+    #[test]
+    fn resource_update_for_watched_uri_refreshes_watch() {
+        let mut app = app_with_server();
+        app.mcp
+            .server_mut("alpha")
+            .expect("server")
+            .resource_watches
+            .insert(
+                "file:///notes".to_string(),
+                McpResourceWatch::new("file:///notes".to_string(), true),
+            );
+
+        let updated = |uri: &str| McpServerNotification {
+            server_id: "alpha".to_string(),
+            notification: ServerJsonrpcNotification::ResourceUpdatedNotification(
+                ResourceUpdatedNotification::new(ResourceUpdatedNotificationParams {
+                    meta: None,
+                    uri: uri.to_string(),
+                }),
+            ),
+        };
+
+        let command =
+            handle_mcp_server_notification(&mut app, updated("file:///notes/today"), default_ctx());
+        assert!(matches!(
+            command,
+            Some(AppCommand::RefreshMcpResourceWatch { ref server_id, ref uri })
+                if server_id == "alpha" && uri == "file:///notes"
+        ));
+        assert!(
+            handle_mcp_server_notification(&mut app, updated("file:///other"), default_ctx())
+                .is_none()
+        );
+
+        handle_mcp_resource_watch_read(
+            &mut app,
+            "alpha".to_string(),
+            "file:///notes".to_string(),
+            Ok("fresh".to_string()),
+            true,
+        );
+        let watch = &app.mcp.server("alpha").expect("server").resource_watches["file:///notes"];
+        assert_eq!(watch.content.as_deref(), Some("fresh"));
+        assert!(watch.pending_injection);
+        assert_eq!(
+            app.ui.status.as_deref(),
+            Some("Resource updated: file:///notes")
+        );
+    }
+}
@@ -1,5 +1,9 @@
-use super::{input, App, AppActionContext, AppCommand, McpPromptAction};
-use crate::core::app::session::McpPromptRequest;
+use super::{input, mcp_attachments, App, AppActionContext, AppCommand, McpPromptAction};
+use crate::core::app::session::{McpCompletionRequest, McpPromptRequest};
+use crate::core::app::ui_helpers::same_argument;
+use crate::core::app::ui_state::{McpArgCompletion, McpPromptInputTarget};
+use rust_mcp_schema::CompleteResultCompletion;
+use tracing::debug;
 
 pub(super) fn handle_mcp_prompt_action(
     app: &mut App,
@@ -8,6 +12,74 @@ pub(super) fn handle_mcp_prompt_action(
 ) -> Option<AppCommand> {
     match action {
         McpPromptAction::CompleteArg { value } => handle_complete_mcp_prompt_arg(app, value, ctx),
+        McpPromptAction::RefreshCompletions => handle_refresh_completions(app),
+        McpPromptAction::CompletionsLoaded { request, result } => {
+            handle_completions_loaded(app, request, result);
+            None
+        }
+    }
+}
+
+fn handle_refresh_completions(app: &mut App) -> Option<AppCommand> {
+    if !app.ui.note_completion_input() {
+        return None;
+    }
+    let target = if app.session.mcp_disabled {
+        None
+    } else {
+        app.mcp_completion_request()
+    };
+    let Some(request) = target else {
+        app.ui.mcp_completion = None;
+        return None;
+    };
+
+    let values = match app.ui.mcp_completion.take() {
+        Some(previous) if previous.request == request => {
+            app.ui.mcp_completion = Some(previous);
+            return None;
+        }
+        Some(previous) if same_argument(&previous.request, &request) => previous
+            .values
+            .into_iter()
+            .filter(|value| value.starts_with(&request.value))
+            .collect(),
+        _ => Vec::new(),
+    };
+    app.ui.mcp_completion = Some(McpArgCompletion {
+        request: request.clone(),
+        values,
+        has_more: false,
+    });
+    Some(AppCommand::RequestMcpCompletion(request))
+}
+
+fn handle_completions_loaded(
+    app: &mut App,
+    request: McpCompletionRequest,
+    result: Result<CompleteResultCompletion, String>,
+) {
+    let Some(current) = app
+        .ui
+        .mcp_completion
+        .as_mut()
+        .filter(|current| current.request == request)
+    else {
+        return;
+    };
+    match result {
+        Ok(completion) => {
+            current.has_more = completion.has_more.unwrap_or(false)
+                || completion
+                    .total
+                    .is_some_and(|total| total > completion.values.len() as i64);
+            current.values = completion.values;
+        }
+        Err(err) => {
+            debug!(server_id = %request.server_id, error = %err, "MCP completion failed");
+            current.values.clear();
+            current.has_more = false;
+        }
     }
 }
 
@@ -45,9 +117,225 @@ fn handle_complete_mcp_prompt_arg(
     app.ui.cancel_mcp_prompt_input();
     app.clear_status();
 
-    Some(AppCommand::RunMcpPrompt(McpPromptRequest {
-        server_id: updated.server_id,
-        prompt_name: updated.prompt_name,
-        arguments: updated.collected,
-    }))
+    match updated.target {
+        McpPromptInputTarget::Prompt => Some(AppCommand::RunMcpPrompt(McpPromptRequest {
+            server_id: updated.server_id,
+            prompt_name: updated.prompt_name,
+            arguments: updated.collected,
+        })),
+        McpPromptInputTarget::ResourceTemplate(draft) => {
+            mcp_attachments::complete_resource_template(app, draft, &updated.collected, ctx)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::app::ui_state::McpPromptInput;
+    use crate::core::config::data::McpServerConfig;
+    use crate::utils::test_utils::create_test_app;
+    use rust_mcp_schema::{InitializeResult, ListPromptsResult};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn app_with_prompt() -> App {
+        let mut app = create_test_app();
+        app.config.mcp_servers.push(McpServerConfig {
+            id: "alpha".to_string(),
+            display_name: "Alpha".to_string(),
+            base_url: Some("https://mcp.example.com".to_string()),
+            transport: Some("streamable-http".to_string()),
+            enabled: Some(true),
+            ..Default::default()
+        });
+        app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
+        let server = app.mcp.server_mut("alpha").expect("server");
+        server.server_details = Some(
+            serde_json::from_value::<InitializeResult>(json!({
+                "protocolVersion": "2025-11-25",
+                "capabilities": { "completions": {}, "prompts": {} },
+                "serverInfo": { "name": "alpha", "version": "1.0.0" }
+            }))
+            .expect("initialize result"),
+        );
+        server.cached_prompts = Some(
+            serde_json::from_value::<ListPromptsResult>(json!({
+                "prompts": [{
+                    "name": "review",
+                    "arguments": [
+                        { "name": "language", "required": true },
+                        { "name": "style" }
+                    ]
+                }]
+            }))
+            .expect("prompts"),
+        );
+        app
+    }
+
+    fn type_input(app: &mut App, text: &str) {
+        app.ui.set_input_text(text.to_string());
+        app.ui.set_cursor_position(text.chars().count());
+    }
+
+    fn loaded(values: &[&str]) -> Result<CompleteResultCompletion, String> {
+        Ok(CompleteResultCompletion {
+            has_more: None,
+            total: None,
+            values: values.iter().map(|value| value.to_string()).collect(),
+        })
+    }
+
+    #[test]
+    fn inline_arguments_request_completions_with_context() {
+        let mut app = app_with_prompt();
+        type_input(&mut app, "/alpha:review style=terse language=ru");
+
+        let Some(AppCommand::RequestMcpCompletion(request)) = handle_refresh_completions(&mut app)
+        else {
+            panic!("expected completion request");
+        };
+        assert_eq!(request.server_id, "alpha");
+        assert_eq!(
+            request.reference,
+            crate::core::app::session::McpCompletionReference::Prompt("review".to_string())
+        );
+        assert_eq!(request.argument, "language");
+        assert_eq!(request.value, "ru");
+        assert_eq!(
+            request.context.get("style").map(String::as_str),
+            Some("terse")
+        );
+
+        // The same argument and value does not trigger another round trip.
+        assert!(handle_refresh_completions(&mut app).is_none());
+    }
+
+    #[test]
+    fn completions_are_skipped_without_server_support_or_argument() {
+        let mut app = app_with_prompt();
+        type_input(&mut app, "/alpha:review");
+        assert!(handle_refresh_completions(&mut app).is_none());
+        type_input(&mut app, "/alpha:review rust");
+        assert!(handle_refresh_completions(&mut app).is_none());
+
+        app.mcp.server_mut("alpha").expect("server").server_details = None;
+        type_input(&mut app, "/alpha:review language=r");
+        assert!(handle_refresh_completions(&mut app).is_none());
+        assert!(app.ui.mcp_completion.is_none());
+    }
+
+    #[test]
+    fn tab_applies_loaded_suggestions() {
+        let mut app = app_with_prompt();
+        type_input(&mut app, "/alpha:review language=r");
+        let Some(AppCommand::RequestMcpCompletion(request)) = handle_refresh_completions(&mut app)
+        else {
+            panic!("expected completion request");
+        };
+        handle_completions_loaded(&mut app, request, loaded(&["rust", "ruby", "rust", "r"]));
+        assert_eq!(app.ui.mcp_completion.as_ref().unwrap().values.len(), 4);
+
+        assert!(app.complete_mcp_argument(80));
+        assert_eq!(app.ui.status.as_deref(), Some("Suggestions: r, ruby, rust"));
+
+        type_input(&mut app, "/alpha:review language=rus");
+        assert!(app.complete_mcp_argument(80));
+        assert_eq!(app.ui.get_input_text(), "/alpha:review language=rust ");
+    }
+
+    #[test]
+    fn quoted_inline_values_complete_inside_their_quotes() {
+        let mut app = app_with_prompt();
+        type_input(&mut app, "/alpha:review language=rust style='very te");
+        let Some(AppCommand::RequestMcpCompletion(request)) = handle_refresh_completions(&mut app)
+        else {
+            panic!("expected completion request");
+        };
+        assert_eq!(request.argument, "style");
+        assert_eq!(request.value, "very te");
+        handle_completions_loaded(&mut app, request, loaded(&["very terse"]));
+        assert!(app.complete_mcp_argument(80));
+        assert_eq!(
+            app.ui.get_input_text(),
+            "/alpha:review language=rust style='very terse' "
+        );
+
+        type_input(&mut app, "/alpha:review style=\"very terse\" language=r");
+        let Some(AppCommand::RequestMcpCompletion(request)) = handle_refresh_completions(&mut app)
+        else {
+            panic!("expected completion request");
+        };
+        assert_eq!(request.argument, "language");
+        assert_eq!(
+            request.context.get("style").map(String::as_str),
+            Some("very terse")
+        );
+    }
+
+    #[test]
+    fn refresh_waits_for_the_input_to_change() {
+        let mut app = app_with_prompt();
+        type_input(&mut app, "/alpha:review language=r");
+        assert!(handle_refresh_completions(&mut app).is_some());
+        app.ui.mcp_completion = None;
+        assert!(handle_refresh_completions(&mut app).is_none());
+        assert!(app.ui.mcp_completion.is_none());
+
+        app.ui
+            .set_cursor_position("/alpha:review language=".chars().count());
+        assert!(handle_refresh_completions(&mut app).is_some());
+    }
+
+    #[test]
+    fn stale_completion_results_are_ignored() {
+        let mut app = app_with_prompt();
+        type_input(&mut app, "/alpha:review language=r");
+        let Some(AppCommand::RequestMcpCompletion(stale)) = handle_refresh_completions(&mut app)
+        else {
+            panic!("expected completion request");
+        };
+        handle_completions_loaded(&mut app, stale.clone(), loaded(&["rust", "ruby"]));
+
+        type_input(&mut app, "/alpha:review language=ru");
+        assert!(handle_refresh_completions(&mut app).is_some());
+        // Earlier suggestions that still match remain visible while loading.
+        assert_eq!(app.ui.mcp_completion.as_ref().unwrap().values.len(), 2);
+
+        handle_completions_loaded(&mut app, stale, loaded(&["r"]));
+        assert_eq!(
+            app.ui.mcp_completion.as_ref().unwrap().values,
+            vec!["rust".to_string(), "ruby".to_string()]
+        );
+    }
+
+    #[test]
+    fn argument_form_completes_the_whole_input() {
+        let mut app = app_with_prompt();
+        app.ui.start_mcp_prompt_input(McpPromptInput {
+            server_id: "alpha".to_string(),
+            server_name: "Alpha".to_string(),
+            prompt_name: "review".to_string(),
+            prompt_title: None,
+            pending_args: vec![crate::core::app::ui_state::McpPromptArgument {
+                name: "language".to_string(),
+                title: None,
+                description: None,
+                required: true,
+            }],
+            collected: HashMap::new(),
+            next_index: 0,
+            target: McpPromptInputTarget::Prompt,
+        });
+        type_input(&mut app, "py");
+        let Some(AppCommand::RequestMcpCompletion(request)) = handle_refresh_completions(&mut app)
+        else {
+            panic!("expected completion request");
+        };
+        assert_eq!(request.value, "py");
+        handle_completions_loaded(&mut app, request, loaded(&["python"]));
+        assert!(app.complete_mcp_argument(80));
+        assert_eq!(app.ui.get_input_text(), "python");
+    }
 }
@@ -21,6 +21,7 @@
 
 mod file_prompt;
 mod input;
+mod mcp_attachments;
 mod mcp_prompt;
 mod picker;
 mod streaming;
@@ -36,8 +37,9 @@ use crate::core::app::session::{McpPromptRequest, ToolCallRequest};
 use crate::core::app::ModelPickerRequest;
 use crate::core::chat_stream::StreamParams;
 use crate::core::chat_stream::ToolCallDelta;
+use crate::core::mcp_resource_mentions::McpResourceMention;
 use crate::core::message::AppMessageKind;
-use crate::mcp::events::McpServerRequest;
+use crate::mcp::events::{McpServerNotification, McpServerRequest};
 
 /// Root action union consumed by the app reducer loop.
 pub enum AppAction {
@@ -71,6 +73,8 @@ pub enum StreamingAction {
         tool_name: String,
         tool_call_id: Option<String>,
         result: Result<String, String>,
+        /// Full `tools/call` result; `None` for built-in resource tools.
+        output: Option<crate::mcp::tool_output::McpToolOutput>,
     },
     McpPromptCompleted {
         request: McpPromptRequest,
@@ -79,6 +83,32 @@ pub enum StreamingAction {
     McpServerRequestReceived {
         request: Box<McpServerRequest>,
     },
+    McpServerNotificationReceived {
+        notification: Box<McpServerNotification>,
+    },
+    McpServerExited {
+        exit: crate::mcp::events::McpServerExit,
+    },
+    McpServerRestarted {
+        server_id: String,
+        attempt: u32,
+        state: Box<crate::mcp::client::McpServerState>,
+    },
+    McpHealthChecked {
+        server_id: String,
+        client_instance: Option<u64>,
+        result: Result<(), String>,
+    },
+    McpResourceAttachmentsRead {
+        message: String,
+        results: Vec<(McpResourceMention, Result<String, String>)>,
+    },
+    McpResourceWatchRead {
+        server_id: String,
+        uri: String,
+        result: Result<String, String>,
+        is_update: bool,
+    },
     McpSamplingFinished,
     StreamErrored {
         message: String,
@@ -95,6 +125,10 @@ pub enum StreamingAction {
         prompt: String,
     },
     RetryLastMessage,
+    /// Have a group chat member reply without a new user message.
+    GroupReply {
+        speaker: usize,
+    },
 }
 
 /// Actions emitted while a picker overlay is active.
@@ -115,6 +149,7 @@ pub enum PickerAction {
         persistent: bool,
     },
     PickerUnsetDefault,
+    PickerToggleSelection,
     PickerBackspace,
     PickerTypeChar {
         ch: char,
@@ -155,7 +190,16 @@ pub enum FilePromptAction {
 
 /// Actions emitted by the MCP prompt-argument modal.
 pub enum McpPromptAction {
-    CompleteArg { value: String },
+    CompleteArg {
+        value: String,
+    },
+    /// Re-evaluate which prompt argument is under the cursor and request
+    /// fresh `completion/complete` suggestions when it changed.
+    RefreshCompletions,
+    CompletionsLoaded {
+        request: crate::core::app::session::McpCompletionRequest,
+        result: Result<rust_mcp_schema::CompleteResultCompletion, String>,
+    },
 }
 
 impl From<StreamingAction> for AppAction {
@@ -268,8 +312,11 @@ impl AppActionDispatcher {
 pub enum AppCommand {
     SpawnStream(StreamParams),
     LoadModelPicker(ModelPickerRequest),
-    RunMcpTool(ToolCallRequest),
+    /// Tool calls to run concurrently; results arrive as `ToolCallCompleted`.
+    RunMcpTools(Vec<ToolCallRequest>),
     RunMcpPrompt(crate::core::app::session::McpPromptRequest),
+    RequestMcpCompletion(crate::core::app::session::McpCompletionRequest),
+    AttachMcpResources(crate::core::app::session::McpResourceAttachmentRequest),
     RunMcpSampling(Box<crate::core::app::session::McpSamplingRequest>),
     SendMcpServerError {
         server_id: String,
@@ -279,6 +326,27 @@ pub enum AppCommand {
     RefreshMcp {
         server_id: String,
     },
+    RestartMcpServer {
+        server_id: String,
+        attempt: u32,
+    },
+    SetMcpLogLevel {
+        server_id: String,
+        level: rust_mcp_schema::LoggingLevel,
+    },
+    WatchMcpResource {
+        server_id: String,
+        uri: String,
+        inject: bool,
+    },
+    UnwatchMcpResource {
+        server_id: String,
+        uri: String,
+    },
+    RefreshMcpResourceWatch {
+        server_id: String,
+        uri: String,
+    },
 }
 
 /// Applies a batch of action envelopes and collects emitted commands.
@@ -63,6 +63,10 @@ pub(super) fn handle_picker_action(
             handle_picker_apply_selection(app, persistent, ctx)
         }
         PickerAction::PickerUnsetDefault => handle_picker_unset_default(app, ctx),
+        PickerAction::PickerToggleSelection => {
+            handle_picker_toggle_selection(app, ctx);
+            None
+        }
         PickerAction::PickerBackspace => {
             handle_picker_backspace(app);
             None
@@ -106,6 +110,21 @@ pub(super) fn handle_picker_action(
     }
 }
 
+/// Tab in the preset picker marks presets to stack; other pickers ignore it.
+fn handle_picker_toggle_selection(app: &mut App, ctx: AppActionContext) {
+    if app.inspect_state().is_some() || app.current_picker_mode() != Some(PickerMode::Preset) {
+        return;
+    }
+    if let Some(stack) = app.picker.toggle_preset_in_stack() {
+        let status = if stack.is_empty() {
+            "Preset stack empty (Enter turns presets off)".to_string()
+        } else {
+            format!("Preset stack: {} (Enter to apply)", stack.join(", "))
+        };
+        input::set_status_message(app, status, ctx);
+    }
+}
+
 enum PickerMovement {
     Up,
     Down,
@@ -206,6 +225,14 @@ fn handle_picker_backspace(app: &mut App) {
                 }
             }
         }
+        Some(PickerMode::Greeting) => {
+            if let Some(state) = app.greeting_picker_state_mut() {
+                if !state.search_filter.is_empty() {
+                    state.search_filter.pop();
+                    app.filter_greetings();
+                }
+            }
+        }
         None => {}
     }
 }
@@ -255,6 +282,12 @@ fn handle_picker_type_char(app: &mut App, ch: char) {
                 app.filter_presets();
             }
         }
+        Some(PickerMode::Greeting) => {
+            if let Some(state) = app.greeting_picker_state_mut() {
+                state.search_filter.push(ch);
+                app.filter_greetings();
+            }
+        }
         None => {}
     }
 }
@@ -360,7 +393,7 @@ fn handle_picker_escape(app: &mut App, ctx: AppActionContext) {
         Some(PickerMode::Persona) => {
             app.close_picker();
         }
-        Some(PickerMode::Preset) => {
+        Some(PickerMode::Preset) | Some(PickerMode::Greeting) => {
             app.close_picker();
         }
         None => {}
@@ -508,6 +541,10 @@ fn handle_picker_apply_selection(
             app.apply_selected_preset(persistent);
             None
         }
+        Some(PickerMode::Greeting) => {
+            app.apply_selected_greeting();
+            None
+        }
         None => None,
     }
 }
@@ -753,19 +790,23 @@ fn load_default_persona_if_configured(app: &mut App, ctx: AppActionContext) {
 }
 
 fn load_default_preset_if_configured(app: &mut App, ctx: AppActionContext) {
-    if app.preset_manager.get_active_preset().is_some() {
+    if !app.preset_manager.active_presets().is_empty() {
         return;
     }
 
-    if let Some(preset_id) = app
+    if let Some(preset_ids) = app
         .preset_manager
         .get_default_for_provider_model(&app.session.provider_name, &app.session.model)
     {
-        let preset_id = preset_id.to_string();
-        if let Err(e) = app.preset_manager.set_active_preset(&preset_id) {
+        let preset_ids = preset_ids.to_vec();
+        if let Err(e) = app.preset_manager.set_active_presets(&preset_ids) {
             push_error_app_message(
                 app,
-                format!("Could not load default preset '{}': {}", preset_id, e),
+                format!(
+                    "Could not load default preset '{}': {}",
+                    preset_ids.join(", "),
+                    e
+                ),
                 ctx,
             );
         }
@@ -952,6 +993,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -1057,11 +1099,13 @@ mod tests {
                     id: "focus".to_string(),
                     pre: "Focus on details.".to_string(),
                     post: String::new(),
+                    ..Default::default()
                 },
                 Preset {
                     id: "summary".to_string(),
                     pre: String::new(),
                     post: "Summarize at the end.".to_string(),
+                    ..Default::default()
                 },
             ],
             ..Default::default()
@@ -1070,22 +1114,21 @@ mod tests {
         config.set_default_preset(
             "test".to_string(),
             "test-model".to_string(),
-            "focus".to_string(),
+            vec!["summary".to_string(), "focus".to_string()],
         );
 
         let mut app = create_test_app();
         app.preset_manager = crate::core::preset::PresetManager::load_presets(&config)
             .expect("Failed to load presets");
 
-        assert!(app.preset_manager.get_active_preset().is_none());
+        assert!(app.preset_manager.active_presets().is_empty());
 
         load_default_preset_if_configured(&mut app, AppActionContext::default());
 
-        let active = app
-            .preset_manager
-            .get_active_preset()
-            .expect("preset active");
-        assert_eq!(active.id, "focus");
+        assert_eq!(
+            app.preset_manager.active_preset_ids(),
+            vec!["summary", "focus"]
+        );
     }
 
     #[test]
@@ -1096,11 +1139,13 @@ mod tests {
                     id: "focus".to_string(),
                     pre: "Focus on details.".to_string(),
                     post: String::new(),
+                    ..Default::default()
                 },
                 Preset {
                     id: "summary".to_string(),
                     pre: String::new(),
                     post: "Summarize at the end.".to_string(),
+                    ..Default::default()
                 },
             ],
             ..Default::default()
@@ -1109,7 +1154,7 @@ mod tests {
         config.set_default_preset(
             "test".to_string(),
             "test-model".to_string(),
-            "focus".to_string(),
+            vec!["focus".to_string()],
         );
 
         let mut app = create_test_app();
@@ -1121,10 +1166,66 @@ mod tests {
 
         load_default_preset_if_configured(&mut app, AppActionContext::default());
 
-        let active = app
-            .preset_manager
-            .get_active_preset()
-            .expect("preset active");
-        assert_eq!(active.id, "summary");
+        assert_eq!(app.preset_manager.active_preset_ids(), vec!["summary"]);
+    }
+
+    #[test]
+    fn test_tab_stacks_presets_in_preset_picker() {
+        let config = Config {
+            builtin_presets: Some(false),
+            presets: vec![
+                Preset {
+                    id: "focus".to_string(),
+                    pre: "Focus on details.".to_string(),
+                    post: String::new(),
+                    ..Default::default()
+                },
+                Preset {
+                    id: "summary".to_string(),
+                    pre: String::new(),
+                    post: "Summarize at the end.".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let mut app = create_test_app();
+        app.preset_manager = crate::core::preset::PresetManager::load_presets(&config)
+            .expect("Failed to load presets");
+        app.open_preset_picker();
+
+        let ctx = AppActionContext::default();
+        for id in ["summary", "focus"] {
+            let state = app.picker_state_mut().expect("preset picker open");
+            state.selected = state
+                .items
+                .iter()
+                .position(|item| item.id == id)
+                .expect("preset listed");
+            handle_picker_action(&mut app, PickerAction::PickerToggleSelection, ctx);
+        }
+
+        let labels: Vec<&str> = app
+            .picker_state()
+            .expect("preset picker open")
+            .items
+            .iter()
+            .map(|item| item.label.as_str())
+            .collect();
+        assert!(labels.contains(&"summary [1]"));
+        assert!(labels.contains(&"focus [2]"));
+
+        handle_picker_action(
+            &mut app,
+            PickerAction::PickerApplySelection { persistent: false },
+            ctx,
+        );
+
+        assert!(app.picker_state().is_none());
+        assert_eq!(
+            app.preset_manager.active_preset_ids(),
+            vec!["summary", "focus"]
+        );
     }
 }
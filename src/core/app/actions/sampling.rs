@@ -85,15 +85,20 @@ pub(super) fn handle_sampling_permission_decision(
             .mcp_permissions
             .record(&request.server_id, crate::mcp::MCP_SAMPLING_TOOL, decision),
         ToolPermissionDecision::DenyOnce => {}
+        ToolPermissionDecision::AllowAlways | ToolPermissionDecision::BlockAlways => {
+            super::tool_calls::remember_tool_policy(
+                app,
+                &request.server_id,
+                crate::mcp::MCP_SAMPLING_TOOL,
+                decision,
+            );
+        }
     }
 
-    if matches!(
-        decision,
-        ToolPermissionDecision::DenyOnce | ToolPermissionDecision::Block
-    ) {
+    if decision.is_denial() {
         let message = match decision {
-            ToolPermissionDecision::Block => "Sampling blocked by user.",
-            _ => "Sampling denied by user.",
+            ToolPermissionDecision::DenyOnce => "Sampling denied by user.",
+            _ => "Sampling blocked by user.",
         };
         return Some(AppCommand::SendMcpServerError {
             server_id: request.server_id,
@@ -116,8 +121,14 @@ pub(super) fn handle_mcp_sampling_finished(
     ctx: AppActionContext,
 ) -> Option<AppCommand> {
     app.session.tool_pipeline.active_sampling_request = None;
-    if let Some(request) = app.session.tool_pipeline.active_tool_request.clone() {
-        super::set_status_for_tool_run(app, &request, ctx);
+    if let Some(running) = app
+        .session
+        .tool_pipeline
+        .running_tool_calls
+        .first()
+        .cloned()
+    {
+        super::set_status_for_tool_run(app, &running.request, ctx);
     } else {
         app.end_mcp_operation_if_active();
     }
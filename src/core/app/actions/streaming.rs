@@ -14,7 +14,7 @@
 //! # Call flow entrypoints
 //! Called from [`super::apply_action`] for `AppAction::Streaming`. It mutates
 //! `App` state and may emit [`super::AppCommand`] values such as `SpawnStream`,
-//! `RunMcpTool`, `RunMcpPrompt`, and `RunMcpSampling`.
+//! `RunMcpTools`, `RunMcpPrompt`, and `RunMcpSampling`.
 
 use std::collections::VecDeque;
 use std::time::Instant;
@@ -22,15 +22,19 @@ use std::time::Instant;
 use super::{App, AppActionContext, AppCommand, StreamingAction};
 use crate::api::{ChatMessage, ChatToolCall, ChatToolCallFunction};
 use crate::core::app::session::{
-    McpPromptRequest, McpSamplingRequest, PendingToolCall, ToolCallRequest, ToolFailureKind,
-    ToolPayloadHistoryEntry, ToolResultRecord, ToolResultStatus,
+    McpPromptRequest, McpSamplingRequest, PendingToolCall, RunningToolCall, ToolCallRequest,
+    ToolFailureKind, ToolPayloadHistoryEntry, ToolResultRecord, ToolResultStatus,
 };
+use crate::core::app::streaming::TOOL_IMAGES_MARKER;
 use crate::core::app::ui_state::ToolPromptRequest;
 use crate::core::chat_stream::StreamParams;
-use crate::core::config::data::McpToolPayloadRetention;
+use crate::core::config::data::{McpPolicyAction, McpToolPayloadRetention};
 use crate::core::mcp_sampling::{serialize_sampling_params, summarize_sampling_request};
 use crate::core::message::{AppMessageKind, Message, TranscriptRole};
+use crate::mcp::audit::AuditDecision;
 use crate::mcp::permissions::ToolPermissionDecision;
+use crate::mcp::policies::evaluate_tool_policy;
+use crate::mcp::tool_output::McpToolOutput;
 use crate::mcp::{MCP_INSTANT_RECALL_TOOL, MCP_SESSION_MEMORY_SERVER_ID};
 use jsonschema::error::{
     TypeKind, ValidationError as JsonSchemaValidationError, ValidationErrorKind,
@@ -42,6 +46,10 @@ use tracing::debug;
 
 #[path = "mcp_gate.rs"]
 mod mcp_gate;
+#[path = "mcp_health.rs"]
+mod mcp_health;
+#[path = "mcp_notifications.rs"]
+mod mcp_notifications;
 #[path = "sampling.rs"]
 mod sampling;
 #[path = "stream_errors.rs"]
@@ -93,13 +101,48 @@ pub(super) fn handle_streaming_action(
             tool_name,
             tool_call_id,
             result,
-        } => tool_calls::handle_tool_call_completed(app, tool_name, tool_call_id, result, ctx),
+            output,
+        } => tool_calls::handle_tool_call_completed(
+            app,
+            tool_name,
+            tool_call_id,
+            result,
+            output,
+            ctx,
+        ),
         StreamingAction::McpPromptCompleted { request, result } => {
             handle_mcp_prompt_completed(app, request, result, ctx)
         }
         StreamingAction::McpServerRequestReceived { request } => {
             sampling::handle_mcp_server_request(app, *request, ctx)
         }
+        StreamingAction::McpServerNotificationReceived { notification } => {
+            mcp_notifications::handle_mcp_server_notification(app, *notification, ctx)
+        }
+        StreamingAction::McpServerExited { exit } => {
+            mcp_health::handle_mcp_server_exited(app, exit)
+        }
+        StreamingAction::McpServerRestarted {
+            server_id,
+            attempt,
+            state,
+        } => mcp_health::handle_mcp_server_restarted(app, server_id, attempt, *state),
+        StreamingAction::McpHealthChecked {
+            server_id,
+            client_instance,
+            result,
+        } => mcp_health::handle_mcp_health_checked(app, server_id, client_instance, result),
+        StreamingAction::McpResourceAttachmentsRead { message, results } => {
+            super::mcp_attachments::handle_resource_attachments_read(app, message, results, ctx)
+        }
+        StreamingAction::McpResourceWatchRead {
+            server_id,
+            uri,
+            result,
+            is_update,
+        } => mcp_notifications::handle_mcp_resource_watch_read(
+            app, server_id, uri, result, is_update,
+        ),
         StreamingAction::McpSamplingFinished => sampling::handle_mcp_sampling_finished(app, ctx),
         StreamingAction::StreamErrored { message, stream_id } => {
             if !app.is_current_stream(stream_id) {
@@ -124,6 +167,9 @@ pub(super) fn handle_streaming_action(
             stream_lifecycle::refine_last_message(app, prompt, ctx)
         }
         StreamingAction::RetryLastMessage => stream_lifecycle::retry_last_message(app, ctx),
+        StreamingAction::GroupReply { speaker } => Some(AppCommand::SpawnStream(
+            prepare_group_reply_stream(app, speaker, ctx),
+        )),
     }
 }
 
@@ -208,11 +254,7 @@ fn prompt_message_content_to_string(message: &PromptMessage) -> String {
 }
 
 fn prompt_content_to_string(content: &ContentBlock) -> String {
-    match content {
-        ContentBlock::TextContent(text) => text.text.clone(),
-        _ => serde_json::to_string(content)
-            .unwrap_or_else(|_| "Unsupported prompt content.".to_string()),
-    }
+    crate::mcp::tool_output::render_content_block(content)
 }
 
 fn prepare_tool_flow(
@@ -466,6 +508,7 @@ fn prepare_tool_flow(
     app.session.tool_pipeline.tool_call_records = tool_call_records;
     app.session.tool_pipeline.pending_tool_queue = pending_queue;
     app.session.tool_pipeline.active_tool_request = None;
+    app.session.tool_pipeline.running_tool_calls.clear();
     app.session.tool_pipeline.tool_results.clear();
 
     for error in pending_errors {
@@ -492,31 +535,93 @@ fn prepare_tool_flow(
     advance_tool_queue(app, ctx)
 }
 
+/// Default number of tool calls from one reply that run at once
+/// (`mcp_tool_concurrency`).
+const MCP_TOOL_CONCURRENCY: usize = 4;
+
+/// Starts as many queued tool calls as the concurrency limit allows, in
+/// queue order. Calls on a server that does not allow parallel calls stay
+/// queued until that server's running call finishes, without holding up calls
+/// behind them; a call that needs approval pauses the queue until the user
+/// answers. Once nothing is queued or running, the follow-up stream is spawned.
 fn advance_tool_queue(app: &mut App, ctx: AppActionContext) -> Option<AppCommand> {
-    if app.session.tool_pipeline.active_tool_request.is_some() {
+    let started = start_queued_tools(app, ctx);
+    if !started.is_empty() {
+        return Some(AppCommand::RunMcpTools(started));
+    }
+    let pipeline = &app.session.tool_pipeline;
+    if pipeline.active_tool_request.is_some()
+        || !pipeline.running_tool_calls.is_empty()
+        || !pipeline.pending_tool_queue.is_empty()
+    {
         return None;
     }
+    spawn_stream_after_tools(app, ctx)
+}
 
-    let Some(request) = app.session.tool_pipeline.pending_tool_queue.pop_front() else {
-        return spawn_stream_after_tools(app, ctx);
-    };
-
-    if is_instant_recall_tool(&request.tool_name) {
-        app.session.tool_pipeline.active_tool_request = Some(request.clone());
-        return handle_instant_recall_tool_request(app, request, ctx);
-    }
+fn start_queued_tools(app: &mut App, ctx: AppActionContext) -> Vec<ToolCallRequest> {
+    let mut started = Vec::new();
+    let mut waiting = Vec::new();
+    let limit = app
+        .config
+        .mcp_tool_concurrency
+        .filter(|limit| *limit > 0)
+        .unwrap_or(MCP_TOOL_CONCURRENCY);
+
+    while app.session.tool_pipeline.active_tool_request.is_none()
+        && app.session.tool_pipeline.running_tool_calls.len() < limit
+    {
+        let Some(request) = app.session.tool_pipeline.pending_tool_queue.pop_front() else {
+            break;
+        };
+        if is_instant_recall_tool(&request.tool_name) {
+            answer_instant_recall(app, request);
+            continue;
+        }
+        if !can_run_alongside(app, &request.server_id) {
+            waiting.push(request);
+            continue;
+        }
 
-    if is_mcp_yolo_enabled(app, &request.server_id) {
-        app.session.tool_pipeline.active_tool_request = Some(request.clone());
-        set_status_for_tool_run(app, &request, ctx);
-        return Some(AppCommand::RunMcpTool(request));
-    }
+        let policy = mcp_policy_action(
+            app,
+            &request.server_id,
+            &request.tool_name,
+            &request.raw_arguments,
+        );
+        let session = app
+            .mcp_permissions
+            .decision_for(&request.server_id, &request.tool_name);
+        // A `deny` rule or a session block always wins. An `ask` rule
+        // prompts even when the tool was allowed for the session or YOLO is
+        // on; only a remembered-forever choice, saved as a rule ahead of it,
+        // skips the prompt.
+        let decision = match (policy, session) {
+            (Some(McpPolicyAction::Deny), _) => AuditDecision::PolicyDeny,
+            (_, Some(ToolPermissionDecision::Block)) => AuditDecision::Block,
+            (Some(McpPolicyAction::Allow), _) => AuditDecision::PolicyAllow,
+            (None, Some(decision)) => decision.into(),
+            (None, None) if is_mcp_yolo_enabled(app, &request.server_id) => AuditDecision::Yolo,
+            (Some(McpPolicyAction::Ask), _) | (None, None) => {
+                prompt_for_tool_permission(app, request);
+                break;
+            }
+        };
+        let blocked_message = match decision {
+            AuditDecision::PolicyDeny => Some("Tool blocked by policy."),
+            AuditDecision::Block => Some("Tool blocked by user."),
+            _ => None,
+        };
 
-    if let Some(decision) = app
-        .mcp_permissions
-        .decision_for(&request.server_id, &request.tool_name)
-    {
-        if decision == ToolPermissionDecision::Block {
+        if let Some(message) = blocked_message {
+            tool_calls::audit_tool_call(
+                app,
+                &request,
+                decision,
+                ToolResultStatus::Blocked,
+                None,
+                message,
+            );
             let server_label = resolve_server_label(app, &request.server_id);
             let meta = tool_calls::ToolResultMeta::new(
                 Some(server_label),
@@ -528,18 +633,71 @@ fn advance_tool_queue(app: &mut App, ctx: AppActionContext) -> Option<AppCommand
                 app,
                 &request.tool_name,
                 meta,
-                "Tool blocked by user.".to_string(),
+                message.to_string(),
                 ToolResultStatus::Blocked,
                 ctx,
             );
-            return advance_tool_queue(app, ctx);
+            continue;
         }
 
-        app.session.tool_pipeline.active_tool_request = Some(request.clone());
+        mark_tool_running(app, request.clone(), decision, ctx);
+        started.push(request);
+    }
+    let queue = &mut app.session.tool_pipeline.pending_tool_queue;
+    for request in waiting.into_iter().rev() {
+        queue.push_front(request);
+    }
+    started
+}
+
+/// Reports whether a call on `server_id` may start while other calls run.
+fn can_run_alongside(app: &App, server_id: &str) -> bool {
+    let busy = app
+        .session
+        .tool_pipeline
+        .running_tool_calls
+        .iter()
+        .any(|running| running.request.server_id.eq_ignore_ascii_case(server_id));
+    !busy
+        || app
+            .mcp
+            .server(server_id)
+            .is_some_and(|server| server.config.allows_parallel_tool_calls())
+}
+
+/// Adds the call's "running" transcript line and tracks it until its result
+/// arrives. The first call of a batch starts the cancellable MCP operation.
+fn mark_tool_running(
+    app: &mut App,
+    request: ToolCallRequest,
+    decision: AuditDecision,
+    ctx: AppActionContext,
+) {
+    if app.session.tool_pipeline.running_tool_calls.is_empty() {
         set_status_for_tool_run(app, &request, ctx);
-        return Some(AppCommand::RunMcpTool(request));
     }
+    let server_label = resolve_server_label(app, &request.server_id);
+    let running_line = format!("{} on {} (running)", request.tool_name, server_label);
+    let running_line_id = app.conversation().add_running_tool_message(running_line);
+    let input_area_height = app.input_area_height(ctx.term_width);
+    {
+        let mut conversation = app.conversation();
+        let available_height =
+            conversation.calculate_available_height(ctx.term_height, input_area_height);
+        conversation.update_scroll_position(available_height, ctx.term_width);
+    }
+    app.session
+        .tool_pipeline
+        .running_tool_calls
+        .push(RunningToolCall {
+            request,
+            running_line_id,
+            decision,
+            started_at: Instant::now(),
+        });
+}
 
+fn prompt_for_tool_permission(app: &mut App, request: ToolCallRequest) {
     let server_name = resolve_server_label(app, &request.server_id);
 
     let args_summary = summarize_tool_arguments(&request.raw_arguments);
@@ -568,7 +726,6 @@ fn advance_tool_queue(app: &mut App, ctx: AppActionContext) -> Option<AppCommand
         raw_arguments,
         batch_index,
     });
-    None
 }
 
 fn advance_sampling_queue(app: &mut App, ctx: AppActionContext) -> Option<AppCommand> {
@@ -597,38 +754,49 @@ fn advance_sampling_queue(app: &mut App, ctx: AppActionContext) -> Option<AppCom
         "Dequeued MCP sampling request"
     );
 
-    if is_mcp_yolo_enabled(app, &request.server_id) {
-        app.session.tool_pipeline.active_sampling_request = Some(request.clone());
-        set_status_for_sampling_run(app, &request, ctx);
-        return Some(AppCommand::RunMcpSampling(Box::new(request)));
-    }
-
-    if let Some(decision) = app
+    let policy = mcp_policy_action(
+        app,
+        &request.server_id,
+        crate::mcp::MCP_SAMPLING_TOOL,
+        &serialize_sampling_params(&request.request),
+    );
+    let session = app
         .mcp_permissions
-        .decision_for(&request.server_id, crate::mcp::MCP_SAMPLING_TOOL)
-    {
-        if decision == ToolPermissionDecision::Block {
-            return Some(AppCommand::SendMcpServerError {
-                server_id: request.server_id,
-                request_id: request.request.id.clone(),
-                error: RpcError {
-                    code: -1,
-                    message: "Sampling blocked by user.".to_string(),
-                    data: None,
-                },
-            });
-        }
+        .decision_for(&request.server_id, crate::mcp::MCP_SAMPLING_TOOL);
+    let blocked_message = match (policy, session) {
+        (Some(McpPolicyAction::Deny), _) => Some("Sampling blocked by policy."),
+        (_, Some(ToolPermissionDecision::Block)) => Some("Sampling blocked by user."),
+        _ => None,
+    };
+    if let Some(message) = blocked_message {
+        return Some(AppCommand::SendMcpServerError {
+            server_id: request.server_id,
+            request_id: request.request.id.clone(),
+            error: RpcError {
+                code: -1,
+                message: message.to_string(),
+                data: None,
+            },
+        });
+    }
 
-        if decision == ToolPermissionDecision::AllowSession {
+    let allowed = match (policy, session) {
+        (Some(McpPolicyAction::Allow), _) => true,
+        (None, Some(ToolPermissionDecision::AllowSession)) => {
             debug!(
                 server_id = %request.server_id,
                 request_id = ?request.request.id,
                 "Auto-allowing sampling for session"
             );
-            app.session.tool_pipeline.active_sampling_request = Some(request.clone());
-            set_status_for_sampling_run(app, &request, ctx);
-            return Some(AppCommand::RunMcpSampling(Box::new(request)));
+            true
         }
+        (None, None) => is_mcp_yolo_enabled(app, &request.server_id),
+        _ => false,
+    };
+    if allowed {
+        app.session.tool_pipeline.active_sampling_request = Some(request.clone());
+        set_status_for_sampling_run(app, &request, ctx);
+        return Some(AppCommand::RunMcpSampling(Box::new(request)));
     }
 
     let server_name = resolve_server_label(app, &request.server_id);
@@ -664,7 +832,12 @@ fn spawn_stream_after_tools(app: &mut App, ctx: AppActionContext) -> Option<AppC
         .as_ref()
         .map(|continuation| continuation.api_messages.clone())?;
 
-    let mut api_messages = base_messages;
+    // A prefill would sit between the conversation and the tool calls it
+    // never made; the reply after tools starts fresh.
+    let mut api_messages: Vec<ChatMessage> = base_messages
+        .into_iter()
+        .filter(|message| !message.is_prefill)
+        .collect();
     if !app.session.tool_pipeline.tool_call_records.is_empty() {
         api_messages.push(ChatMessage {
             role: "assistant".to_string(),
@@ -672,10 +845,15 @@ fn spawn_stream_after_tools(app: &mut App, ctx: AppActionContext) -> Option<AppC
             name: None,
             tool_call_id: None,
             tool_calls: Some(app.session.tool_pipeline.tool_call_records.clone()),
+            images: Vec::new(),
+            is_prefill: false,
         });
     }
 
-    api_messages.extend(app.session.tool_pipeline.tool_results.clone());
+    api_messages.extend(tool_results_in_call_order(app));
+    if let Some(message) = tool_image_message(app) {
+        api_messages.push(message);
+    }
 
     if ctx.term_width == 0 || ctx.term_height == 0 {
         return None;
@@ -702,6 +880,72 @@ fn spawn_stream_after_tools(app: &mut App, ctx: AppActionContext) -> Option<AppC
     )))
 }
 
+/// Returns this round's tool results in the order the model requested the
+/// calls, regardless of which finished first.
+fn tool_results_in_call_order(app: &App) -> Vec<ChatMessage> {
+    let pipeline = &app.session.tool_pipeline;
+    let position = |message: &ChatMessage| {
+        message
+            .tool_call_id
+            .as_deref()
+            .and_then(|id| {
+                pipeline
+                    .tool_call_records
+                    .iter()
+                    .position(|record| record.id == id)
+            })
+            .unwrap_or(usize::MAX)
+    };
+    let mut results = pipeline.tool_results.clone();
+    results.sort_by_key(position);
+    results
+}
+
+/// Collects images from this round's tool results into a user message, since
+/// chat APIs only accept image parts on user turns.
+fn tool_image_message(app: &App) -> Option<ChatMessage> {
+    if !app.config.is_vision_model(&app.session.model) {
+        return None;
+    }
+    let pipeline = &app.session.tool_pipeline;
+    let mut labels = Vec::new();
+    let mut images = Vec::new();
+    for message in &pipeline.tool_results {
+        let Some(record) = pipeline.tool_result_history.iter().rev().find(|record| {
+            record.tool_call_id.is_some() && record.tool_call_id == message.tool_call_id
+        }) else {
+            continue;
+        };
+        let record_images = record
+            .output
+            .as_ref()
+            .map(McpToolOutput::images)
+            .unwrap_or_default();
+        if record_images.is_empty() {
+            continue;
+        }
+        labels.push(format!(
+            "{} (call_id={})",
+            record.tool_name,
+            record.tool_call_id.as_deref().unwrap_or_default()
+        ));
+        images.extend(record_images);
+    }
+    if images.is_empty() {
+        return None;
+    }
+
+    Some(ChatMessage {
+        role: "user".to_string(),
+        content: format!("{TOOL_IMAGES_MARKER} {}", labels.join(", ")),
+        name: None,
+        tool_call_id: None,
+        tool_calls: None,
+        images,
+        is_prefill: false,
+    })
+}
+
 fn resolve_tool_server(app: &App, tool_name: &str) -> Result<(String, String), String> {
     let mut matches = Vec::new();
 
@@ -1284,6 +1528,14 @@ fn handle_instant_recall_tool_request(
     request: ToolCallRequest,
     ctx: AppActionContext,
 ) -> Option<AppCommand> {
+    answer_instant_recall(app, request);
+    app.session.tool_pipeline.active_tool_request = None;
+    advance_tool_queue(app, ctx)
+}
+
+/// Answers an instant-recall call from stored tool results without a round
+/// trip to any server.
+fn answer_instant_recall(app: &mut App, request: ToolCallRequest) {
     let tool_call_id = request.tool_call_id.clone();
 
     let result = recall_tool_payload(app, &request);
@@ -1313,12 +1565,13 @@ fn handle_instant_recall_tool_request(
         name: None,
         tool_call_id,
         tool_calls: None,
+        images: Vec::new(),
+        is_prefill: false,
     };
     app.session.tool_pipeline.tool_results.push(tool_message);
-
-    app.clear_status();
-    app.session.tool_pipeline.active_tool_request = None;
-    advance_tool_queue(app, ctx)
+    if app.session.tool_pipeline.running_tool_calls.is_empty() {
+        app.clear_status();
+    }
 }
 
 fn recall_tool_payload(
@@ -1363,6 +1616,7 @@ fn record_tool_result(
     let server_id = meta.server_id.clone();
     let server_label = meta.server_label.clone();
     let failure_kind = meta.failure_kind;
+    let media_summary = meta.output.as_ref().and_then(McpToolOutput::media_summary);
     let summary = build_tool_result_summary(tool_name, &meta, status);
     let (payload_policy, payload_window) =
         resolve_tool_payload_policy(app, meta.server_id.as_deref());
@@ -1380,11 +1634,29 @@ fn record_tool_result(
     };
     transcript_payload.push_str(status_label);
     transcript_payload.push(')');
+    if let Some(media) = media_summary {
+        transcript_payload.push_str(" · ");
+        transcript_payload.push_str(&media);
+    }
 
     let input_area_height = app.input_area_height(ctx.term_width);
+    let running_line = meta.running_line_id.and_then(|id| {
+        app.ui
+            .messages
+            .iter_mut()
+            .find(|message| message.id == Some(id))
+    });
+    match running_line {
+        Some(message) => {
+            message.content = transcript_payload;
+            app.invalidate_prewrap_cache();
+        }
+        None => app
+            .conversation()
+            .add_tool_result_message(transcript_payload),
+    }
     {
         let mut conversation = app.conversation();
-        conversation.add_tool_result_message(transcript_payload);
         let available_height =
             conversation.calculate_available_height(ctx.term_height, input_area_height);
         conversation.update_scroll_position(available_height, ctx.term_width);
@@ -1402,6 +1674,8 @@ fn record_tool_result(
         name: None,
         tool_call_id: tool_call_id.clone(),
         tool_calls: None,
+        images: Vec::new(),
+        is_prefill: false,
     });
 
     app.session
@@ -1418,6 +1692,7 @@ fn record_tool_result(
             tool_call_id: tool_call_id.clone(),
             raw_arguments: raw_arguments.clone(),
             assistant_message_index,
+            output: meta.output,
         });
 
     if matches!(
@@ -1438,6 +1713,8 @@ fn record_tool_result(
                         arguments: raw_arguments.clone().unwrap_or_default(),
                     },
                 }]),
+                images: Vec::new(),
+                is_prefill: false,
             };
             let tool_message = ChatMessage {
                 role: "tool".to_string(),
@@ -1445,6 +1722,8 @@ fn record_tool_result(
                 name: None,
                 tool_call_id: Some(tool_call_id.clone()),
                 tool_calls: None,
+                images: Vec::new(),
+                is_prefill: false,
             };
             app.session
                 .tool_pipeline
@@ -1570,6 +1849,16 @@ fn set_status_for_sampling_run(
     conversation.update_scroll_position(available_height, ctx.term_width);
 }
 
+fn mcp_policy_action(
+    app: &App,
+    server_id: &str,
+    tool_name: &str,
+    raw_arguments: &str,
+) -> Option<McpPolicyAction> {
+    let policies = app.mcp.server(server_id)?.config.policies.as_deref()?;
+    evaluate_tool_policy(policies, tool_name, raw_arguments)
+}
+
 fn is_mcp_yolo_enabled(app: &App, server_id: &str) -> bool {
     app.mcp
         .server(server_id)
@@ -1616,6 +1905,29 @@ fn prepare_stream_params_for_message(
     app.build_stream_params(api_messages, cancel_token, stream_id)
 }
 
+fn prepare_group_reply_stream(
+    app: &mut App,
+    speaker: usize,
+    ctx: AppActionContext,
+) -> StreamParams {
+    app.ui.focus_transcript();
+    let term_width = ctx.term_width.max(1);
+    let term_height = ctx.term_height.max(1);
+    app.enable_auto_scroll();
+    let input_area_height = app.input_area_height(term_width);
+    let (cancel_token, stream_id, api_messages) = {
+        let mut conversation = app.conversation();
+        let (cancel_token, stream_id) = conversation.start_new_stream();
+        let api_messages = conversation.add_group_reply(speaker);
+        let available_height =
+            conversation.calculate_available_height(term_height, input_area_height);
+        conversation.update_scroll_position(available_height, term_width);
+        (cancel_token, stream_id, api_messages)
+    };
+
+    app.build_stream_params(api_messages, cancel_token, stream_id)
+}
+
 fn prepare_retry_stream(app: &mut App, ctx: AppActionContext) -> Option<AppCommand> {
     let now = Instant::now();
     if now.duration_since(app.last_retry_time()).as_millis() < 200 {
@@ -1690,7 +2002,7 @@ fn prepare_refine_stream(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::config::data::McpServerConfig;
+    use crate::core::config::data::{McpArgumentMatcher, McpServerConfig, McpToolPolicy};
     use crate::utils::test_utils::create_test_app;
     use rust_mcp_schema::{ListToolsResult, Tool, ToolInputSchema};
     use std::collections::HashMap;
@@ -1712,17 +2024,9 @@ mod tests {
             id: server_id.to_string(),
             display_name: "Alpha MCP".to_string(),
             base_url: Some("https://mcp.example.com".to_string()),
-            command: None,
-            args: None,
-            env: None,
-            headers: None,
             transport: Some("streamable-http".to_string()),
-            allowed_tools: None,
-            protocol_version: None,
             enabled: Some(true),
-            tool_payloads: None,
-            tool_payload_window: None,
-            yolo: None,
+            ..Default::default()
         });
         app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
 
@@ -1932,4 +2236,472 @@ mod tests {
         assert!(app.session.tool_pipeline.active_tool_request.is_some());
         assert!(app.ui.tool_prompt().is_some());
     }
+
+    fn add_policy_tool(app: &mut App, policies: Vec<McpToolPolicy>, yolo: bool) {
+        add_test_tool(
+            app,
+            "alpha",
+            "read_file",
+            ToolInputSchema::new(Vec::new(), None, None),
+        );
+        let server = app.mcp.server_mut("alpha").expect("server");
+        server.config.policies = Some(policies);
+        server.config.yolo = Some(yolo);
+    }
+
+    fn read_file_call(path: &str) -> Vec<(u32, PendingToolCall)> {
+        vec![(
+            0,
+            PendingToolCall {
+                id: Some("call-1".to_string()),
+                name: Some("read_file".to_string()),
+                arguments: serde_json::json!({ "path": path }).to_string(),
+            },
+        )]
+    }
+
+    #[test]
+    fn tool_policies_allow_matching_arguments_and_prompt_otherwise() {
+        let mut app = create_test_app();
+        add_policy_tool(
+            &mut app,
+            vec![McpToolPolicy {
+                tool: "read_file".to_string(),
+                action: McpPolicyAction::Allow,
+                arguments: vec![McpArgumentMatcher {
+                    pointer: "/path".to_string(),
+                    equals: None,
+                    prefix: None,
+                    path_prefix: Some("/srv/docs".to_string()),
+                }],
+            }],
+            false,
+        );
+
+        let command = prepare_tool_flow(&mut app, read_file_call("/srv/docs/a.md"), default_ctx());
+        assert!(matches!(command, Some(AppCommand::RunMcpTools(_))));
+        assert!(app.ui.tool_prompt().is_none());
+
+        app.session.tool_pipeline.active_tool_request = None;
+        let command = prepare_tool_flow(&mut app, read_file_call("/etc/passwd"), default_ctx());
+        assert!(command.is_none());
+        assert!(app.ui.tool_prompt().is_some());
+    }
+
+    #[test]
+    fn tool_policies_deny_and_ask_override_yolo() {
+        let mut app = create_test_app();
+        add_policy_tool(
+            &mut app,
+            vec![McpToolPolicy {
+                tool: "*".to_string(),
+                action: McpPolicyAction::Ask,
+                arguments: Vec::new(),
+            }],
+            true,
+        );
+        let command = prepare_tool_flow(&mut app, read_file_call("/tmp/a"), default_ctx());
+        assert!(command.is_none());
+        assert!(app.ui.tool_prompt().is_some());
+
+        let mut app = create_test_app();
+        add_policy_tool(
+            &mut app,
+            vec![McpToolPolicy {
+                tool: "read_file".to_string(),
+                action: McpPolicyAction::Deny,
+                arguments: Vec::new(),
+            }],
+            true,
+        );
+        prepare_tool_flow(&mut app, read_file_call("/tmp/a"), default_ctx());
+        let record = app
+            .session
+            .tool_pipeline
+            .tool_result_history
+            .last()
+            .expect("blocked record");
+        assert_eq!(record.status, ToolResultStatus::Blocked);
+        assert_eq!(record.content, "Tool blocked by policy.");
+    }
+
+    #[test]
+    fn session_block_beats_allow_rule_and_ask_rule_beats_session_allow() {
+        let mut app = create_test_app();
+        add_policy_tool(
+            &mut app,
+            vec![McpToolPolicy {
+                tool: "read_file".to_string(),
+                action: McpPolicyAction::Allow,
+                arguments: Vec::new(),
+            }],
+            false,
+        );
+        app.mcp_permissions
+            .record("alpha", "read_file", ToolPermissionDecision::Block);
+        let command = prepare_tool_flow(&mut app, read_file_call("/tmp/a"), default_ctx());
+        assert!(!matches!(command, Some(AppCommand::RunMcpTools(_))));
+        let record = app
+            .session
+            .tool_pipeline
+            .tool_result_history
+            .last()
+            .expect("blocked record");
+        assert_eq!(record.content, "Tool blocked by user.");
+
+        let mut app = create_test_app();
+        add_policy_tool(
+            &mut app,
+            vec![McpToolPolicy {
+                tool: "read_file".to_string(),
+                action: McpPolicyAction::Ask,
+                arguments: Vec::new(),
+            }],
+            false,
+        );
+        app.mcp_permissions
+            .record("alpha", "read_file", ToolPermissionDecision::AllowSession);
+        let command = prepare_tool_flow(&mut app, read_file_call("/tmp/a"), default_ctx());
+        assert!(command.is_none());
+        assert!(app.ui.tool_prompt().is_some());
+    }
+
+    #[test]
+    fn allow_always_decision_prepends_policy_rule() {
+        let mut app = create_test_app();
+        add_policy_tool(
+            &mut app,
+            vec![McpToolPolicy {
+                tool: "*".to_string(),
+                action: McpPolicyAction::Ask,
+                arguments: Vec::new(),
+            }],
+            false,
+        );
+        prepare_tool_flow(&mut app, read_file_call("/tmp/a"), default_ctx());
+        assert!(app.ui.tool_prompt().is_some());
+
+        let command = tool_calls::handle_tool_permission_decision(
+            &mut app,
+            ToolPermissionDecision::AllowAlways,
+            default_ctx(),
+        );
+        assert!(matches!(command, Some(AppCommand::RunMcpTools(_))));
+        let policies = app
+            .mcp
+            .server("alpha")
+            .and_then(|server| server.config.policies.clone())
+            .expect("policies");
+        assert_eq!(policies.len(), 2);
+        assert_eq!(policies[0].tool, "read_file");
+        assert_eq!(policies[0].action, McpPolicyAction::Allow);
+    }
+
+    fn add_yolo_servers(app: &mut App, servers: &[(&str, &str)]) {
+        for (server_id, _) in servers {
+            app.config.mcp_servers.push(McpServerConfig {
+                id: server_id.to_string(),
+                display_name: server_id.to_uppercase(),
+                base_url: Some("https://mcp.example.com".to_string()),
+                transport: Some("streamable-http".to_string()),
+                enabled: Some(true),
+                yolo: Some(true),
+                ..Default::default()
+            });
+        }
+        app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
+        for (server_id, tool_name) in servers {
+            let tool = Tool {
+                annotations: None,
+                description: None,
+                execution: None,
+                icons: Vec::new(),
+                input_schema: ToolInputSchema::new(Vec::new(), None, None),
+                meta: None,
+                name: tool_name.to_string(),
+                output_schema: None,
+                title: None,
+            };
+            app.mcp
+                .server_mut(server_id)
+                .expect("server")
+                .set_cached_tools(ListToolsResult {
+                    meta: None,
+                    next_cursor: None,
+                    tools: vec![tool],
+                });
+        }
+    }
+
+    fn tool_calls(names: &[&str]) -> Vec<(u32, PendingToolCall)> {
+        names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                (
+                    index as u32,
+                    PendingToolCall {
+                        id: Some(format!("call-{index}")),
+                        name: Some(name.to_string()),
+                        arguments: "{}".to_string(),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn complete(app: &mut App, tool_name: &str, id: &str) -> Option<AppCommand> {
+        tool_calls::handle_tool_call_completed(
+            app,
+            tool_name.to_string(),
+            Some(id.to_string()),
+            Ok(format!("{id} done")),
+            None,
+            default_ctx(),
+        )
+    }
+
+    fn started_ids(command: Option<AppCommand>) -> Vec<String> {
+        let Some(AppCommand::RunMcpTools(requests)) = command else {
+            panic!("expected tool calls to start");
+        };
+        requests
+            .into_iter()
+            .filter_map(|request| request.tool_call_id)
+            .collect()
+    }
+
+    #[test]
+    fn tool_calls_on_different_servers_run_concurrently_in_call_order() {
+        let mut app = create_test_app();
+        add_yolo_servers(&mut app, &[("alpha", "lookup"), ("beta", "search")]);
+        app.session
+            .tool_pipeline
+            .set_continuation(Vec::new(), Vec::new());
+
+        let command = prepare_tool_flow(
+            &mut app,
+            tool_calls(&["lookup", "search", "lookup"]),
+            default_ctx(),
+        );
+        // The second alpha call waits for the first one on the same server.
+        assert_eq!(started_ids(command), vec!["call-0", "call-1"]);
+        let running: Vec<&str> = app
+            .ui
+            .messages
+            .iter()
+            .filter(|message| message.role == TranscriptRole::ToolResult)
+            .map(|message| message.content.as_str())
+            .collect();
+        assert_eq!(
+            running,
+            vec!["lookup on ALPHA (running)", "search on BETA (running)"]
+        );
+
+        assert!(complete(&mut app, "search", "call-1").is_none());
+        assert_eq!(
+            started_ids(complete(&mut app, "lookup", "call-0")),
+            vec!["call-2"]
+        );
+        let Some(AppCommand::SpawnStream(params)) = complete(&mut app, "lookup", "call-2") else {
+            panic!("expected follow-up stream");
+        };
+
+        let tool_ids: Vec<&str> = params
+            .api_messages
+            .iter()
+            .filter(|message| message.role == "tool")
+            .filter_map(|message| message.tool_call_id.as_deref())
+            .collect();
+        assert_eq!(tool_ids, vec!["call-0", "call-1", "call-2"]);
+        let statuses: Vec<&str> = app
+            .ui
+            .messages
+            .iter()
+            .filter(|message| message.role == TranscriptRole::ToolResult)
+            .map(|message| message.content.as_str())
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                "lookup on ALPHA (success)",
+                "search on BETA (success)",
+                "lookup on ALPHA (success)"
+            ]
+        );
+    }
+
+    #[test]
+    fn busy_server_does_not_hold_up_later_calls() {
+        let mut app = create_test_app();
+        add_yolo_servers(&mut app, &[("alpha", "lookup"), ("beta", "search")]);
+        app.session
+            .tool_pipeline
+            .set_continuation(Vec::new(), Vec::new());
+
+        let command = prepare_tool_flow(
+            &mut app,
+            tool_calls(&["lookup", "lookup", "search"]),
+            default_ctx(),
+        );
+        assert_eq!(started_ids(command), vec!["call-0", "call-2"]);
+
+        // Transcript edits while calls run don't lose their running lines.
+        app.ui
+            .messages
+            .insert(0, Message::new(TranscriptRole::User, "earlier"));
+        assert_eq!(
+            started_ids(complete(&mut app, "lookup", "call-0")),
+            vec!["call-1"]
+        );
+        assert!(complete(&mut app, "search", "call-2").is_none());
+        let statuses: Vec<&str> = app
+            .ui
+            .messages
+            .iter()
+            .filter(|message| message.role == TranscriptRole::ToolResult)
+            .map(|message| message.content.as_str())
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                "lookup on ALPHA (success)",
+                "search on BETA (success)",
+                "lookup on ALPHA (running)"
+            ]
+        );
+    }
+
+    #[test]
+    fn running_lines_with_the_same_text_finish_in_place() {
+        let mut app = create_test_app();
+        add_yolo_servers(&mut app, &[("alpha", "lookup")]);
+        app.mcp
+            .server_mut("alpha")
+            .expect("server")
+            .config
+            .parallel_tool_calls = Some(true);
+        app.session
+            .tool_pipeline
+            .set_continuation(Vec::new(), Vec::new());
+        prepare_tool_flow(&mut app, tool_calls(&["lookup", "lookup"]), default_ctx());
+
+        tool_calls::handle_tool_call_completed(
+            &mut app,
+            "lookup".to_string(),
+            Some("call-1".to_string()),
+            Err("boom".to_string()),
+            None,
+            default_ctx(),
+        );
+        let statuses: Vec<&str> = app
+            .ui
+            .messages
+            .iter()
+            .filter(|message| message.role == TranscriptRole::ToolResult)
+            .map(|message| message.content.as_str())
+            .collect();
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses[0], "lookup on ALPHA (running)");
+        assert_ne!(statuses[1], "lookup on ALPHA (running)");
+    }
+
+    #[test]
+    fn stale_running_line_from_cancelled_turn_is_left_alone() {
+        let mut app = create_test_app();
+        add_yolo_servers(&mut app, &[("alpha", "lookup")]);
+        app.session
+            .tool_pipeline
+            .set_continuation(Vec::new(), Vec::new());
+        app.ui
+            .messages
+            .push_back(Message::tool_result("lookup on ALPHA (running)"));
+
+        prepare_tool_flow(&mut app, tool_calls(&["lookup"]), default_ctx());
+        complete(&mut app, "lookup", "call-0");
+        let statuses: Vec<&str> = app
+            .ui
+            .messages
+            .iter()
+            .filter(|message| message.role == TranscriptRole::ToolResult)
+            .map(|message| message.content.as_str())
+            .collect();
+        assert_eq!(
+            statuses,
+            vec!["lookup on ALPHA (running)", "lookup on ALPHA (success)"]
+        );
+    }
+
+    #[test]
+    fn continuation_after_tools_drops_preset_prefill() {
+        let mut app = create_test_app();
+        add_yolo_servers(&mut app, &[("alpha", "lookup")]);
+        let config = crate::core::config::data::Config {
+            builtin_presets: Some(false),
+            presets: vec![crate::core::config::data::Preset {
+                id: "prefill".to_string(),
+                blocks: vec![crate::core::config::data::PresetBlock {
+                    content: "Sure,".to_string(),
+                    position: crate::core::config::data::PresetPosition::Prefill,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        app.preset_manager =
+            crate::core::preset::PresetManager::load_presets(&config).expect("presets");
+        app.preset_manager
+            .set_active_preset("prefill")
+            .expect("activate preset");
+
+        let api_messages = app
+            .conversation()
+            .add_user_message("Look it up".to_string());
+        assert!(api_messages
+            .last()
+            .is_some_and(|message| message.is_prefill));
+        app.session
+            .tool_pipeline
+            .set_continuation(api_messages.clone(), api_messages);
+
+        prepare_tool_flow(&mut app, tool_calls(&["lookup"]), default_ctx());
+        let Some(AppCommand::SpawnStream(params)) = complete(&mut app, "lookup", "call-0") else {
+            panic!("expected follow-up stream");
+        };
+        let roles: Vec<&str> = params
+            .api_messages
+            .iter()
+            .skip_while(|message| message.role != "user")
+            .map(|message| message.role.as_str())
+            .collect();
+        assert_eq!(roles, vec!["user", "assistant", "tool"]);
+        assert!(params
+            .api_messages
+            .iter()
+            .all(|message| !message.is_prefill));
+    }
+
+    #[test]
+    fn tool_concurrency_limit_and_parallel_servers_are_respected() {
+        let mut app = create_test_app();
+        add_yolo_servers(&mut app, &[("alpha", "lookup"), ("beta", "search")]);
+        app.config.mcp_tool_concurrency = Some(1);
+        let command = prepare_tool_flow(&mut app, tool_calls(&["lookup", "search"]), default_ctx());
+        assert_eq!(started_ids(command), vec!["call-0"]);
+
+        let mut app = create_test_app();
+        add_yolo_servers(&mut app, &[("alpha", "lookup")]);
+        app.mcp
+            .server_mut("alpha")
+            .expect("server")
+            .config
+            .parallel_tool_calls = Some(true);
+        let command = prepare_tool_flow(
+            &mut app,
+            tool_calls(&["lookup", "lookup", "lookup"]),
+            default_ctx(),
+        );
+        assert_eq!(started_ids(command), vec!["call-0", "call-1", "call-2"]);
+    }
 }
@@ -1,7 +1,13 @@
 use super::{App, AppActionContext, AppCommand};
-use crate::core::app::session::ToolFailureKind;
+use crate::core::app::session::{
+    RunningToolCall, ToolCallRequest, ToolFailureKind, ToolResultStatus,
+};
+use crate::core::config::data::{Config, McpPolicyAction, McpToolPolicy};
+use crate::mcp::audit::{AuditDecision, ToolAuditEntry};
 use crate::mcp::permissions::ToolPermissionDecision;
+use crate::mcp::tool_output::McpToolOutput;
 use serde_json::Value;
+use std::time::Duration;
 use tracing::debug;
 
 #[derive(Debug, Clone)]
@@ -11,6 +17,9 @@ pub(super) struct ToolResultMeta {
     pub(super) tool_call_id: Option<String>,
     pub(super) raw_arguments: Option<String>,
     pub(super) failure_kind: Option<ToolFailureKind>,
+    pub(super) output: Option<McpToolOutput>,
+    /// ID of the "running" line added when the call started, updated in place.
+    pub(super) running_line_id: Option<u64>,
 }
 
 impl ToolResultMeta {
@@ -26,6 +35,8 @@ impl ToolResultMeta {
             tool_call_id,
             raw_arguments,
             failure_kind: None,
+            output: None,
+            running_line_id: None,
         }
     }
 }
@@ -67,23 +78,22 @@ pub(super) fn handle_tool_permission_decision(
                 .mcp_permissions
                 .record(&request.server_id, &request.tool_name, decision),
             ToolPermissionDecision::DenyOnce => {}
+            ToolPermissionDecision::AllowAlways | ToolPermissionDecision::BlockAlways => {
+                remember_tool_policy(app, &request.server_id, &request.tool_name, decision);
+            }
         }
 
-        if matches!(
-            decision,
-            ToolPermissionDecision::DenyOnce | ToolPermissionDecision::Block
-        ) {
+        if decision.is_denial() {
             let message = match decision {
-                ToolPermissionDecision::Block => "Tool blocked by user.",
-                _ => "Tool denied by user.",
+                ToolPermissionDecision::DenyOnce => "Tool denied by user.",
+                _ => "Tool blocked by user.",
             };
             let server_label = super::resolve_server_label(app, &request.server_id);
             let status = match decision {
-                ToolPermissionDecision::Block => {
-                    crate::core::app::session::ToolResultStatus::Blocked
-                }
-                _ => crate::core::app::session::ToolResultStatus::Denied,
+                ToolPermissionDecision::DenyOnce => ToolResultStatus::Denied,
+                _ => ToolResultStatus::Blocked,
             };
+            audit_tool_call(app, &request, decision.into(), status, None, message);
             let meta = ToolResultMeta::new(
                 Some(server_label),
                 Some(request.server_id.clone()),
@@ -101,70 +111,148 @@ pub(super) fn handle_tool_permission_decision(
             return super::advance_tool_queue(app, ctx);
         }
 
-        app.session.tool_pipeline.active_tool_request = Some(request.clone());
         if super::is_instant_recall_tool(&request.tool_name) {
             return super::handle_instant_recall_tool_request(app, request, ctx);
         }
-        super::set_status_for_tool_run(app, &request, ctx);
-        return Some(AppCommand::RunMcpTool(request));
+        super::mark_tool_running(app, request.clone(), decision.into(), ctx);
+        let mut started = vec![request];
+        if let Some(AppCommand::RunMcpTools(more)) = super::advance_tool_queue(app, ctx) {
+            started.extend(more);
+        }
+        return Some(AppCommand::RunMcpTools(started));
     }
 
     let request = app.session.tool_pipeline.active_sampling_request.take()?;
     super::sampling::handle_sampling_permission_decision(app, request, decision, ctx)
 }
 
+/// Prepends an unconditional allow/deny rule for the tool and saves it to
+/// config.toml so the choice survives restarts.
+pub(super) fn remember_tool_policy(
+    app: &mut App,
+    server_id: &str,
+    tool_name: &str,
+    decision: ToolPermissionDecision,
+) {
+    let policy = McpToolPolicy {
+        tool: tool_name.to_string(),
+        action: if decision == ToolPermissionDecision::BlockAlways {
+            McpPolicyAction::Deny
+        } else {
+            McpPolicyAction::Allow
+        },
+        arguments: Vec::new(),
+    };
+    let insert = |policies: &mut Option<Vec<McpToolPolicy>>| {
+        let policies = policies.get_or_insert_with(Vec::new);
+        policies.retain(|existing| existing.tool != policy.tool || !existing.arguments.is_empty());
+        policies.insert(0, policy.clone());
+    };
+
+    if let Some(server) = app.mcp.server_mut(server_id) {
+        insert(&mut server.config.policies);
+    }
+    if let Some(server) = app
+        .config
+        .mcp_servers
+        .iter_mut()
+        .find(|server| server.id.eq_ignore_ascii_case(server_id))
+    {
+        insert(&mut server.policies);
+    }
+
+    let saved = Config::mutate(|config| {
+        if let Some(server) = config
+            .mcp_servers
+            .iter_mut()
+            .find(|server| server.id.eq_ignore_ascii_case(server_id))
+        {
+            insert(&mut server.policies);
+        }
+        Ok(())
+    });
+    if let Err(err) = saved {
+        debug!(server_id, tool_name, error = %err, "Failed to save MCP tool policy");
+        app.conversation()
+            .set_status(format!("Couldn't save the rule for {tool_name}: {err}"));
+    }
+}
+
 pub(super) fn handle_tool_call_completed(
     app: &mut App,
     tool_name: String,
     tool_call_id: Option<String>,
     result: Result<String, String>,
+    output: Option<McpToolOutput>,
     ctx: AppActionContext,
 ) -> Option<AppCommand> {
-    let Some(active_request) = app.session.tool_pipeline.active_tool_request.as_ref() else {
-        app.end_mcp_operation_if_active();
-        return None;
+    let running = &app.session.tool_pipeline.running_tool_calls;
+    let position = match tool_call_id.as_deref() {
+        Some(id) => running
+            .iter()
+            .position(|call| call.request.tool_call_id.as_deref() == Some(id)),
+        None => running
+            .iter()
+            .position(|call| call.request.tool_name == tool_name),
     };
-    if let (Some(active_id), Some(completed_id)) = (
-        active_request.tool_call_id.as_deref(),
-        tool_call_id.as_deref(),
-    ) {
-        if active_id != completed_id {
-            return None;
+    let Some(position) = position else {
+        if running.is_empty() {
+            app.end_mcp_operation_if_active();
         }
-    }
-    let request = app
+        return None;
+    };
+    let RunningToolCall {
+        request,
+        running_line_id,
+        decision,
+        started_at,
+    } = app
         .session
         .tool_pipeline
-        .active_tool_request
-        .take()
-        .expect("active tool request should still be present");
+        .running_tool_calls
+        .remove(position);
     let server_label = Some(super::resolve_server_label(app, &request.server_id));
-    app.end_mcp_operation_if_active();
+    if app.session.tool_pipeline.running_tool_calls.is_empty() {
+        app.end_mcp_operation_if_active();
+    }
+    let latency = Some(started_at.elapsed());
 
     match result {
         Ok(payload) => {
-            let is_tool_error = is_tool_error_payload(&payload);
+            let output = output.map(|mut output| {
+                output.validate(
+                    app.mcp
+                        .server(&request.server_id)
+                        .and_then(|server| server.tool_output_schema(&tool_name)),
+                );
+                output
+            });
+            let is_tool_error = match output.as_ref() {
+                Some(output) => output.is_error,
+                None => is_tool_error_payload(&payload),
+            };
+            let payload = match output.as_ref() {
+                Some(output) => output.render_text(),
+                None => payload,
+            };
             let mut meta = ToolResultMeta::new(
                 server_label,
                 Some(request.server_id.clone()),
                 tool_call_id.clone(),
                 Some(request.raw_arguments.clone()),
             );
+            meta.output = output;
+            meta.running_line_id = Some(running_line_id);
             if is_tool_error {
                 meta.failure_kind = Some(ToolFailureKind::ToolError);
             }
-            super::record_tool_result(
-                app,
-                &tool_name,
-                meta,
-                payload,
-                if is_tool_error {
-                    crate::core::app::session::ToolResultStatus::Error
-                } else {
-                    crate::core::app::session::ToolResultStatus::Success
-                },
-                ctx,
-            );
+            let status = if is_tool_error {
+                ToolResultStatus::Error
+            } else {
+                ToolResultStatus::Success
+            };
+            audit_tool_call(app, &request, decision, status, latency, &payload);
+            super::record_tool_result(app, &tool_name, meta, payload, status, ctx);
         }
         Err(err) => {
             let mut meta = ToolResultMeta::new(
@@ -174,20 +262,50 @@ pub(super) fn handle_tool_call_completed(
                 Some(request.raw_arguments.clone()),
             );
             meta.failure_kind = Some(ToolFailureKind::ToolCallFailure);
-            super::record_tool_result(
+            meta.running_line_id = Some(running_line_id);
+            let payload = format!("Tool call failure: {err}");
+            audit_tool_call(
                 app,
-                &tool_name,
-                meta,
-                format!("Tool call failure: {err}"),
-                crate::core::app::session::ToolResultStatus::Error,
-                ctx,
+                &request,
+                decision,
+                ToolResultStatus::Error,
+                latency,
+                &payload,
             );
+            super::record_tool_result(app, &tool_name, meta, payload, ToolResultStatus::Error, ctx);
         }
     }
 
     super::advance_tool_queue(app, ctx)
 }
 
+/// Appends the call to the MCP audit log when it is enabled. Write failures
+/// are logged and otherwise ignored so they never interrupt the chat.
+pub(super) fn audit_tool_call(
+    app: &App,
+    request: &ToolCallRequest,
+    decision: AuditDecision,
+    status: ToolResultStatus,
+    latency: Option<Duration>,
+    payload: &str,
+) {
+    let Some(audit) = app.mcp_audit.as_ref() else {
+        return;
+    };
+    let entry = ToolAuditEntry::new(
+        &request.server_id,
+        &request.tool_name,
+        &request.raw_arguments,
+        decision,
+        status.into(),
+        latency,
+        payload,
+    );
+    if let Err(err) = audit.append(&entry) {
+        debug!(server_id = %request.server_id, tool = %request.tool_name, error = %err, "Failed to write MCP audit entry");
+    }
+}
+
 fn is_tool_error_payload(payload: &str) -> bool {
     serde_json::from_str::<Value>(payload)
         .ok()
@@ -198,7 +316,7 @@ fn is_tool_error_payload(payload: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::app::session::{ToolCallRequest, ToolResultStatus};
+    use crate::mcp::audit::{AuditStatus, ToolAuditLog};
     use crate::utils::test_utils::create_test_app;
 
     fn default_ctx() -> AppActionContext {
@@ -212,13 +330,18 @@ mod tests {
     fn tool_call_completed_flags_tool_error_payloads() {
         let mut app = create_test_app();
         let ctx = default_ctx();
-        app.session.tool_pipeline.active_tool_request = Some(ToolCallRequest {
-            server_id: "alpha".to_string(),
-            tool_name: "lookup".to_string(),
-            arguments: None,
-            raw_arguments: "{}".to_string(),
-            tool_call_id: Some("call-1".to_string()),
-        });
+        super::super::mark_tool_running(
+            &mut app,
+            ToolCallRequest {
+                server_id: "alpha".to_string(),
+                tool_name: "lookup".to_string(),
+                arguments: None,
+                raw_arguments: "{}".to_string(),
+                tool_call_id: Some("call-1".to_string()),
+            },
+            AuditDecision::AllowOnce,
+            ctx,
+        );
 
         let payload = serde_json::json!({"content": [], "isError": true}).to_string();
         let result = handle_tool_call_completed(
@@ -226,6 +349,7 @@ mod tests {
             "lookup".to_string(),
             Some("call-1".to_string()),
             Ok(payload),
+            None,
             ctx,
         );
         assert!(result.is_none());
@@ -240,6 +364,75 @@ mod tests {
         assert_eq!(record.failure_kind, Some(ToolFailureKind::ToolError));
     }
 
+    #[test]
+    fn tool_call_completed_keeps_image_blocks_for_vision_models() {
+        use rust_mcp_schema::{CallToolResult, ContentBlock, ImageContent, TextContent};
+
+        let mut app = create_test_app();
+        app.config.vision_models = vec!["gpt-4o*".to_string()];
+        app.session.model = "gpt-4o-mini".to_string();
+        super::super::mark_tool_running(
+            &mut app,
+            ToolCallRequest {
+                server_id: "alpha".to_string(),
+                tool_name: "chart".to_string(),
+                arguments: None,
+                raw_arguments: "{}".to_string(),
+                tool_call_id: Some("call-1".to_string()),
+            },
+            AuditDecision::AllowOnce,
+            default_ctx(),
+        );
+        let output = McpToolOutput::from_result(CallToolResult {
+            content: vec![
+                ContentBlock::TextContent(TextContent::new("Done.".to_string(), None, None)),
+                ContentBlock::ImageContent(ImageContent::new(
+                    "iVBORw0KGgo=".to_string(),
+                    "image/png".to_string(),
+                    None,
+                    None,
+                )),
+            ],
+            is_error: None,
+            meta: None,
+            structured_content: None,
+        });
+
+        handle_tool_call_completed(
+            &mut app,
+            "chart".to_string(),
+            Some("call-1".to_string()),
+            Ok("{}".to_string()),
+            Some(output),
+            default_ctx(),
+        );
+
+        let record = app
+            .session
+            .tool_pipeline
+            .tool_result_history
+            .last()
+            .expect("record");
+        assert_eq!(record.content, "Done.\n\n[image: image/png, 8 bytes]");
+        assert_eq!(record.output.as_ref().map(|o| o.content.len()), Some(2));
+        assert!(app
+            .ui
+            .messages
+            .back()
+            .is_some_and(|message| message.content.ends_with("(success) · 1 image")));
+
+        let message = super::super::tool_image_message(&app).expect("image message");
+        let serialized = serde_json::to_value(&message).expect("serialize");
+        assert_eq!(serialized["role"], "user");
+        assert_eq!(
+            serialized["content"][1]["image_url"]["url"],
+            "data:image/png;base64,iVBORw0KGgo="
+        );
+
+        app.session.model = "text-only".to_string();
+        assert!(super::super::tool_image_message(&app).is_none());
+    }
+
     #[test]
     fn tool_call_completed_ignores_stale_completion_without_active_request() {
         let mut app = create_test_app();
@@ -252,6 +445,7 @@ mod tests {
             "lookup".to_string(),
             Some("call-old".to_string()),
             Ok("{\"ok\":true}".to_string()),
+            None,
             ctx,
         );
 
@@ -280,6 +474,7 @@ mod tests {
             "lookup".to_string(),
             Some("call-stale".to_string()),
             Ok("{\"ok\":true}".to_string()),
+            None,
             ctx,
         );
 
@@ -294,4 +489,48 @@ mod tests {
             Some("call-current")
         );
     }
+
+    #[test]
+    fn tool_calls_are_written_to_the_audit_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = ToolAuditLog::new(dir.path().join("mcp-audit.jsonl"));
+        let mut app = create_test_app();
+        app.mcp_audit = Some(log.clone());
+        let ctx = default_ctx();
+        let request = |id: &str| ToolCallRequest {
+            server_id: "alpha".to_string(),
+            tool_name: "lookup".to_string(),
+            arguments: None,
+            raw_arguments: r#"{"q":"rust"}"#.to_string(),
+            tool_call_id: Some(id.to_string()),
+        };
+
+        super::super::mark_tool_running(
+            &mut app,
+            request("call-1"),
+            AuditDecision::PolicyAllow,
+            ctx,
+        );
+        handle_tool_call_completed(
+            &mut app,
+            "lookup".to_string(),
+            Some("call-1".to_string()),
+            Err("timed out".to_string()),
+            None,
+            ctx,
+        );
+        app.session.tool_pipeline.active_tool_request = Some(request("call-2"));
+        handle_tool_permission_decision(&mut app, ToolPermissionDecision::DenyOnce, ctx);
+
+        let entries = log.read_entries().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].decision, AuditDecision::PolicyAllow);
+        assert_eq!(entries[0].status, AuditStatus::Error);
+        assert_eq!(entries[0].arguments["q"], "rust");
+        assert!(entries[0].latency_ms.is_some());
+        assert_eq!(entries[0].payload, "Tool call failure: timed out");
+        assert_eq!(entries[1].decision, AuditDecision::DenyOnce);
+        assert_eq!(entries[1].status, AuditStatus::Denied);
+        assert_eq!(entries[1].latency_ms, None);
+    }
 }
@@ -73,12 +73,16 @@ impl App {
             mcp_disabled: false,
             active_character: None,
             character_greeting_shown: false,
+            greeting_index: 0,
             has_received_assistant_message: false,
             tool_pipeline: crate::core::app::session::ToolPipelineState::default(),
             mcp_init: crate::core::app::session::McpInitState::default(),
             active_assistant_message_index: None,
             mcp_tools_enabled: false,
             mcp_tools_unsupported: false,
+            active_lore_entries: Vec::new(),
+            group: None,
+            macros: crate::core::macros::MacroState::default(),
         };
 
         let ui = UiState::new_basic(theme, markdown_enabled, syntax_enabled, None);
@@ -102,6 +106,7 @@ impl App {
             config: test_config,
             mcp,
             mcp_permissions: crate::mcp::permissions::ToolPermissionStore::default(),
+            mcp_audit: None,
         }
     }
 
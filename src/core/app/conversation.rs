@@ -20,6 +20,8 @@
 
 use super::{session::PendingToolCall, session::SessionContext, ui_state::UiState};
 use crate::character::card::CharacterCard;
+use crate::character::group::speaker_prefix;
+use crate::core::macros::MacroContext;
 use crate::core::message::{AppMessageKind, Message, TranscriptRole};
 use crate::utils::scroll::ScrollCalculator;
 use serde_json::Value;
@@ -50,35 +52,169 @@ impl<'a> ConversationController<'a> {
         }
     }
 
+    /// Values for the template macros in cards, personas and presets
+    fn macro_context(&self) -> MacroContext<'_> {
+        let last_message = self
+            .ui
+            .messages
+            .iter()
+            .rev()
+            .filter(|msg| msg.is_user() || msg.is_assistant())
+            .map(|msg| msg.content.as_str())
+            .find(|content| !content.trim().is_empty());
+        MacroContext {
+            user: self
+                .persona_manager
+                .get_active_persona()
+                .map(|p| p.display_name.as_str()),
+            char: self
+                .session
+                .get_character()
+                .map(|character| character.display_name()),
+            model: Some(self.session.model.as_str()),
+            last_message: Some(last_message.unwrap_or_default()),
+            state: Some(&self.session.macros),
+        }
+    }
+
     /// Apply persona modifications to a system prompt
     /// Returns the modified prompt if a persona is active, otherwise returns the original
-    fn apply_persona_to_system_prompt(&self, base_prompt: &str, char_name: Option<&str>) -> String {
+    fn apply_persona_to_system_prompt(&self, base_prompt: &str) -> String {
         self.persona_manager
-            .get_modified_system_prompt(base_prompt, char_name)
+            .get_modified_system_prompt(base_prompt, &self.macro_context())
     }
 
     fn apply_preset_to_messages(&self, messages: &mut Vec<crate::api::ChatMessage>) {
-        let char_name = self
-            .session
-            .get_character()
-            .map(|character| character.data.name.as_str());
-        self.preset_manager
-            .apply_to_messages(messages, self.persona_manager, char_name);
+        self.preset_manager.apply_to_messages(
+            messages,
+            self.persona_manager,
+            &self.macro_context(),
+        );
     }
 
     fn character_greeting_text(&self) -> Option<String> {
         let character = self.session.get_character()?;
-        let user_name = self
-            .persona_manager
-            .get_active_persona()
-            .map(|p| p.display_name.as_str());
-        let char_name = Some(character.data.name.as_str());
-        let greeting = character.get_greeting_with_substitutions(user_name, char_name);
+        let macros = self.macro_context();
+        let greeting = character
+            .get_greeting_at_with_substitutions(self.session.greeting_index, &macros)
+            .unwrap_or_else(|| character.get_greeting_with_substitutions(&macros));
 
         if greeting.trim().is_empty() {
             None
         } else {
-            Some(greeting)
+            Some(format!("{}{}", self.group_speaker_prefix(), greeting))
+        }
+    }
+
+    /// `Name: ` label for the current group speaker, or empty outside groups.
+    fn group_speaker_prefix(&self) -> String {
+        match (&self.session.group, self.session.get_character()) {
+            (Some(_), Some(character)) => speaker_prefix(character.display_name()),
+            _ => String::new(),
+        }
+    }
+
+    /// Have group member `index` reply next without a new user message and
+    /// return the API messages for the request.
+    pub fn add_group_reply(&mut self, index: usize) -> Vec<crate::api::ChatMessage> {
+        self.clear_status();
+        self.remove_trailing_empty_assistant_messages();
+        self.session.set_group_speaker(index);
+
+        self.add_assistant_placeholder();
+        let history_len = self.ui.messages.len().saturating_sub(1);
+        let assembled = self.assemble_api_messages(self.ui.messages.iter().take(history_len), None);
+        self.record_lore(assembled)
+    }
+
+    /// The active character's greetings with macros expanded, in
+    /// [`CharacterCard::greetings`] order. `{{setvar}}` in them takes effect
+    /// only once a greeting is chosen.
+    pub fn greeting_options(&self) -> Vec<String> {
+        let Some(character) = self.session.get_character() else {
+            return Vec::new();
+        };
+        let preview = self.session.macros.preview();
+        let macros = MacroContext {
+            state: Some(&preview),
+            ..self.macro_context()
+        };
+        (0..character.greetings().len())
+            .filter_map(|index| character.get_greeting_at_with_substitutions(index, &macros))
+            .collect()
+    }
+
+    /// Number of greetings the active character offers.
+    pub fn greeting_count(&self) -> usize {
+        self.session
+            .get_character()
+            .map(|character| character.greetings().len())
+            .unwrap_or(0)
+    }
+
+    /// Transcript index of the greeting while it can still be swapped: the
+    /// first assistant message, provided no user message has been sent.
+    fn swappable_greeting_index(&self) -> Option<usize> {
+        if !self.session.character_greeting_shown
+            || self.ui.messages.iter().any(|msg| msg.is_user())
+        {
+            return None;
+        }
+        self.ui.messages.iter().position(|msg| msg.is_assistant())
+    }
+
+    /// Replace the opening greeting with greeting `index` (0-based) and
+    /// return the number of greetings available.
+    pub fn select_greeting(&mut self, index: usize) -> Result<usize, String> {
+        let count = self.greeting_count();
+        if count == 0 {
+            return Err("No character is active.".to_string());
+        }
+        if index >= count {
+            return Err(format!(
+                "Greeting {} does not exist ({} available).",
+                index + 1,
+                count
+            ));
+        }
+        let Some(message_index) = self.swappable_greeting_index() else {
+            return Err("The greeting can only be changed before your first message.".to_string());
+        };
+
+        self.session.greeting_index = index;
+        let greeting = self.character_greeting_text().unwrap_or_default();
+        if let Some(msg) = self.ui.messages.get_mut(message_index) {
+            msg.content = greeting;
+        }
+        self.ui.invalidate_prewrap_cache();
+        Ok(count)
+    }
+
+    /// Advance to the next greeting when retrying before the first user
+    /// message, so retry swipes through the alternates.
+    fn swipe_greeting_for_retry(&mut self) {
+        let count = self.greeting_count();
+        if count > 1 && !self.ui.messages.iter().any(|msg| msg.is_user()) {
+            self.session.greeting_index = (self.session.greeting_index + 1) % count;
+        }
+    }
+
+    /// In a group, make the author of the reply being retried speak again.
+    fn select_group_speaker_for_retry(&mut self) {
+        let Some(group) = self.session.group.as_ref() else {
+            return;
+        };
+        let target = self.session.retrying_message_index.or_else(|| {
+            self.ui
+                .messages
+                .iter()
+                .rposition(|msg| msg.is_assistant() && !msg.content.is_empty())
+        });
+        if let Some(speaker) = target
+            .and_then(|index| self.ui.messages.get(index))
+            .and_then(|msg| group.speaker_of(&msg.content))
+        {
+            self.session.set_group_speaker(speaker);
         }
     }
 
@@ -105,6 +241,15 @@ impl<'a> ConversationController<'a> {
         self.session.has_received_assistant_message = false;
         self.session.character_greeting_shown = false;
         self.session.tool_pipeline.reset();
+        self.session.macros.clear_vars();
+    }
+
+    /// Replace the transcript with an earlier conversation, such as an
+    /// imported chat. The character greeting is treated as already shown.
+    pub fn load_history(&mut self, messages: Vec<Message>) {
+        self.clear_transcript();
+        self.ui.messages.extend(messages);
+        self.session.mark_greeting_shown();
     }
 
     pub fn remove_trailing_empty_assistant_messages(&mut self) {
@@ -128,34 +273,45 @@ impl<'a> ConversationController<'a> {
         }
     }
 
+    /// Builds the API message list for `history`, returning it with the
+    /// labels of the lorebook entries it activated.
     fn assemble_api_messages<'m, I>(
         &self,
         history: I,
         additional_system_prompt: Option<String>,
-    ) -> Vec<crate::api::ChatMessage>
+    ) -> (Vec<crate::api::ChatMessage>, Vec<String>)
     where
         I: Iterator<Item = &'m Message>,
     {
         let mut api_messages = Vec::new();
+        let history: Vec<&Message> = history.collect();
+        let mut lore_labels = Vec::new();
 
         let character = self.session.get_character();
 
         let base_system_prompt = if let Some(character) = character {
-            let user_name = self
-                .persona_manager
-                .get_active_persona()
-                .map(|p| p.display_name.as_str());
-            let char_name = Some(character.data.name.as_str());
-            character.build_system_prompt_with_substitutions(user_name, char_name)
+            let scanned: Vec<&str> = history
+                .iter()
+                .filter(|msg| msg.is_user() || msg.is_assistant())
+                .map(|msg| msg.content.as_str())
+                .filter(|content| !content.trim().is_empty())
+                .collect();
+            let lore = character.active_lore_entries(&scanned);
+            lore_labels = lore.iter().map(|entry| entry.label()).collect();
+            character.build_system_prompt_with_substitutions(&self.macro_context(), &lore)
         } else {
             "".to_string()
         };
 
-        let char_name = character.map(|c| c.data.name.as_str());
-        let modified_system_prompt =
-            self.apply_persona_to_system_prompt(&base_system_prompt, char_name);
+        let modified_system_prompt = self.apply_persona_to_system_prompt(&base_system_prompt);
 
         let mut final_system_prompt = modified_system_prompt;
+        if let Some(instruction) = self.group_turn_instruction() {
+            if !final_system_prompt.is_empty() {
+                final_system_prompt.push_str("\n\n");
+            }
+            final_system_prompt.push_str(&instruction);
+        }
         if let Some(additional) = additional_system_prompt {
             if !final_system_prompt.is_empty() {
                 final_system_prompt.push_str("\n\n");
@@ -170,6 +326,8 @@ impl<'a> ConversationController<'a> {
                 name: None,
                 tool_call_id: None,
                 tool_calls: None,
+                images: Vec::new(),
+                is_prefill: false,
             });
         }
 
@@ -185,6 +343,8 @@ impl<'a> ConversationController<'a> {
                     name: None,
                     tool_call_id: None,
                     tool_calls: None,
+                    images: Vec::new(),
+                    is_prefill: false,
                 });
             }
         }
@@ -197,11 +357,34 @@ impl<'a> ConversationController<'a> {
 
         self.apply_preset_to_messages(&mut api_messages);
 
+        (api_messages, lore_labels)
+    }
+
+    fn group_turn_instruction(&self) -> Option<String> {
+        let group = self.session.group.as_ref()?;
+        let speaker = group.position(&self.session.get_character()?.data.name)?;
+        let user_name = self
+            .persona_manager
+            .get_active_persona()
+            .map(|p| p.display_name.as_str())
+            .unwrap_or("the user");
+        group.turn_instruction(speaker, user_name)
+    }
+
+    /// Remembers which lorebook entries an assembled request used, for the
+    /// character inspect view, and returns its messages.
+    fn record_lore(
+        &mut self,
+        assembled: (Vec<crate::api::ChatMessage>, Vec<String>),
+    ) -> Vec<crate::api::ChatMessage> {
+        let (api_messages, lore_labels) = assembled;
+        self.session.active_lore_entries = lore_labels;
         api_messages
     }
 
-    pub fn api_messages_from_history(&self) -> Vec<crate::api::ChatMessage> {
-        self.assemble_api_messages(self.ui.messages.iter(), None)
+    pub fn api_messages_from_history(&mut self) -> Vec<crate::api::ChatMessage> {
+        let assembled = self.assemble_api_messages(self.ui.messages.iter(), None);
+        self.record_lore(assembled)
     }
 
     pub fn add_message(&mut self, message: Message) {
@@ -231,6 +414,16 @@ impl<'a> ConversationController<'a> {
         }
 
         self.ui.messages.push_back(user_message);
+        self.session.macros.record_user_message();
+
+        if let Some(speaker) = self
+            .session
+            .group
+            .as_ref()
+            .and_then(|group| group.next_speaker(Some(&content)))
+        {
+            self.session.set_group_speaker(speaker);
+        }
 
         let assistant_message = Message::new(TranscriptRole::Assistant, String::new());
         self.ui.messages.push_back(assistant_message);
@@ -242,7 +435,8 @@ impl<'a> ConversationController<'a> {
         self.session.original_refining_content = None;
 
         let history_len = self.ui.messages.len().saturating_sub(1);
-        self.assemble_api_messages(self.ui.messages.iter().take(history_len), None)
+        let assembled = self.assemble_api_messages(self.ui.messages.iter().take(history_len), None);
+        self.record_lore(assembled)
     }
 
     pub fn add_assistant_placeholder(&mut self) {
@@ -318,6 +512,19 @@ impl<'a> ConversationController<'a> {
         self.ui.messages.push_back(Message::tool_result(content));
     }
 
+    /// Add a tool call's "running" line and return the ID it can be found by
+    /// once the call finishes.
+    pub fn add_running_tool_message(&mut self, content: String) -> u64 {
+        let pipeline = &mut self.session.tool_pipeline;
+        pipeline.last_running_line_id += 1;
+        let id = pipeline.last_running_line_id;
+        self.ui.messages.push_back(Message {
+            id: Some(id),
+            ..Message::tool_result(content)
+        });
+        id
+    }
+
     pub fn set_status<S: Into<String>>(&mut self, s: S) {
         self.ui.status = Some(s.into());
         self.ui.status_set_at = Some(Instant::now());
@@ -339,18 +546,27 @@ impl<'a> ConversationController<'a> {
             self.session.is_refining = false; // consume the flag
         }
 
-        if let Some(retry_index) = self.session.retrying_message_index {
-            if let Some(msg) = self.ui.messages.get_mut(retry_index) {
-                if msg.is_assistant() {
-                    if is_first_refine_chunk {
-                        msg.content.clear();
-                    }
+        let prefix = self.group_speaker_prefix();
+        let target = match self.session.retrying_message_index {
+            Some(retry_index) => self.ui.messages.get_mut(retry_index),
+            None => self.ui.messages.back_mut(),
+        };
+        if let Some(msg) = target.filter(|msg| msg.is_assistant()) {
+            if is_first_refine_chunk && self.session.retrying_message_index.is_some() {
+                msg.content.clear();
+            }
+            if prefix.is_empty() {
+                msg.content.push_str(content);
+            } else {
+                if msg.content.is_empty() {
+                    msg.content.push_str(&prefix);
+                }
+                if msg.content == prefix {
+                    msg.content.push_str(content.trim_start());
+                } else {
                     msg.content.push_str(content);
                 }
-            }
-        } else if let Some(last_msg) = self.ui.messages.back_mut() {
-            if last_msg.is_assistant() {
-                last_msg.content.push_str(content);
+                strip_repeated_speaker_label(&mut msg.content, &prefix);
             }
         }
 
@@ -424,7 +640,12 @@ impl<'a> ConversationController<'a> {
             }
 
             // Then log the new response
-            if let Err(e) = self.session.logging.log_message(&self.ui.current_response) {
+            let prefix = self.group_speaker_prefix();
+            let mut logged = format!("{prefix}{}", self.ui.current_response);
+            if !prefix.is_empty() {
+                strip_repeated_speaker_label(&mut logged, &prefix);
+            }
+            if let Err(e) = self.session.logging.log_message(&logged) {
                 self.add_app_message(
                     AppMessageKind::Warning,
                     format!("Logging error: {}. Response may not be saved to log.", e),
@@ -478,6 +699,7 @@ impl<'a> ConversationController<'a> {
         }
 
         self.session.last_retry_time = Instant::now();
+        self.select_group_speaker_for_retry();
 
         if let Some(retry_index) = self.session.retrying_message_index {
             if retry_index < self.ui.messages.len() {
@@ -486,6 +708,7 @@ impl<'a> ConversationController<'a> {
                     .tool_pipeline
                     .prune_for_assistant_index(retry_index);
                 if !self.session.has_received_assistant_message {
+                    self.swipe_greeting_for_retry();
                     if let Some(greeting) = self.character_greeting_text() {
                         if let Some(msg) = self.ui.messages.get_mut(retry_index) {
                             if msg.is_assistant() {
@@ -517,6 +740,7 @@ impl<'a> ConversationController<'a> {
 
             if let Some(index) = target_index {
                 if !self.session.has_received_assistant_message {
+                    self.swipe_greeting_for_retry();
                     if let Some(greeting) = self.character_greeting_text() {
                         if let Some(msg) = self.ui.messages.get_mut(index) {
                             if msg.is_assistant() {
@@ -559,10 +783,9 @@ impl<'a> ConversationController<'a> {
 
         let retry_index = self.session.retrying_message_index?;
 
-        let api_messages =
-            self.assemble_api_messages(self.ui.messages.iter().take(retry_index), None);
+        let assembled = self.assemble_api_messages(self.ui.messages.iter().take(retry_index), None);
 
-        Some(api_messages)
+        Some(self.record_lore(assembled))
     }
 
     fn post_history_system_message(
@@ -570,14 +793,8 @@ impl<'a> ConversationController<'a> {
         character: &CharacterCard,
     ) -> Option<crate::api::ChatMessage> {
         // Apply persona substitutions to post-history instructions
-        let user_name = self
-            .persona_manager
-            .get_active_persona()
-            .map(|p| p.display_name.as_str());
-        let char_name = Some(character.data.name.as_str());
-
         character
-            .get_post_history_instructions_with_substitutions(user_name, char_name)
+            .get_post_history_instructions_with_substitutions(&self.macro_context())
             .and_then(|instructions| {
                 let trimmed = instructions.trim();
                 if trimmed.is_empty() {
@@ -589,6 +806,8 @@ impl<'a> ConversationController<'a> {
                         name: None,
                         tool_call_id: None,
                         tool_calls: None,
+                        images: Vec::new(),
+                        is_prefill: false,
                     })
                 }
             })
@@ -607,7 +826,8 @@ impl<'a> ConversationController<'a> {
         additional_system_prompt: Option<String>,
     ) -> crate::core::chat_stream::StreamParams {
         let (cancel_token, stream_id) = self.start_new_stream_headless();
-        let api_messages = self.assemble_api_messages(messages.iter(), additional_system_prompt);
+        let assembled = self.assemble_api_messages(messages.iter(), additional_system_prompt);
+        let api_messages = self.record_lore(assembled);
         crate::core::chat_stream::StreamParams {
             api_messages,
             client: self.session.client.clone(),
@@ -707,15 +927,17 @@ impl<'a> ConversationController<'a> {
             }
 
             let instructions = self.session.refine_instructions.clone();
+            let assembled = self.assemble_api_messages(history_for_api.iter(), Some(instructions));
+            let mut api_messages = self.record_lore(assembled);
             let prefix = self.session.refine_prefix.as_str();
-            let mut api_messages =
-                self.assemble_api_messages(history_for_api.iter(), Some(instructions));
             api_messages.push(crate::api::ChatMessage {
                 role: "user".to_string(),
                 content: format!("{} {}", prefix, prompt),
                 name: None,
                 tool_call_id: None,
                 tool_calls: None,
+                images: Vec::new(),
+                is_prefill: false,
             });
             Some(api_messages)
         } else {
@@ -724,6 +946,18 @@ impl<'a> ConversationController<'a> {
     }
 }
 
+/// Drops a `Name:` label the model repeated after the transcript's own one.
+fn strip_repeated_speaker_label(content: &mut String, prefix: &str) {
+    let label = prefix.trim_end();
+    let Some(rest) = content.strip_prefix(prefix) else {
+        return;
+    };
+    if let Some(after) = rest.strip_prefix(label) {
+        let repeated = rest.len() - after.trim_start().len();
+        content.replace_range(prefix.len()..prefix.len() + repeated, "");
+    }
+}
+
 fn summarize_tool_call_arguments(raw: &str) -> Option<String> {
     if raw.is_empty() {
         return None;
@@ -946,6 +1180,7 @@ mod tests {
         app.ui.messages.push_back(Message {
             role: TranscriptRole::User,
             content: "Test question".to_string(),
+            id: None,
         });
 
         {
@@ -964,6 +1199,7 @@ mod tests {
         app.ui.messages.push_back(Message {
             role: TranscriptRole::Assistant,
             content: "Test response".to_string(),
+            id: None,
         });
 
         app.session.retrying_message_index = Some(2);
@@ -1012,6 +1248,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -1056,6 +1293,63 @@ mod tests {
         assert_eq!(api_messages[4].content, "Always be polite.");
     }
 
+    #[test]
+    fn test_add_user_message_injects_activated_lorebook_entries() {
+        use crate::character::card::{CharacterCard, CharacterData};
+        use crate::character::lorebook::CharacterBook;
+
+        let mut app = create_test_app();
+        let book: CharacterBook = serde_json::from_value(serde_json::json!({
+            "entries": [
+                { "keys": ["castle"], "content": "The castle sits on a cliff.",
+                  "name": "Castle", "position": "before_char" },
+                { "keys": ["dragon"], "content": "Dragons are extinct.", "name": "Dragon" }
+            ]
+        }))
+        .unwrap();
+        let character = CharacterCard {
+            spec: "chara_card_v2".to_string(),
+            spec_version: "2.0".to_string(),
+            data: CharacterData {
+                name: "TestBot".to_string(),
+                description: "A test character".to_string(),
+                personality: String::new(),
+                scenario: String::new(),
+                first_mes: "Hi!".to_string(),
+                mes_example: String::new(),
+                creator_notes: None,
+                system_prompt: None,
+                post_history_instructions: None,
+                alternate_greetings: None,
+                tags: None,
+                creator: None,
+                character_version: None,
+                character_book: Some(book),
+                ..Default::default()
+            },
+        };
+        app.session.set_character(character);
+
+        let api_messages = {
+            let mut conversation = ConversationController::new(
+                &mut app.session,
+                &mut app.ui,
+                &app.persona_manager,
+                &app.preset_manager,
+            );
+            conversation.add_user_message("Tell me about the castle".to_string())
+        };
+
+        let system = &api_messages[0].content;
+        assert!(system.contains("The castle sits on a cliff."));
+        assert!(!system.contains("Dragons are extinct."));
+        assert!(
+            system.find("The castle sits on a cliff.").unwrap()
+                < system.find("Character: TestBot").unwrap()
+        );
+        assert_eq!(app.session.active_lore_entries, vec!["Castle".to_string()]);
+    }
+
     #[test]
     fn test_persona_bio_char_placeholder_with_active_character() {
         use crate::character::card::{CharacterCard, CharacterData};
@@ -1093,6 +1387,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -1252,6 +1547,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -1322,6 +1618,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -1432,6 +1729,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -1469,6 +1767,64 @@ mod tests {
         assert!(!app.session.has_received_assistant_message);
     }
 
+    #[test]
+    fn test_greeting_swipe_and_select_before_first_user_message() {
+        use crate::character::card::{CharacterCard, CharacterData};
+
+        let mut app = create_test_app();
+        let character = CharacterCard {
+            spec: "chara_card_v2".to_string(),
+            spec_version: "2.0".to_string(),
+            data: CharacterData {
+                name: "TestBot".to_string(),
+                description: "A test character".to_string(),
+                personality: "Helpful".to_string(),
+                scenario: "Testing".to_string(),
+                first_mes: "Hello!".to_string(),
+                mes_example: "".to_string(),
+                creator_notes: None,
+                system_prompt: None,
+                post_history_instructions: None,
+                alternate_greetings: Some(vec![
+                    "Welcome back, {{user}}.".to_string(),
+                    "Oh, it's you.".to_string(),
+                ]),
+                tags: None,
+                creator: None,
+                character_version: None,
+                ..Default::default()
+            },
+        };
+        app.session.set_character(character);
+
+        let mut conversation = ConversationController::new(
+            &mut app.session,
+            &mut app.ui,
+            &app.persona_manager,
+            &app.preset_manager,
+        );
+        conversation.show_character_greeting_if_needed();
+        assert_eq!(conversation.greeting_count(), 3);
+
+        assert!(conversation.prepare_retry(10, 80).is_none());
+        assert_eq!(conversation.ui.messages[0].content, "Welcome back, Anon.");
+        assert!(conversation.prepare_retry(10, 80).is_none());
+        assert!(conversation.prepare_retry(10, 80).is_none());
+        assert_eq!(conversation.ui.messages[0].content, "Hello!");
+
+        assert_eq!(conversation.select_greeting(2), Ok(3));
+        assert_eq!(conversation.ui.messages[0].content, "Oh, it's you.");
+        assert_eq!(conversation.ui.messages.len(), 1);
+        assert!(conversation.select_greeting(3).is_err());
+
+        conversation.add_user_message("Hi".to_string());
+        assert_eq!(
+            conversation.select_greeting(0),
+            Err("The greeting can only be changed before your first message.".to_string())
+        );
+        assert_eq!(conversation.session.greeting_index, 2);
+    }
+
     #[test]
     fn test_retry_character_greeting_updates_after_persona_change() {
         use crate::character::card::{CharacterCard, CharacterData};
@@ -1515,6 +1871,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -1579,6 +1936,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -1664,6 +2022,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -1705,6 +2064,39 @@ mod tests {
         assert_eq!(app.ui.messages.len(), 1);
     }
 
+    #[test]
+    fn test_load_history_replaces_transcript_and_skips_greeting() {
+        use crate::character::card::{CharacterCard, CharacterData};
+
+        let mut app = create_test_app();
+        app.session.set_character(CharacterCard {
+            spec: "chara_card_v2".to_string(),
+            spec_version: "2.0".to_string(),
+            data: CharacterData {
+                name: "TestBot".to_string(),
+                first_mes: "Hello! I'm TestBot.".to_string(),
+                ..Default::default()
+            },
+        });
+        app.conversation()
+            .add_app_message(AppMessageKind::Info, "Old".to_string());
+
+        app.conversation().load_history(vec![
+            Message::new(TranscriptRole::Assistant, "Welcome back."),
+            Message::new(TranscriptRole::User, "Thanks!"),
+        ]);
+        app.conversation().show_character_greeting_if_needed();
+
+        let transcript: Vec<&str> = app
+            .ui
+            .messages
+            .iter()
+            .map(|msg| msg.content.as_str())
+            .collect();
+        assert_eq!(transcript, vec!["Welcome back.", "Thanks!"]);
+        assert!(app.session.character_greeting_shown);
+    }
+
     #[test]
     fn test_show_character_greeting_empty_greeting() {
         use crate::character::card::{CharacterCard, CharacterData};
@@ -1720,7 +2112,8 @@ mod tests {
                 description: "A test character".to_string(),
                 personality: "Helpful".to_string(),
                 scenario: "Testing".to_string(),
-                first_mes: "   ".to_string(), // Empty/whitespace greeting
+                first_mes: "   ".to_string(),
+                // Empty/whitespace greeting
                 mes_example: "".to_string(),
                 creator_notes: None,
                 system_prompt: None,
@@ -1729,6 +2122,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -1814,6 +2208,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -1865,6 +2260,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -1937,6 +2333,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -1977,6 +2374,158 @@ mod tests {
         assert_eq!(api_messages[2].content, "Hello");
     }
 
+    fn group_member(name: &str) -> CharacterCard {
+        use crate::character::card::CharacterData;
+
+        CharacterCard {
+            spec: "chara_card_v2".to_string(),
+            spec_version: "2.0".to_string(),
+            data: CharacterData {
+                name: name.to_string(),
+                first_mes: format!("{name} waves."),
+                system_prompt: Some(format!("You are {name}.")),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_group_chat_round_robin_labels_replies() {
+        use crate::character::group::GroupChat;
+
+        let mut app = create_test_app();
+        let alice = group_member("Alice");
+        app.session.set_character(alice.clone());
+        app.session.group = Some(GroupChat::new(vec![alice, group_member("Bob")]));
+
+        let mut conversation = ConversationController::new(
+            &mut app.session,
+            &mut app.ui,
+            &app.persona_manager,
+            &app.preset_manager,
+        );
+        conversation.show_character_greeting_if_needed();
+
+        let api_messages = conversation.add_user_message("Hi all".to_string());
+        assert!(api_messages[0].content.contains("You are Alice."));
+        assert!(api_messages[0]
+            .content
+            .contains("Write only Alice's next reply"));
+        assert_eq!(api_messages[1].content, "Alice: Alice waves.");
+
+        // The model repeating the label is tolerated.
+        conversation.append_to_response(" Alice:", 10, 80);
+        conversation.append_to_response(" Hello!", 10, 80);
+        conversation.finalize_response();
+        assert_eq!(
+            conversation.ui.messages.back().unwrap().content,
+            "Alice: Hello!"
+        );
+
+        let api_messages = conversation.add_user_message("And you?".to_string());
+        assert!(api_messages[0].content.contains("You are Bob."));
+        assert_eq!(api_messages[3].role, "assistant");
+        assert_eq!(api_messages[3].content, "Alice: Hello!");
+        conversation.append_to_response("Likewise.", 10, 80);
+        conversation.finalize_response();
+        assert_eq!(
+            conversation.ui.messages.back().unwrap().content,
+            "Bob: Likewise."
+        );
+    }
+
+    #[test]
+    fn test_group_reply_and_retry_keep_the_speaker() {
+        use crate::character::group::GroupChat;
+
+        let mut app = create_test_app();
+        let alice = group_member("Alice");
+        app.session.set_character(alice.clone());
+        app.session.group = Some(GroupChat::new(vec![alice, group_member("Bob")]));
+        app.ui.messages.push_back(create_test_message("user", "Hi"));
+        app.ui
+            .messages
+            .push_back(create_test_message("assistant", "Alice: Hello."));
+        app.session.has_received_assistant_message = true;
+
+        let mut conversation = ConversationController::new(
+            &mut app.session,
+            &mut app.ui,
+            &app.persona_manager,
+            &app.preset_manager,
+        );
+        let api_messages = conversation.add_group_reply(1);
+        assert!(api_messages[0].content.contains("You are Bob."));
+        assert_eq!(api_messages.last().unwrap().content, "Alice: Hello.");
+        conversation.append_to_response("Hey.", 10, 80);
+        conversation.finalize_response();
+        assert_eq!(
+            conversation.ui.messages.back().unwrap().content,
+            "Bob: Hey."
+        );
+
+        conversation.session.retrying_message_index = Some(1);
+        conversation.session.set_group_speaker(1);
+        let api_messages = conversation.prepare_retry(10, 80).unwrap();
+        assert!(api_messages[0].content.contains("You are Alice."));
+        conversation.append_to_response("Hi again.", 10, 80);
+        assert_eq!(conversation.ui.messages[1].content, "Alice: Hi again.");
+    }
+
+    #[test]
+    fn test_macros_use_session_model_variables_and_last_message() {
+        let mut app = create_test_app();
+        app.session.model = "test-model".to_string();
+        let mut character = group_member("Alice");
+        character.data.system_prompt =
+            Some("You are {{char}} on {{model}}.{{setvar::mood::curious}}".to_string());
+        character.data.post_history_instructions =
+            Some("Stay {{getvar::mood}}. Reply to: {{lastMessage}}".to_string());
+        app.session.set_character(character);
+
+        let mut conversation = ConversationController::new(
+            &mut app.session,
+            &mut app.ui,
+            &app.persona_manager,
+            &app.preset_manager,
+        );
+        let api_messages = conversation.add_user_message("Hi there".to_string());
+        assert!(api_messages[0]
+            .content
+            .starts_with("You are Alice on test-model.\n\n"));
+        assert_eq!(
+            api_messages.last().unwrap().content,
+            "Stay curious. Reply to: Hi there"
+        );
+
+        conversation.clear_transcript();
+        assert_eq!(conversation.session.macros.get_var("mood"), None);
+    }
+
+    #[test]
+    fn test_greeting_options_do_not_set_variables() {
+        let mut app = create_test_app();
+        let mut character = group_member("Alice");
+        character.data.first_mes = "Hello.{{setvar::mood::calm}}".to_string();
+        character.data.alternate_greetings = Some(vec!["Hey.{{setvar::mood::wild}}".to_string()]);
+        app.session.set_character(character);
+
+        let mut conversation = ConversationController::new(
+            &mut app.session,
+            &mut app.ui,
+            &app.persona_manager,
+            &app.preset_manager,
+        );
+        assert_eq!(conversation.greeting_options(), vec!["Hello.", "Hey."]);
+        assert_eq!(conversation.session.macros.get_var("mood"), None);
+
+        conversation.show_character_greeting_if_needed();
+        assert_eq!(
+            conversation.session.macros.get_var("mood").as_deref(),
+            Some("calm")
+        );
+    }
+
     #[test]
     fn test_persona_with_blank_bio_does_not_add_system_message() {
         let cases = [
@@ -14,6 +14,7 @@ use crate::core::app::ui_state::UiState;
 use crate::core::config::data::Config;
 use crate::core::message::AppMessageKind;
 use crate::core::providers::ProviderSession;
+use crate::mcp::audit::ToolAuditLog;
 use crate::mcp::client::McpClientManager;
 use crate::mcp::permissions::ToolPermissionStore;
 
@@ -72,6 +73,10 @@ pub struct AppInitConfig {
     /// Character card to load (name or path).
     pub character: Option<String>,
 
+    /// Start without a character instead of the provider/model default
+    /// when `character` is `None`.
+    pub no_default_character: bool,
+
     /// Persona ID to activate for this session.
     pub persona: Option<String>,
 
@@ -104,7 +109,9 @@ fn build_app(
         config,
         mcp,
         mcp_permissions: ToolPermissionStore::default(),
+        mcp_audit: None,
     };
+    app.mcp_audit = ToolAuditLog::from_config(&app.config);
 
     app.ui.set_input_text(String::new());
     app.configure_textarea_appearance();
@@ -155,6 +162,7 @@ fn build_app(
 ///     env_only: false,
 ///     pre_resolved_session: None,
 ///     character: None,
+///     no_default_character: false,
 ///     persona: None,
 ///     preset: None,
 ///     disable_mcp: false,
@@ -189,6 +197,7 @@ pub async fn new_with_auth(
         config: &effective_config,
         pre_resolved_session: init_config.pre_resolved_session,
         character: init_config.character,
+        no_default_character: init_config.no_default_character,
         character_service: &mut character_service,
     })
     .await?;
@@ -215,16 +224,17 @@ pub async fn new_with_auth(
 
     // Initialize PresetManager and apply CLI preset if provided
     let mut preset_manager = crate::core::preset::PresetManager::load_presets(&effective_config)?;
-    if let Some(preset_id) = init_config.preset {
-        preset_manager.set_active_preset(&preset_id)?;
-    } else if let Some(default_preset_id) =
+    if let Some(preset_ids) = init_config.preset {
+        preset_manager.set_active_presets(&crate::core::preset::parse_preset_ids(&preset_ids))?;
+    } else if let Some(default_preset_ids) =
         preset_manager.get_default_for_provider_model(&session.provider_name, &session.model)
     {
-        let default_preset_id = default_preset_id.to_string();
-        if let Err(e) = preset_manager.set_active_preset(&default_preset_id) {
+        let default_preset_ids = default_preset_ids.to_vec();
+        if let Err(e) = preset_manager.set_active_presets(&default_preset_ids) {
             startup_errors.push(format!(
                 "Could not load default preset '{}': {}",
-                default_preset_id, e
+                default_preset_ids.join(", "),
+                e
             ));
         }
     }
@@ -378,4 +388,7 @@ pub struct App {
 
     /// Tool permission decisions for MCP tools.
     pub mcp_permissions: ToolPermissionStore,
+
+    /// Audit log for MCP tool calls; `None` when disabled.
+    pub mcp_audit: Option<ToolAuditLog>,
 }
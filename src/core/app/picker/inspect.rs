@@ -1,3 +1,4 @@
+use crate::character::lorebook::CharacterBook;
 use crate::character::CharacterCard;
 use crate::core::builtin_providers::BuiltinProvider;
 use crate::core::config::data::CustomProvider;
@@ -270,16 +271,22 @@ fn provider_metadata(
     (summary, inspect)
 }
 
-pub(super) fn character_inspect(card: &CharacterCard) -> String {
+/// `active_lore` holds the labels of lorebook entries used by the latest
+/// request when `card` is the active character.
+pub(super) fn character_inspect(card: &CharacterCard, active_lore: &[String]) -> String {
     let mut lines = vec![
         format!("Character: {}", card.data.name),
         format!("Spec: {} (version {})", card.spec, card.spec_version),
     ];
+    if let Some(nickname) = card.data.nickname.as_ref() {
+        if !nickname.trim().is_empty() {
+            lines.push(format!("Nickname: {}", nickname.trim()));
+        }
+    }
 
     append_character_block(&mut lines, "Description", &card.data.description);
     append_character_block(&mut lines, "Personality", &card.data.personality);
     append_character_block(&mut lines, "Scenario", &card.data.scenario);
-    append_character_block(&mut lines, "First message", &card.data.first_mes);
     append_character_block(&mut lines, "Example dialogue", &card.data.mes_example);
     append_character_block_optional(
         &mut lines,
@@ -296,12 +303,35 @@ pub(super) fn character_inspect(card: &CharacterCard) -> String {
         "Post-history instructions",
         card.data.post_history_instructions.as_deref(),
     );
+    if let Some(notes) = card.data.creator_notes_multilingual.as_ref() {
+        for (language, text) in notes {
+            append_character_block(&mut lines, &format!("Creator notes ({language})"), text);
+        }
+    }
+    append_character_greetings(&mut lines, &card.greetings(), card.has_first_message());
     append_character_list(
         &mut lines,
-        "Alternate greetings",
-        card.data.alternate_greetings.as_ref(),
+        "Group-only greetings",
+        card.data.group_only_greetings.as_ref(),
     );
 
+    if let Some(assets) = card.data.assets.as_ref() {
+        if !assets.is_empty() {
+            lines.push(String::new());
+            lines.push("Assets:".to_string());
+            for asset in assets {
+                lines.push(format!(
+                    "  - {} ({}, {}): {}",
+                    asset.name, asset.asset_type, asset.ext, asset.uri
+                ));
+            }
+        }
+    }
+
+    if let Some(book) = card.data.character_book.as_ref() {
+        append_character_lorebook(&mut lines, book, active_lore);
+    }
+
     if let Some(tags) = card.data.tags.as_ref() {
         if !tags.is_empty() {
             lines.push(String::new());
@@ -323,9 +353,98 @@ pub(super) fn character_inspect(card: &CharacterCard) -> String {
         }
     }
 
+    if let Some(source) = card.data.source.as_ref() {
+        if !source.is_empty() {
+            lines.push(String::new());
+            lines.push(format!("Source: {}", source.join(", ")));
+        }
+    }
+
+    let dates: Vec<String> = [
+        ("Created", card.data.creation_date),
+        ("Modified", card.data.modification_date),
+    ]
+    .into_iter()
+    .filter_map(|(label, secs)| {
+        let date = chrono::DateTime::from_timestamp(secs?, 0)?;
+        Some(format!("{label}: {}", date.format("%Y-%m-%d %H:%M UTC")))
+    })
+    .collect();
+    if !dates.is_empty() {
+        lines.push(String::new());
+        lines.extend(dates);
+    }
+
     build_inspect_text(lines)
 }
 
+/// Numbered like `/greeting <n>`, starting with the first message when the
+/// card has one.
+fn append_character_greetings(
+    lines: &mut Vec<String>,
+    greetings: &[&str],
+    has_first_message: bool,
+) {
+    if greetings.iter().all(|greeting| greeting.trim().is_empty()) {
+        return;
+    }
+
+    lines.push(String::new());
+    lines.push("Greetings:".to_string());
+    for (index, greeting) in greetings.iter().enumerate() {
+        let kind = if index == 0 && has_first_message {
+            "first message"
+        } else {
+            "alternate"
+        };
+        lines.push(format!("  {}. ({})", index + 1, kind));
+        for line in greeting.trim().lines() {
+            lines.push(format!("     {}", line.trim_end()));
+        }
+    }
+}
+
+fn append_character_lorebook(lines: &mut Vec<String>, book: &CharacterBook, active: &[String]) {
+    if book.entries.is_empty() {
+        return;
+    }
+
+    lines.push(String::new());
+    let name = book
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| format!(" {name}"))
+        .unwrap_or_default();
+    lines.push(format!(
+        "Lorebook{} ({} entries, scan depth {}, budget {} tokens, {} active):",
+        name,
+        book.entries.len(),
+        book.scan_depth(),
+        book.token_budget(),
+        active.len()
+    ));
+    for entry in &book.entries {
+        let label = entry.label();
+        let marker = if active.contains(&label) { "*" } else { "-" };
+        let mut line = format!("  {marker} {label}");
+        if !entry.keys.is_empty() {
+            line.push_str(&format!(" — keys: {}", entry.keys.join(", ")));
+        }
+        if entry.is_constant() {
+            line.push_str(" (constant)");
+        }
+        if !entry.enabled {
+            line.push_str(" (disabled)");
+        }
+        lines.push(line);
+    }
+    if !active.is_empty() {
+        lines.push("  (* = used in the latest request)".to_string());
+    }
+}
+
 fn append_character_block(lines: &mut Vec<String>, heading: &str, content: &str) {
     let trimmed = content.trim();
     if trimmed.is_empty() {
@@ -24,7 +24,10 @@ use crate::api::ModelsResponse;
 use crate::auth::AuthManager;
 use crate::character::CharacterCard;
 use crate::core::builtin_providers::load_builtin_providers;
-use crate::core::config::data::{Config, CustomProvider};
+use crate::core::config::data::{
+    Config, CustomProvider, PresetBlock, PresetCondition, PresetPosition,
+};
+use crate::core::macros::{expand, MacroContext};
 use crate::ui::builtin_themes::load_builtin_themes;
 use crate::ui::picker::{PickerItem, PickerState, SortMode};
 use crate::ui::theme::Theme;
@@ -80,6 +83,36 @@ pub(super) fn sanitize_picker_metadata_for_inspect(text: &str) -> String {
     cleaned.trim().to_string()
 }
 
+/// Preset label with `*` for a default preset and its position in the
+/// active stack, e.g. "focus* [2]".
+fn preset_picker_label(id: &str, default_ids: &[String], stack: &[String]) -> String {
+    let mut label = id.to_string();
+    if default_ids.iter().any(|default_id| default_id == id) {
+        label.push('*');
+    }
+    if let Some(index) = stack.iter().position(|stacked| stacked == id) {
+        label.push_str(&format!(" [{}]", index + 1));
+    }
+    label
+}
+
+/// Where and when a preset block applies, e.g. "depth 2, user, when character".
+fn preset_block_placement(block: &PresetBlock) -> String {
+    let mut placement = match block.position {
+        PresetPosition::Depth => format!("depth {}", block.depth),
+        position => position.as_str().to_string(),
+    };
+    if block.position != PresetPosition::Prefill {
+        placement.push_str(", ");
+        placement.push_str(block.role.as_str());
+    }
+    if block.when != PresetCondition::Always {
+        placement.push_str(", when ");
+        placement.push_str(block.when.as_str());
+    }
+    placement
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PickerMode {
     Theme,
@@ -88,6 +121,7 @@ pub enum PickerMode {
     Character,
     Persona,
     Preset,
+    Greeting,
 }
 
 #[derive(Debug, Clone)]
@@ -129,6 +163,18 @@ pub struct PersonaPickerState {
 pub struct PresetPickerState {
     pub search_filter: String,
     pub all_items: Vec<PickerItem>,
+    /// Presets marked with Tab, in the order they will apply.
+    pub stack: Vec<String>,
+    /// Whether Tab changed `stack`; Enter then applies the stack instead of
+    /// the highlighted preset.
+    pub stack_changed: bool,
+    pub default_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GreetingPickerState {
+    pub search_filter: String,
+    pub all_items: Vec<PickerItem>,
 }
 
 #[derive(Debug, Clone)]
@@ -138,7 +184,8 @@ pub enum PickerData {
     Provider(Box<ProviderPickerState>),
     Character(CharacterPickerState),
     Persona(PersonaPickerState),
-    Preset(PresetPickerState),
+    Preset(Box<PresetPickerState>),
+    Greeting(GreetingPickerState),
 }
 
 impl PickerData {
@@ -150,6 +197,7 @@ impl PickerData {
             PickerData::Character(_) => PickerMode::Character,
             PickerData::Persona(_) => PickerMode::Persona,
             PickerData::Preset(_) => PickerMode::Preset,
+            PickerData::Greeting(_) => PickerMode::Greeting,
         }
     }
 
@@ -160,7 +208,8 @@ impl PickerData {
             | PickerData::Provider(_)
             | PickerData::Character(_)
             | PickerData::Persona(_)
-            | PickerData::Preset(_) => true,
+            | PickerData::Preset(_)
+            | PickerData::Greeting(_) => true,
         }
     }
 
@@ -179,6 +228,7 @@ impl PickerData {
             PickerMode::Character => "Pick Character",
             PickerMode::Persona => "Pick Persona",
             PickerMode::Preset => "Pick Preset",
+            PickerMode::Greeting => "Pick Greeting",
         }
     }
 
@@ -190,6 +240,7 @@ impl PickerData {
             PickerData::Character(state) => &state.search_filter,
             PickerData::Persona(state) => &state.search_filter,
             PickerData::Preset(state) => &state.search_filter,
+            PickerData::Greeting(state) => &state.search_filter,
         }
     }
 
@@ -201,6 +252,7 @@ impl PickerData {
             PickerData::Character(state) => &state.all_items,
             PickerData::Persona(state) => &state.all_items,
             PickerData::Preset(state) => &state.all_items,
+            PickerData::Greeting(state) => &state.all_items,
         }
     }
 }
@@ -288,6 +340,7 @@ picker_state_accessors! {
     (Character, character_state, character_state_mut, CharacterPickerState),
     (Persona, persona_state, persona_state_mut, PersonaPickerState),
     (Preset, preset_state, preset_state_mut, PresetPickerState),
+    (Greeting, greeting_state, greeting_state_mut, GreetingPickerState),
 }
 
 pub struct PickerController {
@@ -903,6 +956,41 @@ impl PickerController {
         self.filter_session_items(PickerMode::Preset, &[TURN_OFF_PRESET_ID]);
     }
 
+    /// Adds the highlighted preset to the stack, or removes it if already
+    /// stacked, and returns the new stack.
+    pub fn toggle_preset_in_stack(&mut self) -> Option<Vec<String>> {
+        let session = self.session_mut()?;
+        let id = session.state.selected_id()?.to_string();
+        if id == TURN_OFF_PRESET_ID {
+            return None;
+        }
+        let preset_state = session.data.preset_state_mut()?;
+        match preset_state.stack.iter().position(|stacked| *stacked == id) {
+            Some(index) => {
+                preset_state.stack.remove(index);
+            }
+            None => preset_state.stack.push(id),
+        }
+        preset_state.stack_changed = true;
+
+        for item in session
+            .state
+            .items
+            .iter_mut()
+            .chain(preset_state.all_items.iter_mut())
+        {
+            if item.id != TURN_OFF_PRESET_ID {
+                item.label =
+                    preset_picker_label(&item.id, &preset_state.default_ids, &preset_state.stack);
+            }
+        }
+        Some(preset_state.stack.clone())
+    }
+
+    pub fn filter_greetings(&mut self) {
+        self.filter_session_items(PickerMode::Greeting, &[]);
+    }
+
     pub fn open_character_picker(
         &mut self,
         cards: Vec<CharacterCard>,
@@ -934,7 +1022,12 @@ impl PickerController {
                 } else {
                     sanitized_description
                 };
-                let inspect_definition = character_inspect(&card);
+                let active_lore: &[String] = if active_character_id.as_deref() == Some(&name) {
+                    &session_context.active_lore_entries
+                } else {
+                    &[]
+                };
+                let inspect_definition = character_inspect(&card, active_lore);
                 let is_default = default_character.map(|def| def == &name).unwrap_or(false);
                 let label = if is_default {
                     format!("{}*", name)
@@ -1022,11 +1115,11 @@ impl PickerController {
                     .bio
                     .as_ref()
                     .and_then(|bio| {
-                        let char_replacement = active_character_name.unwrap_or("Assistant");
-                        let user_replacement = persona.display_name.as_str();
-                        let substituted = bio
-                            .replace("{{char}}", char_replacement)
-                            .replace("{{user}}", user_replacement);
+                        let macros = MacroContext::names(
+                            Some(persona.display_name.as_str()),
+                            Some(active_character_name.unwrap_or("Assistant")),
+                        );
+                        let substituted = expand(bio, &macros);
                         let sanitized = sanitize_picker_metadata(&substituted);
                         if sanitized.is_empty() {
                             None
@@ -1091,26 +1184,26 @@ impl PickerController {
         session_context: &SessionContext,
     ) -> Result<(), String> {
         let presets = preset_manager.list_presets();
-        let active_preset_id = preset_manager
-            .get_active_preset()
-            .map(|preset| preset.id.clone());
+        let stack: Vec<String> = preset_manager
+            .active_preset_ids()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        let active_preset_id = stack.first().cloned();
 
         if presets.is_empty() {
             return Err("No presets found. Add presets to your config.toml file.".to_string());
         }
 
-        let default_preset = preset_manager
-            .get_default_for_provider_model(&session_context.provider_name, &session_context.model);
+        let default_ids = preset_manager
+            .get_default_for_provider_model(&session_context.provider_name, &session_context.model)
+            .map(<[String]>::to_vec)
+            .unwrap_or_default();
 
         let mut items: Vec<PickerItem> = presets
             .iter()
             .map(|preset| {
-                let is_default = default_preset.map(|def| def == preset.id).unwrap_or(false);
-                let label = if is_default {
-                    format!("{}*", preset.id)
-                } else {
-                    preset.id.clone()
-                };
+                let label = preset_picker_label(&preset.id, &default_ids, &stack);
 
                 let mut parts = Vec::new();
                 let mut inspect_parts = Vec::new();
@@ -1132,6 +1225,23 @@ impl PickerController {
                         inspect_parts.push(format!("Post:\n{}", inspect));
                     }
                 }
+                for block in &preset.blocks {
+                    let content = block.content.trim();
+                    if content.is_empty() {
+                        continue;
+                    }
+                    let name = block
+                        .name
+                        .as_deref()
+                        .unwrap_or_else(|| block.position.as_str());
+                    parts.push(format!("{}: {}", name, sanitize_picker_metadata(content)));
+                    inspect_parts.push(format!(
+                        "{} ({}):\n{}",
+                        name,
+                        preset_block_placement(block),
+                        sanitize_picker_metadata_for_inspect(content)
+                    ));
+                }
 
                 let metadata = if parts.is_empty() {
                     Some("No instructions".to_string())
@@ -1155,14 +1265,14 @@ impl PickerController {
             })
             .collect();
 
-        if preset_manager.get_active_preset().is_some() {
+        if !stack.is_empty() {
             items.insert(
                 0,
                 PickerItem {
                     id: TURN_OFF_PRESET_ID.to_string(),
                     label: "[Turn off preset]".to_string(),
-                    metadata: Some("Deactivate current preset".to_string()),
-                    inspect_metadata: Some("Deactivate current preset".to_string()),
+                    metadata: Some("Deactivate all active presets".to_string()),
+                    inspect_metadata: Some("Deactivate all active presets".to_string()),
                     sort_key: None,
                 },
             );
@@ -1175,10 +1285,13 @@ impl PickerController {
         let picker_state = PickerState::new("Pick Preset", items.clone(), selected);
         let session = PickerSession {
             state: picker_state,
-            data: PickerData::Preset(PresetPickerState {
+            data: PickerData::Preset(Box::new(PresetPickerState {
                 search_filter: String::new(),
                 all_items: items,
-            }),
+                stack,
+                stack_changed: false,
+                default_ids,
+            })),
         };
 
         self.start_picker_session(session, active_preset_id);
@@ -1186,6 +1299,60 @@ impl PickerController {
         Ok(())
     }
 
+    /// Opens a picker over the active character's greetings. Item IDs are
+    /// 0-based greeting indices; labels are numbered from 1 and padded so
+    /// name sorting keeps card order. `has_first_message` says whether the
+    /// first greeting is the card's first message rather than an alternate.
+    pub fn open_greeting_picker(
+        &mut self,
+        greetings: &[String],
+        has_first_message: bool,
+        current_index: usize,
+    ) -> Result<(), String> {
+        if greetings.is_empty() {
+            return Err("No character is active.".to_string());
+        }
+
+        let width = greetings.len().to_string().len();
+        let items: Vec<PickerItem> = greetings
+            .iter()
+            .enumerate()
+            .map(|(index, greeting)| {
+                let kind = if index == 0 && has_first_message {
+                    "first message"
+                } else {
+                    "alternate"
+                };
+                let metadata = sanitize_picker_metadata(greeting);
+                PickerItem {
+                    id: index.to_string(),
+                    label: format!("{:>width$}. {}", index + 1, kind),
+                    metadata: Some(if metadata.is_empty() {
+                        "Empty greeting".to_string()
+                    } else {
+                        metadata
+                    }),
+                    inspect_metadata: Some(sanitize_picker_metadata_for_inspect(greeting)),
+                    sort_key: None,
+                }
+            })
+            .collect();
+
+        let selected = current_index.min(items.len() - 1);
+        let picker_state = PickerState::new("Pick Greeting", items.clone(), selected);
+        let session = PickerSession {
+            state: picker_state,
+            data: PickerData::Greeting(GreetingPickerState {
+                search_filter: String::new(),
+                all_items: items,
+            }),
+        };
+
+        self.start_picker_session(session, Some(selected.to_string()));
+
+        Ok(())
+    }
+
     fn prefers_alphabetical(&self) -> bool {
         self.session()
             .map(|session| session.prefers_alphabetical())
@@ -1335,10 +1502,13 @@ mod tests {
 
         let mut session = PickerSession {
             state: picker_state,
-            data: PickerData::Preset(PresetPickerState {
+            data: PickerData::Preset(Box::new(PresetPickerState {
                 search_filter: "FOCUS".to_string(),
                 all_items: items,
-            }),
+                stack: Vec::new(),
+                stack_changed: false,
+                default_ids: Vec::new(),
+            })),
         };
         session.state.sort_mode = session.default_sort_mode();
 
@@ -1360,6 +1530,7 @@ mod tests {
         assert!(size_of::<ModelPickerState>() > small_inline);
         assert!(size_of::<ProviderPickerState>() > small_inline);
         assert!(size_of::<ThemePickerState>() > small_inline);
+        assert!(size_of::<PresetPickerState>() > small_inline);
         assert!(size_of::<PickerData>() < size_of::<ModelPickerState>());
     }
 
@@ -1482,6 +1653,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         });
 
@@ -1606,6 +1778,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         });
 
@@ -1714,6 +1887,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -1737,6 +1911,7 @@ mod tests {
                     tags: None,
                     creator: None,
                     character_version: None,
+                    ..Default::default()
                 },
             },
             CharacterCard {
@@ -1756,6 +1931,7 @@ mod tests {
                     tags: None,
                     creator: None,
                     character_version: None,
+                    ..Default::default()
                 },
             },
         ];
@@ -1822,11 +1998,13 @@ mod tests {
                     id: "focus".to_string(),
                     pre: "Focus".to_string(),
                     post: String::new(),
+                    ..Default::default()
                 },
                 Preset {
                     id: "casual".to_string(),
                     pre: "Casual".to_string(),
                     post: String::new(),
+                    ..Default::default()
                 },
             ],
             ..Default::default()
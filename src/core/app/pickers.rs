@@ -1,6 +1,6 @@
 use super::picker::{
-    self, CharacterPickerState, ModelPickerState, PersonaPickerState, PickerMode, PickerSession,
-    PresetPickerState, ProviderPickerState, ThemePickerState,
+    self, CharacterPickerState, GreetingPickerState, ModelPickerState, PersonaPickerState,
+    PickerMode, PickerSession, PresetPickerState, ProviderPickerState, ThemePickerState,
 };
 use super::ui_state::ActivityKind;
 use super::App;
@@ -396,56 +396,73 @@ impl App {
         }
     }
 
-    /// Apply the selected preset from the picker
+    /// Apply the selected preset from the picker. If presets were stacked
+    /// with Tab, the whole stack is applied instead of the highlighted one.
     pub fn apply_selected_preset(&mut self, set_as_default: bool) {
-        let preset_id = self
-            .picker
-            .session()
-            .and_then(|picker| picker.state.selected_id())
-            .map(|s| s.to_string());
-
-        if let Some(preset_id) = preset_id {
-            if preset_id == picker::TURN_OFF_PRESET_ID {
-                self.preset_manager.clear_active_preset();
-                self.conversation()
-                    .set_status("Preset deactivated".to_string());
-                self.close_picker();
-                return;
+        let stack = self
+            .preset_picker_state()
+            .filter(|state| state.stack_changed)
+            .map(|state| state.stack.clone());
+        let preset_ids = match stack {
+            Some(stack) => stack,
+            None => {
+                let Some(preset_id) = self
+                    .picker
+                    .session()
+                    .and_then(|picker| picker.state.selected_id())
+                    .map(|s| s.to_string())
+                else {
+                    self.close_picker();
+                    return;
+                };
+                if preset_id == picker::TURN_OFF_PRESET_ID {
+                    Vec::new()
+                } else {
+                    vec![preset_id]
+                }
             }
+        };
+
+        if preset_ids.is_empty() {
+            self.preset_manager.clear_active_preset();
+            self.conversation()
+                .set_status("Preset deactivated".to_string());
+            self.close_picker();
+            return;
+        }
 
-            match self.preset_manager.set_active_preset(&preset_id) {
-                Ok(()) => {
-                    if set_as_default {
-                        let provider = self.session.provider_name.clone();
-                        let model = self.session.model.clone();
-                        match self
-                            .preset_manager
-                            .set_default_for_provider_model_persistent(
-                                &provider, &model, &preset_id,
-                            ) {
-                            Ok(()) => {
-                                self.conversation().set_status(format!(
-                                    "Preset activated: {} (saved as default for {}:{})",
-                                    preset_id, provider, model
-                                ));
-                            }
-                            Err(e) => {
-                                self.conversation().set_status(format!(
-                                    "Preset activated: {} (failed to save as default: {})",
-                                    preset_id, e
-                                ));
-                            }
+        let label = preset_ids.join(", ");
+        match self.preset_manager.set_active_presets(&preset_ids) {
+            Ok(()) => {
+                if set_as_default {
+                    let provider = self.session.provider_name.clone();
+                    let model = self.session.model.clone();
+                    match self
+                        .preset_manager
+                        .set_default_for_provider_model_persistent(&provider, &model, &preset_ids)
+                    {
+                        Ok(()) => {
+                            self.conversation().set_status(format!(
+                                "Preset activated: {} (saved as default for {}:{})",
+                                label, provider, model
+                            ));
+                        }
+                        Err(e) => {
+                            self.conversation().set_status(format!(
+                                "Preset activated: {} (failed to save as default: {})",
+                                label, e
+                            ));
                         }
-                    } else {
-                        self.conversation()
-                            .set_status(format!("Preset activated: {}", preset_id));
                     }
-                }
-                Err(e) => {
+                } else {
                     self.conversation()
-                        .set_status(format!("Preset error: {}", e));
+                        .set_status(format!("Preset activated: {}", label));
                 }
             }
+            Err(e) => {
+                self.conversation()
+                    .set_status(format!("Preset error: {}", e));
+            }
         }
 
         self.close_picker();
@@ -456,6 +473,51 @@ impl App {
         self.picker.filter_presets();
     }
 
+    /// Open a greeting picker over the active character's greetings
+    pub fn open_greeting_picker(&mut self) {
+        self.close_inspect();
+        let greetings = self.conversation().greeting_options();
+        let has_first_message = self
+            .session
+            .get_character()
+            .is_some_and(|character| character.has_first_message());
+        if let Err(message) = self.picker.open_greeting_picker(
+            &greetings,
+            has_first_message,
+            self.session.greeting_index,
+        ) {
+            self.conversation().set_status(message);
+        }
+    }
+
+    /// Apply the selected greeting from the picker
+    pub fn apply_selected_greeting(&mut self) {
+        let index = self
+            .picker
+            .session()
+            .and_then(|picker| picker.state.selected_id())
+            .and_then(|id| id.parse::<usize>().ok());
+        self.close_picker();
+
+        if let Some(index) = index {
+            self.select_greeting(index);
+        }
+    }
+
+    /// Swap the opening greeting and report the result in the status bar
+    pub fn select_greeting(&mut self, index: usize) {
+        let mut conversation = self.conversation();
+        match conversation.select_greeting(index) {
+            Ok(count) => conversation.set_status(format!("Greeting {} of {}", index + 1, count)),
+            Err(message) => conversation.set_status(message),
+        }
+    }
+
+    /// Filter greetings based on search term and update picker
+    pub fn filter_greetings(&mut self) {
+        self.picker.filter_greetings();
+    }
+
     /// Get character picker state accessor
     pub fn character_picker_state(&self) -> Option<&CharacterPickerState> {
         self.picker
@@ -493,4 +555,18 @@ impl App {
             .session_mut()
             .and_then(PickerSession::preset_state_mut)
     }
+
+    /// Get greeting picker state accessor
+    pub fn greeting_picker_state(&self) -> Option<&GreetingPickerState> {
+        self.picker
+            .session()
+            .and_then(PickerSession::greeting_state)
+    }
+
+    /// Get mutable greeting picker state accessor
+    pub fn greeting_picker_state_mut(&mut self) -> Option<&mut GreetingPickerState> {
+        self.picker
+            .session_mut()
+            .and_then(PickerSession::greeting_state_mut)
+    }
 }
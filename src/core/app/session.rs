@@ -17,14 +17,18 @@ use tokio_util::sync::CancellationToken;
 use crate::api::{ChatMessage, ChatToolCall};
 use crate::auth::AuthManager;
 use crate::character::card::CharacterCard;
+use crate::character::group::GroupChat;
 use crate::character::service::CharacterService;
 use crate::core::config::data::Config;
 #[cfg(test)]
 use crate::core::config::data::{DEFAULT_REFINE_INSTRUCTIONS, DEFAULT_REFINE_PREFIX};
+use crate::core::macros::MacroState;
 use crate::core::providers::{
     resolve_env_session, resolve_session, ProviderResolutionError, ProviderSession,
     ResolveSessionError,
 };
+use crate::mcp::audit::AuditDecision;
+use crate::mcp::routing::ToolRoutingTurn;
 use crate::ui::appearance::{detect_preferred_appearance, Appearance};
 use crate::ui::builtin_themes::{find_builtin_theme, theme_spec_from_custom};
 use crate::ui::theme::Theme;
@@ -53,19 +57,30 @@ pub struct SessionContext {
     pub mcp_disabled: bool,
     pub active_character: Option<CharacterCard>,
     pub character_greeting_shown: bool,
+    /// Which of the active character's greetings opens the chat.
+    pub greeting_index: usize,
     pub has_received_assistant_message: bool,
     pub tool_pipeline: ToolPipelineState,
     pub mcp_init: McpInitState,
     pub active_assistant_message_index: Option<usize>,
     pub mcp_tools_enabled: bool,
     pub mcp_tools_unsupported: bool,
+    /// Labels of the lorebook entries used by the latest request.
+    pub active_lore_entries: Vec<String>,
+    /// Group chat members; `active_character` is then the current speaker.
+    pub group: Option<GroupChat>,
+    /// Variables and timing behind `{{setvar}}`/`{{idle_duration}}` macros.
+    pub macros: MacroState,
 }
 
 #[derive(Default, Clone)]
 pub struct ToolPipelineState {
     pub pending_tool_calls: BTreeMap<u32, PendingToolCall>,
     pub pending_tool_queue: VecDeque<ToolCallRequest>,
+    /// Call waiting on a permission prompt (or being answered from memory).
     pub active_tool_request: Option<ToolCallRequest>,
+    /// Approved calls currently executing; several may run at once.
+    pub running_tool_calls: Vec<RunningToolCall>,
     pub pending_sampling_queue: VecDeque<McpSamplingRequest>,
     pub active_sampling_request: Option<McpSamplingRequest>,
     pub tool_call_records: Vec<ChatToolCall>,
@@ -73,6 +88,24 @@ pub struct ToolPipelineState {
     pub tool_result_history: Vec<ToolResultRecord>,
     pub tool_payload_history: Vec<ToolPayloadHistoryEntry>,
     pub continuation_messages: Option<StreamContinuation>,
+    /// Recent tool routing decisions, oldest first, shown by `/tools`.
+    pub tool_routing_history: VecDeque<ToolRoutingTurn>,
+    /// Last [`crate::core::message::Message::id`] given to a running line.
+    /// IDs are never reused, so a stale line from a cancelled turn is not
+    /// mistaken for a new one.
+    pub last_running_line_id: u64,
+}
+
+/// A tool call in flight and the transcript line showing its status.
+#[derive(Debug, Clone)]
+pub struct RunningToolCall {
+    pub request: ToolCallRequest,
+    /// [`crate::core::message::Message::id`] of the "running" transcript
+    /// line, so transcript edits while the call runs don't move it.
+    pub running_line_id: u64,
+    /// How the call was authorized, for the audit log.
+    pub decision: AuditDecision,
+    pub started_at: Instant,
 }
 
 #[derive(Clone)]
@@ -157,6 +190,8 @@ pub struct ToolResultRecord {
     pub tool_call_id: Option<String>,
     pub raw_arguments: Option<String>,
     pub assistant_message_index: Option<usize>,
+    /// Every content block from the MCP result; `content` is its text rendering.
+    pub output: Option<crate::mcp::tool_output::McpToolOutput>,
 }
 
 #[derive(Clone)]
@@ -191,6 +226,32 @@ pub struct McpPromptRequest {
     pub arguments: std::collections::HashMap<String, String>,
 }
 
+/// What a `completion/complete` request completes an argument of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum McpCompletionReference {
+    Prompt(String),
+    /// A resource template, identified by its URI template.
+    ResourceTemplate(String),
+}
+
+/// A `completion/complete` request for one prompt or resource-template argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct McpCompletionRequest {
+    pub server_id: String,
+    pub reference: McpCompletionReference,
+    pub argument: String,
+    pub value: String,
+    /// Arguments already entered, sent so servers can narrow suggestions.
+    pub context: std::collections::HashMap<String, String>,
+}
+
+/// A user message whose `@server:uri` mentions must be read before sending.
+#[derive(Debug, Clone)]
+pub struct McpResourceAttachmentRequest {
+    pub message: String,
+    pub mentions: Vec<crate::core::mcp_resource_mentions::McpResourceMention>,
+}
+
 pub struct SessionBootstrap {
     pub session: SessionContext,
     pub theme: Theme,
@@ -213,6 +274,7 @@ pub(crate) struct PrepareWithAuthInput<'a> {
     pub config: &'a Config,
     pub pre_resolved_session: Option<ProviderSession>,
     pub character: Option<String>,
+    pub no_default_character: bool,
     pub character_service: &'a mut CharacterService,
 }
 
@@ -226,18 +288,42 @@ impl SessionContext {
             .map(|current| current.data.name == card.data.name)
             .unwrap_or(false);
 
+        // Picking a character outside the group ends the group chat
+        if self
+            .group
+            .as_ref()
+            .is_some_and(|group| group.position(&card.data.name).is_none())
+        {
+            self.group = None;
+        }
+
         self.active_character = Some(card);
 
         // Only reset greeting flag if this is a different character
         if !is_same_character {
             self.character_greeting_shown = false;
+            self.greeting_index = 0;
+            self.active_lore_entries.clear();
         }
     }
 
     /// Clear the active character card
     pub fn clear_character(&mut self) {
         self.active_character = None;
+        self.group = None;
         self.character_greeting_shown = false;
+        self.greeting_index = 0;
+        self.active_lore_entries.clear();
+    }
+
+    /// Make group member `index` the active character for the next reply,
+    /// keeping the greeting state of the conversation.
+    pub fn set_group_speaker(&mut self, index: usize) -> Option<&CharacterCard> {
+        let group = self.group.as_mut()?;
+        let card = group.member(index)?.clone();
+        group.record_speaker(index);
+        self.active_character = Some(card);
+        self.active_character.as_ref()
     }
 
     /// Get a reference to the active character card
@@ -282,12 +368,16 @@ impl SessionContext {
             mcp_disabled: false,
             active_character: None,
             character_greeting_shown: false,
+            greeting_index: 0,
             has_received_assistant_message: false,
             tool_pipeline: ToolPipelineState::default(),
             mcp_init: McpInitState::default(),
             active_assistant_message_index: None,
             mcp_tools_enabled: false,
             mcp_tools_unsupported: false,
+            active_lore_entries: Vec::new(),
+            group: None,
+            macros: MacroState::default(),
         }
     }
 }
@@ -297,6 +387,7 @@ impl ToolPipelineState {
         self.pending_tool_calls.clear();
         self.pending_tool_queue.clear();
         self.active_tool_request = None;
+        self.running_tool_calls.clear();
         self.pending_sampling_queue.clear();
         self.active_sampling_request = None;
         self.tool_call_records.clear();
@@ -536,6 +627,7 @@ pub(crate) async fn prepare_with_auth(
         config,
         pre_resolved_session,
         character,
+        no_default_character,
         character_service,
     } = input;
 
@@ -571,13 +663,20 @@ pub(crate) async fn prepare_with_auth(
     let CharacterLoadOutcome {
         character: active_character,
         errors: startup_errors,
-    } = load_character_for_session(
-        character.as_deref(),
-        &provider_name,
-        &final_model,
-        config,
-        character_service,
-    )?;
+    } = if no_default_character && character.is_none() {
+        CharacterLoadOutcome {
+            character: None,
+            errors: Vec::new(),
+        }
+    } else {
+        load_character_for_session(
+            character.as_deref(),
+            &provider_name,
+            &final_model,
+            config,
+            character_service,
+        )?
+    };
 
     let session = SessionContext {
         client: Client::new(),
@@ -600,12 +699,16 @@ pub(crate) async fn prepare_with_auth(
         mcp_disabled: false,
         active_character,
         character_greeting_shown: false,
+        greeting_index: 0,
         has_received_assistant_message: false,
         tool_pipeline: ToolPipelineState::default(),
         mcp_init: McpInitState::default(),
         active_assistant_message_index: None,
         mcp_tools_enabled: false,
         mcp_tools_unsupported: false,
+        active_lore_entries: Vec::new(),
+        group: None,
+        macros: MacroState::default(),
     };
 
     Ok(SessionBootstrap {
@@ -646,12 +749,16 @@ pub(crate) async fn prepare_uninitialized(
         mcp_disabled: false,
         active_character: None,
         character_greeting_shown: false,
+        greeting_index: 0,
         has_received_assistant_message: false,
         tool_pipeline: ToolPipelineState::default(),
         mcp_init: McpInitState::default(),
         active_assistant_message_index: None,
         mcp_tools_enabled: false,
         mcp_tools_unsupported: false,
+        active_lore_entries: Vec::new(),
+        group: None,
+        macros: MacroState::default(),
     };
 
     Ok(UninitializedSessionBootstrap {
@@ -670,6 +777,41 @@ mod tests {
     use crate::utils::test_utils::TestEnvVarGuard;
     use tempfile::tempdir;
 
+    fn test_session() -> SessionContext {
+        SessionContext {
+            client: Client::new(),
+            model: String::new(),
+            api_key: String::new(),
+            base_url: String::new(),
+            provider_name: String::new(),
+            provider_display_name: String::new(),
+            logging: LoggingState::new(None).unwrap(),
+            stream_cancel_token: None,
+            current_stream_id: 0,
+            last_retry_time: Instant::now(),
+            retrying_message_index: None,
+            is_refining: false,
+            original_refining_content: None,
+            last_refine_prompt: None,
+            refine_instructions: DEFAULT_REFINE_INSTRUCTIONS.to_string(),
+            refine_prefix: DEFAULT_REFINE_PREFIX.to_string(),
+            startup_env_only: false,
+            mcp_disabled: false,
+            active_character: None,
+            character_greeting_shown: false,
+            greeting_index: 0,
+            has_received_assistant_message: false,
+            tool_pipeline: ToolPipelineState::default(),
+            mcp_init: McpInitState::default(),
+            active_assistant_message_index: None,
+            mcp_tools_enabled: false,
+            mcp_tools_unsupported: false,
+            active_lore_entries: Vec::new(),
+            group: None,
+            macros: MacroState::default(),
+        }
+    }
+
     #[test]
     fn theme_from_appearance_matches_light_theme() {
         let theme = theme_from_appearance(Appearance::Light);
@@ -722,6 +864,7 @@ mod tests {
                 config: &config,
                 pre_resolved_session: Some(provider_session.clone()),
                 character: None,
+                no_default_character: false,
                 character_service: &mut service,
             }))
             .expect("prepare_with_auth");
@@ -761,6 +904,7 @@ mod tests {
                 config: &config,
                 pre_resolved_session: None,
                 character: None,
+                no_default_character: false,
                 character_service: &mut service,
             }))
             .expect("prepare_with_auth");
@@ -798,10 +942,17 @@ mod tests {
             tool_call_id: Some("call".into()),
         });
         pipeline.active_tool_request = pipeline.pending_tool_queue.front().cloned();
+        pipeline.running_tool_calls.push(RunningToolCall {
+            request: pipeline.pending_tool_queue[0].clone(),
+            running_line_id: 1,
+            decision: AuditDecision::Yolo,
+            started_at: Instant::now(),
+        });
         pipeline.set_continuation(Vec::new(), Vec::new());
 
         pipeline.reset();
 
+        assert!(pipeline.running_tool_calls.is_empty());
         assert!(pipeline.pending_tool_queue.is_empty());
         assert!(pipeline.pending_sampling_queue.is_empty());
         assert!(pipeline.active_tool_request.is_none());
@@ -841,6 +992,7 @@ mod tests {
             tool_call_id: Some("keep".into()),
             raw_arguments: None,
             assistant_message_index: Some(1),
+            output: None,
         });
         pipeline.tool_result_history.push(ToolResultRecord {
             tool_name: "drop".into(),
@@ -853,6 +1005,7 @@ mod tests {
             tool_call_id: Some("drop".into()),
             raw_arguments: None,
             assistant_message_index: Some(3),
+            output: None,
         });
 
         pipeline.prune_for_assistant_index(3);
@@ -914,34 +1067,7 @@ mod tests {
     fn session_context_set_character() {
         use crate::character::card::{CharacterCard, CharacterData};
 
-        let mut session = SessionContext {
-            client: Client::new(),
-            model: String::new(),
-            api_key: String::new(),
-            base_url: String::new(),
-            provider_name: String::new(),
-            provider_display_name: String::new(),
-            logging: LoggingState::new(None).unwrap(),
-            stream_cancel_token: None,
-            current_stream_id: 0,
-            last_retry_time: Instant::now(),
-            retrying_message_index: None,
-            is_refining: false,
-            original_refining_content: None,
-            last_refine_prompt: None,
-            refine_instructions: DEFAULT_REFINE_INSTRUCTIONS.to_string(),
-            refine_prefix: DEFAULT_REFINE_PREFIX.to_string(),
-            startup_env_only: false,
-            mcp_disabled: false,
-            active_character: None,
-            character_greeting_shown: false,
-            has_received_assistant_message: false,
-            tool_pipeline: ToolPipelineState::default(),
-            mcp_init: McpInitState::default(),
-            active_assistant_message_index: None,
-            mcp_tools_enabled: false,
-            mcp_tools_unsupported: false,
-        };
+        let mut session = test_session();
 
         let card = CharacterCard {
             spec: "chara_card_v2".to_string(),
@@ -960,6 +1086,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -974,24 +1101,6 @@ mod tests {
         use crate::character::card::{CharacterCard, CharacterData};
 
         let mut session = SessionContext {
-            client: Client::new(),
-            model: String::new(),
-            api_key: String::new(),
-            base_url: String::new(),
-            provider_name: String::new(),
-            provider_display_name: String::new(),
-            logging: LoggingState::new(None).unwrap(),
-            stream_cancel_token: None,
-            current_stream_id: 0,
-            last_retry_time: Instant::now(),
-            retrying_message_index: None,
-            is_refining: false,
-            original_refining_content: None,
-            last_refine_prompt: None,
-            refine_instructions: DEFAULT_REFINE_INSTRUCTIONS.to_string(),
-            refine_prefix: DEFAULT_REFINE_PREFIX.to_string(),
-            startup_env_only: false,
-            mcp_disabled: false,
             active_character: Some(CharacterCard {
                 spec: "chara_card_v2".to_string(),
                 spec_version: "2.0".to_string(),
@@ -1009,15 +1118,11 @@ mod tests {
                     tags: None,
                     creator: None,
                     character_version: None,
+                    ..Default::default()
                 },
             }),
             character_greeting_shown: true,
-            has_received_assistant_message: false,
-            tool_pipeline: ToolPipelineState::default(),
-            mcp_init: McpInitState::default(),
-            active_assistant_message_index: None,
-            mcp_tools_enabled: false,
-            mcp_tools_unsupported: false,
+            ..test_session()
         };
 
         session.clear_character();
@@ -1030,24 +1135,6 @@ mod tests {
         use crate::character::card::{CharacterCard, CharacterData};
 
         let mut session = SessionContext {
-            client: Client::new(),
-            model: String::new(),
-            api_key: String::new(),
-            base_url: String::new(),
-            provider_name: String::new(),
-            provider_display_name: String::new(),
-            logging: LoggingState::new(None).unwrap(),
-            stream_cancel_token: None,
-            current_stream_id: 0,
-            last_retry_time: Instant::now(),
-            retrying_message_index: None,
-            is_refining: false,
-            original_refining_content: None,
-            last_refine_prompt: None,
-            refine_instructions: DEFAULT_REFINE_INSTRUCTIONS.to_string(),
-            refine_prefix: DEFAULT_REFINE_PREFIX.to_string(),
-            startup_env_only: false,
-            mcp_disabled: false,
             active_character: Some(CharacterCard {
                 spec: "chara_card_v2".to_string(),
                 spec_version: "2.0".to_string(),
@@ -1065,15 +1152,10 @@ mod tests {
                     tags: None,
                     creator: None,
                     character_version: None,
+                    ..Default::default()
                 },
             }),
-            character_greeting_shown: false,
-            has_received_assistant_message: false,
-            tool_pipeline: ToolPipelineState::default(),
-            mcp_init: McpInitState::default(),
-            active_assistant_message_index: None,
-            mcp_tools_enabled: false,
-            mcp_tools_unsupported: false,
+            ..test_session()
         };
 
         // Should show greeting when character is active and greeting not shown
@@ -1089,24 +1171,6 @@ mod tests {
         use crate::character::card::{CharacterCard, CharacterData};
 
         let session = SessionContext {
-            client: Client::new(),
-            model: String::new(),
-            api_key: String::new(),
-            base_url: String::new(),
-            provider_name: String::new(),
-            provider_display_name: String::new(),
-            logging: LoggingState::new(None).unwrap(),
-            stream_cancel_token: None,
-            current_stream_id: 0,
-            last_retry_time: Instant::now(),
-            retrying_message_index: None,
-            is_refining: false,
-            original_refining_content: None,
-            last_refine_prompt: None,
-            refine_instructions: DEFAULT_REFINE_INSTRUCTIONS.to_string(),
-            refine_prefix: DEFAULT_REFINE_PREFIX.to_string(),
-            startup_env_only: false,
-            mcp_disabled: false,
             active_character: Some(CharacterCard {
                 spec: "chara_card_v2".to_string(),
                 spec_version: "2.0".to_string(),
@@ -1115,7 +1179,8 @@ mod tests {
                     description: "Test character".to_string(),
                     personality: "Friendly".to_string(),
                     scenario: "Testing".to_string(),
-                    first_mes: "   ".to_string(), // Empty/whitespace greeting
+                    first_mes: "   ".to_string(),
+                    // Empty/whitespace greeting
                     mes_example: String::new(),
                     creator_notes: None,
                     system_prompt: None,
@@ -1124,15 +1189,10 @@ mod tests {
                     tags: None,
                     creator: None,
                     character_version: None,
+                    ..Default::default()
                 },
             }),
-            character_greeting_shown: false,
-            has_received_assistant_message: false,
-            tool_pipeline: ToolPipelineState::default(),
-            mcp_init: McpInitState::default(),
-            active_assistant_message_index: None,
-            mcp_tools_enabled: false,
-            mcp_tools_unsupported: false,
+            ..test_session()
         };
 
         // Should not show empty/whitespace greeting
@@ -1179,6 +1239,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -1242,6 +1303,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -1292,6 +1354,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -1319,34 +1382,7 @@ mod tests {
 
     #[test]
     fn session_context_get_character_returns_none_initially() {
-        let session = SessionContext {
-            client: Client::new(),
-            model: String::new(),
-            api_key: String::new(),
-            base_url: String::new(),
-            provider_name: String::new(),
-            provider_display_name: String::new(),
-            logging: LoggingState::new(None).unwrap(),
-            stream_cancel_token: None,
-            current_stream_id: 0,
-            last_retry_time: Instant::now(),
-            retrying_message_index: None,
-            is_refining: false,
-            original_refining_content: None,
-            last_refine_prompt: None,
-            refine_instructions: DEFAULT_REFINE_INSTRUCTIONS.to_string(),
-            refine_prefix: DEFAULT_REFINE_PREFIX.to_string(),
-            startup_env_only: false,
-            mcp_disabled: false,
-            active_character: None,
-            character_greeting_shown: false,
-            has_received_assistant_message: false,
-            tool_pipeline: ToolPipelineState::default(),
-            mcp_init: McpInitState::default(),
-            active_assistant_message_index: None,
-            mcp_tools_enabled: false,
-            mcp_tools_unsupported: false,
-        };
+        let session = test_session();
 
         assert!(session.get_character().is_none());
         assert!(!session.should_show_greeting());
@@ -1356,34 +1392,7 @@ mod tests {
     fn session_context_greeting_lifecycle() {
         use crate::character::card::{CharacterCard, CharacterData};
 
-        let mut session = SessionContext {
-            client: Client::new(),
-            model: String::new(),
-            api_key: String::new(),
-            base_url: String::new(),
-            provider_name: String::new(),
-            provider_display_name: String::new(),
-            logging: LoggingState::new(None).unwrap(),
-            stream_cancel_token: None,
-            current_stream_id: 0,
-            last_retry_time: Instant::now(),
-            retrying_message_index: None,
-            is_refining: false,
-            original_refining_content: None,
-            last_refine_prompt: None,
-            refine_instructions: DEFAULT_REFINE_INSTRUCTIONS.to_string(),
-            refine_prefix: DEFAULT_REFINE_PREFIX.to_string(),
-            startup_env_only: false,
-            mcp_disabled: false,
-            active_character: None,
-            character_greeting_shown: false,
-            has_received_assistant_message: false,
-            tool_pipeline: ToolPipelineState::default(),
-            mcp_init: McpInitState::default(),
-            active_assistant_message_index: None,
-            mcp_tools_enabled: false,
-            mcp_tools_unsupported: false,
-        };
+        let mut session = test_session();
 
         // Initially no greeting
         assert!(!session.should_show_greeting());
@@ -1406,6 +1415,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -1431,34 +1441,7 @@ mod tests {
     fn session_context_reselecting_same_character_preserves_greeting_flag() {
         use crate::character::card::{CharacterCard, CharacterData};
 
-        let mut session = SessionContext {
-            client: Client::new(),
-            model: String::new(),
-            api_key: String::new(),
-            base_url: String::new(),
-            provider_name: String::new(),
-            provider_display_name: String::new(),
-            logging: LoggingState::new(None).unwrap(),
-            stream_cancel_token: None,
-            current_stream_id: 0,
-            last_retry_time: Instant::now(),
-            retrying_message_index: None,
-            is_refining: false,
-            original_refining_content: None,
-            last_refine_prompt: None,
-            refine_instructions: DEFAULT_REFINE_INSTRUCTIONS.to_string(),
-            refine_prefix: DEFAULT_REFINE_PREFIX.to_string(),
-            startup_env_only: false,
-            mcp_disabled: false,
-            active_character: None,
-            character_greeting_shown: false,
-            has_received_assistant_message: false,
-            tool_pipeline: ToolPipelineState::default(),
-            mcp_init: McpInitState::default(),
-            active_assistant_message_index: None,
-            mcp_tools_enabled: false,
-            mcp_tools_unsupported: false,
-        };
+        let mut session = test_session();
 
         let card = CharacterCard {
             spec: "chara_card_v2".to_string(),
@@ -1477,6 +1460,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -1498,34 +1482,7 @@ mod tests {
     fn session_context_selecting_different_character_resets_greeting_flag() {
         use crate::character::card::{CharacterCard, CharacterData};
 
-        let mut session = SessionContext {
-            client: Client::new(),
-            model: String::new(),
-            api_key: String::new(),
-            base_url: String::new(),
-            provider_name: String::new(),
-            provider_display_name: String::new(),
-            logging: LoggingState::new(None).unwrap(),
-            stream_cancel_token: None,
-            current_stream_id: 0,
-            last_retry_time: Instant::now(),
-            retrying_message_index: None,
-            is_refining: false,
-            original_refining_content: None,
-            last_refine_prompt: None,
-            refine_instructions: DEFAULT_REFINE_INSTRUCTIONS.to_string(),
-            refine_prefix: DEFAULT_REFINE_PREFIX.to_string(),
-            startup_env_only: false,
-            mcp_disabled: false,
-            active_character: None,
-            character_greeting_shown: false,
-            has_received_assistant_message: false,
-            tool_pipeline: ToolPipelineState::default(),
-            mcp_init: McpInitState::default(),
-            active_assistant_message_index: None,
-            mcp_tools_enabled: false,
-            mcp_tools_unsupported: false,
-        };
+        let mut session = test_session();
 
         let card1 = CharacterCard {
             spec: "chara_card_v2".to_string(),
@@ -1544,6 +1501,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -1564,6 +1522,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
 
@@ -4,11 +4,29 @@ use std::time::Instant;
 use super::App;
 use crate::api::{ChatMessage, ChatToolDefinition, ChatToolFunction};
 use crate::core::chat_stream::StreamParams;
+use crate::mcp::routing::{
+    bm25_scores, rank_indices, tokenize, RouteReason, RoutedTool, ToolRoutingTurn,
+    MCP_TOOL_ROUTING_HISTORY, MCP_TOOL_ROUTING_LIMIT,
+};
 use serde_json::json;
 use tokio_util::sync::CancellationToken;
 
 const MCP_RESOURCES_MARKER: &str = "MCP resources and templates (by server id):";
 const MCP_PAYLOAD_NOTE_MARKER: &str = "MCP tool payload retention note:";
+/// Starts the user-role message that carries images from tool results.
+pub(super) const TOOL_IMAGES_MARKER: &str = "IMAGES FROM TOOL RESULTS (system-added):";
+/// Recent user and assistant messages the tool router matches against.
+const TOOL_ROUTING_CONTEXT_MESSAGES: usize = 4;
+/// Query terms kept on each routing turn for the `/tools` view.
+const TOOL_ROUTING_QUERY_TERMS: usize = 16;
+
+/// A server tool eligible for this request, with the terms it is ranked by.
+struct ToolCandidate {
+    server_id: String,
+    pinned: bool,
+    terms: Vec<String>,
+    definition: ChatToolDefinition,
+}
 
 impl App {
     pub fn is_current_stream(&self, stream_id: u64) -> bool {
@@ -62,7 +80,7 @@ impl App {
         let tools = if self.session.mcp_tools_unsupported {
             None
         } else {
-            self.collect_mcp_tools()
+            self.collect_mcp_tools(&api_messages)
         };
         let mut api_messages = api_messages;
         if tools.is_some() {
@@ -79,6 +97,7 @@ impl App {
         }
         self.inject_tool_payload_history(&mut api_messages);
         self.inject_tool_summary_history(&mut api_messages);
+        self.inject_resource_updates(&mut api_messages);
         self.session
             .tool_pipeline
             .set_continuation(api_messages.clone(), base_messages);
@@ -103,8 +122,11 @@ impl App {
         self.session.last_retry_time = instant;
     }
 
-    fn collect_mcp_tools(&self) -> Option<Vec<ChatToolDefinition>> {
-        let mut tools = Vec::new();
+    fn collect_mcp_tools(
+        &mut self,
+        api_messages: &[ChatMessage],
+    ) -> Option<Vec<ChatToolDefinition>> {
+        let mut candidates = Vec::new();
         let mut has_resources = false;
         let mut can_list_resources = false;
         let mut any_enabled = false;
@@ -173,17 +195,47 @@ impl App {
                     .map(|desc| format!("[MCP: {}] {}", server_label, desc))
                     .or_else(|| Some(format!("[MCP: {}] MCP tool", server_label)));
 
-                tools.push(ChatToolDefinition {
-                    kind: "function".to_string(),
-                    function: ChatToolFunction {
-                        name: tool.name.clone(),
-                        description,
-                        parameters,
+                // Names count twice so a name match outranks a passing mention.
+                let mut terms = tokenize(&tool.name);
+                terms.extend(tokenize(&tool.name));
+                terms.extend(tokenize(description.as_deref().unwrap_or_default()));
+                if let Some(properties) = tool.input_schema.properties.as_ref() {
+                    for name in properties.keys() {
+                        terms.extend(tokenize(name));
+                    }
+                }
+
+                candidates.push(ToolCandidate {
+                    server_id: server.config.id.clone(),
+                    pinned: server.config.is_pinned_tool(&tool.name),
+                    terms,
+                    definition: ChatToolDefinition {
+                        kind: "function".to_string(),
+                        function: ChatToolFunction {
+                            name: tool.name.clone(),
+                            description,
+                            parameters,
+                        },
                     },
                 });
             }
         }
 
+        let limit = self
+            .config
+            .mcp_tool_routing_limit
+            .unwrap_or(MCP_TOOL_ROUTING_LIMIT);
+        let mut tools = Vec::new();
+        if !candidates.is_empty() {
+            let (routed, turn) = route_tools(candidates, api_messages, limit);
+            tools = routed;
+            let history = &mut self.session.tool_pipeline.tool_routing_history;
+            if history.len() >= MCP_TOOL_ROUTING_HISTORY {
+                history.pop_front();
+            }
+            history.push_back(turn);
+        }
+
         if has_resources {
             tools.push(ChatToolDefinition {
                 kind: "function".to_string(),
@@ -406,6 +458,53 @@ impl App {
         api_messages.splice(insert_pos..insert_pos, history_messages);
     }
 
+    /// Inserts content from watched resources that changed since the last
+    /// request, then clears their pending flags so each update is sent once.
+    fn inject_resource_updates(&mut self, api_messages: &mut Vec<ChatMessage>) {
+        if self.session.mcp_disabled {
+            return;
+        }
+
+        let mut updates = Vec::new();
+        for server in self.mcp.servers_mut() {
+            if !server.config.is_enabled() {
+                continue;
+            }
+            let server_id = server.config.id.clone();
+            for watch in server.resource_watches.values_mut() {
+                if !watch.pending_injection {
+                    continue;
+                }
+                watch.pending_injection = false;
+                let Some(content) = watch.content.as_ref() else {
+                    continue;
+                };
+                updates.push(ChatMessage {
+                    role: "assistant".to_string(),
+                    content: format!(
+                        "RESOURCE UPDATE (system-added from MCP subscription): server_id={server_id} uri={}\n\n{content}",
+                        watch.uri
+                    ),
+                    name: None,
+                    tool_call_id: None,
+                    tool_calls: None,
+                    images: Vec::new(),
+                    is_prefill: false,
+                });
+            }
+        }
+
+        if updates.is_empty() {
+            return;
+        }
+
+        let insert_pos = api_messages
+            .iter()
+            .rposition(|msg| msg.role == "user")
+            .unwrap_or(api_messages.len());
+        api_messages.splice(insert_pos..insert_pos, updates);
+    }
+
     fn inject_tool_summary_history(&self, api_messages: &mut Vec<ChatMessage>) {
         if self.session.tool_pipeline.tool_result_history.is_empty() {
             return;
@@ -452,6 +551,8 @@ impl App {
                 name: None,
                 tool_call_id: None,
                 tool_calls: None,
+                images: Vec::new(),
+                is_prefill: false,
             });
         }
 
@@ -467,6 +568,130 @@ impl App {
     }
 }
 
+/// Picks the server tools to offer. Below `limit` candidates (or with a
+/// limit of 0) every tool is offered; otherwise pinned tools and tools already
+/// called this turn are kept and the remaining slots go to the tools that
+/// best match the recent conversation.
+fn route_tools(
+    candidates: Vec<ToolCandidate>,
+    api_messages: &[ChatMessage],
+    limit: usize,
+) -> (Vec<ChatToolDefinition>, ToolRoutingTurn) {
+    let total = candidates.len();
+    let mut turn = ToolRoutingTurn {
+        at: chrono::Local::now(),
+        candidates: total,
+        offered: Vec::new(),
+        query_terms: Vec::new(),
+    };
+
+    if limit == 0 || total <= limit {
+        turn.offered = candidates
+            .iter()
+            .map(|candidate| RoutedTool {
+                server_id: candidate.server_id.clone(),
+                tool: candidate.definition.function.name.clone(),
+                score: 0.0,
+                reason: RouteReason::All,
+            })
+            .collect();
+        let tools = candidates
+            .into_iter()
+            .map(|candidate| candidate.definition)
+            .collect();
+        return (tools, turn);
+    }
+
+    let query_terms = tokenize(&routing_query_text(api_messages));
+    let recent = tools_called_this_turn(api_messages);
+    let documents: Vec<Vec<String>> = candidates
+        .iter()
+        .map(|candidate| candidate.terms.clone())
+        .collect();
+    let scores = bm25_scores(&query_terms, &documents);
+
+    let mut reasons: Vec<Option<RouteReason>> = candidates
+        .iter()
+        .map(|candidate| {
+            if candidate.pinned {
+                Some(RouteReason::Pinned)
+            } else if recent.contains(&candidate.definition.function.name) {
+                Some(RouteReason::Recent)
+            } else {
+                None
+            }
+        })
+        .collect();
+    let mut selected = reasons.iter().filter(|reason| reason.is_some()).count();
+    for index in rank_indices(&scores) {
+        if selected >= limit {
+            break;
+        }
+        if reasons[index].is_none() {
+            reasons[index] = Some(RouteReason::Ranked);
+            selected += 1;
+        }
+    }
+
+    let mut seen = HashSet::new();
+    turn.query_terms = query_terms
+        .into_iter()
+        .filter(|term| seen.insert(term.clone()))
+        .take(TOOL_ROUTING_QUERY_TERMS)
+        .collect();
+    let mut tools = Vec::with_capacity(selected);
+    for ((candidate, reason), score) in candidates.into_iter().zip(reasons).zip(scores) {
+        let Some(reason) = reason else {
+            continue;
+        };
+        turn.offered.push(RoutedTool {
+            server_id: candidate.server_id,
+            tool: candidate.definition.function.name.clone(),
+            score,
+            reason,
+        });
+        tools.push(candidate.definition);
+    }
+    (tools, turn)
+}
+
+/// Joins the most recent user and assistant messages into the ranking query.
+fn routing_query_text(api_messages: &[ChatMessage]) -> String {
+    let mut recent: Vec<&str> = api_messages
+        .iter()
+        .rev()
+        .filter(|message| matches!(message.role.as_str(), "user" | "assistant"))
+        .filter(|message| !is_tool_images_message(message))
+        .map(|message| message.content.as_str())
+        .filter(|content| !content.trim().is_empty())
+        .take(TOOL_ROUTING_CONTEXT_MESSAGES)
+        .collect();
+    recent.reverse();
+    recent.join("\n")
+}
+
+/// Names of tools the model called after the latest user message, so a
+/// multi-step tool chain keeps the tools it is using. Tool-image messages are
+/// sent as the user but don't start a new turn.
+fn tools_called_this_turn(api_messages: &[ChatMessage]) -> HashSet<String> {
+    let start = api_messages
+        .iter()
+        .rposition(|message| message.role == "user" && !is_tool_images_message(message))
+        .map_or(0, |index| index + 1);
+    api_messages[start..]
+        .iter()
+        .filter_map(|message| message.tool_calls.as_ref())
+        .flatten()
+        .map(|call| call.function.name.clone())
+        .collect()
+}
+
+fn is_tool_images_message(message: &ChatMessage) -> bool {
+    message.role == "user"
+        && message.tool_call_id.is_none()
+        && message.content.starts_with(TOOL_IMAGES_MARKER)
+}
+
 fn inject_mcp_preamble(api_messages: &mut Vec<ChatMessage>) {
     let preamble = crate::core::builtin_mcp::builtin_mcp_preamble().trim();
     if preamble.is_empty() {
@@ -491,6 +716,8 @@ fn inject_mcp_preamble(api_messages: &mut Vec<ChatMessage>) {
             name: None,
             tool_call_id: None,
             tool_calls: None,
+            images: Vec::new(),
+            is_prefill: false,
         },
     );
 }
@@ -571,6 +798,8 @@ fn inject_or_replace_system_block(api_messages: &mut Vec<ChatMessage>, marker: &
             name: None,
             tool_call_id: None,
             tool_calls: None,
+            images: Vec::new(),
+            is_prefill: false,
         },
     );
 }
@@ -219,6 +219,8 @@ fn start_new_stream_preserves_tool_history_and_clears_transient_state() {
         name: None,
         tool_call_id: Some("call-1".to_string()),
         tool_calls: None,
+        images: Vec::new(),
+        is_prefill: false,
     });
     app.session.tool_pipeline.continuation_messages = Some(StreamContinuation {
         api_messages: vec![ChatMessage {
@@ -227,6 +229,8 @@ fn start_new_stream_preserves_tool_history_and_clears_transient_state() {
             name: None,
             tool_call_id: None,
             tool_calls: None,
+            images: Vec::new(),
+            is_prefill: false,
         }],
         api_messages_base: vec![],
     });
@@ -245,6 +249,7 @@ fn start_new_stream_preserves_tool_history_and_clears_transient_state() {
             tool_call_id: Some("call-1".to_string()),
             raw_arguments: Some("{\"q\":\"now\"}".to_string()),
             assistant_message_index: Some(0),
+            output: None,
         });
     app.session
         .tool_pipeline
@@ -265,6 +270,8 @@ fn start_new_stream_preserves_tool_history_and_clears_transient_state() {
                         arguments: "{\"q\":\"now\"}".to_string(),
                     },
                 }]),
+                images: Vec::new(),
+                is_prefill: false,
             },
             tool_message: ChatMessage {
                 role: "tool".to_string(),
@@ -272,6 +279,8 @@ fn start_new_stream_preserves_tool_history_and_clears_transient_state() {
                 name: None,
                 tool_call_id: Some("call-1".to_string()),
                 tool_calls: None,
+                images: Vec::new(),
+                is_prefill: false,
             },
             assistant_message_index: Some(0),
         });
@@ -373,17 +382,10 @@ fn build_stream_params_includes_mcp_tools() {
             id: "alpha".to_string(),
             display_name: "Alpha MCP".to_string(),
             base_url: Some("https://mcp.example.com".to_string()),
-            command: None,
-            args: None,
-            env: None,
-            headers: None,
             transport: Some("streamable-http".to_string()),
             allowed_tools: Some(vec!["search".to_string()]),
-            protocol_version: None,
             enabled: Some(true),
-            tool_payloads: None,
-            tool_payload_window: None,
-            yolo: None,
+            ..Default::default()
         });
     app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
 
@@ -436,6 +438,8 @@ fn build_stream_params_includes_mcp_tools() {
             name: None,
             tool_call_id: None,
             tool_calls: None,
+            images: Vec::new(),
+            is_prefill: false,
         }],
         CancellationToken::new(),
         1,
@@ -458,6 +462,145 @@ fn build_stream_params_includes_mcp_tools() {
     assert!(description.contains("Alpha MCP"));
 }
 
+#[test]
+fn build_stream_params_routes_large_tool_catalogs() {
+    let mut app = create_test_app();
+    app.config.mcp_tool_routing_limit = Some(2);
+    app.config.mcp_servers.push(McpServerConfig {
+        id: "alpha".to_string(),
+        display_name: "Alpha".to_string(),
+        command: Some("alpha-mcp".to_string()),
+        transport: Some("stdio".to_string()),
+        enabled: Some(true),
+        pinned_tools: Some(vec!["read_file".to_string()]),
+        ..Default::default()
+    });
+    app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
+
+    let tool = |name: &str, description: &str| Tool {
+        annotations: None,
+        description: Some(description.to_string()),
+        execution: None,
+        icons: Vec::new(),
+        input_schema: ToolInputSchema::new(Vec::new(), None, None),
+        meta: None,
+        name: name.to_string(),
+        output_schema: None,
+        title: None,
+    };
+    app.mcp
+        .server_mut("alpha")
+        .expect("missing MCP server state")
+        .set_cached_tools(ListToolsResult {
+            meta: None,
+            next_cursor: None,
+            tools: vec![
+                tool("read_file", "Read a file from disk"),
+                tool("get_forecast", "Weather forecast for a city"),
+                tool("search_docs", "Search the documentation"),
+                tool("list_calendar", "List calendar events"),
+            ],
+        });
+    let message = |role: &str, content: &str| ChatMessage {
+        role: role.to_string(),
+        content: content.to_string(),
+        name: None,
+        tool_call_id: None,
+        tool_calls: None,
+        images: Vec::new(),
+        is_prefill: false,
+    };
+
+    let params = app.build_stream_params(
+        vec![message(
+            "user",
+            "Will it rain in Paris? Check the forecast.",
+        )],
+        CancellationToken::new(),
+        1,
+    );
+    let names: Vec<String> = params
+        .tools
+        .expect("expected MCP tools")
+        .into_iter()
+        .map(|tool| tool.function.name)
+        .filter(|name| name != crate::mcp::MCP_INSTANT_RECALL_TOOL)
+        .filter(|name| name != crate::mcp::MCP_LIST_RESOURCES_TOOL)
+        .collect();
+    assert_eq!(names, vec!["read_file", "get_forecast"]);
+
+    // Tools called earlier in the turn stay available for the follow-up.
+    let mut call = message("assistant", "");
+    call.tool_calls = Some(vec![ChatToolCall {
+        id: "call-1".to_string(),
+        kind: "function".to_string(),
+        function: ChatToolCallFunction {
+            name: "list_calendar".to_string(),
+            arguments: "{}".to_string(),
+        },
+    }]);
+    let params = app.build_stream_params(
+        vec![
+            message("user", "Will it rain in Paris? Check the forecast."),
+            call,
+        ],
+        CancellationToken::new(),
+        2,
+    );
+    let names: Vec<String> = params
+        .tools
+        .expect("expected MCP tools")
+        .into_iter()
+        .map(|tool| tool.function.name)
+        .collect();
+    assert!(names.contains(&"list_calendar".to_string()));
+    assert!(!names.contains(&"search_docs".to_string()));
+
+    // An image from a tool result is sent as the user but is still the same turn.
+    let mut call = message("assistant", "");
+    call.tool_calls = Some(vec![ChatToolCall {
+        id: "call-1".to_string(),
+        kind: "function".to_string(),
+        function: ChatToolCallFunction {
+            name: "list_calendar".to_string(),
+            arguments: "{}".to_string(),
+        },
+    }]);
+    let mut result = message("tool", "calendar.png");
+    result.tool_call_id = Some("call-1".to_string());
+    let images = message(
+        "user",
+        "IMAGES FROM TOOL RESULTS (system-added): list_calendar (call_id=call-1)",
+    );
+    let params = app.build_stream_params(
+        vec![
+            message("user", "Will it rain in Paris? Check the forecast."),
+            call,
+            result,
+            images,
+        ],
+        CancellationToken::new(),
+        3,
+    );
+    let names: Vec<String> = params
+        .tools
+        .expect("expected MCP tools")
+        .into_iter()
+        .map(|tool| tool.function.name)
+        .collect();
+    assert!(names.contains(&"list_calendar".to_string()));
+
+    let history = &app.session.tool_pipeline.tool_routing_history;
+    assert_eq!(history.len(), 3);
+    assert_eq!(history[0].candidates, 4);
+    assert!(history[0].routed());
+    assert_eq!(
+        history[0].offered[0].reason,
+        crate::mcp::routing::RouteReason::Pinned
+    );
+    assert!(history[0].query_terms.contains(&"forecast".to_string()));
+}
+
 #[test]
 fn build_stream_params_includes_mcp_resources() {
     let mut app = create_test_app();
@@ -468,17 +611,9 @@ fn build_stream_params_includes_mcp_resources() {
             id: "alpha".to_string(),
             display_name: "Alpha MCP".to_string(),
             base_url: Some("https://mcp.example.com".to_string()),
-            command: None,
-            args: None,
-            env: None,
-            headers: None,
             transport: Some("streamable-http".to_string()),
-            allowed_tools: None,
-            protocol_version: None,
             enabled: Some(true),
-            tool_payloads: None,
-            tool_payload_window: None,
-            yolo: None,
+            ..Default::default()
         });
     app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
 
@@ -526,6 +661,8 @@ fn build_stream_params_includes_mcp_resources() {
             name: None,
             tool_call_id: None,
             tool_calls: None,
+            images: Vec::new(),
+            is_prefill: false,
         }],
         CancellationToken::new(),
         1,
@@ -601,17 +738,9 @@ fn build_stream_params_includes_tool_history_and_payloads() {
         id: "alpha".to_string(),
         display_name: "Alpha MCP".to_string(),
         base_url: Some("https://mcp.example.com".to_string()),
-        command: None,
-        args: None,
-        env: None,
-        headers: None,
         transport: Some("streamable-http".to_string()),
-        allowed_tools: None,
-        protocol_version: None,
         enabled: Some(true),
-        tool_payloads: None,
-        tool_payload_window: None,
-        yolo: None,
+        ..Default::default()
     });
     app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
     app.session
@@ -628,6 +757,7 @@ fn build_stream_params_includes_tool_history_and_payloads() {
             tool_call_id: Some("call-1".to_string()),
             raw_arguments: Some("{\"q\":\"test\"}".to_string()),
             assistant_message_index: None,
+            output: None,
         });
     app.session
         .tool_pipeline
@@ -643,6 +773,7 @@ fn build_stream_params_includes_tool_history_and_payloads() {
             tool_call_id: Some("call-2".to_string()),
             raw_arguments: Some("{\"q\":\"missing\"}".to_string()),
             assistant_message_index: None,
+            output: None,
         });
 
     let assistant_message = ChatMessage {
@@ -658,6 +789,8 @@ fn build_stream_params_includes_tool_history_and_payloads() {
                 arguments: "{\"q\":\"test\"}".to_string(),
             },
         }]),
+        images: Vec::new(),
+        is_prefill: false,
     };
     let tool_message = ChatMessage {
         role: "tool".to_string(),
@@ -665,6 +798,8 @@ fn build_stream_params_includes_tool_history_and_payloads() {
         name: None,
         tool_call_id: Some("call-1".to_string()),
         tool_calls: None,
+        images: Vec::new(),
+        is_prefill: false,
     };
     app.session
         .tool_pipeline
@@ -684,6 +819,8 @@ fn build_stream_params_includes_tool_history_and_payloads() {
             name: None,
             tool_call_id: None,
             tool_calls: None,
+            images: Vec::new(),
+            is_prefill: false,
         }],
         CancellationToken::new(),
         1,
@@ -743,6 +880,60 @@ fn build_stream_params_includes_tool_history_and_payloads() {
     assert!(summary_idx < last_user_idx);
 }
 
+#[test]
+fn build_stream_params_injects_pending_resource_updates_once() {
+    let mut app = create_test_app();
+    app.config.mcp_servers.push(McpServerConfig {
+        id: "alpha".to_string(),
+        display_name: "Alpha MCP".to_string(),
+        base_url: Some("https://mcp.example.com".to_string()),
+        transport: Some("streamable-http".to_string()),
+        enabled: Some(true),
+        ..Default::default()
+    });
+    app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
+    {
+        let server = app.mcp.server_mut("alpha").expect("server");
+        let mut watch =
+            crate::mcp::subscriptions::McpResourceWatch::new("db://orders".to_string(), true);
+        watch.record_read(Ok("3 open orders".to_string()), true);
+        server
+            .resource_watches
+            .insert("db://orders".to_string(), watch);
+    }
+    let user_message = ChatMessage {
+        role: "user".to_string(),
+        content: "Any news?".to_string(),
+        name: None,
+        tool_call_id: None,
+        tool_calls: None,
+        images: Vec::new(),
+        is_prefill: false,
+    };
+
+    let params = app.build_stream_params(vec![user_message.clone()], CancellationToken::new(), 1);
+    let last_user_idx = params
+        .api_messages
+        .iter()
+        .rposition(|msg| msg.role == "user")
+        .expect("missing user message");
+    let update_idx = params
+        .api_messages
+        .iter()
+        .position(|msg| msg.content.starts_with("RESOURCE UPDATE"))
+        .expect("missing resource update");
+    assert!(update_idx < last_user_idx);
+    let update = &params.api_messages[update_idx].content;
+    assert!(update.contains("uri=db://orders"));
+    assert!(update.ends_with("3 open orders"));
+
+    let params = app.build_stream_params(vec![user_message], CancellationToken::new(), 2);
+    assert!(!params
+        .api_messages
+        .iter()
+        .any(|msg| msg.content.starts_with("RESOURCE UPDATE")));
+}
+
 #[test]
 fn test_prewrap_cache_reuse_when_unchanged() {
     let mut app = create_test_app();
@@ -754,6 +945,7 @@ fn test_prewrap_cache_reuse_when_unchanged() {
                 TranscriptRole::Assistant
             },
             content: "lorem ipsum dolor sit amet consectetur adipiscing elit".into(),
+            id: None,
         });
     }
     let w = 100u16;
@@ -919,10 +1111,12 @@ fn prewrap_cache_plain_text_last_message_wrapping() {
     app.ui.messages.push_back(Message {
         role: TranscriptRole::Assistant,
         content: "Short".into(),
+        id: None,
     });
     app.ui.messages.push_back(Message {
         role: TranscriptRole::Assistant,
         content: "This is a very long plain text line that should wrap when width is small".into(),
+        id: None,
     });
 
     let width = 20u16;
@@ -1395,6 +1589,87 @@ fn complete_slash_command_reports_unknown_prefix() {
     assert_eq!(app.ui.status.as_deref(), Some("No command matches '/zzz'"));
 }
 
+#[test]
+fn complete_mcp_resource_mention_completes_server_then_uri() {
+    let mut app = create_test_app();
+    app.config.mcp_servers.push(McpServerConfig {
+        id: "alpha".to_string(),
+        display_name: "Alpha MCP".to_string(),
+        base_url: Some("https://mcp.example.com".to_string()),
+        transport: Some("streamable-http".to_string()),
+        enabled: Some(true),
+        ..Default::default()
+    });
+    app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
+    let resource = |uri: &str| Resource {
+        annotations: None,
+        description: None,
+        icons: Vec::new(),
+        meta: None,
+        mime_type: None,
+        name: uri.to_string(),
+        size: None,
+        title: None,
+        uri: uri.to_string(),
+    };
+    if let Some(server) = app.mcp.server_mut("alpha") {
+        server.cached_resources = Some(ListResourcesResult {
+            meta: None,
+            next_cursor: None,
+            resources: vec![resource("mcp://alpha/doc"), resource("mcp://alpha/data")],
+        });
+        server.cached_resource_templates = Some(ListResourceTemplatesResult {
+            meta: None,
+            next_cursor: None,
+            resource_templates: Vec::new(),
+        });
+    }
+
+    app.ui.set_input_text("Read @al".into());
+    app.ui.set_cursor_position("Read @al".chars().count());
+    assert!(app.has_mcp_resource_mention_at_cursor());
+    assert!(app.complete_mcp_resource_mention(80));
+    assert_eq!(app.ui.get_input_text(), "Read @alpha:");
+
+    assert!(app.complete_mcp_resource_mention(80));
+    assert_eq!(app.ui.get_input_text(), "Read @alpha:mcp://alpha/d");
+    assert!(app.complete_mcp_resource_mention(80));
+    assert_eq!(
+        app.ui.status.as_deref(),
+        Some("Resources: mcp://alpha/data, mcp://alpha/doc")
+    );
+
+    app.ui.set_input_text("Read @alpha:mcp://alpha/do".into());
+    app.ui
+        .set_cursor_position("Read @alpha:mcp://alpha/do".chars().count());
+    assert!(app.complete_mcp_resource_mention(80));
+    assert_eq!(app.ui.get_input_text(), "Read @alpha:mcp://alpha/doc ");
+
+    app.ui.set_input_text("Read @beta".into());
+    app.ui.set_cursor_position("Read @beta".chars().count());
+    assert!(!app.complete_mcp_resource_mention(80));
+    app.ui.set_input_text("Read @alpha:file://".into());
+    app.ui
+        .set_cursor_position("Read @alpha:file://".chars().count());
+    assert!(!app.complete_mcp_resource_mention(80));
+    assert_eq!(app.ui.get_input_text(), "Read @alpha:file://");
+
+    app.session.mcp_disabled = true;
+    app.ui.set_input_text("Read @al".into());
+    app.ui.set_cursor_position("Read @al".chars().count());
+    assert!(!app.has_mcp_resource_mention_at_cursor());
+    assert!(!app.complete_mcp_resource_mention(80));
+}
+
+#[test]
+fn mcp_resource_mention_ignores_email_like_words() {
+    let mut app = create_test_app();
+    app.ui.set_input_text("mail me@example".into());
+    app.ui
+        .set_cursor_position("mail me@example".chars().count());
+    assert!(!app.has_mcp_resource_mention_at_cursor());
+}
+
 #[test]
 fn complete_slash_command_completes_mcp_server() {
     let mut app = create_test_app();
@@ -1402,17 +1677,9 @@ fn complete_slash_command_completes_mcp_server() {
         id: "agpedia".to_string(),
         display_name: "Agpedia".to_string(),
         base_url: Some("https://mcp.example.com".to_string()),
-        command: None,
-        args: None,
-        env: None,
-        headers: None,
         transport: Some("streamable-http".to_string()),
-        allowed_tools: None,
-        protocol_version: None,
         enabled: Some(true),
-        tool_payloads: None,
-        tool_payload_window: None,
-        yolo: None,
+        ..Default::default()
     });
     app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
 
@@ -1435,17 +1702,9 @@ fn complete_slash_command_completes_yolo_server() {
         id: "agpedia".to_string(),
         display_name: "Agpedia".to_string(),
         base_url: Some("https://mcp.example.com".to_string()),
-        command: None,
-        args: None,
-        env: None,
-        headers: None,
         transport: Some("streamable-http".to_string()),
-        allowed_tools: None,
-        protocol_version: None,
         enabled: Some(true),
-        tool_payloads: None,
-        tool_payload_window: None,
-        yolo: None,
+        ..Default::default()
     });
     app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
 
@@ -1468,33 +1727,17 @@ fn complete_slash_command_lists_mcp_servers() {
         id: "agpedia".to_string(),
         display_name: "Agpedia".to_string(),
         base_url: Some("https://mcp.example.com".to_string()),
-        command: None,
-        args: None,
-        env: None,
-        headers: None,
         transport: Some("streamable-http".to_string()),
-        allowed_tools: None,
-        protocol_version: None,
         enabled: Some(true),
-        tool_payloads: None,
-        tool_payload_window: None,
-        yolo: None,
+        ..Default::default()
     });
     app.config.mcp_servers.push(McpServerConfig {
         id: "alpha".to_string(),
         display_name: "Alpha".to_string(),
         base_url: Some("https://mcp.example.com".to_string()),
-        command: None,
-        args: None,
-        env: None,
-        headers: None,
         transport: Some("streamable-http".to_string()),
-        allowed_tools: None,
-        protocol_version: None,
         enabled: Some(true),
-        tool_payloads: None,
-        tool_payload_window: None,
-        yolo: None,
+        ..Default::default()
     });
     app.mcp = crate::mcp::client::McpClientManager::from_config(&app.config);
 
@@ -1546,6 +1789,7 @@ fn test_scroll_height_consistency_with_tables_regression() {
     app.ui.messages.push_back(Message {
         role: TranscriptRole::Assistant,
         content: table_content.to_string(),
+        id: None,
     });
 
     let width = 80u16;
@@ -1626,6 +1870,7 @@ Some additional text after the table."#;
     app.ui.messages.push_back(Message {
         role: TranscriptRole::Assistant,
         content: wide_table.to_string(),
+        id: None,
     });
 
     // Use very narrow width that will force aggressive table column rebalancing
@@ -1824,6 +2069,7 @@ fn test_turn_off_character_mode_from_picker() {
             tags: None,
             creator: None,
             character_version: None,
+            ..Default::default()
         },
     };
 
@@ -1,5 +1,7 @@
+use super::session::{McpCompletionReference, McpCompletionRequest};
+use super::ui_state::{McpPromptInput, McpPromptInputTarget, UiMode};
 use super::App;
-use crate::commands::matching_commands;
+use crate::commands::{matching_commands, scan_prompt_args};
 use crate::core::message::{Message, TranscriptRole};
 use crate::ui::span::SpanKind;
 use ratatui::text::Line;
@@ -117,6 +119,119 @@ impl App {
         true
     }
 
+    /// Reports whether the word before the cursor is an `@server:uri` mention
+    /// that an enabled MCP server could complete.
+    pub fn has_mcp_resource_mention_at_cursor(&self) -> bool {
+        if self.session.mcp_disabled
+            || !self.ui.is_input_active()
+            || self.ui.mcp_prompt_input().is_some()
+            || !self.mcp.servers().any(|server| server.config.is_enabled())
+        {
+            return false;
+        }
+        let chars: Vec<char> = self.ui.get_input_text().chars().collect();
+        let cursor = self.ui.get_input_cursor_position().min(chars.len());
+        let start = word_start(&chars, cursor);
+        start < cursor && chars[start] == '@'
+    }
+
+    /// Completes `@server:` and then resource URIs from cached listings.
+    ///
+    /// Returns `false` when there is nothing to complete so Tab can fall back
+    /// to toggling focus.
+    pub fn complete_mcp_resource_mention(&mut self, term_width: u16) -> bool {
+        if !self.has_mcp_resource_mention_at_cursor() {
+            return false;
+        }
+
+        let chars: Vec<char> = self.ui.get_input_text().chars().collect();
+        let cursor = self.ui.get_input_cursor_position().min(chars.len());
+        let start = word_start(&chars, cursor);
+        let mut end = cursor;
+        while end < chars.len() && !chars[end].is_whitespace() {
+            end += 1;
+        }
+        let typed: String = chars[start + 1..cursor].iter().collect();
+        let before: String = chars[..start].iter().collect();
+        let remainder: String = chars[end..].iter().collect();
+
+        let Some((server_part, uri_prefix)) = typed.split_once(':') else {
+            return self.complete_mention_server(term_width, &typed, &before, &remainder);
+        };
+
+        let Some(server) = self
+            .mcp
+            .server(server_part)
+            .filter(|server| server.config.is_enabled())
+        else {
+            return false;
+        };
+        let server_id = server.config.id.clone();
+
+        let mut uris: Vec<String> = Vec::new();
+        if let Some(list) = &server.cached_resources {
+            uris.extend(list.resources.iter().map(|resource| resource.uri.clone()));
+        }
+        if let Some(list) = &server.cached_resource_templates {
+            uris.extend(
+                list.resource_templates
+                    .iter()
+                    .map(|template| template.uri_template.clone()),
+            );
+        }
+        if server.cached_resources.is_none() && server.cached_resource_templates.is_none() {
+            self.conversation().set_status(format!(
+                "No cached resources for {}. Run /mcp {} to refresh.",
+                server_id, server_id
+            ));
+            return false;
+        }
+
+        let mut matches: Vec<&str> = uris
+            .iter()
+            .map(String::as_str)
+            .filter(|uri| uri.starts_with(uri_prefix))
+            .collect();
+        matches.sort();
+        matches.dedup();
+
+        if matches.is_empty() {
+            return false;
+        }
+
+        self.ui.focus_input();
+        let mention_prefix = format!("{before}@{server_id}:");
+        if matches.len() == 1 {
+            apply_argument_completion(
+                &mut self.ui,
+                &mention_prefix,
+                matches[0],
+                &remainder,
+                true,
+                term_width,
+            );
+            return true;
+        }
+
+        let common = longest_common_prefix(&matches);
+        if common.len() > uri_prefix.len() {
+            apply_argument_completion(
+                &mut self.ui,
+                &mention_prefix,
+                &common,
+                &remainder,
+                false,
+                term_width,
+            );
+            return true;
+        }
+
+        let suggestions = format_mcp_server_suggestions(&matches);
+        self.conversation()
+            .set_status(format!("Resources: {}", suggestions));
+        true
+    }
+
     pub fn complete_in_place_edit(&mut self, index: usize, new_text: String) {
         let Some(actual_index) = self.ui.take_in_place_edit_index() else {
             return;
@@ -295,6 +410,61 @@ impl App {
         true
     }
 
+    fn complete_mention_server(
+        &mut self,
+        term_width: u16,
+        prefix: &str,
+        before: &str,
+        remainder: &str,
+    ) -> bool {
+        let mut server_ids: Vec<String> = self
+            .mcp
+            .servers()
+            .filter(|server| server.config.is_enabled())
+            .map(|server| server.config.id.clone())
+            .filter(|id| id.starts_with(prefix))
+            .collect();
+        server_ids.sort();
+
+        if server_ids.is_empty() {
+            return false;
+        }
+
+        self.ui.focus_input();
+        let server_ids_ref: Vec<&str> = server_ids.iter().map(String::as_str).collect();
+        let mention_prefix = format!("{before}@");
+        if server_ids_ref.len() == 1 {
+            let completion = format!("{}:", server_ids_ref[0]);
+            apply_argument_completion(
+                &mut self.ui,
+                &mention_prefix,
+                &completion,
+                remainder,
+                false,
+                term_width,
+            );
+            return true;
+        }
+
+        let common = longest_common_prefix(&server_ids_ref);
+        if common.len() > prefix.len() {
+            apply_argument_completion(
+                &mut self.ui,
+                &mention_prefix,
+                &common,
+                remainder,
+                false,
+                term_width,
+            );
+            return true;
+        }
+
+        let suggestions = format_mcp_server_suggestions(&server_ids_ref);
+        self.conversation()
+            .set_status(format!("MCP servers: {}", suggestions));
+        true
+    }
+
     fn matching_mcp_prompt_commands(&self, prefix: &str) -> Vec<String> {
         let mut commands = Vec::new();
         for server in self.mcp.servers() {
@@ -310,6 +480,228 @@ impl App {
         }
         commands
     }
+
+    /// Builds the `completion/complete` request for the argument at the
+    /// cursor, if the server being prompted advertises completions.
+    pub(crate) fn mcp_completion_request(&self) -> Option<McpCompletionRequest> {
+        self.mcp_completion_target().map(|target| target.request)
+    }
+
+    /// Completes the argument value at the cursor from the latest
+    /// `completion/complete` suggestions.
+    pub fn complete_mcp_argument(&mut self, term_width: u16) -> bool {
+        let Some(target) = self.mcp_completion_target() else {
+            return false;
+        };
+        let Some(completion) = self
+            .ui
+            .mcp_completion
+            .as_ref()
+            .filter(|completion| same_argument(&completion.request, &target.request))
+        else {
+            return false;
+        };
+        let typed = target.request.value.as_str();
+        let mut candidates: Vec<&str> = completion
+            .values
+            .iter()
+            .map(String::as_str)
+            .filter(|value| completion.request.value == typed || value.starts_with(typed))
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+        if candidates.is_empty() {
+            return false;
+        }
+
+        let chars: Vec<char> = self.ui.get_input_text().chars().collect();
+        let before: String = chars[..target.start].iter().collect();
+        let remainder: String = chars[target.end..].iter().collect();
+        let open_quote = target.quote.map(String::from).unwrap_or_default();
+        if candidates.len() == 1 {
+            let value = format!("{open_quote}{}{open_quote}", candidates[0]);
+            apply_argument_completion(
+                &mut self.ui,
+                &before,
+                &value,
+                &remainder,
+                target.inline,
+                term_width,
+            );
+            return true;
+        }
+
+        let common = longest_common_prefix(&candidates);
+        if common.chars().count() > typed.chars().count() {
+            apply_argument_completion(
+                &mut self.ui,
+                &before,
+                &format!("{open_quote}{common}"),
+                &remainder,
+                false,
+                term_width,
+            );
+            return true;
+        }
+
+        let suggestions = format_mcp_server_suggestions(&candidates);
+        self.conversation()
+            .set_status(format!("Suggestions: {}", suggestions));
+        true
+    }
+
+    fn mcp_completion_target(&self) -> Option<McpCompletionTarget> {
+        if !self.ui.is_input_active() {
+            return None;
+        }
+        if let Some(prompt) = self.ui.mcp_prompt_input() {
+            return self.prompt_form_completion_target(prompt);
+        }
+        if matches!(self.ui.mode, UiMode::Typing) {
+            return self.inline_prompt_completion_target();
+        }
+        None
+    }
+
+    /// The whole input is the value while the prompt-argument form is open.
+    fn prompt_form_completion_target(
+        &self,
+        prompt: &McpPromptInput,
+    ) -> Option<McpCompletionTarget> {
+        let server = self.mcp.server(&prompt.server_id)?;
+        if !server.config.is_enabled() || !server.supports_completions() {
+            return None;
+        }
+        let argument = prompt.pending_args.get(prompt.next_index)?;
+        let input = self.ui.get_input_text();
+        let reference = match prompt.target {
+            McpPromptInputTarget::Prompt => {
+                McpCompletionReference::Prompt(prompt.prompt_name.clone())
+            }
+            McpPromptInputTarget::ResourceTemplate(_) => {
+                McpCompletionReference::ResourceTemplate(prompt.prompt_name.clone())
+            }
+        };
+        Some(McpCompletionTarget {
+            request: McpCompletionRequest {
+                server_id: server.config.id.clone(),
+                reference,
+                argument: argument.name.clone(),
+                value: input.to_string(),
+                context: prompt.collected.clone(),
+            },
+            start: 0,
+            end: input.chars().count(),
+            inline: false,
+            quote: None,
+        })
+    }
+
+    /// Finds the `key=value` token (or the bare value of a one-argument
+    /// prompt) under the cursor in `/server:prompt …`.
+    fn inline_prompt_completion_target(&self) -> Option<McpCompletionTarget> {
+        let chars: Vec<char> = self.ui.get_input_text().chars().collect();
+        if chars.first() != Some(&'/') {
+            return None;
+        }
+        let cursor = self.ui.get_input_cursor_position().min(chars.len());
+        if chars[..cursor].contains(&'\n') {
+            return None;
+        }
+        let command_end = chars
+            .iter()
+            .position(|ch| ch.is_whitespace())
+            .unwrap_or(chars.len());
+        if cursor <= command_end {
+            return None;
+        }
+        let command: String = chars[1..command_end].iter().collect();
+        let (server_id, prompt_name) = command.split_once(':')?;
+        let server = self
+            .mcp
+            .server(server_id)
+            .filter(|server| server.config.is_enabled() && server.supports_completions())?;
+        let prompt = server
+            .cached_prompts
+            .as_ref()?
+            .prompts
+            .iter()
+            .find(|prompt| prompt.name == prompt_name)?;
+
+        let args = &chars[command_end..];
+        let (tokens, _) = scan_prompt_args(&args.iter().collect::<String>());
+        let at = cursor - command_end;
+        let current = tokens
+            .iter()
+            .position(|token| token.start <= at && at <= token.end);
+        let (start, end) =
+            current.map_or((at, at), |index| (tokens[index].start, tokens[index].end));
+        let raw = &args[start..end];
+        let (argument, value_start) = match raw.iter().position(|ch| *ch == '=') {
+            Some(eq) => {
+                let key: String = raw[..eq].iter().collect();
+                if key.contains(['"', '\'']) {
+                    return None;
+                }
+                (key, start + eq + 1)
+            }
+            None if prompt.arguments.len() == 1 => (prompt.arguments[0].name.clone(), start),
+            None => return None,
+        };
+        if !prompt.arguments.iter().any(|arg| arg.name == argument) {
+            return None;
+        }
+        let quote = args
+            .get(value_start)
+            .copied()
+            .filter(|ch| value_start < end && matches!(ch, '"' | '\''));
+        let typed_start = value_start + usize::from(quote.is_some());
+        if at < typed_start {
+            return None;
+        }
+
+        let context = tokens
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| Some(*index) != current)
+            .filter_map(|(_, token)| token.text.split_once('='))
+            .filter(|(key, _)| *key != argument)
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        Some(McpCompletionTarget {
+            request: McpCompletionRequest {
+                server_id: server.config.id.clone(),
+                reference: McpCompletionReference::Prompt(prompt.name.clone()),
+                argument,
+                value: args[typed_start..at]
+                    .iter()
+                    .filter(|ch| Some(**ch) != quote)
+                    .collect(),
+                context,
+            },
+            start: command_end + value_start,
+            end: command_end + end,
+            inline: true,
+            quote,
+        })
+    }
+}
+
+/// The argument value under the cursor and the input range it occupies.
+struct McpCompletionTarget {
+    request: McpCompletionRequest,
+    start: usize,
+    end: usize,
+    /// Inline `/server:prompt` invocations get a trailing space after a
+    /// unique completion, like slash commands.
+    inline: bool,
+    /// Opening quote of a quoted inline value; the range starts at it.
+    quote: Option<char>,
+}
+
+/// Reports whether two requests complete the same argument (ignoring the value).
+pub(crate) fn same_argument(a: &McpCompletionRequest, b: &McpCompletionRequest) -> bool {
+    a.server_id == b.server_id && a.reference == b.reference && a.argument == b.argument
 }
 
 fn apply_command_completion(
@@ -385,6 +777,14 @@ fn apply_argument_completion(
     ui.recompute_input_layout_after_edit(term_width);
 }
 
+fn word_start(chars: &[char], cursor: usize) -> usize {
+    let mut start = cursor;
+    while start > 0 && !chars[start - 1].is_whitespace() {
+        start -= 1;
+    }
+    start
+}
+
 fn longest_common_prefix(names: &[&str]) -> String {
     if names.is_empty() {
         return String::new();
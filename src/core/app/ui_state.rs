@@ -113,6 +113,35 @@ pub struct McpPromptInput {
     pub pending_args: Vec<McpPromptArgument>,
     pub collected: std::collections::HashMap<String, String>,
     pub next_index: usize,
+    pub target: McpPromptInputTarget,
+}
+
+/// What the collected values are used for once every argument is entered.
+#[derive(Debug, Clone, Default)]
+pub enum McpPromptInputTarget {
+    /// Run the MCP prompt named by `prompt_name`.
+    #[default]
+    Prompt,
+    /// Expand the resource template named by `prompt_name` for a pending message.
+    ResourceTemplate(McpResourceAttachmentDraft),
+}
+
+/// Argument suggestions from `completion/complete`, listed above the input.
+#[derive(Debug, Clone)]
+pub struct McpArgCompletion {
+    /// The request the current `values` answer (or are waiting on).
+    pub request: crate::core::app::session::McpCompletionRequest,
+    pub values: Vec<String>,
+    pub has_more: bool,
+}
+
+/// A user message waiting on `@server:uri` resource reads.
+#[derive(Debug, Clone)]
+pub struct McpResourceAttachmentDraft {
+    pub message: String,
+    pub mentions: Vec<crate::core::mcp_resource_mentions::McpResourceMention>,
+    /// Index of the mention whose template variables are being collected.
+    pub mention_index: usize,
 }
 
 /// Target message type for edit-select operations.
@@ -212,6 +241,9 @@ pub struct UiState {
     editing_assistant_message: bool,
     input_layout_cache: Option<InputLayoutCache>,
     input_revision: u64,
+    pub mcp_completion: Option<McpArgCompletion>,
+    /// Input revision and cursor that completion targets were last checked for.
+    completion_input: Option<(u64, usize)>,
 }
 
 /// Vertical movement direction for wrapped-input cursor navigation.
@@ -266,6 +298,17 @@ impl UiState {
         self.input_layout_cache = None;
     }
 
+    /// Records the current input and cursor for completion lookups, returning
+    /// `false` when neither has changed since the last call.
+    pub fn note_completion_input(&mut self) -> bool {
+        let current = (self.input_revision, self.input_cursor_position);
+        if self.completion_input == Some(current) {
+            return false;
+        }
+        self.completion_input = Some(current);
+        true
+    }
+
     pub fn in_edit_select_mode(&self) -> bool {
         matches!(self.mode, UiMode::EditSelect { .. })
     }
@@ -584,6 +627,8 @@ impl UiState {
             editing_assistant_message: false,
             input_layout_cache: None,
             input_revision: 0,
+            mcp_completion: None,
+            completion_input: None,
         }
     }
 
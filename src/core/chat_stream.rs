@@ -495,6 +495,8 @@ impl ChatStreamService {
     ///             name: None,
     ///             tool_call_id: None,
     ///             tool_calls: None,
+    ///             images: Vec::new(),
+    ///             is_prefill: false,
     ///         },
     ///     ],
     ///     tools: None,
@@ -21,6 +21,50 @@ pub enum McpToolPayloadRetention {
     All,
 }
 
+/// What happens when an MCP tool call matches a persistent policy rule.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum McpPolicyAction {
+    Allow,
+    Ask,
+    Deny,
+}
+
+impl McpPolicyAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            McpPolicyAction::Allow => "allow",
+            McpPolicyAction::Ask => "ask",
+            McpPolicyAction::Deny => "deny",
+        }
+    }
+}
+
+/// Persistent permission rule from `[[mcp_servers.policies]]`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct McpToolPolicy {
+    /// Tool name, or `*` for every tool on the server.
+    pub tool: String,
+    pub action: McpPolicyAction,
+    /// Argument matchers that must all hold for the rule to apply.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<McpArgumentMatcher>,
+}
+
+/// Matches one tool argument addressed by a JSON pointer (e.g. `/path`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct McpArgumentMatcher {
+    pub pointer: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub equals: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    /// Like `prefix`, but compares whole path components and never matches
+    /// paths containing `..`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_prefix: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CustomProvider {
     pub id: String,
@@ -74,9 +118,110 @@ pub struct Preset {
     pub pre: String,
     #[serde(default)]
     pub post: String,
+    /// Extra instructions from `[[presets.blocks]]`, injected in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocks: Vec<PresetBlock>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// A preset instruction injected at a chosen place in the request.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct PresetBlock {
+    /// Label shown when inspecting the preset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub content: String,
+    #[serde(default)]
+    pub position: PresetPosition,
+    /// Number of messages after the block when `position = "depth"`.
+    #[serde(default)]
+    pub depth: usize,
+    /// Role of the injected message. Prefill blocks are always `assistant`.
+    #[serde(default)]
+    pub role: PresetRole,
+    #[serde(default)]
+    pub when: PresetCondition,
+}
+
+/// Where a [`PresetBlock`] is injected.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum PresetPosition {
+    /// Start of the request; system blocks join the first system message.
+    #[default]
+    SystemStart,
+    /// Just before the most recent user message.
+    BeforeLastUser,
+    /// `depth` messages from the end of the conversation.
+    Depth,
+    /// A trailing assistant message the model continues from.
+    Prefill,
+}
+
+impl PresetPosition {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PresetPosition::SystemStart => "system-start",
+            PresetPosition::BeforeLastUser => "before-last-user",
+            PresetPosition::Depth => "depth",
+            PresetPosition::Prefill => "prefill",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PresetRole {
+    #[default]
+    System,
+    User,
+    Assistant,
+}
+
+impl PresetRole {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PresetRole::System => "system",
+            PresetRole::User => "user",
+            PresetRole::Assistant => "assistant",
+        }
+    }
+}
+
+/// When a [`PresetBlock`] applies.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum PresetCondition {
+    #[default]
+    Always,
+    Character,
+    NoCharacter,
+    Persona,
+    NoPersona,
+}
+
+impl PresetCondition {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PresetCondition::Always => "always",
+            PresetCondition::Character => "character",
+            PresetCondition::NoCharacter => "no-character",
+            PresetCondition::Persona => "persona",
+            PresetCondition::NoPersona => "no-persona",
+        }
+    }
+
+    pub fn matches(self, character_active: bool, persona_active: bool) -> bool {
+        match self {
+            PresetCondition::Always => true,
+            PresetCondition::Character => character_active,
+            PresetCondition::NoCharacter => !character_active,
+            PresetCondition::Persona => persona_active,
+            PresetCondition::NoPersona => !persona_active,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct McpServerConfig {
     pub id: String,
     pub display_name: String,
@@ -93,6 +238,28 @@ pub struct McpServerConfig {
     pub tool_payload_window: Option<usize>,
     #[serde(default)]
     pub yolo: Option<bool>,
+    /// Minimum server log level requested via `logging/setLevel` on connect.
+    #[serde(default)]
+    pub log_level: Option<String>,
+    /// Persistent allow/ask/deny rules, evaluated in order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policies: Option<Vec<McpToolPolicy>>,
+    /// Per-request timeout; overrides `mcp_request_timeout_seconds`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_timeout_seconds: Option<u64>,
+    /// Timeout for sampling requests that do not name their own; overrides
+    /// `mcp_sampling_timeout_seconds`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampling_timeout_seconds: Option<u64>,
+    /// Maximum number of tools kept from `tools/list`; overrides `mcp_max_tools`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tools: Option<usize>,
+    /// Let this server run several tool calls from one reply at the same time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parallel_tool_calls: Option<bool>,
+    /// Tools always offered to the model when tool routing trims the list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned_tools: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
@@ -128,9 +295,14 @@ pub struct Config {
     /// Default presets for provider/model combinations
     /// Outer key: provider (e.g., "openai")
     /// Inner key: model (e.g., "gpt-4")
-    /// Value: preset ID (e.g., "concise")
-    #[serde(default)]
-    pub default_presets: HashMap<String, HashMap<String, String>>,
+    /// Value: preset IDs applied in order (e.g., ["concise", "german"]); a
+    /// single ID string is also accepted
+    #[serde(
+        default,
+        deserialize_with = "deserialize_preset_defaults",
+        serialize_with = "serialize_preset_defaults"
+    )]
+    pub default_presets: HashMap<String, HashMap<String, Vec<String>>>,
     /// User-defined personas for conversation contexts
     #[serde(default)]
     pub personas: Vec<Persona>,
@@ -139,8 +311,34 @@ pub struct Config {
     pub presets: Vec<Preset>,
     pub refine_instructions: Option<String>,
     pub refine_prefix: Option<String>,
+    /// Models that accept image input; MCP tool images are forwarded to them.
+    /// Entries match model ids exactly or by prefix when ending in `*`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vision_models: Vec<String>,
     #[serde(default)]
     pub mcp_servers: Vec<McpServerConfig>,
+    /// Default per-request timeout for MCP servers (seconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_request_timeout_seconds: Option<u64>,
+    /// Default timeout for MCP sampling requests (seconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_sampling_timeout_seconds: Option<u64>,
+    /// Default cap on tools kept per MCP server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_max_tools: Option<usize>,
+    /// Number of MCP servers connected concurrently at startup
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_startup_concurrency: Option<usize>,
+    /// Maximum number of MCP tool calls from one reply that run at once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_tool_concurrency: Option<usize>,
+    /// Server tools offered per request before routing trims the list by
+    /// relevance; 0 always offers every tool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_tool_routing_limit: Option<usize>,
+    /// Record MCP tool calls in `mcp-audit.jsonl` (default false)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_audit_log: Option<bool>,
 }
 
 pub const DEFAULT_REFINE_INSTRUCTIONS: &str = r#"
@@ -162,6 +360,71 @@ so it MUST be a seamless replacement _without_ any new preamble or postamble.
 pub const DEFAULT_REFINE_PREFIX: &str = "REFINE:";
 pub const DEFAULT_MCP_TOOL_PAYLOAD_WINDOW: usize = 5;
 
+/// Preset ID lists keyed by provider, then model.
+type PresetDefaults = HashMap<String, HashMap<String, Vec<String>>>;
+
+/// Reads `default_presets` values written either as one preset ID (the
+/// format before presets could be stacked) or as a list of IDs.
+fn deserialize_preset_defaults<'de, D>(deserializer: D) -> Result<PresetDefaults, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum PresetIds {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    let raw: HashMap<String, HashMap<String, PresetIds>> = HashMap::deserialize(deserializer)?;
+    Ok(raw
+        .into_iter()
+        .map(|(provider, models)| {
+            let models = models
+                .into_iter()
+                .map(|(model, ids)| match ids {
+                    PresetIds::One(id) => (model, vec![id]),
+                    PresetIds::Many(ids) => (model, ids),
+                })
+                .collect();
+            (provider, models)
+        })
+        .collect())
+}
+
+/// Writes single-preset defaults as a plain ID so versions that predate
+/// stacked presets can still read them.
+fn serialize_preset_defaults<S>(defaults: &PresetDefaults, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    #[derive(Serialize)]
+    #[serde(untagged)]
+    enum PresetIds<'a> {
+        One(&'a str),
+        Many(&'a [String]),
+    }
+
+    fn ids(ids: &[String]) -> PresetIds<'_> {
+        match ids {
+            [id] => PresetIds::One(id),
+            ids => PresetIds::Many(ids),
+        }
+    }
+
+    let raw: HashMap<&String, HashMap<&String, PresetIds>> = defaults
+        .iter()
+        .map(|(provider, models)| {
+            let models = models
+                .iter()
+                .map(|(model, preset_ids)| (model, ids(preset_ids)))
+                .collect();
+            (provider, models)
+        })
+        .collect();
+    raw.serialize(serializer)
+}
+
 /// Get a user-friendly display string for a path
 /// Converts absolute paths to use ~ notation on Unix-like systems when possible
 ///
@@ -225,6 +488,18 @@ impl Config {
         self.mcp_servers.iter().collect()
     }
 
+    /// Whether `model` is listed in `vision_models`.
+    pub fn is_vision_model(&self, model: &str) -> bool {
+        self.vision_models
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => model
+                    .to_ascii_lowercase()
+                    .starts_with(&prefix.to_ascii_lowercase()),
+                None => pattern.eq_ignore_ascii_case(model),
+            })
+    }
+
     pub fn refine_instructions(&self) -> Cow<'_, str> {
         self.refine_instructions
             .as_deref()
@@ -257,6 +532,18 @@ impl McpServerConfig {
         self.tool_payload_window
             .unwrap_or(DEFAULT_MCP_TOOL_PAYLOAD_WINDOW)
     }
+
+    pub fn allows_parallel_tool_calls(&self) -> bool {
+        self.parallel_tool_calls.unwrap_or(false)
+    }
+
+    pub fn is_pinned_tool(&self, tool_name: &str) -> bool {
+        self.pinned_tools
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(tool_name))
+    }
 }
 
 #[cfg(test)]
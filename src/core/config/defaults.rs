@@ -69,12 +69,12 @@ impl Config {
         }
     }
 
-    pub fn set_default_preset(&mut self, provider: String, model: String, preset_id: String) {
+    pub fn set_default_preset(&mut self, provider: String, model: String, preset_ids: Vec<String>) {
         let provider_key = provider.to_lowercase();
         self.default_presets
             .entry(provider_key)
             .or_default()
-            .insert(model, preset_id);
+            .insert(model, preset_ids);
     }
 
     pub fn unset_default_preset(&mut self, provider: &str, model: &str) {
@@ -1,7 +1,7 @@
 use super::data::suggest_provider_id;
 use super::data::{
-    path_display, Config, CustomProvider, CustomTheme, McpServerConfig, McpToolPayloadRetention,
-    Persona,
+    path_display, Config, CustomProvider, CustomTheme, McpArgumentMatcher, McpPolicyAction,
+    McpServerConfig, McpToolPayloadRetention, McpToolPolicy, Persona,
 };
 use super::orchestrator::ConfigOrchestrator;
 use crate::core::persona::PersonaManager;
@@ -663,9 +663,6 @@ fn test_mcp_server_config_persistence() {
             id: "alpha".to_string(),
             display_name: "Alpha MCP".to_string(),
             base_url: Some("https://mcp.example.com".to_string()),
-            command: None,
-            args: None,
-            env: None,
             headers: Some(headers),
             transport: Some("streamable-http".to_string()),
             allowed_tools: Some(vec!["alpha.tool".to_string()]),
@@ -674,7 +671,25 @@ fn test_mcp_server_config_persistence() {
             tool_payloads: Some(McpToolPayloadRetention::Window),
             tool_payload_window: Some(4),
             yolo: Some(true),
+            policies: Some(vec![McpToolPolicy {
+                tool: "read_file".to_string(),
+                action: McpPolicyAction::Allow,
+                arguments: vec![McpArgumentMatcher {
+                    pointer: "/path".to_string(),
+                    equals: None,
+                    prefix: None,
+                    path_prefix: Some("/srv/docs".to_string()),
+                }],
+            }]),
+            request_timeout_seconds: Some(300),
+            max_tools: Some(500),
+            parallel_tool_calls: Some(true),
+            pinned_tools: Some(vec!["search".to_string()]),
+            ..Default::default()
         }],
+        mcp_startup_concurrency: Some(6),
+        mcp_tool_concurrency: Some(2),
+        mcp_audit_log: Some(false),
         ..Default::default()
     };
 
@@ -707,6 +722,20 @@ fn test_mcp_server_config_persistence() {
     assert_eq!(server.tool_payloads, Some(McpToolPayloadRetention::Window));
     assert_eq!(server.tool_payload_window, Some(4));
     assert_eq!(server.yolo, Some(true));
+    let policies = server.policies.as_deref().expect("policies");
+    assert_eq!(policies[0].action, McpPolicyAction::Allow);
+    assert_eq!(
+        policies[0].arguments[0].path_prefix.as_deref(),
+        Some("/srv/docs")
+    );
+    assert_eq!(server.request_timeout_seconds, Some(300));
+    assert_eq!(server.sampling_timeout_seconds, None);
+    assert_eq!(server.max_tools, Some(500));
+    assert_eq!(server.parallel_tool_calls, Some(true));
+    assert!(server.is_pinned_tool("Search"));
+    assert_eq!(loaded_config.mcp_startup_concurrency, Some(6));
+    assert_eq!(loaded_config.mcp_tool_concurrency, Some(2));
+    assert_eq!(loaded_config.mcp_audit_log, Some(false));
 }
 
 #[test]
@@ -721,18 +750,12 @@ fn test_mcp_server_stdio_config_persistence() {
         mcp_servers: vec![McpServerConfig {
             id: "stdio".to_string(),
             display_name: "Stdio MCP".to_string(),
-            base_url: None,
             command: Some("mcp-server".to_string()),
             args: Some(vec!["--mode".to_string(), "stdio".to_string()]),
             env: Some(env),
-            headers: None,
             transport: Some("stdio".to_string()),
-            allowed_tools: None,
-            protocol_version: None,
             enabled: Some(true),
-            tool_payloads: None,
-            tool_payload_window: None,
-            yolo: None,
+            ..Default::default()
         }],
         ..Default::default()
     };
@@ -931,3 +954,27 @@ fn test_overwrite_default_persona() {
         Some("bob-student")
     );
 }
+
+#[test]
+fn test_default_presets_accept_single_id_or_list() {
+    let config: Config = toml::from_str(
+        r#"
+[default_presets.openai]
+"gpt-4" = "focus"
+"gpt-4o" = ["focus", "roleplay"]
+"#,
+    )
+    .expect("parse config");
+
+    let openai = &config.default_presets["openai"];
+    assert_eq!(openai["gpt-4"], vec!["focus".to_string()]);
+    assert_eq!(
+        openai["gpt-4o"],
+        vec!["focus".to_string(), "roleplay".to_string()]
+    );
+
+    let serialized = toml::to_string(&config).expect("serialize config");
+    assert!(serialized.contains("gpt-4 = \"focus\""));
+    let reloaded: Config = toml::from_str(&serialized).expect("reparse config");
+    assert_eq!(reloaded.default_presets, config.default_presets);
+}
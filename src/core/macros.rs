@@ -0,0 +1,341 @@
+//! Template macros shared by character cards, personas and presets.
+//!
+//! Text is scanned once, left to right, for `{{name}}`, `{{name:args}}` or
+//! `{{name::arg::arg}}`. Expanded values are not scanned again, so a message
+//! quoted by `{{lastMessage}}` cannot inject macros of its own. Unknown
+//! macros, and macros whose value the caller did not provide, are left as
+//! written.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+
+/// Upper bound on dice per `{{roll}}`, so a typo cannot stall expansion.
+const MAX_DICE: u32 = 100;
+
+/// Values macros can refer to besides the text itself.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MacroContext<'a> {
+    /// Replaces `{{user}}`.
+    pub user: Option<&'a str>,
+    /// Replaces `{{char}}`.
+    pub char: Option<&'a str>,
+    /// Replaces `{{model}}`.
+    pub model: Option<&'a str>,
+    /// Replaces `{{lastMessage}}`.
+    pub last_message: Option<&'a str>,
+    /// Session variables and timing for `{{setvar}}`, `{{getvar}}` and
+    /// `{{idle_duration}}`.
+    pub state: Option<&'a MacroState>,
+}
+
+impl<'a> MacroContext<'a> {
+    /// A context that only knows the user and character names.
+    pub fn names(user: Option<&'a str>, char: Option<&'a str>) -> Self {
+        Self {
+            user,
+            char,
+            ..Self::default()
+        }
+    }
+
+    /// This context with `user` and `char` filled in where they are unset.
+    pub fn with_default_names<'b>(&self, user: &'b str, char: &'b str) -> MacroContext<'b>
+    where
+        'a: 'b,
+    {
+        MacroContext {
+            user: Some(self.user.unwrap_or(user)),
+            char: Some(self.char.unwrap_or(char)),
+            ..*self
+        }
+    }
+}
+
+/// Per-session macro state: variables set with `{{setvar}}` and the timing
+/// behind `{{idle_duration}}`.
+#[derive(Debug, Default)]
+pub struct MacroState {
+    variables: RefCell<HashMap<String, String>>,
+    last_user_message_at: Option<Instant>,
+    idle: Option<Duration>,
+}
+
+impl MacroState {
+    pub fn get_var(&self, name: &str) -> Option<String> {
+        self.variables.borrow().get(name).cloned()
+    }
+
+    pub fn set_var(&self, name: &str, value: &str) {
+        self.variables
+            .borrow_mut()
+            .insert(name.to_string(), value.to_string());
+    }
+
+    /// A copy to expand text that is only shown, never sent: `{{getvar}}`
+    /// sees the current variables, but `{{setvar}}` changes are dropped.
+    pub fn preview(&self) -> MacroState {
+        MacroState {
+            variables: RefCell::new(self.variables.borrow().clone()),
+            last_user_message_at: self.last_user_message_at,
+            idle: self.idle,
+        }
+    }
+
+    pub fn clear_vars(&mut self) {
+        self.variables.get_mut().clear();
+    }
+
+    /// Notes that the user just sent a message; `{{idle_duration}}` then
+    /// reports how long it had been since the one before.
+    pub fn record_user_message(&mut self) {
+        let now = Instant::now();
+        self.idle = self.last_user_message_at.map(|last| now - last);
+        self.last_user_message_at = Some(now);
+    }
+
+    pub fn idle(&self) -> Option<Duration> {
+        self.idle
+    }
+}
+
+/// Expand every macro in `text`.
+pub fn expand(text: &str, context: &MacroContext) -> String {
+    expand_at(text, context, Local::now())
+}
+
+fn expand_at(text: &str, context: &MacroContext, now: DateTime<Local>) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+        let raw = &rest[start..start + 2 + length + 2];
+        match expand_macro(&raw[2..raw.len() - 2], context, now) {
+            Some(value) => output.push_str(&value),
+            None => output.push_str(raw),
+        }
+        rest = &rest[start + raw.len()..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Value of one macro body (the text between the braces), or `None` to
+/// leave the macro as written.
+fn expand_macro(body: &str, context: &MacroContext, now: DateTime<Local>) -> Option<String> {
+    if body.trim_start().starts_with("//") {
+        return Some(String::new());
+    }
+    let body = body.trim();
+    let (name, args) = split_name(body);
+
+    match name.to_ascii_lowercase().as_str() {
+        "user" => context.user.map(str::to_string),
+        "char" => context.char.map(str::to_string),
+        "model" => context.model.map(str::to_string),
+        "lastmessage" => context.last_message.map(str::to_string),
+        "time" => Some(now.format("%-I:%M %p").to_string()),
+        "date" => Some(now.format("%B %-d, %Y").to_string()),
+        "weekday" => Some(now.format("%A").to_string()),
+        "idle_duration" => context.state.map(|state| {
+            state
+                .idle()
+                .map_or("just now".to_string(), humanize_duration)
+        }),
+        "random" => {
+            let options: Vec<&str> = match args? {
+                Args::Colon(list) => list.split(',').map(str::trim).collect(),
+                Args::DoubleColon(list) => list.split("::").collect(),
+            };
+            let index = random_below(options.len() as u64) as usize;
+            options.get(index).map(|option| option.to_string())
+        }
+        "roll" => roll(args?.text()).map(|total| total.to_string()),
+        "setvar" => {
+            let (var, value) = args?.text().split_once("::")?;
+            context.state?.set_var(var.trim(), value);
+            Some(String::new())
+        }
+        "getvar" => {
+            let state = context.state?;
+            Some(state.get_var(args?.text().trim()).unwrap_or_default())
+        }
+        _ => None,
+    }
+}
+
+enum Args<'a> {
+    /// `{{name:args}}` or `{{name args}}`
+    Colon(&'a str),
+    /// `{{name::arg::arg}}`
+    DoubleColon(&'a str),
+}
+
+impl<'a> Args<'a> {
+    fn text(&self) -> &'a str {
+        match self {
+            Args::Colon(text) | Args::DoubleColon(text) => text,
+        }
+    }
+}
+
+fn split_name(body: &str) -> (&str, Option<Args<'_>>) {
+    let Some(end) = body.find(|ch: char| ch == ':' || ch.is_whitespace()) else {
+        return (body, None);
+    };
+    let (name, rest) = body.split_at(end);
+    let args = match rest.strip_prefix("::") {
+        Some(args) => Args::DoubleColon(args),
+        None => {
+            let separator = rest.chars().next().map_or(0, char::len_utf8);
+            Args::Colon(rest[separator..].trim_start())
+        }
+    };
+    (name, Some(args))
+}
+
+/// Total of a dice expression such as `2d6`, `d20+3` or `6` (one six-sided
+/// die).
+fn roll(expression: &str) -> Option<i64> {
+    let expression: String = expression.split_whitespace().collect();
+    let (dice, modifier) = match expression.find(['+', '-']) {
+        Some(index) => (
+            &expression[..index],
+            expression[index..].parse::<i64>().ok()?,
+        ),
+        None => (expression.as_str(), 0),
+    };
+    let (count, sides) = match dice.split_once(['d', 'D']) {
+        Some(("", sides)) => (1, sides.parse::<u32>().ok()?),
+        Some((count, sides)) => (count.parse::<u32>().ok()?, sides.parse::<u32>().ok()?),
+        None => (1, dice.parse::<u32>().ok()?),
+    };
+    if count == 0 || count > MAX_DICE || sides == 0 {
+        return None;
+    }
+    let total: i64 = (0..count)
+        .map(|_| random_below(u64::from(sides)) as i64 + 1)
+        .sum();
+    Some(total + modifier)
+}
+
+/// Uniform-enough random number in `0..bound` for picking options and dice.
+fn random_below(bound: u64) -> u64 {
+    if bound == 0 {
+        return 0;
+    }
+    let seed = getrandom::u64().unwrap_or_else(|_| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos() as u64)
+            .unwrap_or_default()
+    });
+    seed % bound
+}
+
+fn humanize_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (value, unit) = match seconds {
+        0..=59 => (seconds, "second"),
+        60..=3_599 => (seconds / 60, "minute"),
+        3_600..=86_399 => (seconds / 3_600, "hour"),
+        _ => (seconds / 86_400, "day"),
+    };
+    if value == 1 {
+        format!("1 {unit}")
+    } else {
+        format!("{value} {unit}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn noon() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2024, 3, 5, 12, 7, 0).unwrap()
+    }
+
+    #[test]
+    fn expands_names_clock_and_context_values() {
+        let context = MacroContext {
+            model: Some("gpt-test"),
+            last_message: Some("see {{user}}"),
+            ..MacroContext::names(Some("Sam"), Some("Ada"))
+        };
+        let text = "{{user}} meets {{Char}} at {{time}} on {{weekday}}, {{date}} \
+                    via {{model}}{{// hidden}}. Last: {{lastMessage}}";
+        assert_eq!(
+            expand_at(text, &context, noon()),
+            "Sam meets Ada at 12:07 PM on Tuesday, March 5, 2024 via gpt-test. \
+             Last: see {{user}}"
+        );
+    }
+
+    #[test]
+    fn leaves_unknown_and_unavailable_macros_alone() {
+        let context = MacroContext::default();
+        assert_eq!(
+            expand(
+                "{{user}} {{unknown}} {{getvar::x}} {{roll:zero}} {{ unclosed",
+                &context
+            ),
+            "{{user}} {{unknown}} {{getvar::x}} {{roll:zero}} {{ unclosed"
+        );
+        let named = context.with_default_names("Anon", "Assistant");
+        assert_eq!(expand("{{user}}/{{char}}", &named), "Anon/Assistant");
+    }
+
+    #[test]
+    fn random_and_roll_stay_in_range() {
+        let context = MacroContext::default();
+        for _ in 0..50 {
+            let picked = expand("{{random:red, green,blue}}", &context);
+            assert!(["red", "green", "blue"].contains(&picked.as_str()));
+            let picked = expand("{{random::a, b::c}}", &context);
+            assert!(["a, b", "c"].contains(&picked.as_str()));
+
+            let total: i64 = expand("{{roll:2d6}}", &context).parse().unwrap();
+            assert!((2..=12).contains(&total));
+            let total: i64 = expand("{{roll d20+3}}", &context).parse().unwrap();
+            assert!((4..=23).contains(&total));
+            assert_eq!(expand("{{roll:1d1-1}}", &context), "0");
+
+            // Non-ASCII whitespace separates the name from its arguments too.
+            let picked = expand("{{random\u{3000}a,b}}", &context);
+            assert!(["a", "b"].contains(&picked.as_str()));
+            let total: i64 = expand("{{roll\u{a0}1d4}}", &context).parse().unwrap();
+            assert!((1..=4).contains(&total));
+        }
+    }
+
+    #[test]
+    fn variables_persist_in_session_state() {
+        let mut state = MacroState::default();
+        let context = MacroContext {
+            state: Some(&state),
+            ..MacroContext::default()
+        };
+        assert_eq!(
+            expand("{{setvar::mood::calm}}Mood: {{getvar::mood}}", &context),
+            "Mood: calm"
+        );
+        assert_eq!(
+            expand("{{getvar::mood}}|{{getvar::other}}|", &context),
+            "calm||"
+        );
+        assert_eq!(expand("{{idle_duration}}", &context), "just now");
+
+        state.clear_vars();
+        assert_eq!(state.get_var("mood"), None);
+        assert_eq!(humanize_duration(Duration::from_secs(1)), "1 second");
+        assert_eq!(humanize_duration(Duration::from_secs(7_300)), "2 hours");
+        assert_eq!(humanize_duration(Duration::from_secs(200_000)), "2 days");
+    }
+}
@@ -0,0 +1,278 @@
+//! `@server:uri` resource mentions in user messages.
+//!
+//! A mention starts with `@` at the beginning of the message or after
+//! whitespace, names a configured MCP server, and runs until the next
+//! whitespace. URIs containing `{...}` are RFC 6570 templates whose variables
+//! are collected from the user before the resource is read.
+
+use std::collections::HashMap;
+
+/// One `@server:uri` token found in a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct McpResourceMention {
+    /// Canonical server id from config.
+    pub server_id: String,
+    pub uri: String,
+    /// The token exactly as typed, used to rewrite expanded templates.
+    pub raw: String,
+}
+
+impl McpResourceMention {
+    pub fn is_template(&self) -> bool {
+        !template_variables(&self.uri).is_empty()
+    }
+}
+
+/// A resource read completed for an outgoing message.
+#[derive(Debug, Clone)]
+pub struct McpResourceAttachment {
+    pub server_id: String,
+    pub uri: String,
+    pub text: String,
+}
+
+/// Finds resource mentions whose server id resolves via `resolve_server`.
+///
+/// Trailing sentence punctuation is not treated as part of the URI, and
+/// repeated mentions of the same resource are reported once.
+pub fn parse_resource_mentions(
+    message: &str,
+    resolve_server: impl Fn(&str) -> Option<String>,
+) -> Vec<McpResourceMention> {
+    let mut mentions: Vec<McpResourceMention> = Vec::new();
+    let mut previous: Option<char> = None;
+    for (index, ch) in message.char_indices() {
+        let at_boundary = previous.is_none_or(char::is_whitespace);
+        previous = Some(ch);
+        if ch != '@' || !at_boundary {
+            continue;
+        }
+
+        let rest = &message[index + 1..];
+        let token_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let token = rest[..token_end].trim_end_matches(['.', ',', ';', '!', '?', ')']);
+        let Some((server, uri)) = token.split_once(':') else {
+            continue;
+        };
+        if server.is_empty() || uri.is_empty() {
+            continue;
+        }
+        let Some(server_id) = resolve_server(server) else {
+            continue;
+        };
+        if mentions
+            .iter()
+            .any(|existing| existing.server_id == server_id && existing.uri == uri)
+        {
+            continue;
+        }
+        mentions.push(McpResourceMention {
+            server_id,
+            uri: uri.to_string(),
+            raw: format!("@{token}"),
+        });
+    }
+    mentions
+}
+
+/// Lists the variable names in a URI template, in order of appearance.
+pub fn template_variables(template: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    for expression in template_expressions(template) {
+        let (_, variables) = split_operator(expression);
+        for variable in variables.split(',') {
+            let name = variable
+                .split(':')
+                .next()
+                .unwrap_or_default()
+                .trim_end_matches('*')
+                .trim();
+            if !name.is_empty() && !names.iter().any(|existing| existing == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names
+}
+
+/// Expands a URI template with the supplied values.
+///
+/// Supports the RFC 6570 level 3 operators (`+ # . / ; ? &`); undefined or
+/// empty variables are omitted as the RFC specifies.
+pub fn expand_template(template: &str, values: &HashMap<String, String>) -> String {
+    let mut output = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(length) = rest[start..].find('}') else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+        let expression = &rest[start + 1..start + length];
+        output.push_str(&expand_expression(expression, values));
+        rest = &rest[start + length + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Formats resource text as a fenced block appended to the user message.
+pub fn render_attachment_block(attachment: &McpResourceAttachment) -> String {
+    let fence = if attachment.text.contains("```") {
+        "````"
+    } else {
+        "```"
+    };
+    format!(
+        "Resource @{}:{}\n{fence}\n{}\n{fence}",
+        attachment.server_id,
+        attachment.uri,
+        attachment.text.trim_end()
+    )
+}
+
+/// Appends attachment blocks to the message text.
+pub fn embed_attachments(message: &str, attachments: &[McpResourceAttachment]) -> String {
+    let mut output = message.trim_end().to_string();
+    for attachment in attachments {
+        output.push_str("\n\n");
+        output.push_str(&render_attachment_block(attachment));
+    }
+    output
+}
+
+fn template_expressions(template: &str) -> impl Iterator<Item = &str> {
+    template.split('{').skip(1).filter_map(|segment| {
+        segment
+            .find('}')
+            .map(|end| &segment[..end])
+            .filter(|expression| !expression.is_empty())
+    })
+}
+
+fn split_operator(expression: &str) -> (Option<char>, &str) {
+    match expression.chars().next() {
+        Some(op @ ('+' | '#' | '.' | '/' | ';' | '?' | '&')) => (Some(op), &expression[1..]),
+        _ => (None, expression),
+    }
+}
+
+fn expand_expression(expression: &str, values: &HashMap<String, String>) -> String {
+    let (operator, variables) = split_operator(expression);
+    let (first, separator, named, allow_reserved) = match operator {
+        None => ("", ",", false, false),
+        Some('+') => ("", ",", false, true),
+        Some('#') => ("#", ",", false, true),
+        Some('.') => (".", ".", false, false),
+        Some('/') => ("/", "/", false, false),
+        Some(';') => (";", ";", true, false),
+        Some('?') => ("?", "&", true, false),
+        Some('&') => ("&", "&", true, false),
+        Some(_) => ("", ",", false, false),
+    };
+
+    let mut parts = Vec::new();
+    for variable in variables.split(',') {
+        let mut pieces = variable.split(':');
+        let name = pieces.next().unwrap_or_default().trim_end_matches('*');
+        let prefix_len = pieces.next().and_then(|len| len.parse::<usize>().ok());
+        let Some(value) = values.get(name).filter(|value| !value.is_empty()) else {
+            continue;
+        };
+        let value: String = match prefix_len {
+            Some(len) => value.chars().take(len).collect(),
+            None => value.clone(),
+        };
+        let encoded = percent_encode(&value, allow_reserved);
+        parts.push(if named {
+            format!("{name}={encoded}")
+        } else {
+            encoded
+        });
+    }
+
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!("{first}{}", parts.join(separator))
+    }
+}
+
+pub(crate) fn percent_encode(value: &str, allow_reserved: bool) -> String {
+    const RESERVED: &str = ":/?#[]@!$&'()*+,;=";
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        let ch = byte as char;
+        let unreserved = ch.is_ascii_alphanumeric() || matches!(ch, '-' | '.' | '_' | '~');
+        if unreserved || (allow_reserved && RESERVED.contains(ch)) {
+            encoded.push(ch);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(id: &str) -> Option<String> {
+        id.eq_ignore_ascii_case("alpha")
+            .then(|| "alpha".to_string())
+    }
+
+    #[test]
+    fn parse_resource_mentions_requires_known_server_and_boundary() {
+        let mentions = parse_resource_mentions(
+            "Compare @alpha:file:///a.md, @Alpha:file:///a.md and @beta:x. Mail me@alpha:x",
+            resolve,
+        );
+        assert_eq!(
+            mentions,
+            vec![McpResourceMention {
+                server_id: "alpha".to_string(),
+                uri: "file:///a.md".to_string(),
+                raw: "@alpha:file:///a.md".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn template_variables_strip_operators_and_modifiers() {
+        assert_eq!(
+            template_variables("db://{+table}/rows{?limit,offset}{/path*}{id:3}"),
+            vec!["table", "limit", "offset", "path", "id"]
+        );
+        assert!(template_variables("file:///plain.txt").is_empty());
+    }
+
+    #[test]
+    fn expand_template_handles_common_operators() {
+        let values = HashMap::from([
+            ("city".to_string(), "São Paulo".to_string()),
+            ("path".to_string(), "a/b".to_string()),
+            ("limit".to_string(), "5".to_string()),
+        ]);
+        assert_eq!(
+            expand_template("weather://{city}/today{?limit,missing}", &values),
+            "weather://S%C3%A3o%20Paulo/today?limit=5"
+        );
+        assert_eq!(expand_template("file:///{+path}", &values), "file:///a/b");
+    }
+
+    #[test]
+    fn embed_attachments_appends_fenced_blocks() {
+        let message = embed_attachments(
+            "Summarize @alpha:mem://notes",
+            &[McpResourceAttachment {
+                server_id: "alpha".to_string(),
+                uri: "mem://notes".to_string(),
+                text: "line one\n".to_string(),
+            }],
+        );
+        assert_eq!(
+            message,
+            "Summarize @alpha:mem://notes\n\nResource @alpha:mem://notes\n```\nline one\n```"
+        );
+    }
+}
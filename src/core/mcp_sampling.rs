@@ -31,6 +31,8 @@ pub fn build_sampling_messages(request: &CreateMessageRequest) -> Result<Vec<Cha
                 name: None,
                 tool_call_id: None,
                 tool_calls: None,
+                images: Vec::new(),
+                is_prefill: false,
             });
         }
     }
@@ -47,14 +49,21 @@ pub fn build_sampling_messages(request: &CreateMessageRequest) -> Result<Vec<Cha
             name: None,
             tool_call_id: None,
             tool_calls: None,
+            images: Vec::new(),
+            is_prefill: false,
         });
     }
 
     Ok(messages)
 }
 
-pub fn sampling_timeout_for_request(request: &CreateMessageRequest) -> Option<Duration> {
-    sampling_timeout_from_params(&request.params)
+/// Returns the timeout named in the request's `_meta`, or the server's
+/// configured sampling timeout when the request does not specify one.
+pub fn sampling_timeout_for_request(
+    request: &CreateMessageRequest,
+    configured: Duration,
+) -> Duration {
+    sampling_timeout_from_params(&request.params).unwrap_or(configured)
 }
 
 fn sampling_timeout_from_params(params: &CreateMessageRequestParams) -> Option<Duration> {
@@ -224,7 +233,7 @@ mod tests {
             )])),
         });
 
-        let timeout = sampling_timeout_for_request(&request).expect("timeout");
+        let timeout = sampling_timeout_for_request(&request, Duration::from_secs(300));
         assert_eq!(timeout.as_millis(), 120_000);
     }
 
@@ -239,7 +248,7 @@ mod tests {
             )])),
         });
 
-        let timeout = sampling_timeout_for_request(&request).expect("timeout");
+        let timeout = sampling_timeout_for_request(&request, Duration::from_secs(300));
         assert_eq!(timeout.as_secs(), 90);
     }
 
@@ -254,6 +263,9 @@ mod tests {
             )])),
         });
 
-        assert!(sampling_timeout_for_request(&request).is_none());
+        assert_eq!(
+            sampling_timeout_for_request(&request, Duration::from_secs(42)),
+            Duration::from_secs(42)
+        );
     }
 }
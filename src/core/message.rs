@@ -17,6 +17,10 @@ pub enum TranscriptRole {
 pub struct Message {
     pub role: TranscriptRole,
     pub content: String,
+    /// Handle for a message that is rewritten in place later, such as a
+    /// tool call's "running" line. Not persisted.
+    #[serde(skip)]
+    pub id: Option<u64>,
 }
 
 impl TranscriptRole {
@@ -151,6 +155,7 @@ impl Message {
         Self {
             role,
             content: content.into(),
+            id: None,
         }
     }
 
@@ -10,6 +10,9 @@
 //! - [`config`], [`providers`], and [`preset`]: model/provider settings and
 //!   runtime defaults.
 //! - [`mcp_auth`] and [`mcp_sampling`]: MCP-specific auth and sampling bridges.
+//! - [`mcp_resource_mentions`]: `@server:uri` resource attachment parsing.
+//! - [`macros`]: `{{...}}` template macros shared by cards, personas and
+//!   presets.
 //! - [`text_wrapping`] and [`message`]: shared message/text shaping utilities
 //!   used by both core flows and UI rendering.
 //!
@@ -24,7 +27,9 @@ pub mod builtin_providers;
 pub mod chat_stream;
 pub mod config;
 pub mod keyring;
+pub mod macros;
 pub mod mcp_auth;
+pub mod mcp_resource_mentions;
 pub mod mcp_sampling;
 pub mod message;
 pub mod oauth;
@@ -1,5 +1,6 @@
 use super::shared_selection::{ManagedItem, SelectionState};
 use crate::core::config::data::{Config, Persona};
+use crate::core::macros::{expand, MacroContext};
 
 impl ManagedItem for Persona {
     fn id(&self) -> &str {
@@ -56,15 +57,19 @@ impl PersonaManager {
     /// {{char}} is replaced with the character name (or "Assistant" if None)
     /// {{user}} is replaced with the active persona name (or "Anon" if no persona)
     pub fn apply_substitutions(&self, text: &str, char_name: Option<&str>) -> String {
-        let char_replacement = char_name.unwrap_or("Assistant");
-        let user_replacement = self
+        self.expand_macros(text, &MacroContext::names(None, char_name))
+    }
+
+    /// Expand all macros in text, filling in `{{user}}` and `{{char}}` as
+    /// [`PersonaManager::apply_substitutions`] does when `macros` leaves them unset
+    pub fn expand_macros(&self, text: &str, macros: &MacroContext) -> String {
+        let user_name = self
             .shared
             .get_active()
             .map(|persona| persona.display_name.as_str())
             .unwrap_or("Anon");
 
-        text.replace("{{char}}", char_replacement)
-            .replace("{{user}}", user_replacement)
+        expand(text, &macros.with_default_names(user_name, "Assistant"))
     }
 
     /// Get the display name for the user in conversations
@@ -78,11 +83,11 @@ impl PersonaManager {
 
     /// Get the modified system prompt with persona bio prepended
     /// If a persona is active, prepends the persona's bio (with substitutions applied) to the base prompt
-    pub fn get_modified_system_prompt(&self, base_prompt: &str, char_name: Option<&str>) -> String {
+    pub fn get_modified_system_prompt(&self, base_prompt: &str, macros: &MacroContext) -> String {
         match self.shared.get_active() {
             Some(persona) => {
                 if let Some(bio) = &persona.bio {
-                    let substituted_bio = self.apply_substitutions(bio, char_name);
+                    let substituted_bio = self.expand_macros(bio, macros);
                     let trimmed_bio = substituted_bio.trim();
                     if trimmed_bio.is_empty() {
                         base_prompt.to_string()
@@ -99,7 +104,9 @@ impl PersonaManager {
 
     /// Get the default persona for a provider/model combination
     pub fn get_default_for_provider_model(&self, provider: &str, model: &str) -> Option<&str> {
-        self.shared.get_default_for_provider_model(provider, model)
+        self.shared
+            .get_default_for_provider_model(provider, model)
+            .map(String::as_str)
     }
 
     /// Set the default persona for a provider/model combination and persist to config
@@ -110,7 +117,7 @@ impl PersonaManager {
         persona_id: &str,
     ) -> Result<(), Box<dyn std::error::Error>> {
         self.shared
-            .set_default_persistent(provider, model, persona_id)
+            .set_default_persistent(provider, model, persona_id.to_string())
     }
 
     /// Unset the default persona for a provider/model combination and persist to config
@@ -264,7 +271,7 @@ mod tests {
         let manager = PersonaManager::load_personas(&config).expect("Failed to load personas");
 
         let base_prompt = "You are a helpful assistant.";
-        let result = manager.get_modified_system_prompt(base_prompt, None);
+        let result = manager.get_modified_system_prompt(base_prompt, &MacroContext::default());
         assert_eq!(result, base_prompt);
     }
 
@@ -278,7 +285,7 @@ mod tests {
             .expect("Failed to activate persona");
 
         let base_prompt = "You are a helpful assistant.";
-        let result = manager.get_modified_system_prompt(base_prompt, None);
+        let result = manager.get_modified_system_prompt(base_prompt, &MacroContext::default());
         let expected =
             "You are talking to Alice, a senior developer.\n\nYou are a helpful assistant.";
         assert_eq!(result, expected);
@@ -294,7 +301,7 @@ mod tests {
             .expect("Failed to activate persona");
 
         let base_prompt = "You are a helpful assistant.";
-        let result = manager.get_modified_system_prompt(base_prompt, None);
+        let result = manager.get_modified_system_prompt(base_prompt, &MacroContext::default());
         assert_eq!(result, base_prompt);
     }
 
@@ -322,13 +329,15 @@ mod tests {
         manager
             .set_active_persona("dana-empty")
             .expect("Failed to activate persona");
-        let result_empty = manager.get_modified_system_prompt(base_prompt, None);
+        let result_empty =
+            manager.get_modified_system_prompt(base_prompt, &MacroContext::default());
         assert_eq!(result_empty, base_prompt);
 
         manager
             .set_active_persona("erin-whitespace")
             .expect("Failed to activate persona");
-        let result_whitespace = manager.get_modified_system_prompt(base_prompt, None);
+        let result_whitespace =
+            manager.get_modified_system_prompt(base_prompt, &MacroContext::default());
         assert_eq!(result_whitespace, base_prompt);
     }
 
@@ -342,7 +351,7 @@ mod tests {
             .expect("Failed to activate persona");
 
         let base_prompt = "You are a helpful assistant.";
-        let result = manager.get_modified_system_prompt(base_prompt, None);
+        let result = manager.get_modified_system_prompt(base_prompt, &MacroContext::default());
         let expected =
             "Bob is a computer science student learning about AI.\n\nYou are a helpful assistant.";
         assert_eq!(result, expected);
@@ -466,6 +475,7 @@ fn test_message_rendering_with_persona_display_name() {
     let message = Message {
         role: TranscriptRole::User,
         content: "Hello world".to_string(),
+        id: None,
     };
 
     // Test with default "You:"
@@ -6,6 +6,7 @@ mod integration_tests {
     use crate::commands::{process_input, CommandResult};
     use crate::core::app::conversation::ConversationController;
     use crate::core::config::data::{Config, Persona};
+    use crate::core::macros::MacroContext;
     use crate::core::persona::PersonaManager;
     use crate::utils::test_utils::create_test_app;
     use std::fs;
@@ -231,7 +232,8 @@ mod integration_tests {
         let base_prompt = "You are a helpful assistant.";
 
         // Test without persona
-        let prompt_no_persona = persona_manager.get_modified_system_prompt(base_prompt, None);
+        let prompt_no_persona =
+            persona_manager.get_modified_system_prompt(base_prompt, &MacroContext::default());
         assert_eq!(
             prompt_no_persona, base_prompt,
             "Prompt should be unchanged without persona"
@@ -241,7 +243,8 @@ mod integration_tests {
         persona_manager
             .set_active_persona("alice-dev")
             .expect("Failed to activate persona");
-        let prompt_with_persona = persona_manager.get_modified_system_prompt(base_prompt, None);
+        let prompt_with_persona =
+            persona_manager.get_modified_system_prompt(base_prompt, &MacroContext::default());
 
         assert!(prompt_with_persona.contains("Alice, a senior software developer"));
         assert!(prompt_with_persona.contains(base_prompt));
@@ -254,7 +257,8 @@ mod integration_tests {
         persona_manager
             .set_active_persona("charlie-no-bio")
             .expect("Failed to activate persona");
-        let prompt_no_bio = persona_manager.get_modified_system_prompt(base_prompt, None);
+        let prompt_no_bio =
+            persona_manager.get_modified_system_prompt(base_prompt, &MacroContext::default());
         assert_eq!(
             prompt_no_bio, base_prompt,
             "Prompt should be unchanged for persona without bio"
@@ -459,7 +463,7 @@ mod integration_tests {
         assert_eq!(app.persona_manager.get_display_name(), "Alice");
         let initial_prompt = app
             .persona_manager
-            .get_modified_system_prompt("You are helpful.", None);
+            .get_modified_system_prompt("You are helpful.", &MacroContext::default());
         assert!(initial_prompt.contains("Alice, a senior software developer"));
 
         // Step 3: Add user message with persona active
@@ -482,7 +486,7 @@ mod integration_tests {
         assert_eq!(app.persona_manager.get_display_name(), "Bob");
         let switched_prompt = app
             .persona_manager
-            .get_modified_system_prompt("You are helpful.", None);
+            .get_modified_system_prompt("You are helpful.", &MacroContext::default());
         assert!(switched_prompt.contains("Bob, a computer science student"));
         assert!(!switched_prompt.contains("Alice"));
 
@@ -504,7 +508,7 @@ mod integration_tests {
         assert_eq!(app.persona_manager.get_display_name(), "You");
         let final_prompt = app
             .persona_manager
-            .get_modified_system_prompt("You are helpful.", None);
+            .get_modified_system_prompt("You are helpful.", &MacroContext::default());
         assert_eq!(final_prompt, "You are helpful.");
     }
 
@@ -1,7 +1,8 @@
 use super::shared_selection::{ManagedItem, SelectionState};
 use crate::api::ChatMessage;
 use crate::core::builtin_presets;
-use crate::core::config::data::{Config, Preset};
+use crate::core::config::data::{Config, Preset, PresetBlock, PresetPosition, PresetRole};
+use crate::core::macros::MacroContext;
 use crate::core::persona::PersonaManager;
 use std::collections::HashSet;
 
@@ -11,9 +12,22 @@ impl ManagedItem for Preset {
     }
 }
 
+/// Split a comma-separated list of preset IDs, such as `--preset short,roleplay`.
+pub fn parse_preset_ids(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 /// Manages preset state and operations
+///
+/// Several presets can be active at once; they apply in the order they were
+/// activated.
 pub struct PresetManager {
-    shared: SelectionState<Preset>,
+    shared: SelectionState<Preset, Vec<String>>,
 }
 
 impl PresetManager {
@@ -56,63 +70,127 @@ impl PresetManager {
         self.shared.find_by_id(id)
     }
 
-    /// Set the active preset by ID
+    /// Make `preset_id` the only active preset
     pub fn set_active_preset(&mut self, preset_id: &str) -> Result<(), String> {
-        self.shared.set_active(preset_id)
+        self.set_active_presets(&[preset_id])
     }
 
-    /// Clear the active preset (deactivate)
+    /// Replace the active presets with `preset_ids`, in order. Repeated IDs
+    /// are applied once; nothing changes if any ID is unknown.
+    pub fn set_active_presets<S: AsRef<str>>(&mut self, preset_ids: &[S]) -> Result<(), String> {
+        self.shared.set_active_many(preset_ids)
+    }
+
+    /// Deactivate all presets
     pub fn clear_active_preset(&mut self) {
         self.shared.clear_active();
     }
 
-    /// Get the currently active preset
-    pub fn get_active_preset(&self) -> Option<&Preset> {
-        self.shared.get_active()
+    /// The active presets, in the order they apply
+    pub fn active_presets(&self) -> &[Preset] {
+        self.shared.active_items()
+    }
+
+    /// IDs of the active presets, in the order they apply
+    pub fn active_preset_ids(&self) -> Vec<&str> {
+        self.active_presets()
+            .iter()
+            .map(|preset| preset.id.as_str())
+            .collect()
     }
 
-    /// Apply preset instructions to the provided messages
-    /// Adds or augments system messages at the beginning/end after persona substitutions
+    /// Apply the active presets' instructions to the provided messages
+    /// Adds or augments system messages at the beginning/end after persona substitutions,
+    /// then injects the presets' blocks. Presets apply in order: earlier presets' `pre`
+    /// text comes first and their `post` text first after the conversation. Blocks
+    /// conditioned on a character apply when `macros` names one.
     pub fn apply_to_messages(
         &self,
         messages: &mut Vec<ChatMessage>,
         persona_manager: &PersonaManager,
-        char_name: Option<&str>,
+        macros: &MacroContext,
     ) {
-        let Some(active_preset) = self.shared.get_active() else {
+        if self.active_presets().is_empty() {
             return;
-        };
-
-        let pre_text = active_preset.pre.trim();
-        let post_text = active_preset.post.trim();
+        }
 
-        let substituted_pre = if pre_text.is_empty() {
-            None
-        } else {
-            let substituted = persona_manager.apply_substitutions(pre_text, char_name);
-            let trimmed = substituted.trim().to_string();
+        let substitute = |text: &str| {
+            let text = text.trim();
+            if text.is_empty() {
+                return None;
+            }
+            let substituted = persona_manager.expand_macros(text, macros);
+            let trimmed = substituted.trim();
             if trimmed.is_empty() {
                 None
             } else {
-                Some(trimmed)
+                Some(trimmed.to_string())
             }
         };
 
-        let substituted_post = if post_text.is_empty() {
-            None
-        } else {
-            let substituted = persona_manager.apply_substitutions(post_text, char_name);
-            let trimmed = substituted.trim().to_string();
-            if trimmed.is_empty() {
-                None
-            } else {
-                Some(trimmed)
+        let character_active = macros.char.is_some();
+        let persona_active = persona_manager.get_active_persona().is_some();
+        let mut pre_parts = Vec::new();
+        let mut post_parts = Vec::new();
+        let mut blocks: Vec<(&PresetBlock, String)> = Vec::new();
+        for preset in self.active_presets() {
+            pre_parts.extend(substitute(&preset.pre));
+            for block in &preset.blocks {
+                if !block.when.matches(character_active, persona_active) {
+                    continue;
+                }
+                let Some(content) = substitute(&block.content) else {
+                    continue;
+                };
+                if block.position == PresetPosition::SystemStart && block.role == PresetRole::System
+                {
+                    pre_parts.push(content);
+                } else {
+                    blocks.push((block, content));
+                }
             }
-        };
+            post_parts.extend(substitute(&preset.post));
+        }
 
-        if substituted_pre.is_none() && substituted_post.is_none() {
-            return;
+        // In-conversation blocks go first so their positions only count the
+        // conversation, not the system messages added below. Every position is
+        // taken from the original conversation, so one block never shifts
+        // another; blocks sharing a position keep their order.
+        let system_count = leading_system_count(messages);
+        let mut insertions: Vec<(usize, ChatMessage)> = blocks
+            .iter()
+            .filter_map(|(block, content)| {
+                let index = match block.position {
+                    PresetPosition::BeforeLastUser => messages
+                        .iter()
+                        .rposition(|msg| msg.role == "user")
+                        .unwrap_or(messages.len()),
+                    PresetPosition::Depth => {
+                        messages.len().saturating_sub(block.depth).max(system_count)
+                    }
+                    PresetPosition::SystemStart | PresetPosition::Prefill => return None,
+                };
+                Some((index, preset_message(block.role.as_str(), content.clone())))
+            })
+            .collect();
+        insertions.sort_by_key(|(index, _)| *index);
+        for (index, message) in insertions.into_iter().rev() {
+            messages.insert(index, message);
+        }
+
+        let mut start_messages = Vec::new();
+        let mut prefill_parts = Vec::new();
+        for (block, content) in blocks {
+            match block.position {
+                PresetPosition::SystemStart => {
+                    start_messages.push(preset_message(block.role.as_str(), content))
+                }
+                PresetPosition::Prefill => prefill_parts.push(content),
+                _ => {}
+            }
         }
+        let substituted_pre = (!pre_parts.is_empty()).then(|| pre_parts.join("\n\n"));
+        let substituted_post = (!post_parts.is_empty()).then(|| post_parts.join("\n\n"));
 
         if substituted_pre.is_some()
             && messages
@@ -120,16 +198,7 @@ impl PresetManager {
                 .map(|msg| msg.role != "system")
                 .unwrap_or(true)
         {
-            messages.insert(
-                0,
-                ChatMessage {
-                    role: "system".to_string(),
-                    content: String::new(),
-                    name: None,
-                    tool_call_id: None,
-                    tool_calls: None,
-                },
-            );
+            messages.insert(0, preset_message("system", String::new()));
         }
 
         if substituted_post.is_some()
@@ -138,13 +207,7 @@ impl PresetManager {
                 .map(|msg| msg.role != "system")
                 .unwrap_or(true)
         {
-            messages.push(ChatMessage {
-                role: "system".to_string(),
-                content: String::new(),
-                name: None,
-                tool_call_id: None,
-                tool_calls: None,
-            });
+            messages.push(preset_message("system", String::new()));
         }
 
         if let Some(pre) = substituted_pre {
@@ -166,22 +229,35 @@ impl PresetManager {
                 }
             }
         }
+
+        let start = leading_system_count(messages);
+        messages.splice(start..start, start_messages);
+
+        if !prefill_parts.is_empty() {
+            messages.push(ChatMessage {
+                is_prefill: true,
+                ..preset_message("assistant", prefill_parts.join("\n\n"))
+            });
+        }
     }
 
-    /// Get the default preset for a provider/model combination
-    pub fn get_default_for_provider_model(&self, provider: &str, model: &str) -> Option<&str> {
-        self.shared.get_default_for_provider_model(provider, model)
+    /// Get the default presets for a provider/model combination, in order
+    pub fn get_default_for_provider_model(&self, provider: &str, model: &str) -> Option<&[String]> {
+        self.shared
+            .get_default_for_provider_model(provider, model)
+            .map(Vec::as_slice)
+            .filter(|ids| !ids.is_empty())
     }
 
-    /// Set the default preset for a provider/model combination and persist to config
+    /// Set the default presets for a provider/model combination and persist to config
     pub fn set_default_for_provider_model_persistent(
         &mut self,
         provider: &str,
         model: &str,
-        preset_id: &str,
+        preset_ids: &[String],
     ) -> Result<(), Box<dyn std::error::Error>> {
         self.shared
-            .set_default_persistent(provider, model, preset_id)
+            .set_default_persistent(provider, model, preset_ids.to_vec())
     }
 
     /// Unset the default preset for a provider/model combination and persist to config
@@ -194,6 +270,23 @@ impl PresetManager {
     }
 }
 
+fn preset_message(role: &str, content: String) -> ChatMessage {
+    ChatMessage {
+        role: role.to_string(),
+        content,
+        name: None,
+        tool_call_id: None,
+        tool_calls: None,
+        images: Vec::new(),
+        is_prefill: false,
+    }
+}
+
+/// 1 when the request opens with a system message, which blocks never precede.
+fn leading_system_count(messages: &[ChatMessage]) -> usize {
+    usize::from(messages.first().is_some_and(|msg| msg.role == "system"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -210,6 +303,7 @@ mod tests {
                 id: "focus".to_string(),
                 pre: "Focus on {{user}}'s requirements.".to_string(),
                 post: "Confirm actions with {{char}}.".to_string(),
+                ..Default::default()
             }],
             ..Default::default()
         }
@@ -249,6 +343,7 @@ mod tests {
                 id: "short".to_string(),
                 pre: "Custom short instructions.".to_string(),
                 post: String::new(),
+                ..Default::default()
             }],
             ..Default::default()
         };
@@ -268,6 +363,8 @@ mod tests {
             name: None,
             tool_call_id: None,
             tool_calls: None,
+            images: Vec::new(),
+            is_prefill: false,
         }]
     }
 
@@ -276,15 +373,56 @@ mod tests {
         let config = create_test_config();
         let mut manager = PresetManager::load_presets(&config).expect("load presets");
 
-        assert!(manager.get_active_preset().is_none());
+        assert!(manager.active_presets().is_empty());
         manager.set_active_preset("focus").expect("set preset");
-        assert_eq!(
-            manager.get_active_preset().map(|p| p.id.as_str()),
-            Some("focus")
-        );
+        assert_eq!(manager.active_preset_ids(), vec!["focus"]);
 
         manager.clear_active_preset();
-        assert!(manager.get_active_preset().is_none());
+        assert!(manager.active_presets().is_empty());
+    }
+
+    #[test]
+    fn test_set_active_presets_keeps_order_and_rejects_unknown_ids() {
+        let config = Config {
+            builtin_presets: Some(false),
+            presets: vec![
+                Preset {
+                    id: "focus".to_string(),
+                    pre: "Focus.".to_string(),
+                    post: "Stay focused.".to_string(),
+                    ..Default::default()
+                },
+                Preset {
+                    id: "brief".to_string(),
+                    pre: "Be brief.".to_string(),
+                    post: "Keep it short.".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let mut manager = PresetManager::load_presets(&config).expect("load presets");
+
+        manager
+            .set_active_presets(&parse_preset_ids("brief, focus,brief"))
+            .expect("stack presets");
+        assert_eq!(manager.active_preset_ids(), vec!["brief", "focus"]);
+
+        let error = manager
+            .set_active_presets(&["focus", "missing"])
+            .expect_err("expected failure for missing preset");
+        assert!(error.contains("Preset 'missing' not found"));
+        assert_eq!(manager.active_preset_ids(), vec!["brief", "focus"]);
+
+        let persona_manager = PersonaManager::load_personas(&config).expect("load personas");
+        let mut messages = create_messages();
+        manager.apply_to_messages(&mut messages, &persona_manager, &MacroContext::default());
+
+        assert_eq!(messages[0].content, "Be brief.\n\nFocus.");
+        assert_eq!(
+            messages.last().unwrap().content,
+            "Keep it short.\n\nStay focused."
+        );
     }
 
     #[test]
@@ -312,7 +450,11 @@ mod tests {
             .expect("set persona");
 
         let mut messages = create_messages();
-        manager.apply_to_messages(&mut messages, &persona_manager, Some("HelperBot"));
+        manager.apply_to_messages(
+            &mut messages,
+            &persona_manager,
+            &MacroContext::names(None, Some("HelperBot")),
+        );
 
         assert!(messages.first().unwrap().role == "system");
         assert!(messages.last().unwrap().role == "system");
@@ -326,6 +468,89 @@ mod tests {
             .contains("Confirm actions with HelperBot."));
     }
 
+    #[test]
+    fn test_apply_to_messages_injects_blocks_by_position() {
+        let mut config: Config = toml::from_str(
+            r#"
+[[presets]]
+id = "blocks"
+pre = "Pre."
+
+[[presets.blocks]]
+content = "Start note for {{char}}."
+
+[[presets.blocks]]
+name = "reminder"
+content = "Remember the rules."
+position = "before-last-user"
+
+[[presets.blocks]]
+content = "Depth note."
+position = "depth"
+depth = 2
+role = "user"
+
+[[presets.blocks]]
+content = "Example turn."
+role = "assistant"
+
+[[presets.blocks]]
+content = "Sure,"
+position = "prefill"
+
+[[presets.blocks]]
+content = "Only with a persona."
+when = "persona"
+"#,
+        )
+        .expect("parse presets");
+        config.builtin_presets = Some(false);
+        let mut manager = PresetManager::load_presets(&config).expect("load presets");
+        manager
+            .set_active_preset("blocks")
+            .expect("activate preset");
+        let persona_manager = PersonaManager::load_personas(&config).expect("load personas");
+
+        let message = |role: &str, content: &str| ChatMessage {
+            role: role.to_string(),
+            content: content.to_string(),
+            name: None,
+            tool_call_id: None,
+            tool_calls: None,
+            images: Vec::new(),
+            is_prefill: false,
+        };
+        let mut messages = vec![
+            message("system", "Sys"),
+            message("user", "Q1"),
+            message("assistant", "A1"),
+            message("user", "Q2"),
+        ];
+        manager.apply_to_messages(
+            &mut messages,
+            &persona_manager,
+            &MacroContext::names(None, Some("Bot")),
+        );
+
+        let summary: Vec<(&str, &str)> = messages
+            .iter()
+            .map(|msg| (msg.role.as_str(), msg.content.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("system", "Pre.\n\nStart note for Bot.\n\nSys"),
+                ("assistant", "Example turn."),
+                ("user", "Q1"),
+                ("user", "Depth note."),
+                ("assistant", "A1"),
+                ("system", "Remember the rules."),
+                ("user", "Q2"),
+                ("assistant", "Sure,"),
+            ]
+        );
+    }
+
     #[test]
     fn test_apply_to_messages_skips_when_empty() {
         let mut config = create_test_config();
@@ -338,7 +563,7 @@ mod tests {
         let persona_manager = PersonaManager::load_personas(&config).expect("load personas");
 
         let mut messages = create_messages();
-        manager.apply_to_messages(&mut messages, &persona_manager, None);
+        manager.apply_to_messages(&mut messages, &persona_manager, &MacroContext::default());
 
         assert_eq!(messages.len(), 1);
         assert_eq!(messages[0].role, "user");
@@ -351,12 +576,12 @@ mod tests {
             .default_presets
             .entry("openai".to_string())
             .or_default()
-            .insert("gpt-4".to_string(), "focus".to_string());
+            .insert("gpt-4".to_string(), vec!["focus".to_string()]);
 
         let manager = PresetManager::load_presets(&config).expect("load presets");
         assert_eq!(
             manager.get_default_for_provider_model("OpenAI", "gpt-4"),
-            Some("focus")
+            Some(&["focus".to_string()][..])
         );
     }
 }
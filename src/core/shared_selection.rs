@@ -5,21 +5,25 @@ pub(crate) trait ManagedItem: Clone {
     fn id(&self) -> &str;
 }
 
-pub(crate) struct SelectionState<T: ManagedItem> {
+/// Items loaded from config, the active ones, and per provider/model defaults
+/// of type `D` (an item ID unless the manager stores something richer).
+/// Managers that allow a single active item use [`SelectionState::set_active`]
+/// and [`SelectionState::get_active`].
+pub(crate) struct SelectionState<T: ManagedItem, D = String> {
     items: Vec<T>,
-    active: Option<T>,
-    defaults: HashMap<(String, String), String>,
-    set_default_fn: fn(&mut Config, String, String, String),
+    active: Vec<T>,
+    defaults: HashMap<(String, String), D>,
+    set_default_fn: fn(&mut Config, String, String, D),
     unset_default_fn: fn(&mut Config, &str, &str),
     item_label: &'static str,
 }
 
-impl<T: ManagedItem> SelectionState<T> {
+impl<T: ManagedItem, D: Clone> SelectionState<T, D> {
     pub(crate) fn load_from_config(
         config: &Config,
         items_getter: impl Fn(&Config) -> &Vec<T>,
-        defaults_getter: impl Fn(&Config) -> &HashMap<String, HashMap<String, String>>,
-        set_default_fn: fn(&mut Config, String, String, String),
+        defaults_getter: impl Fn(&Config) -> &HashMap<String, HashMap<String, D>>,
+        set_default_fn: fn(&mut Config, String, String, D),
         unset_default_fn: fn(&mut Config, &str, &str),
         item_label: &'static str,
     ) -> Result<Self, Box<dyn std::error::Error>> {
@@ -33,7 +37,7 @@ impl<T: ManagedItem> SelectionState<T> {
 
         Ok(Self {
             items: items_getter(config).clone(),
-            active: None,
+            active: Vec::new(),
             defaults,
             set_default_fn,
             unset_default_fn,
@@ -49,51 +53,69 @@ impl<T: ManagedItem> SelectionState<T> {
         self.items.iter().find(|item| item.id() == id)
     }
 
+    /// Make `item_id` the only active item.
     pub(crate) fn set_active(&mut self, item_id: &str) -> Result<(), String> {
-        if let Some(item) = self.items.iter().find(|item| item.id() == item_id).cloned() {
-            self.active = Some(item);
-            Ok(())
-        } else {
-            let available_ids: Vec<&str> = self.items.iter().map(|item| item.id()).collect();
-            Err(format!(
-                "{} '{}' not found. Available {}s: {}",
-                self.item_label,
-                item_id,
-                self.item_label.to_lowercase(),
-                available_ids.join(", ")
-            ))
+        self.set_active_many(&[item_id])
+    }
+
+    /// Replace the active items with `item_ids`, in order. Repeated IDs are
+    /// kept once; nothing changes if any ID is unknown.
+    pub(crate) fn set_active_many<S: AsRef<str>>(&mut self, item_ids: &[S]) -> Result<(), String> {
+        let mut active: Vec<T> = Vec::new();
+        for item_id in item_ids {
+            let item_id = item_id.as_ref();
+            if active.iter().any(|item| item.id() == item_id) {
+                continue;
+            }
+            match self.find_by_id(item_id) {
+                Some(item) => active.push(item.clone()),
+                None => {
+                    let available_ids: Vec<&str> =
+                        self.items.iter().map(|item| item.id()).collect();
+                    return Err(format!(
+                        "{} '{}' not found. Available {}s: {}",
+                        self.item_label,
+                        item_id,
+                        self.item_label.to_lowercase(),
+                        available_ids.join(", ")
+                    ));
+                }
+            }
         }
+        self.active = active;
+        Ok(())
     }
 
     pub(crate) fn clear_active(&mut self) {
-        self.active = None;
+        self.active.clear();
     }
 
+    /// The first active item.
     pub(crate) fn get_active(&self) -> Option<&T> {
-        self.active.as_ref()
+        self.active.first()
     }
 
-    pub(crate) fn get_default_for_provider_model(
-        &self,
-        provider: &str,
-        model: &str,
-    ) -> Option<&str> {
+    /// All active items, in the order they were given.
+    pub(crate) fn active_items(&self) -> &[T] {
+        &self.active
+    }
+
+    pub(crate) fn get_default_for_provider_model(&self, provider: &str, model: &str) -> Option<&D> {
         let key = (provider.to_lowercase(), model.to_string());
-        self.defaults.get(&key).map(|s| s.as_str())
+        self.defaults.get(&key)
     }
 
     pub(crate) fn set_default_persistent(
         &mut self,
         provider: &str,
         model: &str,
-        item_id: &str,
+        item_id: D,
     ) -> Result<(), Box<dyn std::error::Error>> {
         let key = (provider.to_lowercase(), model.to_string());
-        self.defaults.insert(key, item_id.to_string());
+        self.defaults.insert(key, item_id.clone());
 
         let provider = provider.to_string();
         let model = model.to_string();
-        let item_id = item_id.to_string();
         let setter = self.set_default_fn;
 
         Config::mutate(move |config| {
@@ -0,0 +1,396 @@
+//! Append-only JSONL audit log of MCP tool calls made on the model's behalf.
+//!
+//! When `mcp_audit_log = true`, every tool call that reaches a permission
+//! decision gets one line in `mcp-audit.jsonl` under the config directory
+//! (readable only by the owner): when it finished, which
+//! server and tool ran, the arguments, how it was authorized, the outcome,
+//! latency, and the start of the result payload. Once the file passes
+//! [`MCP_AUDIT_LOG_MAX_BYTES`] it is moved to `mcp-audit.jsonl.1`, replacing
+//! the previous one. `chabeau mcp audit` reads the file back and
+//! `chabeau mcp audit replay` re-runs a logged call.
+
+use crate::core::app::session::ToolResultStatus;
+use crate::core::config::data::Config;
+use crate::mcp::permissions::ToolPermissionDecision;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// File name of the audit log inside the config directory.
+pub const MCP_AUDIT_LOG_FILE: &str = "mcp-audit.jsonl";
+
+/// Maximum number of payload characters kept per entry.
+pub const MCP_AUDIT_PAYLOAD_LIMIT: usize = 2000;
+
+/// Size at which the log is rotated to `mcp-audit.jsonl.1`.
+pub const MCP_AUDIT_LOG_MAX_BYTES: u64 = 5 * 1024 * 1024;
+
+/// How a tool call was authorized (or refused).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditDecision {
+    AllowOnce,
+    AllowSession,
+    DenyOnce,
+    Block,
+    AllowAlways,
+    BlockAlways,
+    /// Allowed by an `allow` policy rule without prompting.
+    PolicyAllow,
+    /// Refused by a `deny` policy rule without prompting.
+    PolicyDeny,
+    /// Allowed because YOLO mode is on for the server.
+    Yolo,
+}
+
+impl From<ToolPermissionDecision> for AuditDecision {
+    fn from(decision: ToolPermissionDecision) -> Self {
+        match decision {
+            ToolPermissionDecision::AllowOnce => AuditDecision::AllowOnce,
+            ToolPermissionDecision::AllowSession => AuditDecision::AllowSession,
+            ToolPermissionDecision::DenyOnce => AuditDecision::DenyOnce,
+            ToolPermissionDecision::Block => AuditDecision::Block,
+            ToolPermissionDecision::AllowAlways => AuditDecision::AllowAlways,
+            ToolPermissionDecision::BlockAlways => AuditDecision::BlockAlways,
+        }
+    }
+}
+
+impl AuditDecision {
+    pub fn label(self) -> &'static str {
+        match self {
+            AuditDecision::AllowOnce => "allow_once",
+            AuditDecision::AllowSession => "allow_session",
+            AuditDecision::DenyOnce => "deny_once",
+            AuditDecision::Block => "block",
+            AuditDecision::AllowAlways => "allow_always",
+            AuditDecision::BlockAlways => "block_always",
+            AuditDecision::PolicyAllow => "policy_allow",
+            AuditDecision::PolicyDeny => "policy_deny",
+            AuditDecision::Yolo => "yolo",
+        }
+    }
+}
+
+/// Outcome of an audited tool call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditStatus {
+    Success,
+    Error,
+    Denied,
+    Blocked,
+}
+
+impl From<ToolResultStatus> for AuditStatus {
+    fn from(status: ToolResultStatus) -> Self {
+        match status {
+            ToolResultStatus::Success => AuditStatus::Success,
+            ToolResultStatus::Error => AuditStatus::Error,
+            ToolResultStatus::Denied => AuditStatus::Denied,
+            ToolResultStatus::Blocked => AuditStatus::Blocked,
+        }
+    }
+}
+
+impl AuditStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            AuditStatus::Success => "success",
+            AuditStatus::Error => "error",
+            AuditStatus::Denied => "denied",
+            AuditStatus::Blocked => "blocked",
+        }
+    }
+
+    /// Parses a status name as printed by [`AuditStatus::label`].
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "success" | "ok" => Some(AuditStatus::Success),
+            "error" => Some(AuditStatus::Error),
+            "denied" => Some(AuditStatus::Denied),
+            "blocked" => Some(AuditStatus::Blocked),
+            _ => None,
+        }
+    }
+}
+
+/// One line of the audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolAuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub server_id: String,
+    pub tool: String,
+    /// Parsed call arguments, or the raw string when it was not valid JSON.
+    pub arguments: Value,
+    pub decision: AuditDecision,
+    pub status: AuditStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    pub payload: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub payload_truncated: bool,
+}
+
+impl ToolAuditEntry {
+    pub fn new(
+        server_id: &str,
+        tool: &str,
+        raw_arguments: &str,
+        decision: AuditDecision,
+        status: AuditStatus,
+        latency: Option<Duration>,
+        payload: &str,
+    ) -> Self {
+        let arguments = serde_json::from_str(raw_arguments)
+            .unwrap_or_else(|_| Value::String(raw_arguments.to_string()));
+        let payload_truncated = payload.chars().count() > MCP_AUDIT_PAYLOAD_LIMIT;
+        let payload = if payload_truncated {
+            payload.chars().take(MCP_AUDIT_PAYLOAD_LIMIT).collect()
+        } else {
+            payload.to_string()
+        };
+        Self {
+            timestamp: Utc::now(),
+            server_id: server_id.to_string(),
+            tool: tool.to_string(),
+            arguments,
+            decision,
+            status,
+            latency_ms: latency.map(|latency| latency.as_millis() as u64),
+            payload,
+            payload_truncated,
+        }
+    }
+}
+
+/// Filters applied when listing audit entries; `None` matches everything.
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub server_id: Option<String>,
+    pub tool: Option<String>,
+    pub status: Option<AuditStatus>,
+}
+
+impl AuditFilter {
+    pub fn matches(&self, entry: &ToolAuditEntry) -> bool {
+        self.server_id
+            .as_deref()
+            .is_none_or(|server| entry.server_id.eq_ignore_ascii_case(server))
+            && self.tool.as_deref().is_none_or(|tool| entry.tool == tool)
+            && self.status.is_none_or(|status| entry.status == status)
+    }
+}
+
+/// Handle to the audit log file.
+#[derive(Debug, Clone)]
+pub struct ToolAuditLog {
+    path: PathBuf,
+}
+
+impl ToolAuditLog {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Returns the log under the config directory, or `None` unless
+    /// `mcp_audit_log = true`.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        if config.mcp_audit_log != Some(true) {
+            return None;
+        }
+        Some(Self::new(Self::default_path()))
+    }
+
+    pub fn default_path() -> PathBuf {
+        Config::get_config_base_dir().join(MCP_AUDIT_LOG_FILE)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(&self, entry: &ToolAuditEntry) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+        }
+        let mut line = serde_json::to_string(entry)
+            .map_err(|err| format!("Failed to encode audit entry: {err}"))?;
+        line.push('\n');
+        self.rotate_if_full(MCP_AUDIT_LOG_MAX_BYTES)?;
+        let mut options = OpenOptions::new();
+        options.create(true).append(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|err| format!("Failed to write {}: {err}", self.path.display()))
+    }
+
+    /// Path the log is moved to when it is rotated.
+    pub fn rotated_path(&self) -> PathBuf {
+        let mut name = self.path.file_name().unwrap_or_default().to_os_string();
+        name.push(".1");
+        self.path.with_file_name(name)
+    }
+
+    fn rotate_if_full(&self, max_bytes: u64) -> Result<(), String> {
+        let size = match fs::metadata(&self.path) {
+            Ok(metadata) => metadata.len(),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(format!("Failed to read {}: {err}", self.path.display())),
+        };
+        if size < max_bytes {
+            return Ok(());
+        }
+        fs::rename(&self.path, self.rotated_path())
+            .map_err(|err| format!("Failed to rotate {}: {err}", self.path.display()))
+    }
+
+    /// Reads every entry in file order. A missing file is an empty log, and
+    /// lines that do not parse (such as one cut short by a crash) are skipped.
+    pub fn read_entries(&self) -> Result<Vec<ToolAuditEntry>, String> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(format!("Failed to read {}: {err}", self.path.display())),
+        };
+        Ok(contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn entry(server: &str, tool: &str, status: AuditStatus) -> ToolAuditEntry {
+        ToolAuditEntry::new(
+            server,
+            tool,
+            r#"{"q":"rust"}"#,
+            AuditDecision::AllowSession,
+            status,
+            Some(Duration::from_millis(42)),
+            "ok",
+        )
+    }
+
+    #[test]
+    fn append_and_read_round_trip_skipping_bad_lines() {
+        let dir = tempdir().unwrap();
+        let log = ToolAuditLog::new(dir.path().join("nested").join(MCP_AUDIT_LOG_FILE));
+        assert!(log.read_entries().unwrap().is_empty());
+
+        let first = entry("alpha", "search", AuditStatus::Success);
+        log.append(&first).unwrap();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(log.path())
+            .unwrap()
+            .write_all(b"{\"truncated\n")
+            .unwrap();
+        let second = entry("beta", "fetch", AuditStatus::Error);
+        log.append(&second).unwrap();
+
+        let entries = log.read_entries().unwrap();
+        assert_eq!(entries, vec![first, second]);
+        assert_eq!(entries[0].arguments["q"], "rust");
+        assert_eq!(entries[0].latency_ms, Some(42));
+    }
+
+    #[test]
+    fn log_is_opt_in() {
+        let mut config = Config::default();
+        assert!(ToolAuditLog::from_config(&config).is_none());
+        config.mcp_audit_log = Some(false);
+        assert!(ToolAuditLog::from_config(&config).is_none());
+        config.mcp_audit_log = Some(true);
+        assert!(ToolAuditLog::from_config(&config).is_some());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn log_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let log = ToolAuditLog::new(dir.path().join(MCP_AUDIT_LOG_FILE));
+        log.append(&entry("alpha", "search", AuditStatus::Success))
+            .unwrap();
+        let mode = fs::metadata(log.path()).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn full_log_is_rotated() {
+        let dir = tempdir().unwrap();
+        let log = ToolAuditLog::new(dir.path().join(MCP_AUDIT_LOG_FILE));
+        let first = entry("alpha", "search", AuditStatus::Success);
+        log.append(&first).unwrap();
+
+        log.rotate_if_full(1).unwrap();
+        let second = entry("beta", "fetch", AuditStatus::Error);
+        log.append(&second).unwrap();
+
+        assert_eq!(log.read_entries().unwrap(), vec![second]);
+        let rotated = ToolAuditLog::new(log.rotated_path());
+        assert_eq!(rotated.read_entries().unwrap(), vec![first]);
+    }
+
+    #[test]
+    fn new_entry_truncates_payload_and_keeps_raw_arguments() {
+        let payload = "x".repeat(MCP_AUDIT_PAYLOAD_LIMIT + 10);
+        let entry = ToolAuditEntry::new(
+            "alpha",
+            "search",
+            "not json",
+            AuditDecision::PolicyDeny,
+            AuditStatus::Blocked,
+            None,
+            &payload,
+        );
+
+        assert!(entry.payload_truncated);
+        assert_eq!(entry.payload.chars().count(), MCP_AUDIT_PAYLOAD_LIMIT);
+        assert_eq!(entry.arguments, Value::String("not json".to_string()));
+        let line = serde_json::to_string(&entry).unwrap();
+        assert!(line.contains(r#""decision":"policy_deny""#));
+        assert!(!line.contains("latency_ms"));
+    }
+
+    #[test]
+    fn filter_matches_server_tool_and_status() {
+        let entry = entry("Alpha", "search", AuditStatus::Error);
+        assert!(AuditFilter::default().matches(&entry));
+        assert!(AuditFilter {
+            server_id: Some("alpha".to_string()),
+            tool: Some("search".to_string()),
+            status: AuditStatus::parse("error"),
+        }
+        .matches(&entry));
+        assert!(!AuditFilter {
+            status: Some(AuditStatus::Success),
+            ..Default::default()
+        }
+        .matches(&entry));
+        assert!(!AuditFilter {
+            tool: Some("fetch".to_string()),
+            ..Default::default()
+        }
+        .matches(&entry));
+    }
+}
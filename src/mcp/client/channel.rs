@@ -0,0 +1,429 @@
+//! Request/response correlation for MCP transports with a persistent inbound
+//! channel.
+//!
+//! Stdio servers answer on the child's stdout and legacy HTTP+SSE servers
+//! answer on the event stream, so both share this client: outbound messages go
+//! through a [`ChannelWriter`], and a transport-owned reader task feeds every
+//! inbound message to [`ChannelInbound::dispatch`], which resolves pending
+//! requests by JSON-RPC id and forwards server requests and notifications as
+//! `McpServerEvent`s.
+//!
+//! Failure semantics:
+//! - Request send/wait paths enforce lock, write, and response timeouts.
+//! - Closing the inbound channel drops all pending requests, which surface as
+//!   "response channel closed" errors instead of waiting for the timeout. A
+//!   closed client is treated as disconnected so the manager reconnects it.
+
+use super::transport_sse::SsePostTarget;
+use super::transport_stdio::StdinWriter;
+use super::{protocol, McpServerLimits};
+use crate::mcp::events::{McpServerEvent, McpServerNotification, McpServerRequest};
+use rust_mcp_schema::schema_utils::{
+    ClientMessage, FromMessage, MessageFromClient, NotificationFromClient, RequestFromClient,
+    ResultFromClient, ServerMessage,
+};
+use rust_mcp_schema::{InitializeRequestParams, InitializeResult, RequestId, RpcError};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tokio::process::ChildStdin;
+use tokio::sync::{mpsc, oneshot, Mutex, Notify, RwLock};
+use tracing::debug;
+
+type PendingRequests = Arc<Mutex<HashMap<RequestId, oneshot::Sender<ServerMessage>>>>;
+
+static NEXT_CLIENT_INSTANCE: AtomicU64 = AtomicU64::new(1);
+
+/// Outbound half of a channel client.
+pub(crate) enum ChannelWriter {
+    /// Newline-delimited JSON written to a child process.
+    Stdin(StdinWriter),
+    /// JSON `POST`s to the endpoint announced on a legacy SSE stream.
+    SsePost(SsePostTarget),
+}
+
+impl ChannelWriter {
+    fn label(&self) -> &'static str {
+        match self {
+            ChannelWriter::Stdin(_) => "stdio",
+            ChannelWriter::SsePost(_) => "SSE",
+        }
+    }
+}
+
+/// Inbound state shared between a channel client and its reader task.
+#[derive(Clone)]
+pub(crate) struct ChannelInbound {
+    pending: PendingRequests,
+    server_id: String,
+    event_tx: Option<mpsc::UnboundedSender<McpServerEvent>>,
+    activity_notify: Arc<Notify>,
+    inflight_server_requests: Arc<AtomicI64>,
+    instance: u64,
+    closed: Arc<AtomicBool>,
+}
+
+impl ChannelInbound {
+    pub(crate) fn new(
+        server_id: String,
+        event_tx: Option<mpsc::UnboundedSender<McpServerEvent>>,
+    ) -> Self {
+        Self {
+            pending: Arc::new(Mutex::new(HashMap::new())),
+            server_id,
+            event_tx,
+            activity_notify: Arc::new(Notify::new()),
+            inflight_server_requests: Arc::new(AtomicI64::new(0)),
+            instance: NEXT_CLIENT_INSTANCE.fetch_add(1, Ordering::Relaxed),
+            closed: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Process-unique id of the client this inbound channel belongs to.
+    pub(crate) fn instance(&self) -> u64 {
+        self.instance
+    }
+
+    pub(crate) fn server_id(&self) -> &str {
+        &self.server_id
+    }
+
+    pub(crate) fn event_tx(&self) -> Option<&mpsc::UnboundedSender<McpServerEvent>> {
+        self.event_tx.as_ref()
+    }
+
+    /// Dispatches a raw JSON payload, accepting JSON-RPC batches.
+    pub(crate) async fn dispatch_value(&self, value: serde_json::Value) {
+        if let Some(items) = value.as_array() {
+            for item in items {
+                if let Ok(message) = serde_json::from_value::<ServerMessage>(item.clone()) {
+                    self.dispatch(message).await;
+                }
+            }
+        } else if let Ok(message) = serde_json::from_value::<ServerMessage>(value) {
+            self.dispatch(message).await;
+        }
+    }
+
+    pub(crate) async fn dispatch(&self, message: ServerMessage) {
+        match &message {
+            ServerMessage::Response(response) => {
+                if let Some(tx) = self.pending.lock().await.remove(&response.id) {
+                    let _ = tx.send(message);
+                }
+            }
+            ServerMessage::Error(error) => {
+                if let Some(id) = error.id.as_ref() {
+                    if let Some(tx) = self.pending.lock().await.remove(id) {
+                        let _ = tx.send(message);
+                    }
+                }
+            }
+            ServerMessage::Request(request) => {
+                let _ = self.inflight_server_requests.fetch_add(1, Ordering::SeqCst);
+                self.activity_notify.notify_waiters();
+                if let Some(tx) = self.event_tx.as_ref() {
+                    let _ = tx.send(McpServerEvent::Request(McpServerRequest {
+                        server_id: self.server_id.clone(),
+                        request: request.clone(),
+                    }));
+                }
+            }
+            ServerMessage::Notification(notification) => {
+                self.activity_notify.notify_waiters();
+                if let Some(tx) = self.event_tx.as_ref() {
+                    let _ = tx.send(McpServerEvent::Notification(McpServerNotification {
+                        server_id: self.server_id.clone(),
+                        notification: notification.clone(),
+                    }));
+                }
+            }
+        }
+    }
+
+    /// Fails all pending requests once the inbound channel is gone.
+    pub(crate) async fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        self.pending.lock().await.clear();
+    }
+
+    pub(crate) fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+}
+
+/// Stateful channel client with pending-request correlation.
+///
+/// This client tracks inflight server-initiated work so request timeouts can be
+/// extended while the application is processing callbacks such as sampling.
+pub(crate) struct ChannelClient {
+    writer: ChannelWriter,
+    inbound: ChannelInbound,
+    next_request_id: AtomicI64,
+    server_details: RwLock<Option<InitializeResult>>,
+    limits: McpServerLimits,
+}
+
+impl ChannelClient {
+    pub(crate) fn new(
+        writer: ChannelWriter,
+        inbound: ChannelInbound,
+        limits: McpServerLimits,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            writer,
+            inbound,
+            next_request_id: AtomicI64::new(0),
+            server_details: RwLock::new(None),
+            limits,
+        })
+    }
+
+    pub(crate) fn instance(&self) -> u64 {
+        self.inbound.instance()
+    }
+
+    /// Reports whether the process or event stream behind this client is gone.
+    pub(crate) fn is_closed(&self) -> bool {
+        self.inbound.is_closed()
+    }
+
+    /// Recent stderr output for stdio servers; empty for other transports.
+    pub(crate) fn stderr_tail(&self) -> Vec<String> {
+        match &self.writer {
+            ChannelWriter::Stdin(writer) => writer.stderr_tail.snapshot(),
+            ChannelWriter::SsePost(_) => Vec::new(),
+        }
+    }
+
+    /// Runs initialize/initialized handshake and caches server details.
+    pub(crate) async fn initialize(
+        &self,
+        details: InitializeRequestParams,
+    ) -> Result<InitializeResult, String> {
+        let response = self
+            .send_request(RequestFromClient::InitializeRequest(details))
+            .await?;
+        let result = protocol::parse_initialize_result(response)?;
+        *self.server_details.write().await = Some(result.clone());
+        self.send_notification(NotificationFromClient::InitializedNotification(None))
+            .await?;
+        Ok(result)
+    }
+
+    pub(crate) async fn send_request(
+        &self,
+        request: RequestFromClient,
+    ) -> Result<ServerMessage, String> {
+        let label = self.writer.label();
+        let request_id = self.next_request_id();
+        debug!(request_id = ?request_id, transport = label, "Sending MCP channel request");
+        let message = ClientMessage::from_message(
+            MessageFromClient::RequestFromClient(request),
+            Some(request_id.clone()),
+        )
+        .map_err(|err| err.to_string())?;
+
+        let (tx, rx) = oneshot::channel();
+        {
+            let mut pending = self.inbound.pending.lock().await;
+            pending.insert(request_id.clone(), tx);
+        }
+        if self.inbound.is_closed() {
+            self.inbound.pending.lock().await.remove(&request_id);
+            return Err(format!("MCP {label} response channel closed."));
+        }
+
+        if let Err(err) = self.send_client_message(&message).await {
+            self.inbound.pending.lock().await.remove(&request_id);
+            return Err(err);
+        }
+
+        let wait_timeout = self.timeout_for_wait();
+        match tokio::time::timeout(wait_timeout, rx).await {
+            Ok(Ok(message)) => Ok(message),
+            Ok(Err(_)) => {
+                self.inbound.pending.lock().await.remove(&request_id);
+                Err(format!("MCP {label} response channel closed."))
+            }
+            Err(_) => {
+                self.inbound.pending.lock().await.remove(&request_id);
+                Err(format!("Timed out waiting for MCP {label} response."))
+            }
+        }
+    }
+
+    pub(crate) async fn send_result(
+        &self,
+        request_id: RequestId,
+        result: ResultFromClient,
+    ) -> Result<(), String> {
+        debug!(
+            server_id = %self.inbound.server_id,
+            request_id = ?request_id,
+            "Preparing MCP channel result"
+        );
+        let message = ClientMessage::from_message(
+            MessageFromClient::ResultFromClient(result),
+            Some(request_id.clone()),
+        )
+        .map_err(|err| err.to_string())?;
+        let result = self.send_client_message(&message).await;
+        if result.is_ok() {
+            let inflight = self.decrement_inflight();
+            debug!(
+                request_id = ?request_id,
+                inflight_server_requests = inflight,
+                "Sent MCP channel result"
+            );
+            self.inbound.activity_notify.notify_waiters();
+        }
+        result
+    }
+
+    pub(crate) async fn send_error(
+        &self,
+        request_id: RequestId,
+        error: RpcError,
+    ) -> Result<(), String> {
+        debug!(
+            server_id = %self.inbound.server_id,
+            request_id = ?request_id,
+            "Preparing MCP channel error response"
+        );
+        let message =
+            ClientMessage::from_message(MessageFromClient::Error(error), Some(request_id.clone()))
+                .map_err(|err| err.to_string())?;
+        let result = self.send_client_message(&message).await;
+        if result.is_ok() {
+            let inflight = self.decrement_inflight();
+            debug!(
+                request_id = ?request_id,
+                inflight_server_requests = inflight,
+                "Sent MCP channel error response"
+            );
+            self.inbound.activity_notify.notify_waiters();
+        }
+        result
+    }
+
+    async fn send_notification(&self, notification: NotificationFromClient) -> Result<(), String> {
+        let message = ClientMessage::from_message(
+            MessageFromClient::NotificationFromClient(notification),
+            None,
+        )
+        .map_err(|err| err.to_string())?;
+        self.send_client_message(&message).await
+    }
+
+    async fn send_client_message(&self, message: &ClientMessage) -> Result<(), String> {
+        match &self.writer {
+            ChannelWriter::Stdin(writer) => write_stdin_message(&writer.stdin, message).await,
+            ChannelWriter::SsePost(target) => target.post(message).await,
+        }
+    }
+
+    fn timeout_for_wait(&self) -> tokio::time::Duration {
+        let inflight = self.inbound.inflight_server_requests.load(Ordering::SeqCst);
+        if inflight > 0 {
+            self.limits.extended_request_timeout()
+        } else {
+            self.limits.request_timeout
+        }
+    }
+
+    fn decrement_inflight(&self) -> i64 {
+        let inflight = &self.inbound.inflight_server_requests;
+        let mut current = inflight.load(Ordering::SeqCst);
+        while current > 0 {
+            match inflight.compare_exchange(
+                current,
+                current - 1,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => return current - 1,
+                Err(next) => current = next,
+            }
+        }
+        current
+    }
+
+    fn next_request_id(&self) -> RequestId {
+        let id = self.next_request_id.fetch_add(1, Ordering::SeqCst);
+        RequestId::Integer(id)
+    }
+}
+
+async fn write_stdin_message(
+    stdin: &Mutex<ChildStdin>,
+    message: &ClientMessage,
+) -> Result<(), String> {
+    let lock_timeout = tokio::time::Duration::from_secs(10);
+    let write_timeout = tokio::time::Duration::from_secs(10);
+    let payload = serde_json::to_string(message).map_err(|err| err.to_string())?;
+    let mut stdin = match tokio::time::timeout(lock_timeout, stdin.lock()).await {
+        Ok(stdin) => stdin,
+        Err(_) => return Err("Timed out waiting for MCP stdio stdin lock.".to_string()),
+    };
+
+    tokio::time::timeout(write_timeout, stdin.write_all(payload.as_bytes()))
+        .await
+        .map_err(|_| "Timed out writing MCP stdio client message.".to_string())?
+        .map_err(|err| err.to_string())?;
+    tokio::time::timeout(write_timeout, stdin.write_all(b"\n"))
+        .await
+        .map_err(|_| "Timed out writing MCP stdio newline.".to_string())?
+        .map_err(|err| err.to_string())?;
+    tokio::time::timeout(write_timeout, stdin.flush())
+        .await
+        .map_err(|_| "Timed out flushing MCP stdio client message.".to_string())?
+        .map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+pub(crate) async fn send_request(
+    client: Option<Arc<ChannelClient>>,
+    request: RequestFromClient,
+) -> Result<ServerMessage, String> {
+    let Some(client) = client else {
+        return Err("MCP client not connected.".to_string());
+    };
+    client.send_request(request).await
+}
+
+pub(crate) async fn send_result(
+    client: Option<Arc<ChannelClient>>,
+    request_id: RequestId,
+    result: ResultFromClient,
+) -> Result<(), String> {
+    let Some(client) = client else {
+        return Err("MCP client not connected.".to_string());
+    };
+    client.send_result(request_id, result).await
+}
+
+pub(crate) async fn send_error(
+    client: Option<Arc<ChannelClient>>,
+    request_id: RequestId,
+    error: RpcError,
+) -> Result<(), String> {
+    let Some(client) = client else {
+        return Err("MCP client not connected.".to_string());
+    };
+    client.send_error(request_id, error).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn channel_requires_connected_client() {
+        let err = send_request(None, RequestFromClient::PingRequest(None))
+            .await
+            .expect_err("expected missing client error");
+        assert_eq!(err, "MCP client not connected.");
+    }
+}
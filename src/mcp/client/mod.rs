@@ -17,8 +17,8 @@
 //!
 //! Extension notes for contributors adding new MCP operations:
 //! - Add request/response parsing in `client/protocol.rs`.
-//! - Route the operation through `client/operations.rs` so both stdio and
-//!   streamable HTTP remain behaviorally aligned.
+//! - Route the operation through `client/operations.rs` so stdio, legacy SSE,
+//!   and streamable HTTP remain behaviorally aligned.
 //! - Wire any cache updates in [`McpClientManager`] and preserve invariants:
 //!   `connected` reflects handshake success, `session_id` mirrors server-issued
 //!   values, and `last_error` describes the latest recoverable transport/protocol
@@ -27,20 +27,24 @@
 use crate::core::config::data::{Config, McpServerConfig};
 use crate::core::mcp_auth::McpTokenStore;
 use crate::core::oauth::refresh_oauth_grant_if_needed;
-use crate::mcp::events::McpServerRequest;
+use crate::mcp::events::McpServerEvent;
+use crate::mcp::health::McpServerHealth;
+use crate::mcp::logging::{parse_logging_level, McpLogBuffer};
+use crate::mcp::subscriptions::McpResourceWatches;
 pub use crate::mcp::transport::McpTransportKind;
 use crate::mcp::transport::{self, ListFetch};
 use futures_util::{stream, StreamExt};
 use jsonschema::Validator as JsonSchemaValidator;
 pub use operations::{
-    execute_prompt, execute_resource_list, execute_resource_read, execute_resource_template_list,
-    execute_tool_call, send_client_error, send_client_result,
+    execute_completion, execute_ping, execute_prompt, execute_resource_list, execute_resource_read,
+    execute_resource_subscribe, execute_resource_template_list, execute_resource_unsubscribe,
+    execute_set_log_level, execute_tool_call, send_client_error, send_client_result,
 };
 use rust_mcp_schema::schema_utils::{RequestFromClient, ServerMessage};
 use rust_mcp_schema::{
     ClientCapabilities, ClientSampling, Implementation, InitializeRequestParams, InitializeResult,
     ListPromptsResult, ListResourceTemplatesResult, ListResourcesResult, ListToolsResult,
-    PaginatedRequestParams, RpcError, ServerCapabilities,
+    LoggingLevel, PaginatedRequestParams, RpcError, ServerCapabilities, ToolOutputSchema,
 };
 use serde_json::Value;
 use std::collections::HashMap;
@@ -48,18 +52,22 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc;
 
+mod channel;
 mod operations;
 mod protocol;
 mod transport_http;
+mod transport_sse;
 mod transport_stdio;
 
+use channel::ChannelClient;
 use transport_http::StreamableHttpContext;
-use transport_stdio::StdioClient;
 
-/// Upper bound for merged `tools/list` pagination results kept in cache.
+/// Default upper bound for merged `tools/list` pagination results kept in
+/// cache (`max_tools` / `mcp_max_tools`).
 const MCP_MAX_TOOL_LIST: usize = 100;
 
-/// Number of servers initialized concurrently during startup.
+/// Default number of servers initialized concurrently during startup
+/// (`mcp_startup_concurrency`).
 const MCP_STARTUP_CONCURRENCY_LIMIT: usize = 3;
 
 /// Content type used for JSON POSTs over streamable HTTP.
@@ -74,8 +82,13 @@ const MCP_PROTOCOL_VERSION_HEADER: &str = "MCP-Protocol-Version";
 /// TCP connect timeout used for MCP HTTP clients.
 const MCP_HTTP_CONNECT_TIMEOUT_SECONDS: u64 = 10;
 
-/// End-to-end timeout for MCP HTTP requests.
-const MCP_HTTP_REQUEST_TIMEOUT_SECONDS: u64 = 60;
+/// Default end-to-end timeout for MCP requests on every transport
+/// (`request_timeout_seconds` / `mcp_request_timeout_seconds`).
+const MCP_REQUEST_TIMEOUT_SECONDS: u64 = 60;
+
+/// Default timeout for sampling requests that do not name their own
+/// (`sampling_timeout_seconds` / `mcp_sampling_timeout_seconds`).
+const MCP_SAMPLING_TIMEOUT_SECONDS: u64 = 300;
 
 /// Idle keep-alive timeout for pooled MCP HTTP connections.
 const MCP_HTTP_POOL_IDLE_TIMEOUT_SECONDS: u64 = 90;
@@ -83,10 +96,10 @@ const MCP_HTTP_POOL_IDLE_TIMEOUT_SECONDS: u64 = 90;
 /// Per-host idle connection pool size for MCP HTTP clients.
 const MCP_HTTP_POOL_MAX_IDLE_PER_HOST: usize = 8;
 
-fn build_mcp_http_client() -> Result<reqwest::Client, String> {
+fn build_mcp_http_client(request_timeout: Duration) -> Result<reqwest::Client, String> {
     reqwest::Client::builder()
         .connect_timeout(Duration::from_secs(MCP_HTTP_CONNECT_TIMEOUT_SECONDS))
-        .timeout(Duration::from_secs(MCP_HTTP_REQUEST_TIMEOUT_SECONDS))
+        .timeout(request_timeout)
         .pool_idle_timeout(Duration::from_secs(MCP_HTTP_POOL_IDLE_TIMEOUT_SECONDS))
         .pool_max_idle_per_host(MCP_HTTP_POOL_MAX_IDLE_PER_HOST)
         .build()
@@ -135,11 +148,66 @@ fn apply_streamable_http_protocol_version_header(
     }
 }
 
-/// Base timeout for stdio request/response round-trips.
-const STDIO_REQUEST_TIMEOUT_SECONDS: u64 = 60;
+/// Timeout multiplier for stdio/SSE waits while server-initiated requests are
+/// inflight.
+const STDIO_SAMPLING_TIMEOUT_MULTIPLIER: u32 = 5;
+
+/// Effective timeouts and caps for one server once per-server settings,
+/// global config defaults, and built-in defaults are applied in that order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct McpServerLimits {
+    pub request_timeout: Duration,
+    pub sampling_timeout: Duration,
+    pub max_tools: usize,
+}
+
+impl Default for McpServerLimits {
+    fn default() -> Self {
+        Self {
+            request_timeout: Duration::from_secs(MCP_REQUEST_TIMEOUT_SECONDS),
+            sampling_timeout: Duration::from_secs(MCP_SAMPLING_TIMEOUT_SECONDS),
+            max_tools: MCP_MAX_TOOL_LIST,
+        }
+    }
+}
+
+impl McpServerLimits {
+    /// Resolves limits for `server`; zero values fall through to the next level.
+    pub fn resolve(config: &Config, server: &McpServerConfig) -> Self {
+        let defaults = Self::default();
+        let seconds = |server: Option<u64>, global: Option<u64>, default: Duration| {
+            server
+                .filter(|value| *value > 0)
+                .or(global.filter(|value| *value > 0))
+                .map(Duration::from_secs)
+                .unwrap_or(default)
+        };
+        Self {
+            request_timeout: seconds(
+                server.request_timeout_seconds,
+                config.mcp_request_timeout_seconds,
+                defaults.request_timeout,
+            ),
+            sampling_timeout: seconds(
+                server.sampling_timeout_seconds,
+                config.mcp_sampling_timeout_seconds,
+                defaults.sampling_timeout,
+            ),
+            max_tools: server
+                .max_tools
+                .filter(|value| *value > 0)
+                .or(config.mcp_max_tools.filter(|value| *value > 0))
+                .unwrap_or(defaults.max_tools),
+        }
+    }
 
-/// Timeout multiplier while server-initiated requests are inflight.
-const STDIO_SAMPLING_TIMEOUT_MULTIPLIER: u64 = 5;
+    /// How long stdio/SSE requests wait while the server is itself waiting on
+    /// a client callback such as sampling.
+    pub fn extended_request_timeout(&self) -> Duration {
+        (self.request_timeout * STDIO_SAMPLING_TIMEOUT_MULTIPLIER)
+            .max(self.sampling_timeout + self.request_timeout)
+    }
+}
 
 /// Mutable runtime state for one configured MCP server.
 ///
@@ -185,8 +253,15 @@ pub struct McpServerState {
     pub negotiated_protocol_version: Option<String>,
     pub streamable_http_request_id: u64,
     pub event_listener_started: bool,
+    pub log_buffer: McpLogBuffer,
+    pub log_level: Option<LoggingLevel>,
+    pub resource_watches: McpResourceWatches,
+    pub health: McpServerHealth,
+    pub limits: McpServerLimits,
+    /// Stderr lines captured when the stdio process last exited or failed.
+    pub stderr_tail: Vec<String>,
     http_client: Option<reqwest::Client>,
-    client: Option<Arc<StdioClient>>,
+    client: Option<Arc<ChannelClient>>,
 }
 
 impl McpServerState {
@@ -207,11 +282,22 @@ impl McpServerState {
             negotiated_protocol_version: None,
             streamable_http_request_id: 0,
             event_listener_started: false,
+            log_buffer: McpLogBuffer::default(),
+            log_level: None,
+            resource_watches: McpResourceWatches::new(),
+            health: McpServerHealth::default(),
+            limits: McpServerLimits::default(),
+            stderr_tail: Vec::new(),
             http_client: None,
             client: None,
         }
     }
 
+    /// Identifies the live stdio/SSE client, if any.
+    pub fn client_instance(&self) -> Option<u64> {
+        self.client.as_ref().map(|client| client.instance())
+    }
+
     /// Returns the server-specific allow-list configured for tool filtering.
     pub fn allowed_tools(&self) -> Option<&[String]> {
         self.config.allowed_tools.as_deref()
@@ -227,6 +313,18 @@ impl McpServerState {
             .get(&tool_name.to_ascii_lowercase())
     }
 
+    /// Looks up a cached tool's output schema, matching the name case-insensitively
+    /// like [`Self::tool_validator`].
+    pub fn tool_output_schema(&self, tool_name: &str) -> Option<&ToolOutputSchema> {
+        self.cached_tools
+            .as_ref()?
+            .tools
+            .iter()
+            .find(|tool| tool.name.eq_ignore_ascii_case(tool_name))?
+            .output_schema
+            .as_ref()
+    }
+
     pub fn disabled_tool_validation_entries(&self) -> Vec<(&str, &str)> {
         let Some(list) = self.cached_tools.as_ref() else {
             return Vec::new();
@@ -268,10 +366,40 @@ impl McpServerState {
             .unwrap_or(true)
     }
 
+    /// Reports whether the server advertised `completion/complete` support.
+    ///
+    /// Unlike the listing capabilities this defaults to unsupported, since
+    /// completion requests are sent while the user types.
+    pub fn supports_completions(&self) -> bool {
+        self.server_capabilities()
+            .is_some_and(|caps| caps.completions.is_some())
+    }
+
+    /// Reports whether `logging/setLevel` is expected to succeed.
+    pub fn supports_logging(&self) -> bool {
+        self.server_capabilities()
+            .map(|caps| caps.logging.is_some())
+            .unwrap_or(true)
+    }
+
+    /// Reports whether `resources/subscribe` is expected to succeed.
+    pub fn supports_resource_subscribe(&self) -> bool {
+        self.server_capabilities()
+            .map(|caps| {
+                caps.resources
+                    .as_ref()
+                    .is_some_and(|resources| resources.subscribe.unwrap_or(false))
+            })
+            .unwrap_or(true)
+    }
+
     /// Clears all runtime-only fields while preserving persisted configuration.
     ///
     /// This is used before reconnecting to force a fresh initialize handshake,
     /// invalidate stale metadata, and drop any cached auth/session headers.
+    /// Buffered log entries and the stderr tail are kept so crash diagnostics
+    /// survive reconnects; resource watches are dropped because subscriptions
+    /// are session-bound.
     pub fn clear_runtime_state(&mut self) {
         self.connected = false;
         self.last_error = None;
@@ -286,6 +414,9 @@ impl McpServerState {
         self.negotiated_protocol_version = None;
         self.streamable_http_request_id = 0;
         self.event_listener_started = false;
+        self.log_level = None;
+        self.resource_watches.clear();
+        self.health = McpServerHealth::Unknown;
         self.http_client = None;
         self.client = None;
     }
@@ -305,13 +436,16 @@ impl McpServerState {
 ///   process env and direct stdin/stdout IPC are available.
 /// - **streamable-http** is preferred for remote hosted MCP endpoints where
 ///   bearer tokens and custom headers must be attached to HTTP requests.
+/// - **sse** covers remote servers still on the 2024-11-05 HTTP+SSE transport.
 pub struct McpClientManager {
     servers: HashMap<String, McpServerState>,
-    server_request_tx: Option<mpsc::UnboundedSender<McpServerRequest>>,
+    server_event_tx: Option<mpsc::UnboundedSender<McpServerEvent>>,
+    startup_concurrency: Option<usize>,
 }
 
 macro_rules! paginate_tools_list_with {
-    ($fetch_fn:path, ($($arg:expr),*)) => {{
+    ($fetch_fn:path, ($($arg:expr),*), $max_tools:expr) => {{
+        let max_tools: usize = $max_tools;
         match $fetch_fn($($arg),*, None).await {
             Ok(Some(mut list)) => {
                 let meta = list.meta.take();
@@ -319,16 +453,16 @@ macro_rules! paginate_tools_list_with {
                 let mut next_cursor = list.next_cursor.take();
                 let mut error: Option<String> = None;
 
-                if tools.len() >= MCP_MAX_TOOL_LIST {
-                    tools.truncate(MCP_MAX_TOOL_LIST);
+                if tools.len() >= max_tools {
+                    tools.truncate(max_tools);
                 } else {
                     while let Some(cursor) = next_cursor.clone() {
                         match $fetch_fn($($arg),*, Some(cursor)).await {
                             Ok(Some(next_list)) => {
                                 tools.extend(next_list.tools);
                                 next_cursor = next_list.next_cursor;
-                                if tools.len() >= MCP_MAX_TOOL_LIST {
-                                    tools.truncate(MCP_MAX_TOOL_LIST);
+                                if tools.len() >= max_tools {
+                                    tools.truncate(max_tools);
                                     break;
                                 }
                             }
@@ -360,8 +494,8 @@ macro_rules! paginate_tools_list_with {
 }
 
 impl McpClientManager {
-    pub fn set_request_sender(&mut self, sender: mpsc::UnboundedSender<McpServerRequest>) {
-        self.server_request_tx = Some(sender);
+    pub fn set_event_sender(&mut self, sender: mpsc::UnboundedSender<McpServerEvent>) {
+        self.server_event_tx = Some(sender);
     }
 
     fn ensure_http_client(&mut self, id: &str) -> Result<(), String> {
@@ -369,7 +503,7 @@ impl McpClientManager {
             return Err("Unknown MCP server".to_string());
         };
         if server.http_client.is_none() {
-            let client = build_mcp_http_client()
+            let client = build_mcp_http_client(server.limits.request_timeout)
                 .map_err(|err| format!("Failed to build HTTP client: {err}"))?;
             server.http_client = Some(client);
         }
@@ -408,12 +542,25 @@ impl McpClientManager {
         let servers = config
             .mcp_servers
             .iter()
-            .cloned()
-            .map(|server| (server.id.to_ascii_lowercase(), McpServerState::new(server)))
+            .map(|server| {
+                let mut state = McpServerState::new(server.clone());
+                state.limits = McpServerLimits::resolve(config, server);
+                (server.id.to_ascii_lowercase(), state)
+            })
             .collect();
         Self {
             servers,
-            server_request_tx: None,
+            server_event_tx: None,
+            startup_concurrency: config.mcp_startup_concurrency.filter(|limit| *limit > 0),
+        }
+    }
+
+    /// Creates a manager holding only `state`, sharing this manager's event sender.
+    fn single_server(&self, state: McpServerState) -> Self {
+        Self {
+            servers: HashMap::from([(state.config.id.to_ascii_lowercase(), state)]),
+            server_event_tx: self.server_event_tx.clone(),
+            startup_concurrency: None,
         }
     }
 
@@ -421,6 +568,10 @@ impl McpClientManager {
         self.servers.values()
     }
 
+    pub fn servers_mut(&mut self) -> impl Iterator<Item = &mut McpServerState> {
+        self.servers.values_mut()
+    }
+
     pub fn server(&self, id: &str) -> Option<&McpServerState> {
         self.servers.get(&id.to_ascii_lowercase())
     }
@@ -430,31 +581,27 @@ impl McpClientManager {
     }
 
     pub async fn connect_all(&mut self, token_store: &McpTokenStore) {
-        let request_tx = self.server_request_tx.clone();
-        let enabled_server_configs: Vec<McpServerConfig> = self
+        let managers: Vec<McpClientManager> = self
             .servers
             .values()
             .filter(|server| server.config.is_enabled())
-            .map(|server| server.config.clone())
+            .map(|server| self.single_server(server.clone()))
             .collect();
 
-        let connected_states: Vec<McpServerState> = stream::iter(enabled_server_configs)
-            .map(|server_config| {
-                let request_tx = request_tx.clone();
-                async move {
-                    let server_id = server_config.id.clone();
-                    let mut manager = McpClientManager::from_config(&Config {
-                        mcp_servers: vec![server_config],
-                        ..Config::default()
-                    });
-                    if let Some(tx) = request_tx {
-                        manager.set_request_sender(tx);
-                    }
-                    manager.connect_server(&server_id, token_store).await;
-                    manager.server(&server_id).cloned()
-                }
+        let connected_states: Vec<McpServerState> = stream::iter(managers)
+            .map(|mut manager| async move {
+                let server_id = manager
+                    .servers()
+                    .next()
+                    .map(|server| server.config.id.clone())
+                    .unwrap_or_default();
+                manager.connect_server(&server_id, token_store).await;
+                manager.server(&server_id).cloned()
             })
-            .buffer_unordered(MCP_STARTUP_CONCURRENCY_LIMIT)
+            .buffer_unordered(
+                self.startup_concurrency
+                    .unwrap_or(MCP_STARTUP_CONCURRENCY_LIMIT),
+            )
             .collect::<Vec<_>>()
             .await
             .into_iter()
@@ -481,7 +628,6 @@ impl McpClientManager {
         }
 
         let server_id = server.config.id.clone();
-        let request_tx = self.server_request_tx.clone();
 
         let operation_results: Vec<McpServerState> = stream::iter([
             RefreshOperation::Tools,
@@ -490,10 +636,7 @@ impl McpClientManager {
             RefreshOperation::ResourceTemplates,
         ])
         .map(|operation| {
-            let mut manager = Self {
-                servers: HashMap::from([(server_id.to_ascii_lowercase(), server.clone())]),
-                server_request_tx: request_tx.clone(),
-            };
+            let mut manager = self.single_server(server.clone());
             let server_id = server_id.clone();
             async move {
                 match operation {
@@ -538,17 +681,39 @@ impl McpClientManager {
         }
     }
 
+    /// Connects one server and records the outcome in its `health`.
     pub async fn connect_server(&mut self, id: &str, token_store: &McpTokenStore) {
+        let was_connected = self.server(id).is_some_and(|server| server.connected);
+        self.connect_server_inner(id, token_store).await;
+        let Some(server) = self.server_mut(id) else {
+            return;
+        };
+        if !server.config.is_enabled() {
+            server.health = McpServerHealth::Unknown;
+        } else if server.connected {
+            if !was_connected || server.health == McpServerHealth::Unknown {
+                server.health = McpServerHealth::Up;
+            }
+        } else {
+            server.health = McpServerHealth::Failed {
+                reason: server
+                    .last_error
+                    .clone()
+                    .unwrap_or_else(|| "connection failed".to_string()),
+            };
+        }
+    }
+
+    async fn connect_server_inner(&mut self, id: &str, token_store: &McpTokenStore) {
         let oauth_refresh_warning = {
             let Some(server) = self.server(id) else {
                 return;
             };
             if !server.config.is_enabled() {
                 None
-            } else if matches!(
-                McpTransportKind::from_config(&server.config),
-                Ok(McpTransportKind::StreamableHttp)
-            ) {
+            } else if McpTransportKind::from_config(&server.config)
+                .is_ok_and(McpTransportKind::is_remote)
+            {
                 match refresh_oauth_grant_if_needed(&server.config.id, token_store).await {
                     Ok(_) => None,
                     Err(err) => Some(format!(
@@ -561,7 +726,7 @@ impl McpClientManager {
             }
         };
 
-        let (config, transport_kind, auth_header) = {
+        let (config, transport_kind, auth_header, limits) = {
             let Some(server) = self.server_mut(id) else {
                 return;
             };
@@ -586,14 +751,21 @@ impl McpClientManager {
 
             if server.connected {
                 match transport_kind {
-                    McpTransportKind::Stdio if server.client.is_some() => return,
+                    McpTransportKind::Stdio | McpTransportKind::Sse
+                        if server
+                            .client
+                            .as_ref()
+                            .is_some_and(|client| !client.is_closed()) =>
+                    {
+                        return
+                    }
                     McpTransportKind::StreamableHttp if server.http_client.is_some() => return,
                     _ => {}
                 }
             }
 
             let auth_header = match transport_kind {
-                McpTransportKind::StreamableHttp => {
+                McpTransportKind::StreamableHttp | McpTransportKind::Sse => {
                     if let Err(err) = require_http_base_url(&server.config) {
                         server.last_error = Some(err);
                         server.connected = false;
@@ -604,7 +776,7 @@ impl McpClientManager {
 
                     let http_client = match server.http_client.clone() {
                         Some(client) => client,
-                        None => match build_mcp_http_client() {
+                        None => match build_mcp_http_client(server.limits.request_timeout) {
                             Ok(client) => client,
                             Err(err) => {
                                 server.last_error =
@@ -645,10 +817,15 @@ impl McpClientManager {
             };
 
             server.auth_header = auth_header.clone();
-            (server.config.clone(), transport_kind, auth_header)
+            (
+                server.config.clone(),
+                transport_kind,
+                auth_header,
+                server.limits,
+            )
         };
 
-        let request_tx = self.server_request_tx.clone();
+        let event_tx = self.server_event_tx.clone();
 
         match transport_kind {
             McpTransportKind::StreamableHttp => {
@@ -659,7 +836,7 @@ impl McpClientManager {
                             server.last_error = oauth_refresh_warning.clone();
                             server.auth_header = auth_header;
                         }
-                        if let Some(tx) = request_tx.clone() {
+                        if let Some(tx) = event_tx.clone() {
                             if let Some(server) = self.server_mut(id) {
                                 if !server.event_listener_started {
                                     server.event_listener_started = true;
@@ -712,14 +889,40 @@ impl McpClientManager {
                     }
                 }
             }
-            McpTransportKind::Stdio => {
-                let client = match StdioClient::connect(
-                    config.id.clone(),
-                    &config,
-                    request_tx.clone(),
-                )
-                .await
-                {
+            McpTransportKind::Stdio | McpTransportKind::Sse => {
+                let connected = match transport_kind {
+                    McpTransportKind::Sse => {
+                        let http_client = self.ensure_http_client(id).and_then(|()| {
+                            self.server(id)
+                                .and_then(|server| server.http_client.clone())
+                                .ok_or_else(|| "Unknown MCP server".to_string())
+                        });
+                        match http_client {
+                            Ok(http_client) => {
+                                transport_sse::connect(
+                                    config.id.clone(),
+                                    &config,
+                                    http_client,
+                                    auth_header.clone(),
+                                    limits,
+                                    event_tx.clone(),
+                                )
+                                .await
+                            }
+                            Err(err) => Err(err),
+                        }
+                    }
+                    _ => {
+                        transport_stdio::connect(
+                            config.id.clone(),
+                            &config,
+                            limits,
+                            event_tx.clone(),
+                        )
+                        .await
+                    }
+                };
+                let client = match connected {
                     Ok(client) => client,
                     Err(err) => {
                         if let Some(server) = self.server_mut(id) {
@@ -744,6 +947,7 @@ impl McpClientManager {
                         if let Some(server) = self.server_mut(id) {
                             server.last_error = Some(err);
                             server.connected = false;
+                            server.stderr_tail = client.stderr_tail();
                             server.client = None;
                         }
                         return;
@@ -753,11 +957,47 @@ impl McpClientManager {
                 if let Some(server) = self.server_mut(id) {
                     server.connected = true;
                     server.client = Some(client.clone());
-                    server.last_error = None;
+                    server.last_error = oauth_refresh_warning;
                     server.auth_header = auth_header;
                 }
             }
         }
+
+        self.apply_configured_log_level(id).await;
+    }
+
+    /// Sends `logging/setLevel` when the server config requests a level.
+    ///
+    /// Failures are recorded in `last_error` without dropping the connection.
+    async fn apply_configured_log_level(&mut self, id: &str) {
+        let Some(server) = self.server(id) else {
+            return;
+        };
+        if !server.connected || !server.supports_logging() {
+            return;
+        }
+        let Some(level) = server
+            .config
+            .log_level
+            .as_deref()
+            .and_then(parse_logging_level)
+        else {
+            return;
+        };
+        let Some(mut context) = self.tool_call_context(id) else {
+            return;
+        };
+
+        let result = execute_set_log_level(&mut context, level).await;
+        if let Some(server) = self.server_mut(id) {
+            if let Some(session_id) = context.session_id.clone() {
+                server.session_id = Some(session_id);
+            }
+            match result {
+                Ok(()) => server.log_level = Some(level),
+                Err(err) => server.last_error = Some(format!("Log level update failed: {err}")),
+            }
+        }
     }
 
     pub async fn refresh_tools(&mut self, id: &str) {
@@ -845,7 +1085,10 @@ impl McpClientManager {
             if let Err(err) = self.ensure_streamable_http_session(id).await {
                 return ListFetch::Err(err);
             }
-            let list = paginate_tools_list_with!(fetch_tools_page_http, (self, id));
+            let max_tools = self
+                .server(id)
+                .map_or(MCP_MAX_TOOL_LIST, |server| server.limits.max_tools);
+            let list = paginate_tools_list_with!(fetch_tools_page_http, (self, id), max_tools);
             return match list {
                 Ok(Some(list)) => ListFetch::Ok(list, None),
                 Ok(None) => ListFetch::MethodNotFound(None),
@@ -861,7 +1104,10 @@ impl McpClientManager {
             return ListFetch::Err("MCP client not connected.".to_string());
         };
 
-        let list = paginate_tools_list_with!(fetch_tools_page_stdio, (&client));
+        let max_tools = self
+            .server(id)
+            .map_or(MCP_MAX_TOOL_LIST, |server| server.limits.max_tools);
+        let list = paginate_tools_list_with!(fetch_tools_page_stdio, (&client), max_tools);
         match list {
             Ok(Some(list)) => ListFetch::Ok(list, None),
             Ok(None) => ListFetch::MethodNotFound(None),
@@ -1000,7 +1246,7 @@ impl McpClientManager {
         let response = transport_http::send_request_with_context(
             &mut context,
             request,
-            self.server_request_tx.clone(),
+            self.server_event_tx.clone(),
         )
         .await?;
         self.update_tool_call_session(id, context.session_id.clone(), None);
@@ -1019,7 +1265,7 @@ pub struct McpToolCallContext {
     auth_header: Option<String>,
     pub(crate) session_id: Option<String>,
     http_client: Option<reqwest::Client>,
-    client: Option<Arc<StdioClient>>,
+    client: Option<Arc<ChannelClient>>,
     streamable_http_request_id: u64,
     negotiated_protocol_version: Option<String>,
 }
@@ -1031,7 +1277,7 @@ pub struct McpPromptContext {
     auth_header: Option<String>,
     pub(crate) session_id: Option<String>,
     http_client: Option<reqwest::Client>,
-    client: Option<Arc<StdioClient>>,
+    client: Option<Arc<ChannelClient>>,
     streamable_http_request_id: u64,
     negotiated_protocol_version: Option<String>,
 }
@@ -1044,7 +1290,7 @@ pub struct McpServerRequestContext {
     auth_header: Option<String>,
     pub(crate) session_id: Option<String>,
     http_client: Option<reqwest::Client>,
-    client: Option<Arc<StdioClient>>,
+    client: Option<Arc<ChannelClient>>,
     negotiated_protocol_version: Option<String>,
 }
 
@@ -1283,7 +1529,7 @@ fn paginated_params(cursor: Option<String>) -> Option<PaginatedRequestParams> {
 }
 
 async fn fetch_tools_page_stdio(
-    client: &Arc<StdioClient>,
+    client: &Arc<ChannelClient>,
     cursor: Option<String>,
 ) -> Result<Option<ListToolsResult>, String> {
     let params = paginated_params(cursor);
@@ -1,20 +1,25 @@
+use super::channel;
 use super::protocol::{
-    parse_call_tool, parse_get_prompt, parse_list_resource_templates, parse_list_resources,
-    parse_read_resource,
+    parse_call_tool, parse_complete, parse_empty_result, parse_get_prompt,
+    parse_list_resource_templates, parse_list_resources, parse_read_resource,
 };
 use super::transport_http;
-use super::transport_stdio;
 use super::{McpPromptContext, McpServerRequestContext, McpToolCallContext};
-use crate::core::app::session::{McpPromptRequest, ToolCallRequest};
+use crate::core::app::session::{
+    McpCompletionReference, McpCompletionRequest, McpPromptRequest, ToolCallRequest,
+};
 use crate::mcp::transport::McpTransportKind;
 use rust_mcp_schema::schema_utils::{
     ClientMessage, FromMessage, MessageFromClient, RequestFromClient, ResultFromClient,
     ServerMessage,
 };
 use rust_mcp_schema::{
-    CallToolRequestParams, CallToolResult, GetPromptRequestParams, GetPromptResult,
-    ListResourceTemplatesResult, ListResourcesResult, PaginatedRequestParams,
-    ReadResourceRequestParams, ReadResourceResult, RequestId, RpcError,
+    CallToolRequestParams, CallToolResult, CompleteRequestArgument, CompleteRequestContext,
+    CompleteRequestParams, CompleteRequestRef, CompleteResultCompletion, GetPromptRequestParams,
+    GetPromptResult, ListResourceTemplatesResult, ListResourcesResult, LoggingLevel,
+    PaginatedRequestParams, PromptReference, ReadResourceRequestParams, ReadResourceResult,
+    RequestId, ResourceTemplateReference, RpcError, SetLevelRequestParams, SubscribeRequestParams,
+    UnsubscribeRequestParams,
 };
 use tracing::debug;
 
@@ -27,7 +32,9 @@ where
     C: OperationContext,
 {
     let response = match context.transport_kind() {
-        McpTransportKind::Stdio => transport_stdio::send_request(context.client(), request).await?,
+        McpTransportKind::Stdio | McpTransportKind::Sse => {
+            channel::send_request(context.client(), request).await?
+        }
         McpTransportKind::StreamableHttp => {
             transport_http::ensure_session_context(context).await?;
             transport_http::send_request_with_context(context, request, None).await?
@@ -38,7 +45,7 @@ where
 
 trait OperationContext: transport_http::StreamableHttpContext {
     fn transport_kind(&self) -> McpTransportKind;
-    fn client(&self) -> Option<std::sync::Arc<super::transport_stdio::StdioClient>>;
+    fn client(&self) -> Option<std::sync::Arc<super::channel::ChannelClient>>;
 }
 
 impl OperationContext for McpToolCallContext {
@@ -46,7 +53,7 @@ impl OperationContext for McpToolCallContext {
         self.transport_kind
     }
 
-    fn client(&self) -> Option<std::sync::Arc<super::transport_stdio::StdioClient>> {
+    fn client(&self) -> Option<std::sync::Arc<super::channel::ChannelClient>> {
         self.client.clone()
     }
 }
@@ -56,7 +63,7 @@ impl OperationContext for McpPromptContext {
         self.transport_kind
     }
 
-    fn client(&self) -> Option<std::sync::Arc<super::transport_stdio::StdioClient>> {
+    fn client(&self) -> Option<std::sync::Arc<super::channel::ChannelClient>> {
         self.client.clone()
     }
 }
@@ -125,6 +132,61 @@ pub async fn execute_resource_template_list(
     .await
 }
 
+pub async fn execute_resource_subscribe(
+    context: &mut McpToolCallContext,
+    uri: &str,
+) -> Result<(), String> {
+    let params = SubscribeRequestParams {
+        meta: None,
+        uri: uri.to_string(),
+    };
+    execute_transport_request(
+        context,
+        RequestFromClient::SubscribeRequest(params),
+        parse_empty_result,
+    )
+    .await
+}
+
+pub async fn execute_resource_unsubscribe(
+    context: &mut McpToolCallContext,
+    uri: &str,
+) -> Result<(), String> {
+    let params = UnsubscribeRequestParams {
+        meta: None,
+        uri: uri.to_string(),
+    };
+    execute_transport_request(
+        context,
+        RequestFromClient::UnsubscribeRequest(params),
+        parse_empty_result,
+    )
+    .await
+}
+
+pub async fn execute_set_log_level(
+    context: &mut McpToolCallContext,
+    level: LoggingLevel,
+) -> Result<(), String> {
+    let params = SetLevelRequestParams { level, meta: None };
+    execute_transport_request(
+        context,
+        RequestFromClient::SetLevelRequest(params),
+        parse_empty_result,
+    )
+    .await
+}
+
+/// Sends `ping`; used by periodic health checks.
+pub async fn execute_ping(context: &mut McpToolCallContext) -> Result<(), String> {
+    execute_transport_request(
+        context,
+        RequestFromClient::PingRequest(None),
+        parse_empty_result,
+    )
+    .await
+}
+
 pub async fn execute_prompt(
     context: &mut McpPromptContext,
     request: &McpPromptRequest,
@@ -142,6 +204,41 @@ pub async fn execute_prompt(
     .await
 }
 
+/// Asks the server for values of one prompt or resource-template argument.
+pub async fn execute_completion(
+    context: &mut McpPromptContext,
+    request: &McpCompletionRequest,
+) -> Result<CompleteResultCompletion, String> {
+    let ref_ = match &request.reference {
+        McpCompletionReference::Prompt(name) => {
+            CompleteRequestRef::PromptReference(PromptReference::new(name.clone(), None))
+        }
+        McpCompletionReference::ResourceTemplate(uri) => {
+            CompleteRequestRef::ResourceTemplateReference(ResourceTemplateReference::new(
+                uri.clone(),
+            ))
+        }
+    };
+    let params = CompleteRequestParams {
+        argument: CompleteRequestArgument {
+            name: request.argument.clone(),
+            value: request.value.clone(),
+        },
+        context: (!request.context.is_empty()).then(|| CompleteRequestContext {
+            arguments: Some(request.context.clone()),
+        }),
+        meta: None,
+        ref_,
+    };
+    execute_transport_request(
+        context,
+        RequestFromClient::CompleteRequest(params),
+        parse_complete,
+    )
+    .await
+    .map(|result| result.completion)
+}
+
 pub async fn send_client_result(
     context: &mut McpServerRequestContext,
     request_id: RequestId,
@@ -149,8 +246,8 @@ pub async fn send_client_result(
 ) -> Result<(), String> {
     debug!(server_id = %context.server_id, request_id = ?request_id, transport = ?context.transport_kind, "Sending MCP client result");
     match context.transport_kind {
-        McpTransportKind::Stdio => {
-            transport_stdio::send_result(context.client.clone(), request_id, result).await
+        McpTransportKind::Stdio | McpTransportKind::Sse => {
+            channel::send_result(context.client.clone(), request_id, result).await
         }
         McpTransportKind::StreamableHttp => {
             let message = ClientMessage::from_message(
@@ -170,8 +267,8 @@ pub async fn send_client_error(
 ) -> Result<(), String> {
     debug!(server_id = %context.server_id, request_id = ?request_id, transport = ?context.transport_kind, "Sending MCP client error");
     match context.transport_kind {
-        McpTransportKind::Stdio => {
-            transport_stdio::send_error(context.client.clone(), request_id, error).await
+        McpTransportKind::Stdio | McpTransportKind::Sse => {
+            channel::send_error(context.client.clone(), request_id, error).await
         }
         McpTransportKind::StreamableHttp => {
             let message =
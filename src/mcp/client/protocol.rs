@@ -2,7 +2,7 @@ use super::{format_rpc_error, format_unexpected_server_message};
 use crate::core::config::data::McpServerConfig;
 use rust_mcp_schema::schema_utils::ServerMessage;
 use rust_mcp_schema::{
-    CallToolResult, GetPromptResult, InitializeResult, ListPromptsResult,
+    CallToolResult, CompleteResult, GetPromptResult, InitializeResult, ListPromptsResult,
     ListResourceTemplatesResult, ListResourcesResult, ListToolsResult, ReadResourceResult,
     LATEST_PROTOCOL_VERSION,
 };
@@ -65,6 +65,14 @@ pub(crate) fn parse_call_tool(message: ServerMessage) -> Result<CallToolResult,
     parse_response(message)
 }
 
+pub(crate) fn parse_complete(message: ServerMessage) -> Result<CompleteResult, String> {
+    parse_response(message)
+}
+
+pub(crate) fn parse_empty_result(message: ServerMessage) -> Result<(), String> {
+    parse_response_value(message).map(|_| ())
+}
+
 fn parse_response<T: serde::de::DeserializeOwned>(message: ServerMessage) -> Result<T, String> {
     let value = parse_response_value(message)?;
     serde_json::from_value::<T>(value).map_err(|err| err.to_string())
@@ -106,18 +114,9 @@ mod tests {
         let config = McpServerConfig {
             id: "alpha".to_string(),
             display_name: "Alpha".to_string(),
-            base_url: None,
-            command: None,
-            args: None,
-            env: None,
-            headers: None,
-            transport: None,
-            allowed_tools: None,
             protocol_version: Some("2025-01-01".to_string()),
             enabled: Some(true),
-            tool_payloads: None,
-            tool_payload_window: None,
-            yolo: None,
+            ..Default::default()
         };
 
         assert_eq!(
@@ -8,17 +8,9 @@ fn sample_config() -> McpServerConfig {
         id: "alpha".to_string(),
         display_name: "Alpha MCP".to_string(),
         base_url: Some("https://mcp.example.com".to_string()),
-        command: None,
-        args: None,
-        env: None,
-        headers: None,
         transport: Some("streamable-http".to_string()),
-        allowed_tools: None,
-        protocol_version: None,
         enabled: Some(true),
-        tool_payloads: None,
-        tool_payload_window: None,
-        yolo: None,
+        ..Default::default()
     }
 }
 
@@ -73,6 +65,27 @@ fn server_capability_flags_enable_present_lists() {
     assert!(state.supports_prompts());
 }
 
+#[test]
+fn tool_output_schema_matches_tool_name_case_insensitively() {
+    let mut state = McpServerState::new(sample_config());
+    let mut tool = sample_tool("Weather".to_string());
+    tool.output_schema = Some(rust_mcp_schema::ToolOutputSchema::new(
+        vec!["temperature".to_string()],
+        None,
+        None,
+    ));
+    state.set_cached_tools(ListToolsResult {
+        meta: None,
+        next_cursor: None,
+        tools: vec![tool, sample_tool("plain".to_string())],
+    });
+
+    let schema = state.tool_output_schema("weather").expect("schema");
+    assert_eq!(schema.required, vec!["temperature".to_string()]);
+    assert!(state.tool_output_schema("plain").is_none());
+    assert!(state.tool_output_schema("missing").is_none());
+}
+
 #[tokio::test]
 async fn connect_all_attempts_each_enabled_server_when_one_fails() {
     let config = Config {
@@ -80,34 +93,18 @@ async fn connect_all_attempts_each_enabled_server_when_one_fails() {
             McpServerConfig {
                 id: "alpha".to_string(),
                 display_name: "Alpha".to_string(),
-                base_url: None,
                 command: Some("/definitely-missing-command".to_string()),
-                args: None,
-                env: None,
-                headers: None,
                 transport: Some("stdio".to_string()),
-                allowed_tools: None,
-                protocol_version: None,
                 enabled: Some(true),
-                tool_payloads: None,
-                tool_payload_window: None,
-                yolo: None,
+                ..Default::default()
             },
             McpServerConfig {
                 id: "beta".to_string(),
                 display_name: "Beta".to_string(),
-                base_url: None,
                 command: Some("/definitely-missing-command-2".to_string()),
-                args: None,
-                env: None,
-                headers: None,
                 transport: Some("stdio".to_string()),
-                allowed_tools: None,
-                protocol_version: None,
                 enabled: Some(true),
-                tool_payloads: None,
-                tool_payload_window: None,
-                yolo: None,
+                ..Default::default()
             },
         ],
         ..Config::default()
@@ -254,7 +251,7 @@ async fn fetch_tools_page_test(
 #[tokio::test]
 async fn paginate_tools_list_caps_and_preserves_cursor() {
     let state = Arc::new(Mutex::new(ToolPageState { calls: Vec::new() }));
-    let result = paginate_tools_list_with!(fetch_tools_page_test, (&state))
+    let result = paginate_tools_list_with!(fetch_tools_page_test, (&state), MCP_MAX_TOOL_LIST)
         .expect("pagination should succeed")
         .expect("expected list tools result");
 
@@ -283,9 +280,13 @@ async fn fetch_tools_page_first_page_full(
 #[tokio::test]
 async fn paginate_tools_list_stops_when_first_page_is_full() {
     let state = Arc::new(Mutex::new(ToolPageState { calls: Vec::new() }));
-    let result = paginate_tools_list_with!(fetch_tools_page_first_page_full, (&state))
-        .expect("pagination should succeed")
-        .expect("expected list tools result");
+    let result = paginate_tools_list_with!(
+        fetch_tools_page_first_page_full,
+        (&state),
+        MCP_MAX_TOOL_LIST
+    )
+    .expect("pagination should succeed")
+    .expect("expected list tools result");
 
     assert_eq!(result.tools.len(), MCP_MAX_TOOL_LIST);
     assert_eq!(result.next_cursor.as_deref(), Some("c1"));
@@ -474,9 +475,6 @@ async fn streamable_http_end_to_end_handles_json_and_sse_responses() {
             id: "alpha".to_string(),
             display_name: "Alpha".to_string(),
             base_url: Some(format!("http://{}", addr)),
-            command: None,
-            args: None,
-            env: None,
             headers: Some(
                 [(
                     "CONTEXT7_API_KEY".to_string(),
@@ -486,12 +484,8 @@ async fn streamable_http_end_to_end_handles_json_and_sse_responses() {
                 .collect(),
             ),
             transport: Some("streamable-http".to_string()),
-            allowed_tools: None,
-            protocol_version: None,
             enabled: Some(true),
-            tool_payloads: None,
-            tool_payload_window: None,
-            yolo: None,
+            ..Default::default()
         }],
         ..Config::default()
     };
@@ -548,3 +542,30 @@ async fn streamable_http_end_to_end_handles_json_and_sse_responses() {
         .and_then(|server| server.session_id.as_deref());
     assert_eq!(stored_session, Some("test-session-2"));
 }
+
+#[test]
+fn server_limits_prefer_server_then_global_then_defaults() {
+    let mut server = sample_config();
+    server.request_timeout_seconds = Some(600);
+    server.max_tools = Some(0);
+    let config = Config {
+        mcp_sampling_timeout_seconds: Some(900),
+        mcp_max_tools: Some(250),
+        mcp_request_timeout_seconds: Some(120),
+        mcp_servers: vec![server.clone()],
+        ..Config::default()
+    };
+
+    let limits = McpServerLimits::resolve(&config, &server);
+    assert_eq!(limits.request_timeout, Duration::from_secs(600));
+    assert_eq!(limits.sampling_timeout, Duration::from_secs(900));
+    assert_eq!(limits.max_tools, 250);
+    assert_eq!(limits.extended_request_timeout(), Duration::from_secs(3000));
+
+    let manager = McpClientManager::from_config(&config);
+    assert_eq!(manager.server("alpha").expect("server").limits, limits);
+
+    let defaults = McpServerLimits::resolve(&Config::default(), &sample_config());
+    assert_eq!(defaults, McpServerLimits::default());
+    assert_eq!(defaults.max_tools, MCP_MAX_TOOL_LIST);
+}
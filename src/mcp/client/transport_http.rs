@@ -20,7 +20,7 @@ use super::{
     client_details_for, protocol, require_http_base_url, McpServerRequestContext,
 };
 use crate::core::config::data::McpServerConfig;
-use crate::mcp::events::McpServerRequest;
+use crate::mcp::events::{McpServerEvent, McpServerNotification, McpServerRequest};
 use crate::mcp::transport::streamable_http::{
     is_event_stream_content_type, next_sse_server_message, sse_data_payload, SseLineBuffer,
 };
@@ -33,7 +33,7 @@ use rust_mcp_schema::RequestId;
 use tokio::sync::mpsc;
 use tracing::debug;
 
-fn apply_mcp_http_headers(
+pub(super) fn apply_mcp_http_headers(
     request: reqwest::RequestBuilder,
     headers: Option<&std::collections::HashMap<String, String>>,
 ) -> reqwest::RequestBuilder {
@@ -109,7 +109,7 @@ pub(crate) async fn ensure_session_context<C: StreamableHttpContext>(
 pub(crate) async fn send_request_with_context<C: StreamableHttpContext>(
     context: &mut C,
     request: RequestFromClient,
-    request_tx: Option<mpsc::UnboundedSender<McpServerRequest>>,
+    event_tx: Option<mpsc::UnboundedSender<McpServerEvent>>,
 ) -> Result<ServerMessage, String> {
     let request_id = context.next_request_id();
     let message = ClientMessage::from_message(
@@ -117,7 +117,7 @@ pub(crate) async fn send_request_with_context<C: StreamableHttpContext>(
         Some(RequestId::Integer(request_id)),
     )
     .map_err(|err| err.to_string())?;
-    send_message(context, message, request_tx).await
+    send_message(context, message, event_tx).await
 }
 
 pub(crate) async fn send_server_result_message(
@@ -127,13 +127,33 @@ pub(crate) async fn send_server_result_message(
     send_client_message_with_context(context, message).await
 }
 
-/// Spawns a detached SSE listener for server-initiated MCP requests.
+/// Wraps server-initiated requests and notifications for the app event channel.
+///
+/// Responses and errors belong to the foreground request and yield `None`.
+fn server_event_for_message(server_id: &str, message: &ServerMessage) -> Option<McpServerEvent> {
+    match message {
+        ServerMessage::Request(request) => Some(McpServerEvent::Request(McpServerRequest {
+            server_id: server_id.to_string(),
+            request: request.clone(),
+        })),
+        ServerMessage::Notification(notification) => {
+            Some(McpServerEvent::Notification(McpServerNotification {
+                server_id: server_id.to_string(),
+                notification: notification.clone(),
+            }))
+        }
+        ServerMessage::Response(_) | ServerMessage::Error(_) => None,
+    }
+}
+
+/// Spawns a detached SSE listener for server-initiated MCP requests and
+/// notifications.
 ///
 /// This is best-effort by design: any transport or decode error exits the task
 /// without mutating foreground request state.
 pub(crate) fn spawn_streamable_http_listener(
     client: reqwest::Client,
-    request_tx: mpsc::UnboundedSender<McpServerRequest>,
+    event_tx: mpsc::UnboundedSender<McpServerEvent>,
     listener: StreamableHttpListenerConfig,
 ) {
     let StreamableHttpListenerConfig {
@@ -196,13 +216,10 @@ pub(crate) fn spawn_streamable_http_listener(
                 if payload.is_empty() {
                     continue;
                 }
-                if let Ok(ServerMessage::Request(request)) =
-                    serde_json::from_str::<ServerMessage>(payload)
-                {
-                    let _ = request_tx.send(McpServerRequest {
-                        server_id: server_id.clone(),
-                        request,
-                    });
+                if let Ok(message) = serde_json::from_str::<ServerMessage>(payload) {
+                    if let Some(event) = server_event_for_message(&server_id, &message) {
+                        let _ = event_tx.send(event);
+                    }
                 }
             }
         }
@@ -214,13 +231,10 @@ pub(crate) fn spawn_streamable_http_listener(
             if payload.is_empty() {
                 continue;
             }
-            if let Ok(ServerMessage::Request(request)) =
-                serde_json::from_str::<ServerMessage>(payload)
-            {
-                let _ = request_tx.send(McpServerRequest {
-                    server_id: server_id.clone(),
-                    request,
-                });
+            if let Ok(message) = serde_json::from_str::<ServerMessage>(payload) {
+                if let Some(event) = server_event_for_message(&server_id, &message) {
+                    let _ = event_tx.send(event);
+                }
             }
         }
     });
@@ -281,7 +295,7 @@ async fn send_client_message_with_context<C: StreamableHttpContext>(
 async fn send_message<C: StreamableHttpContext>(
     context: &mut C,
     message: ClientMessage,
-    request_tx: Option<mpsc::UnboundedSender<McpServerRequest>>,
+    event_tx: Option<mpsc::UnboundedSender<McpServerEvent>>,
 ) -> Result<ServerMessage, String> {
     let payload = serde_json::to_string(&message).map_err(|err| err.to_string())?;
     let client = context
@@ -324,12 +338,9 @@ async fn send_message<C: StreamableHttpContext>(
     let server_message = if is_event_stream_content_type(&content_type) {
         let server_id = context.config().id.clone();
         next_sse_server_message(response, move |message| {
-            if let ServerMessage::Request(request) = message {
-                if let Some(tx) = request_tx.as_ref() {
-                    let _ = tx.send(McpServerRequest {
-                        server_id: server_id.clone(),
-                        request: request.clone(),
-                    });
+            if let Some(tx) = event_tx.as_ref() {
+                if let Some(event) = server_event_for_message(&server_id, message) {
+                    let _ = tx.send(event);
                 }
             }
         })
@@ -385,17 +396,9 @@ mod tests {
                 id: "alpha".to_string(),
                 display_name: "Alpha".to_string(),
                 base_url: Some("https://example.com".to_string()),
-                command: None,
-                args: None,
-                env: None,
-                headers: None,
                 transport: Some("streamable-http".to_string()),
-                allowed_tools: None,
-                protocol_version: None,
                 enabled: Some(true),
-                tool_payloads: None,
-                tool_payload_window: None,
-                yolo: None,
+                ..Default::default()
             },
             session: None,
         };
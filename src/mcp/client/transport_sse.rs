@@ -0,0 +1,455 @@
+//! Legacy HTTP+SSE transport client (protocol revision 2024-11-05).
+//!
+//! Transport expectations:
+//! - `base_url` points at the server's event stream (usually `/sse`).
+//! - The first `endpoint` event names the URL for client `POST`s; it must be
+//!   on the same origin as the stream.
+//! - Responses, server requests, and notifications all arrive as `message`
+//!   events and are correlated by JSON-RPC id in [`ChannelClient`].
+//! - Custom headers and `Authorization` are applied to the stream and to every
+//!   `POST`.
+//!
+//! Failure semantics:
+//! - Stream setup failures (HTTP status, content type, missing endpoint) return
+//!   `Err(String)` from [`connect`].
+//! - Non-2xx `POST` status codes surface as `HTTP error: <status>`.
+//! - When the stream ends, pending requests fail immediately; reconnect logic
+//!   is owned by the manager lifecycle.
+//! - Dropping the client closes the event stream.
+
+use super::channel::{ChannelClient, ChannelInbound, ChannelWriter};
+use super::transport_http::apply_mcp_http_headers;
+use super::{
+    require_http_base_url, McpServerLimits, MCP_HTTP_CONNECT_TIMEOUT_SECONDS, MCP_JSON_CONTENT_TYPE,
+};
+use crate::core::config::data::McpServerConfig;
+use crate::mcp::events::McpServerEvent;
+use crate::mcp::transport::sse::{
+    resolve_endpoint, SseEvent, SseEventReader, ENDPOINT_EVENT, MESSAGE_EVENT,
+};
+use crate::mcp::transport::streamable_http::{is_event_stream_content_type, SseLineBuffer};
+use futures_util::StreamExt;
+use rust_mcp_schema::schema_utils::ClientMessage;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio_util::sync::{CancellationToken, DropGuard};
+use tracing::{debug, warn};
+
+/// How long to wait for the `endpoint` event after the stream opens.
+const SSE_ENDPOINT_TIMEOUT_SECONDS: u64 = 10;
+
+/// Where client messages are `POST`ed for a legacy SSE session.
+pub(crate) struct SsePostTarget {
+    http_client: reqwest::Client,
+    endpoint: String,
+    auth_header: Option<String>,
+    custom_headers: Option<HashMap<String, String>>,
+    _stream_guard: DropGuard,
+}
+
+impl SsePostTarget {
+    pub(super) async fn post(&self, message: &ClientMessage) -> Result<(), String> {
+        let mut request = apply_mcp_http_headers(
+            self.http_client
+                .post(&self.endpoint)
+                .header("Content-Type", MCP_JSON_CONTENT_TYPE),
+            self.custom_headers.as_ref(),
+        );
+        if let Some(auth) = self.auth_header.as_ref() {
+            request = request.header("Authorization", auth);
+        }
+        let response = request
+            .json(message)
+            .send()
+            .await
+            .map_err(|err| err.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("HTTP error: {}", response.status()));
+        }
+        Ok(())
+    }
+}
+
+/// Opens the event stream, waits for the endpoint, and returns a client that
+/// posts to it.
+pub(crate) async fn connect(
+    server_id: String,
+    config: &McpServerConfig,
+    http_client: reqwest::Client,
+    auth_header: Option<String>,
+    limits: McpServerLimits,
+    event_tx: Option<mpsc::UnboundedSender<McpServerEvent>>,
+) -> Result<Arc<ChannelClient>, String> {
+    let base_url = require_http_base_url(config)?;
+    debug!(server_id = %server_id, url = %base_url, "Opening MCP SSE stream");
+
+    // The shared HTTP client has an end-to-end timeout that would cut the
+    // long-lived stream, so the GET uses its own client.
+    let stream_client = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(MCP_HTTP_CONNECT_TIMEOUT_SECONDS))
+        .build()
+        .map_err(|err| format!("Failed to build HTTP client: {err}"))?;
+    let mut request = apply_mcp_http_headers(
+        stream_client
+            .get(&base_url)
+            .header("Accept", "text/event-stream"),
+        config.headers.as_ref(),
+    );
+    if let Some(auth) = auth_header.as_ref() {
+        request = request.header("Authorization", auth);
+    }
+
+    let response = request.send().await.map_err(|err| err.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("");
+    if !is_event_stream_content_type(content_type) {
+        return Err(format!(
+            "Expected text/event-stream from SSE endpoint, got '{content_type}'."
+        ));
+    }
+
+    let inbound = ChannelInbound::new(server_id, event_tx);
+    let cancel = CancellationToken::new();
+    let stream_guard = cancel.clone().drop_guard();
+    let (endpoint_tx, endpoint_rx) = oneshot::channel();
+    spawn_stream_reader(response, inbound.clone(), cancel, endpoint_tx);
+
+    let endpoint = match tokio::time::timeout(
+        Duration::from_secs(SSE_ENDPOINT_TIMEOUT_SECONDS),
+        endpoint_rx,
+    )
+    .await
+    {
+        Ok(Ok(endpoint)) => endpoint,
+        Ok(Err(_)) => return Err("SSE stream closed before announcing an endpoint.".to_string()),
+        Err(_) => return Err("Timed out waiting for the SSE endpoint event.".to_string()),
+    };
+    let endpoint = resolve_endpoint(&base_url, &endpoint)?;
+    debug!(endpoint = %endpoint, "MCP SSE endpoint announced");
+
+    Ok(ChannelClient::new(
+        ChannelWriter::SsePost(SsePostTarget {
+            http_client,
+            endpoint,
+            auth_header,
+            custom_headers: config.headers.clone(),
+            _stream_guard: stream_guard,
+        }),
+        inbound,
+        limits,
+    ))
+}
+
+fn spawn_stream_reader(
+    response: reqwest::Response,
+    inbound: ChannelInbound,
+    cancel: CancellationToken,
+    endpoint_tx: oneshot::Sender<String>,
+) {
+    tokio::spawn(async move {
+        let mut endpoint_tx = Some(endpoint_tx);
+        let mut stream = response.bytes_stream();
+        let mut lines = SseLineBuffer::with_blank_lines();
+        let mut events = SseEventReader::default();
+
+        loop {
+            let chunk = tokio::select! {
+                _ = cancel.cancelled() => break,
+                chunk = stream.next() => chunk,
+            };
+            let Some(Ok(chunk)) = chunk else {
+                break;
+            };
+            for line in lines.push(&chunk) {
+                if let Some(event) = events.push_line(&line) {
+                    handle_event(&inbound, event, &mut endpoint_tx).await;
+                }
+            }
+        }
+
+        inbound.close().await;
+    });
+}
+
+async fn handle_event(
+    inbound: &ChannelInbound,
+    event: SseEvent,
+    endpoint_tx: &mut Option<oneshot::Sender<String>>,
+) {
+    match event.event.as_str() {
+        ENDPOINT_EVENT => {
+            if let Some(tx) = endpoint_tx.take() {
+                let _ = tx.send(event.data);
+            }
+        }
+        MESSAGE_EVENT => match serde_json::from_str::<serde_json::Value>(&event.data) {
+            Ok(value) => inbound.dispatch_value(value).await,
+            Err(err) => warn!(error = %err, "Ignoring MCP SSE message that is not valid JSON"),
+        },
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::data::Config;
+    use crate::core::mcp_auth::McpTokenStore;
+    use crate::mcp::client::{client_details_for, McpClientManager};
+    use rust_mcp_schema::schema_utils::{RequestFromClient, ServerMessage};
+    use serde_json::{json, Value};
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::sync::Mutex;
+
+    const SESSION_ID: &str = "s-1";
+
+    /// Minimal in-process stand-in for a 2024-11-05 SSE server.
+    ///
+    /// `ping` replies are held until two are pending and then sent in reverse
+    /// order so tests can check id correlation.
+    #[derive(Clone, Default)]
+    struct StandInServer {
+        sse: Arc<Mutex<Option<mpsc::UnboundedSender<String>>>>,
+        held_pings: Arc<Mutex<Vec<Value>>>,
+        auth_headers: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl StandInServer {
+        async fn start() -> (Self, String) {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let server = Self::default();
+            let accept_server = server.clone();
+            tokio::spawn(async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    tokio::spawn(accept_server.clone().serve(stream));
+                }
+            });
+            (server, format!("http://{addr}/sse"))
+        }
+
+        async fn serve(self, stream: TcpStream) {
+            let (read, mut write) = stream.into_split();
+            let mut reader = BufReader::new(read);
+            loop {
+                let mut request_line = String::new();
+                if reader.read_line(&mut request_line).await.unwrap_or(0) == 0 {
+                    return;
+                }
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).await.unwrap();
+                    let header = header.trim_end();
+                    if header.is_empty() {
+                        break;
+                    }
+                    let (name, value) = header.split_once(':').unwrap();
+                    match name.to_ascii_lowercase().as_str() {
+                        "content-length" => content_length = value.trim().parse().unwrap(),
+                        "authorization" => self
+                            .auth_headers
+                            .lock()
+                            .await
+                            .push(value.trim().to_string()),
+                        _ => {}
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).await.unwrap();
+
+                let target = request_line.split_whitespace().nth(1).unwrap_or_default();
+                if request_line.starts_with("GET /sse") {
+                    let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+                    *self.sse.lock().await = Some(tx);
+                    write
+                        .write_all(
+                            b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n\r\n",
+                        )
+                        .await
+                        .unwrap();
+                    let endpoint =
+                        format!("event: endpoint\ndata: /messages?sessionId={SESSION_ID}\n\n");
+                    write.write_all(endpoint.as_bytes()).await.unwrap();
+                    while let Some(frame) = rx.recv().await {
+                        if write.write_all(frame.as_bytes()).await.is_err() {
+                            return;
+                        }
+                    }
+                    return;
+                }
+
+                let status = if target == format!("/messages?sessionId={SESSION_ID}") {
+                    self.handle_post(serde_json::from_slice(&body).unwrap())
+                        .await;
+                    "202 Accepted"
+                } else {
+                    "404 Not Found"
+                };
+                let head = format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\n\r\n");
+                write.write_all(head.as_bytes()).await.unwrap();
+            }
+        }
+
+        async fn handle_post(&self, message: Value) {
+            let (Some(id), Some(method)) = (message.get("id"), message["method"].as_str()) else {
+                return;
+            };
+            let result = match method {
+                "initialize" => json!({
+                    "protocolVersion": "2024-11-05",
+                    "capabilities": { "tools": {} },
+                    "serverInfo": { "name": "stand-in", "version": "0.1.0" }
+                }),
+                "tools/list" => json!({
+                    "tools": [{
+                        "name": "echo",
+                        "description": "Echo input",
+                        "inputSchema": { "type": "object" }
+                    }]
+                }),
+                "ping" => {
+                    let mut held = self.held_pings.lock().await;
+                    held.push(id.clone());
+                    if held.len() < 2 {
+                        return;
+                    }
+                    for id in held.drain(..).rev() {
+                        self.send_event(json!({ "jsonrpc": "2.0", "id": id, "result": {} }))
+                            .await;
+                    }
+                    return;
+                }
+                _ => {
+                    self.send_event(json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": { "code": -32601, "message": "Method not found" }
+                    }))
+                    .await;
+                    return;
+                }
+            };
+            self.send_event(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+                .await;
+        }
+
+        async fn send_event(&self, message: Value) {
+            if let Some(tx) = self.sse.lock().await.as_ref() {
+                let _ = tx.send(format!("event: message\ndata: {message}\n\n"));
+            }
+        }
+    }
+
+    fn sse_config(base_url: &str) -> McpServerConfig {
+        McpServerConfig {
+            id: "legacy".to_string(),
+            display_name: "Legacy SSE".to_string(),
+            base_url: Some(base_url.to_string()),
+            transport: Some("sse".to_string()),
+            enabled: Some(true),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn sse_client_initializes_and_correlates_out_of_order_responses() {
+        let (server, url) = StandInServer::start().await;
+        let config = sse_config(&url);
+        let client = connect(
+            config.id.clone(),
+            &config,
+            reqwest::Client::new(),
+            Some("Bearer secret".to_string()),
+            McpServerLimits::default(),
+            None,
+        )
+        .await
+        .expect("connect");
+
+        let details = client
+            .initialize(client_details_for(&config))
+            .await
+            .expect("initialize");
+        assert_eq!(details.protocol_version, "2024-11-05");
+
+        let (first, second) = tokio::join!(
+            client.send_request(RequestFromClient::PingRequest(None)),
+            client.send_request(RequestFromClient::PingRequest(None)),
+        );
+        let ids: Vec<String> = [first.unwrap(), second.unwrap()]
+            .into_iter()
+            .map(|message| match message {
+                ServerMessage::Response(response) => format!("{:?}", response.id),
+                other => panic!("unexpected message: {other:?}"),
+            })
+            .collect();
+        assert_ne!(ids[0], ids[1]);
+
+        let auth_headers = server.auth_headers.lock().await;
+        assert!(auth_headers.len() >= 3);
+        assert!(auth_headers.iter().all(|value| value == "Bearer secret"));
+    }
+
+    #[tokio::test]
+    async fn sse_stream_close_fails_pending_requests() {
+        let (server, url) = StandInServer::start().await;
+        let config = sse_config(&url);
+        let client = connect(
+            config.id.clone(),
+            &config,
+            reqwest::Client::new(),
+            None,
+            McpServerLimits::default(),
+            None,
+        )
+        .await
+        .expect("connect");
+
+        // The first ping is held by the stand-in; dropping the stream sender
+        // ends the event stream while it is pending.
+        let pending = tokio::spawn({
+            let client = client.clone();
+            async move {
+                client
+                    .send_request(RequestFromClient::PingRequest(None))
+                    .await
+            }
+        });
+        while server.held_pings.lock().await.is_empty() {
+            tokio::task::yield_now().await;
+        }
+        server.sse.lock().await.take();
+
+        let err = pending.await.unwrap().expect_err("stream closed");
+        assert_eq!(err, "MCP SSE response channel closed.");
+    }
+
+    #[tokio::test]
+    async fn manager_connects_and_lists_tools_over_sse() {
+        let (_server, url) = StandInServer::start().await;
+        let mut manager = McpClientManager::from_config(&Config {
+            mcp_servers: vec![sse_config(&url)],
+            ..Config::default()
+        });
+
+        manager
+            .connect_server("legacy", &McpTokenStore::new_with_keyring(false))
+            .await;
+        let state = manager.server("legacy").unwrap();
+        assert!(state.connected, "last error: {:?}", state.last_error);
+
+        manager.refresh_tools("legacy").await;
+        let tools = manager.server("legacy").unwrap().cached_tools.clone();
+        assert_eq!(tools.unwrap().tools[0].name, "echo");
+    }
+}
@@ -1,7 +1,32 @@
-use rust_mcp_schema::schema_utils::ServerJsonrpcRequest;
+use rust_mcp_schema::schema_utils::{ServerJsonrpcNotification, ServerJsonrpcRequest};
 
 #[derive(Debug, Clone)]
 pub struct McpServerRequest {
     pub server_id: String,
     pub request: ServerJsonrpcRequest,
 }
+
+#[derive(Debug, Clone)]
+pub struct McpServerNotification {
+    pub server_id: String,
+    pub notification: ServerJsonrpcNotification,
+}
+
+/// An unexpected exit of a stdio server process.
+#[derive(Debug, Clone)]
+pub struct McpServerExit {
+    pub server_id: String,
+    /// Identifies the client that owned the process, so exits reported after a
+    /// reconnect can be ignored.
+    pub client_instance: u64,
+    pub status: String,
+    pub stderr_tail: Vec<String>,
+}
+
+/// Server-initiated traffic forwarded from transports to the app runtime.
+#[derive(Debug, Clone)]
+pub enum McpServerEvent {
+    Request(McpServerRequest),
+    Notification(McpServerNotification),
+    Exited(McpServerExit),
+}
@@ -0,0 +1,133 @@
+//! Connection health tracking for MCP servers.
+//!
+//! Stdio servers are restarted with exponential backoff when their process
+//! exits unexpectedly; every connected server is also pinged periodically so
+//! hung processes and unreachable remote endpoints show up in `/mcp`. The last
+//! lines a stdio server wrote to stderr are kept for crash diagnostics.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How often connected servers receive a `ping` health check.
+pub const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long a health check `ping` may take before the server counts as down.
+pub const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Restart attempts made before a crashed stdio server is marked failed.
+pub const MAX_RESTART_ATTEMPTS: u32 = 5;
+
+/// Number of stderr lines retained per stdio server process.
+pub const STDERR_TAIL_LINES: usize = 20;
+
+const RESTART_BASE_DELAY: Duration = Duration::from_secs(1);
+const RESTART_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Runtime health of a configured MCP server.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum McpServerHealth {
+    /// No connection has been attempted yet (or it was reset).
+    #[default]
+    Unknown,
+    Up,
+    /// A crashed or unresponsive stdio server is being restarted.
+    Restarting {
+        attempt: u32,
+    },
+    Failed {
+        reason: String,
+    },
+}
+
+impl McpServerHealth {
+    /// Short status word shown in `/mcp` listings.
+    pub fn label(&self) -> &'static str {
+        match self {
+            McpServerHealth::Unknown => "not connected",
+            McpServerHealth::Up => "up",
+            McpServerHealth::Restarting { .. } => "restarting",
+            McpServerHealth::Failed { .. } => "failed",
+        }
+    }
+
+    /// Longer description including the restart attempt or failure reason.
+    pub fn describe(&self) -> String {
+        match self {
+            McpServerHealth::Restarting { attempt } => {
+                format!("restarting (attempt {attempt}/{MAX_RESTART_ATTEMPTS})")
+            }
+            McpServerHealth::Failed { reason } => format!("failed ({reason})"),
+            other => other.label().to_string(),
+        }
+    }
+
+    pub fn is_restarting(&self) -> bool {
+        matches!(self, McpServerHealth::Restarting { .. })
+    }
+}
+
+/// Delay before restart `attempt` (1-based): 1s, 2s, 4s, … capped at 30s.
+pub fn restart_delay(attempt: u32) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(16);
+    RESTART_BASE_DELAY
+        .saturating_mul(1 << exponent)
+        .min(RESTART_MAX_DELAY)
+}
+
+/// Bounded buffer of the most recent stderr lines from a server process.
+///
+/// Clones share the same buffer so the reader task and client can both see it.
+#[derive(Debug, Clone, Default)]
+pub struct StderrTail {
+    lines: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl StderrTail {
+    pub fn push(&self, line: String) {
+        let Ok(mut lines) = self.lines.lock() else {
+            return;
+        };
+        if lines.len() == STDERR_TAIL_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    pub fn snapshot(&self) -> Vec<String> {
+        self.lines
+            .lock()
+            .map(|lines| lines.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restart_delay_doubles_up_to_cap() {
+        assert_eq!(restart_delay(1), Duration::from_secs(1));
+        assert_eq!(restart_delay(2), Duration::from_secs(2));
+        assert_eq!(restart_delay(4), Duration::from_secs(8));
+        assert_eq!(restart_delay(6), Duration::from_secs(30));
+        assert_eq!(restart_delay(u32::MAX), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn stderr_tail_keeps_most_recent_lines() {
+        let tail = StderrTail::default();
+        let shared = tail.clone();
+        for index in 0..(STDERR_TAIL_LINES + 5) {
+            shared.push(format!("line {index}"));
+        }
+        let lines = tail.snapshot();
+        assert_eq!(lines.len(), STDERR_TAIL_LINES);
+        assert_eq!(lines.first().map(String::as_str), Some("line 5"));
+        assert_eq!(
+            lines.last().map(String::as_str),
+            Some(format!("line {}", STDERR_TAIL_LINES + 4).as_str())
+        );
+    }
+}
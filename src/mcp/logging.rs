@@ -0,0 +1,196 @@
+//! Per-server buffers for MCP `notifications/message` log entries.
+//!
+//! Servers advertising the `logging` capability push log messages at or above
+//! the level requested through `logging/setLevel`. Chabeau keeps a bounded
+//! history per server so `/mcp <server-id> logs` can show recent output, and
+//! mirrors each entry to the `chabeau::mcp::server_log` tracing target so
+//! `--debug-mcp` captures it in `mcp.log`.
+
+use chrono::{DateTime, Local};
+use rust_mcp_schema::{LoggingLevel, LoggingMessageNotificationParams};
+use serde_json::Value;
+use std::collections::VecDeque;
+
+/// Maximum number of log entries retained per server.
+pub const MCP_LOG_BUFFER_CAPACITY: usize = 500;
+
+/// Tracing target used when mirroring server log entries into `mcp.log`.
+pub const MCP_SERVER_LOG_TARGET: &str = "chabeau::mcp::server_log";
+
+/// Levels in ascending severity order, as defined by RFC 5424.
+pub const MCP_LOG_LEVELS: [LoggingLevel; 8] = [
+    LoggingLevel::Debug,
+    LoggingLevel::Info,
+    LoggingLevel::Notice,
+    LoggingLevel::Warning,
+    LoggingLevel::Error,
+    LoggingLevel::Critical,
+    LoggingLevel::Alert,
+    LoggingLevel::Emergency,
+];
+
+/// Returns the severity rank of a level (higher is more severe).
+pub fn level_rank(level: LoggingLevel) -> usize {
+    MCP_LOG_LEVELS
+        .iter()
+        .position(|candidate| *candidate == level)
+        .unwrap_or(0)
+}
+
+/// Parses a level name such as `warning` (case-insensitive, `warn` accepted).
+pub fn parse_logging_level(value: &str) -> Option<LoggingLevel> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "warn" => Some(LoggingLevel::Warning),
+        "crit" => Some(LoggingLevel::Critical),
+        "emerg" => Some(LoggingLevel::Emergency),
+        other => MCP_LOG_LEVELS
+            .iter()
+            .copied()
+            .find(|level| level.to_string() == other),
+    }
+}
+
+/// One log message received from an MCP server.
+#[derive(Debug, Clone)]
+pub struct McpLogEntry {
+    pub received_at: DateTime<Local>,
+    pub level: LoggingLevel,
+    pub logger: Option<String>,
+    pub data: Value,
+}
+
+impl McpLogEntry {
+    pub fn from_params(params: LoggingMessageNotificationParams) -> Self {
+        Self {
+            received_at: Local::now(),
+            level: params.level,
+            logger: params.logger,
+            data: params.data,
+        }
+    }
+
+    /// Renders the payload as text; strings are shown verbatim.
+    pub fn message(&self) -> String {
+        match &self.data {
+            Value::String(text) => text.clone(),
+            other => serde_json::to_string(other).unwrap_or_else(|_| other.to_string()),
+        }
+    }
+
+    /// Formats the entry as a single display line.
+    pub fn format_line(&self) -> String {
+        let timestamp = self.received_at.format("%H:%M:%S");
+        match self.logger.as_deref() {
+            Some(logger) if !logger.is_empty() => {
+                format!("{timestamp} [{}] {logger}: {}", self.level, self.message())
+            }
+            _ => format!("{timestamp} [{}] {}", self.level, self.message()),
+        }
+    }
+}
+
+/// Bounded, oldest-first log history for a single server.
+#[derive(Debug, Clone, Default)]
+pub struct McpLogBuffer {
+    entries: VecDeque<McpLogEntry>,
+    dropped: usize,
+}
+
+impl McpLogBuffer {
+    pub fn push(&mut self, entry: McpLogEntry) {
+        if self.entries.len() >= MCP_LOG_BUFFER_CAPACITY {
+            self.entries.pop_front();
+            self.dropped = self.dropped.saturating_add(1);
+        }
+        self.entries.push_back(entry);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of entries evicted because the buffer was full.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Iterates entries at or above `min_level`, oldest first.
+    pub fn filtered(&self, min_level: Option<LoggingLevel>) -> impl Iterator<Item = &McpLogEntry> {
+        let min_rank = min_level.map(level_rank).unwrap_or(0);
+        self.entries
+            .iter()
+            .filter(move |entry| level_rank(entry.level) >= min_rank)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.dropped = 0;
+    }
+}
+
+/// Mirrors a server log entry into the tracing pipeline used by `--debug-mcp`.
+pub fn trace_log_entry(server_id: &str, entry: &McpLogEntry) {
+    let logger = entry.logger.as_deref().unwrap_or("");
+    let message = entry.message();
+    match level_rank(entry.level) {
+        0 => tracing::debug!(target: MCP_SERVER_LOG_TARGET, server_id, logger, "{message}"),
+        1 | 2 => tracing::info!(target: MCP_SERVER_LOG_TARGET, server_id, logger, "{message}"),
+        3 => tracing::warn!(target: MCP_SERVER_LOG_TARGET, server_id, logger, "{message}"),
+        _ => tracing::error!(target: MCP_SERVER_LOG_TARGET, server_id, logger, "{message}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(level: LoggingLevel, data: Value) -> McpLogEntry {
+        McpLogEntry {
+            received_at: Local::now(),
+            level,
+            logger: None,
+            data,
+        }
+    }
+
+    #[test]
+    fn parse_logging_level_accepts_aliases() {
+        assert_eq!(parse_logging_level("WARN"), Some(LoggingLevel::Warning));
+        assert_eq!(parse_logging_level("notice"), Some(LoggingLevel::Notice));
+        assert_eq!(parse_logging_level("bogus"), None);
+    }
+
+    #[test]
+    fn filtered_respects_minimum_level() {
+        let mut buffer = McpLogBuffer::default();
+        buffer.push(entry(LoggingLevel::Debug, json!("noise")));
+        buffer.push(entry(LoggingLevel::Warning, json!("careful")));
+        buffer.push(entry(LoggingLevel::Error, json!({"code": 7})));
+
+        let messages: Vec<String> = buffer
+            .filtered(Some(LoggingLevel::Warning))
+            .map(McpLogEntry::message)
+            .collect();
+        assert_eq!(messages, vec!["careful", "{\"code\":7}"]);
+        assert_eq!(buffer.filtered(None).count(), 3);
+    }
+
+    #[test]
+    fn push_evicts_oldest_when_full() {
+        let mut buffer = McpLogBuffer::default();
+        for index in 0..(MCP_LOG_BUFFER_CAPACITY + 2) {
+            buffer.push(entry(LoggingLevel::Info, json!(index)));
+        }
+        assert_eq!(buffer.len(), MCP_LOG_BUFFER_CAPACITY);
+        assert_eq!(buffer.dropped(), 2);
+        assert_eq!(
+            buffer.filtered(None).next().map(McpLogEntry::message),
+            Some("2".to_string())
+        );
+    }
+}
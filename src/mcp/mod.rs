@@ -9,15 +9,29 @@
 //! - [`registry`]: available server/tool metadata management.
 //! - [`events`] and [`permissions`]: runtime event propagation and permission
 //!   decisions consumed by chat flows.
+//! - [`policies`]: persistent allow/ask/deny rules from config.
+//! - [`logging`]: bounded per-server history of server log notifications.
+//! - [`tool_output`]: `tools/call` result rendering for every content kind.
+//! - [`subscriptions`]: watched resources refreshed on update notifications.
+//! - [`health`]: crash restarts, ping health checks, and stderr capture.
+//! - [`audit`]: append-only JSONL record of tool calls made for the model.
+//! - [`routing`]: relevance ranking that trims large tool catalogs per request.
 //!
 //! Ownership boundary: MCP protocol concerns live here; higher-level flow
 //! control remains in [`crate::core::chat_stream`] and interaction stays in
 //! [`crate::ui::chat_loop`].
 
+pub mod audit;
 pub mod client;
 pub mod events;
+pub mod health;
+pub mod logging;
 pub mod permissions;
+pub mod policies;
 pub mod registry;
+pub mod routing;
+pub mod subscriptions;
+pub mod tool_output;
 pub mod transport;
 
 /// Internal tool name used by chat flows to trigger MCP resource reads.
@@ -6,6 +6,29 @@ pub enum ToolPermissionDecision {
     AllowSession,
     DenyOnce,
     Block,
+    /// Allow and persist an `allow` policy rule for the tool.
+    AllowAlways,
+    /// Block and persist a `deny` policy rule for the tool.
+    BlockAlways,
+}
+
+impl ToolPermissionDecision {
+    /// Decisions that are written to config instead of the session store.
+    pub fn is_persistent(self) -> bool {
+        matches!(
+            self,
+            ToolPermissionDecision::AllowAlways | ToolPermissionDecision::BlockAlways
+        )
+    }
+
+    pub fn is_denial(self) -> bool {
+        matches!(
+            self,
+            ToolPermissionDecision::DenyOnce
+                | ToolPermissionDecision::Block
+                | ToolPermissionDecision::BlockAlways
+        )
+    }
 }
 
 #[derive(Debug, Default)]
@@ -15,7 +38,7 @@ pub struct ToolPermissionStore {
 
 impl ToolPermissionStore {
     pub fn record(&mut self, server_id: &str, tool_name: &str, decision: ToolPermissionDecision) {
-        if matches!(decision, ToolPermissionDecision::DenyOnce) {
+        if matches!(decision, ToolPermissionDecision::DenyOnce) || decision.is_persistent() {
             return;
         }
         self.decisions
@@ -102,6 +125,16 @@ mod tests {
         assert_eq!(store.decision_for("alpha", "tool-a"), None);
     }
 
+    #[test]
+    fn persistent_decisions_are_not_recorded() {
+        let mut store = ToolPermissionStore::default();
+        store.record("alpha", "tool-a", ToolPermissionDecision::AllowAlways);
+        store.record("alpha", "tool-b", ToolPermissionDecision::BlockAlways);
+
+        assert_eq!(store.decision_for("alpha", "tool-a"), None);
+        assert_eq!(store.decision_for("alpha", "tool-b"), None);
+    }
+
     #[test]
     fn block_is_retained() {
         let mut store = ToolPermissionStore::default();
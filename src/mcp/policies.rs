@@ -0,0 +1,230 @@
+//! Persistent tool permission policies from `[[mcp_servers.policies]]`.
+//!
+//! Rules are evaluated in config order and the first rule whose tool name and
+//! argument matchers all match decides the call, except that a tool blocked
+//! for the session stays blocked even when a rule allows it. Calls that match
+//! no rule fall back to session decisions, YOLO, and finally the interactive
+//! prompt.
+
+use crate::core::config::data::{McpArgumentMatcher, McpPolicyAction, McpToolPolicy};
+use serde_json::Value;
+use std::path::{Component, Path};
+
+/// Returns the action of the first rule matching `tool_name` and its arguments.
+///
+/// `raw_arguments` is the JSON argument object sent by the model; arguments
+/// that fail to parse only match rules without argument matchers.
+pub fn evaluate_tool_policy(
+    policies: &[McpToolPolicy],
+    tool_name: &str,
+    raw_arguments: &str,
+) -> Option<McpPolicyAction> {
+    let arguments = serde_json::from_str::<Value>(raw_arguments).unwrap_or(Value::Null);
+    policies
+        .iter()
+        .find(|policy| policy_matches(policy, tool_name, &arguments))
+        .map(|policy| policy.action)
+}
+
+/// Renders a rule as a single line for listings.
+pub fn describe_policy(policy: &McpToolPolicy) -> String {
+    let mut line = format!("{} {}", policy.action.as_str(), policy.tool);
+    for matcher in &policy.arguments {
+        line.push_str(&format!(" {}", describe_matcher(matcher)));
+    }
+    line
+}
+
+fn policy_matches(policy: &McpToolPolicy, tool_name: &str, arguments: &Value) -> bool {
+    // `*` covers tools only; sampling needs a rule that names it.
+    let tool_matches = (policy.tool == "*" && tool_name != crate::mcp::MCP_SAMPLING_TOOL)
+        || policy.tool == tool_name;
+    tool_matches
+        && policy
+            .arguments
+            .iter()
+            .all(|matcher| matcher_matches(matcher, arguments))
+}
+
+fn matcher_matches(matcher: &McpArgumentMatcher, arguments: &Value) -> bool {
+    let Some(value) = arguments.pointer(&matcher.pointer) else {
+        return false;
+    };
+    let text = match value {
+        Value::String(text) => text.clone(),
+        Value::Null | Value::Array(_) | Value::Object(_) => {
+            // Only scalars are comparable; a bare pointer asserts presence.
+            return matcher.equals.is_none()
+                && matcher.prefix.is_none()
+                && matcher.path_prefix.is_none();
+        }
+        other => other.to_string(),
+    };
+
+    if matcher
+        .equals
+        .as_ref()
+        .is_some_and(|expected| *expected != text)
+    {
+        return false;
+    }
+    if matcher
+        .prefix
+        .as_ref()
+        .is_some_and(|prefix| !text.starts_with(prefix.as_str()))
+    {
+        return false;
+    }
+    if matcher
+        .path_prefix
+        .as_ref()
+        .is_some_and(|prefix| !is_under_path(&text, prefix))
+    {
+        return false;
+    }
+    true
+}
+
+fn is_under_path(path: &str, prefix: &str) -> bool {
+    let path = Path::new(path);
+    if path
+        .components()
+        .any(|component| matches!(component, Component::ParentDir))
+    {
+        return false;
+    }
+    path.starts_with(Path::new(prefix))
+}
+
+fn describe_matcher(matcher: &McpArgumentMatcher) -> String {
+    if let Some(expected) = &matcher.equals {
+        format!("{}=={}", matcher.pointer, expected)
+    } else if let Some(prefix) = &matcher.prefix {
+        format!("{}^={}", matcher.pointer, prefix)
+    } else if let Some(prefix) = &matcher.path_prefix {
+        format!("{} under {}", matcher.pointer, prefix)
+    } else {
+        format!("{} present", matcher.pointer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(
+        tool: &str,
+        action: McpPolicyAction,
+        arguments: Vec<McpArgumentMatcher>,
+    ) -> McpToolPolicy {
+        McpToolPolicy {
+            tool: tool.to_string(),
+            action,
+            arguments,
+        }
+    }
+
+    fn path_under(prefix: &str) -> McpArgumentMatcher {
+        McpArgumentMatcher {
+            pointer: "/path".to_string(),
+            equals: None,
+            prefix: None,
+            path_prefix: Some(prefix.to_string()),
+        }
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let policies = vec![
+            rule(
+                "read_file",
+                McpPolicyAction::Allow,
+                vec![path_under("/home/me/project")],
+            ),
+            rule("read_file", McpPolicyAction::Ask, Vec::new()),
+            rule("*", McpPolicyAction::Deny, Vec::new()),
+        ];
+
+        assert_eq!(
+            evaluate_tool_policy(
+                &policies,
+                "read_file",
+                r#"{"path":"/home/me/project/a.rs"}"#
+            ),
+            Some(McpPolicyAction::Allow)
+        );
+        assert_eq!(
+            evaluate_tool_policy(&policies, "read_file", r#"{"path":"/etc/passwd"}"#),
+            Some(McpPolicyAction::Ask)
+        );
+        assert_eq!(
+            evaluate_tool_policy(&policies, "write_file", "{}"),
+            Some(McpPolicyAction::Deny)
+        );
+        assert_eq!(
+            evaluate_tool_policy(&policies[..2], "write_file", "{}"),
+            None
+        );
+        assert_eq!(
+            evaluate_tool_policy(&policies, crate::mcp::MCP_SAMPLING_TOOL, "{}"),
+            None
+        );
+    }
+
+    #[test]
+    fn path_prefix_respects_components_and_parent_dirs() {
+        let policies = vec![rule(
+            "read_file",
+            McpPolicyAction::Allow,
+            vec![path_under("/home/me/project")],
+        )];
+
+        for path in [
+            "/home/me/project-evil/a.rs",
+            "/home/me/project/../secrets",
+            "relative/path",
+        ] {
+            let args = serde_json::json!({ "path": path }).to_string();
+            assert_eq!(evaluate_tool_policy(&policies, "read_file", &args), None);
+        }
+        assert_eq!(
+            evaluate_tool_policy(&policies, "read_file", "not json"),
+            None
+        );
+    }
+
+    #[test]
+    fn equals_and_prefix_compare_scalar_text() {
+        let policies = vec![rule(
+            "query",
+            McpPolicyAction::Allow,
+            vec![
+                McpArgumentMatcher {
+                    pointer: "/limit".to_string(),
+                    equals: Some("10".to_string()),
+                    prefix: None,
+                    path_prefix: None,
+                },
+                McpArgumentMatcher {
+                    pointer: "/sql".to_string(),
+                    equals: None,
+                    prefix: Some("SELECT ".to_string()),
+                    path_prefix: None,
+                },
+            ],
+        )];
+
+        assert_eq!(
+            evaluate_tool_policy(&policies, "query", r#"{"limit":10,"sql":"SELECT 1"}"#),
+            Some(McpPolicyAction::Allow)
+        );
+        assert_eq!(
+            evaluate_tool_policy(&policies, "query", r#"{"limit":10,"sql":"DROP TABLE x"}"#),
+            None
+        );
+        assert_eq!(
+            describe_policy(&policies[0]),
+            "allow query /limit==10 /sql^=SELECT "
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! Relevance ranking for MCP tool routing.
+//!
+//! When the enabled servers expose more tools than `mcp_tool_routing_limit`,
+//! each request only offers the tools that best match the recent
+//! conversation. Tools are scored with BM25 over their name, description, and
+//! argument names; pinned tools and tools already called in the current turn
+//! are always offered. The outcome of each request is kept in a short history
+//! shown by `/tools`.
+
+use chrono::{DateTime, Local};
+use std::collections::{HashMap, HashSet};
+
+/// Default number of server tools offered per request once routing engages.
+pub const MCP_TOOL_ROUTING_LIMIT: usize = 32;
+
+/// Number of routing decisions kept for `/tools`.
+pub const MCP_TOOL_ROUTING_HISTORY: usize = 20;
+
+const BM25_K1: f64 = 1.2;
+const BM25_B: f64 = 0.75;
+
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "can", "do", "for", "from", "has",
+    "have", "how", "i", "if", "in", "is", "it", "me", "my", "of", "on", "or", "please", "so",
+    "that", "the", "this", "to", "was", "we", "what", "when", "with", "you", "your",
+];
+
+/// Why a tool was offered on a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteReason {
+    /// Routing was not needed; every tool was offered.
+    All,
+    /// Listed in the server's `pinned_tools`.
+    Pinned,
+    /// Already called earlier in the current turn.
+    Recent,
+    /// Among the best-scoring tools for the conversation.
+    Ranked,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutedTool {
+    pub server_id: String,
+    pub tool: String,
+    pub score: f64,
+    pub reason: RouteReason,
+}
+
+/// Which tools one request offered, for the `/tools` debug view.
+#[derive(Debug, Clone)]
+pub struct ToolRoutingTurn {
+    pub at: DateTime<Local>,
+    /// Server tools that were eligible before routing.
+    pub candidates: usize,
+    pub offered: Vec<RoutedTool>,
+    /// Distinct query terms the ranker matched against, in first-seen order.
+    pub query_terms: Vec<String>,
+}
+
+impl ToolRoutingTurn {
+    pub fn routed(&self) -> bool {
+        self.offered
+            .iter()
+            .any(|tool| tool.reason != RouteReason::All)
+    }
+
+    /// Renders the turn for the `/tools` view: a header line, the query
+    /// terms, and one line per offered tool.
+    pub fn format_lines(&self) -> Vec<String> {
+        let timestamp = self.at.format("%H:%M:%S");
+        if !self.routed() {
+            let names: Vec<String> = self
+                .offered
+                .iter()
+                .map(|tool| format!("{}/{}", tool.server_id, tool.tool))
+                .collect();
+            return vec![
+                format!("{timestamp}  all {} tools offered", self.candidates),
+                format!("  {}", names.join(", ")),
+            ];
+        }
+
+        let mut lines = vec![format!(
+            "{timestamp}  {} of {} tools offered",
+            self.offered.len(),
+            self.candidates
+        )];
+        if self.query_terms.is_empty() {
+            lines.push("  query: (none)".to_string());
+        } else {
+            lines.push(format!("  query: {}", self.query_terms.join(", ")));
+        }
+        for tool in &self.offered {
+            let reason = match tool.reason {
+                RouteReason::Pinned => "pinned".to_string(),
+                RouteReason::Recent => "called this turn".to_string(),
+                RouteReason::Ranked | RouteReason::All => format!("score {:.2}", tool.score),
+            };
+            lines.push(format!("  - {}/{}  {reason}", tool.server_id, tool.tool));
+        }
+        lines
+    }
+}
+
+/// Splits text into lowercase terms, breaking `snake_case`, `kebab-case`,
+/// and `camelCase` identifiers apart and dropping common stopwords.
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    for word in text.split(|ch: char| !ch.is_alphanumeric()) {
+        let mut current = String::new();
+        let mut previous_lower = false;
+        for ch in word.chars() {
+            if ch.is_uppercase() && previous_lower && !current.is_empty() {
+                terms.push(std::mem::take(&mut current));
+            }
+            previous_lower = ch.is_lowercase() || ch.is_ascii_digit();
+            current.extend(ch.to_lowercase());
+        }
+        if !current.is_empty() {
+            terms.push(current);
+        }
+    }
+    terms.retain(|term| term.chars().count() > 1 && !STOPWORDS.contains(&term.as_str()));
+    terms
+}
+
+/// Scores each document against the query terms with Okapi BM25.
+pub fn bm25_scores(query_terms: &[String], documents: &[Vec<String>]) -> Vec<f64> {
+    if documents.is_empty() {
+        return Vec::new();
+    }
+    let total_length: usize = documents.iter().map(Vec::len).sum();
+    let average_length = (total_length as f64 / documents.len() as f64).max(1.0);
+
+    let unique_query: HashSet<&str> = query_terms.iter().map(String::as_str).collect();
+    let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+    for document in documents {
+        let terms: HashSet<&str> = document.iter().map(String::as_str).collect();
+        for term in terms {
+            if unique_query.contains(term) {
+                *document_frequency.entry(term).or_default() += 1;
+            }
+        }
+    }
+
+    let count = documents.len() as f64;
+    documents
+        .iter()
+        .map(|document| {
+            let length_norm =
+                BM25_K1 * (1.0 - BM25_B + BM25_B * document.len() as f64 / average_length);
+            unique_query
+                .iter()
+                .map(|term| {
+                    let frequency = document.iter().filter(|word| word == term).count() as f64;
+                    if frequency == 0.0 {
+                        return 0.0;
+                    }
+                    let df = document_frequency.get(term).copied().unwrap_or(0) as f64;
+                    let idf = ((count - df + 0.5) / (df + 0.5) + 1.0).ln();
+                    idf * frequency * (BM25_K1 + 1.0) / (frequency + length_norm)
+                })
+                .sum()
+        })
+        .collect()
+}
+
+/// Returns document indices ordered by descending score; ties keep their
+/// original order.
+pub fn rank_indices(scores: &[f64]) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..scores.len()).collect();
+    indices.sort_by(|a, b| scores[*b].total_cmp(&scores[*a]).then(a.cmp(b)));
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_splits_identifiers_and_drops_stopwords() {
+        assert_eq!(
+            tokenize("Please getWeather for the search_docs tool-list in Paris"),
+            vec!["get", "weather", "search", "docs", "tool", "list", "paris"]
+        );
+        assert_eq!(tokenize("HTTPServer v2"), vec!["httpserver", "v2"]);
+    }
+
+    #[test]
+    fn bm25_prefers_documents_matching_rare_terms() {
+        let documents = vec![
+            tokenize("search docs Search the documentation index query"),
+            tokenize("get weather Current weather forecast for a city"),
+            tokenize("list files List files in a directory path"),
+        ];
+        let query = tokenize("what's the weather forecast in Paris?");
+
+        let scores = bm25_scores(&query, &documents);
+        assert_eq!(rank_indices(&scores), vec![1, 0, 2]);
+        assert!(scores[1] > 0.0);
+        assert_eq!(scores[0], 0.0);
+    }
+
+    #[test]
+    fn rank_indices_keeps_original_order_for_ties() {
+        assert_eq!(rank_indices(&[0.0, 1.5, 0.0, 1.5]), vec![1, 3, 0, 2]);
+        assert!(bm25_scores(&[], &[]).is_empty());
+    }
+}
@@ -0,0 +1,189 @@
+//! Resource watches backed by MCP `resources/subscribe`.
+//!
+//! `/mcp <server-id> watch <uri>` subscribes to a resource and keeps the latest
+//! text snapshot here. When the server sends `notifications/resources/updated`,
+//! Chabeau re-reads the resource; watches created with `inject` then queue the
+//! fresh content for the next outgoing request.
+
+use chrono::{DateTime, Local};
+use rust_mcp_schema::{ReadResourceContent, ReadResourceResult};
+use std::collections::BTreeMap;
+
+/// Maximum characters of resource text kept per watch.
+pub const MCP_WATCH_CONTENT_LIMIT: usize = 20_000;
+
+/// Subscription state for one watched resource URI.
+#[derive(Debug, Clone)]
+pub struct McpResourceWatch {
+    pub uri: String,
+    /// Queue updated content for the next request context.
+    pub inject: bool,
+    pub content: Option<String>,
+    pub updated_at: Option<DateTime<Local>>,
+    pub update_count: usize,
+    /// Set when an update arrived that has not yet been sent to the model.
+    pub pending_injection: bool,
+    pub last_error: Option<String>,
+}
+
+impl McpResourceWatch {
+    pub fn new(uri: String, inject: bool) -> Self {
+        Self {
+            uri,
+            inject,
+            content: None,
+            updated_at: None,
+            update_count: 0,
+            pending_injection: false,
+            last_error: None,
+        }
+    }
+
+    /// Records a completed read. `is_update` distinguishes change
+    /// notifications from the initial snapshot taken when the watch starts.
+    pub fn record_read(&mut self, result: Result<String, String>, is_update: bool) {
+        match result {
+            Ok(content) => {
+                self.content = Some(content);
+                self.updated_at = Some(Local::now());
+                self.last_error = None;
+                if is_update {
+                    self.update_count = self.update_count.saturating_add(1);
+                    self.pending_injection = self.inject;
+                }
+            }
+            Err(err) => self.last_error = Some(err),
+        }
+    }
+}
+
+/// Watches for a single server keyed by URI.
+pub type McpResourceWatches = BTreeMap<String, McpResourceWatch>;
+
+/// Finds the watch an update notification applies to.
+///
+/// Servers may report a sub-resource of the subscribed URI, so the longest
+/// watched prefix wins when there is no exact match. A prefix only counts
+/// when it ends at a `/`, `?` or `#`, so `file:///notes` does not cover
+/// `file:///notes-archive`.
+pub fn matching_watch_uri(watches: &McpResourceWatches, updated_uri: &str) -> Option<String> {
+    if watches.contains_key(updated_uri) {
+        return Some(updated_uri.to_string());
+    }
+    watches
+        .keys()
+        .filter(|uri| {
+            updated_uri
+                .strip_prefix(uri.as_str())
+                .is_some_and(|rest| uri.ends_with('/') || rest.starts_with(['/', '?', '#']))
+        })
+        .max_by_key(|uri| uri.len())
+        .cloned()
+}
+
+/// Renders resource contents as plain text for a watch, truncated to
+/// [`MCP_WATCH_CONTENT_LIMIT`].
+pub fn render_resource_text(result: &ReadResourceResult) -> String {
+    truncate_chars(resource_contents_text(result), MCP_WATCH_CONTENT_LIMIT)
+}
+
+/// Joins resource contents as plain text, describing binary parts.
+pub fn resource_contents_text(result: &ReadResourceResult) -> String {
+    let mut parts = Vec::new();
+    for content in &result.contents {
+        match content {
+            ReadResourceContent::TextResourceContents(text) => parts.push(text.text.clone()),
+            ReadResourceContent::BlobResourceContents(blob) => parts.push(format!(
+                "[binary content: {}, {} base64 chars]",
+                blob.mime_type.as_deref().unwrap_or("unknown type"),
+                blob.blob.len()
+            )),
+        }
+    }
+    parts.join("\n\n")
+}
+
+fn truncate_chars(text: String, limit: usize) -> String {
+    match text.char_indices().nth(limit) {
+        Some((index, _)) => format!("{}\n[truncated]", &text[..index]),
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_mcp_schema::{BlobResourceContents, TextResourceContents};
+
+    #[test]
+    fn matching_watch_uri_prefers_exact_then_longest_prefix() {
+        let mut watches = McpResourceWatches::new();
+        for uri in [
+            "file:///docs",
+            "file:///docs/plan",
+            "db://orders",
+            "file:///notes",
+        ] {
+            watches.insert(
+                uri.to_string(),
+                McpResourceWatch::new(uri.to_string(), false),
+            );
+        }
+
+        assert_eq!(
+            matching_watch_uri(&watches, "db://orders").as_deref(),
+            Some("db://orders")
+        );
+        assert_eq!(
+            matching_watch_uri(&watches, "file:///docs/plan/week1").as_deref(),
+            Some("file:///docs/plan")
+        );
+        assert_eq!(
+            matching_watch_uri(&watches, "db://orders?page=2").as_deref(),
+            Some("db://orders")
+        );
+        assert_eq!(matching_watch_uri(&watches, "file:///notes-archive"), None);
+        assert_eq!(matching_watch_uri(&watches, "https://elsewhere"), None);
+    }
+
+    #[test]
+    fn record_read_only_queues_injection_for_updates() {
+        let mut watch = McpResourceWatch::new("db://orders".to_string(), true);
+        watch.record_read(Ok("v1".to_string()), false);
+        assert!(!watch.pending_injection);
+        assert_eq!(watch.update_count, 0);
+
+        watch.record_read(Ok("v2".to_string()), true);
+        assert!(watch.pending_injection);
+        assert_eq!(watch.content.as_deref(), Some("v2"));
+
+        watch.record_read(Err("gone".to_string()), true);
+        assert_eq!(watch.content.as_deref(), Some("v2"));
+        assert_eq!(watch.last_error.as_deref(), Some("gone"));
+    }
+
+    #[test]
+    fn render_resource_text_describes_blobs() {
+        let result = ReadResourceResult {
+            contents: vec![
+                ReadResourceContent::TextResourceContents(TextResourceContents {
+                    meta: None,
+                    mime_type: None,
+                    text: "hello".to_string(),
+                    uri: "mem://a".to_string(),
+                }),
+                ReadResourceContent::BlobResourceContents(BlobResourceContents {
+                    blob: "AAAA".to_string(),
+                    meta: None,
+                    mime_type: Some("image/png".to_string()),
+                    uri: "mem://b".to_string(),
+                }),
+            ],
+            meta: None,
+        };
+        assert_eq!(
+            render_resource_text(&result),
+            "hello\n\n[binary content: image/png, 4 base64 chars]"
+        );
+    }
+}
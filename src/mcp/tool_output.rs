@@ -0,0 +1,282 @@
+//! Rendering for MCP `tools/call` results.
+//!
+//! Tool results keep every content block so the transcript, inspect view, and
+//! vision-capable models each see the richest form they can use. Text-only
+//! consumers get a flattened rendering: images and audio become placeholders,
+//! embedded resources become fenced blocks, and `structuredContent` is
+//! pretty-printed and checked against the tool's `outputSchema`.
+
+use crate::api::ChatImage;
+use rust_mcp_schema::{CallToolResult, ContentBlock, EmbeddedResourceResource, ToolOutputSchema};
+use serde_json::{Map, Value};
+
+/// A tool result with all content blocks preserved.
+#[derive(Debug, Clone)]
+pub struct McpToolOutput {
+    pub content: Vec<ContentBlock>,
+    pub structured_content: Option<Map<String, Value>>,
+    pub is_error: bool,
+    /// Set when `structuredContent` does not match the tool's `outputSchema`.
+    pub schema_error: Option<String>,
+}
+
+impl McpToolOutput {
+    pub fn from_result(result: CallToolResult) -> Self {
+        Self {
+            content: result.content,
+            structured_content: result.structured_content,
+            is_error: result.is_error.unwrap_or(false),
+            schema_error: None,
+        }
+    }
+
+    /// Validates `structuredContent` against the tool's declared output schema.
+    pub fn validate(&mut self, schema: Option<&ToolOutputSchema>) {
+        let (Some(schema), Some(structured)) = (schema, self.structured_content.as_ref()) else {
+            return;
+        };
+        self.schema_error = validate_structured_content(schema, structured).err();
+    }
+
+    /// Images returned by the tool, in content order.
+    pub fn images(&self) -> Vec<ChatImage> {
+        self.content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::ImageContent(image) => Some(ChatImage {
+                    mime_type: image.mime_type.clone(),
+                    data: image.data.clone(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Short description of non-text content, e.g. `2 images, 1 audio clip`.
+    pub fn media_summary(&self) -> Option<String> {
+        let images = self
+            .content
+            .iter()
+            .filter(|block| matches!(block, ContentBlock::ImageContent(_)))
+            .count();
+        let audio = self
+            .content
+            .iter()
+            .filter(|block| matches!(block, ContentBlock::AudioContent(_)))
+            .count();
+        let mut parts = Vec::new();
+        if images > 0 {
+            parts.push(format!(
+                "{} image{}",
+                images,
+                if images == 1 { "" } else { "s" }
+            ));
+        }
+        if audio > 0 {
+            parts.push(format!(
+                "{} audio clip{}",
+                audio,
+                if audio == 1 { "" } else { "s" }
+            ));
+        }
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    /// Flattens the result into text for the model and transcript.
+    pub fn render_text(&self) -> String {
+        let mut sections: Vec<String> = self.content.iter().map(render_content_block).collect();
+
+        if let Some(structured) = self.structured_content.as_ref() {
+            let value = Value::Object(structured.clone());
+            // Servers are asked to mirror structured output in a text block;
+            // skip the duplicate when they do.
+            let mirrored = self.content.iter().any(|block| match block {
+                ContentBlock::TextContent(text) => {
+                    serde_json::from_str::<Value>(&text.text).ok().as_ref() == Some(&value)
+                }
+                _ => false,
+            });
+            if !mirrored {
+                let pretty = serde_json::to_string_pretty(&value).unwrap_or_default();
+                sections.push(format!("Structured content:\n```json\n{pretty}\n```"));
+            }
+        }
+
+        if let Some(error) = self.schema_error.as_deref() {
+            sections.push(format!(
+                "Warning: structured content does not match the tool's outputSchema: {error}"
+            ));
+        }
+
+        sections.join("\n\n")
+    }
+}
+
+/// Renders one content block as text.
+pub fn render_content_block(block: &ContentBlock) -> String {
+    match block {
+        ContentBlock::TextContent(text) => text.text.clone(),
+        ContentBlock::ImageContent(image) => format!(
+            "[image: {}, {}]",
+            image.mime_type,
+            format_base64_size(&image.data)
+        ),
+        ContentBlock::AudioContent(audio) => format!(
+            "[audio: {}, {}]",
+            audio.mime_type,
+            format_base64_size(&audio.data)
+        ),
+        ContentBlock::ResourceLink(link) => {
+            let label = link.title.as_deref().unwrap_or(&link.name);
+            match link.description.as_deref() {
+                Some(description) => {
+                    format!("[resource link: {label} <{}>] {description}", link.uri)
+                }
+                None => format!("[resource link: {label} <{}>]", link.uri),
+            }
+        }
+        ContentBlock::EmbeddedResource(embedded) => match &embedded.resource {
+            EmbeddedResourceResource::TextResourceContents(text) => {
+                let fence = if text.text.contains("```") {
+                    "````"
+                } else {
+                    "```"
+                };
+                format!(
+                    "Resource {}\n{fence}\n{}\n{fence}",
+                    text.uri,
+                    text.text.trim_end()
+                )
+            }
+            EmbeddedResourceResource::BlobResourceContents(blob) => format!(
+                "[embedded resource: {}, {}, {}]",
+                blob.uri,
+                blob.mime_type.as_deref().unwrap_or("unknown type"),
+                format_base64_size(&blob.blob)
+            ),
+        },
+    }
+}
+
+fn validate_structured_content(
+    schema: &ToolOutputSchema,
+    structured: &Map<String, Value>,
+) -> Result<(), String> {
+    let schema = serde_json::to_value(schema).map_err(|err| err.to_string())?;
+    let validator = crate::mcp::client::build_tool_schema_validator(&schema)?;
+    let instance = Value::Object(structured.clone());
+    let errors: Vec<String> = validator
+        .iter_errors(&instance)
+        .take(3)
+        .map(|error| {
+            let path = error.instance_path().to_string();
+            if path.is_empty() {
+                error.to_string()
+            } else {
+                format!("{path}: {error}")
+            }
+        })
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+fn format_base64_size(data: &str) -> String {
+    let bytes = data.trim_end_matches('=').len() * 3 / 4;
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else if bytes >= 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{bytes} bytes")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_mcp_schema::{EmbeddedResource, ImageContent, TextContent, TextResourceContents};
+    use std::collections::HashMap;
+
+    fn text_block(text: &str) -> ContentBlock {
+        ContentBlock::TextContent(TextContent::new(text.to_string(), None, None))
+    }
+
+    #[test]
+    fn render_text_covers_images_and_embedded_resources() {
+        let output = McpToolOutput::from_result(CallToolResult {
+            content: vec![
+                text_block("Chart ready."),
+                ContentBlock::ImageContent(ImageContent::new(
+                    "AAAA".to_string(),
+                    "image/png".to_string(),
+                    None,
+                    None,
+                )),
+                ContentBlock::EmbeddedResource(EmbeddedResource::new(
+                    EmbeddedResourceResource::TextResourceContents(TextResourceContents {
+                        meta: None,
+                        mime_type: None,
+                        text: "a,b\n1,2\n".to_string(),
+                        uri: "mem://data.csv".to_string(),
+                    }),
+                    None,
+                    None,
+                )),
+            ],
+            is_error: None,
+            meta: None,
+            structured_content: None,
+        });
+
+        assert_eq!(
+            output.render_text(),
+            "Chart ready.\n\n[image: image/png, 3 bytes]\n\nResource mem://data.csv\n```\na,b\n1,2\n```"
+        );
+        assert_eq!(output.images().len(), 1);
+        assert_eq!(output.media_summary().as_deref(), Some("1 image"));
+    }
+
+    #[test]
+    fn structured_content_is_rendered_once_and_validated() {
+        let structured = serde_json::json!({ "temperature": "warm" })
+            .as_object()
+            .cloned()
+            .unwrap();
+        let schema = ToolOutputSchema::new(
+            vec!["temperature".to_string()],
+            Some(HashMap::from([(
+                "temperature".to_string(),
+                serde_json::json!({ "type": "number" })
+                    .as_object()
+                    .cloned()
+                    .unwrap(),
+            )])),
+            None,
+        );
+
+        let mut output = McpToolOutput::from_result(CallToolResult {
+            content: Vec::new(),
+            is_error: None,
+            meta: None,
+            structured_content: Some(structured.clone()),
+        });
+        output.validate(Some(&schema));
+        let rendered = output.render_text();
+        assert!(
+            rendered.starts_with("Structured content:\n```json\n{\n  \"temperature\": \"warm\"")
+        );
+        assert!(rendered.contains("does not match the tool's outputSchema: /temperature"));
+
+        let mirrored = McpToolOutput::from_result(CallToolResult {
+            content: vec![text_block(r#"{"temperature":"warm"}"#)],
+            is_error: None,
+            meta: None,
+            structured_content: Some(structured),
+        });
+        assert_eq!(mirrored.render_text(), r#"{"temperature":"warm"}"#);
+    }
+}
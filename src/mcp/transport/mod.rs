@@ -1,7 +1,7 @@
 //! Shared MCP transport abstractions.
 //!
-//! Implementations normalize protocol differences across stdio and streamable
-//! HTTP so higher-level code can preserve common state invariants.
+//! Implementations normalize protocol differences across stdio, streamable
+//! HTTP, and legacy HTTP+SSE so higher-level code can preserve common state invariants.
 
 use crate::core::config::data::McpServerConfig;
 use async_trait::async_trait;
@@ -11,6 +11,7 @@ use rust_mcp_schema::{
     ListResourcesResult, ListToolsResult,
 };
 
+pub mod sse;
 pub mod stdio;
 pub mod streamable_http;
 
@@ -22,9 +23,12 @@ pub const MCP_METHOD_NOT_FOUND: i64 = -32601;
 ///
 /// - [`McpTransportKind::Stdio`] for locally spawned processes.
 /// - [`McpTransportKind::StreamableHttp`] for remote servers over HTTP/SSE.
+/// - [`McpTransportKind::Sse`] for remote servers on the 2024-11-05 HTTP+SSE
+///   transport (`GET` event stream plus `POST` to the announced endpoint).
 pub enum McpTransportKind {
     StreamableHttp,
     Stdio,
+    Sse,
 }
 
 /// Normalized outcome for metadata list calls across transports.
@@ -88,7 +92,16 @@ impl McpTransportKind {
         match transport.as_str() {
             "streamable-http" | "streamable_http" | "http" => Ok(McpTransportKind::StreamableHttp),
             "stdio" => Ok(McpTransportKind::Stdio),
+            "sse" => Ok(McpTransportKind::Sse),
             other => Err(format!("Unsupported MCP transport: {}", other)),
         }
     }
+
+    /// Returns true for transports that connect to a remote `base_url`.
+    pub fn is_remote(self) -> bool {
+        matches!(
+            self,
+            McpTransportKind::StreamableHttp | McpTransportKind::Sse
+        )
+    }
 }
@@ -0,0 +1,136 @@
+//! Legacy HTTP+SSE transport utilities (protocol revision 2024-11-05).
+//!
+//! The client opens a long-lived `GET` event stream. The server first sends an
+//! `endpoint` event naming the URL that client messages must be `POST`ed to,
+//! then delivers every response, request, and notification as `message`
+//! events on that same stream.
+
+/// Event name announcing the POST endpoint for client messages.
+pub const ENDPOINT_EVENT: &str = "endpoint";
+
+/// Event name carrying JSON-RPC messages from the server.
+pub const MESSAGE_EVENT: &str = "message";
+
+/// One dispatched SSE event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseEvent {
+    pub event: String,
+    pub data: String,
+}
+
+/// Assembles SSE events from lines produced by
+/// [`SseLineBuffer::with_blank_lines`](super::streamable_http::SseLineBuffer::with_blank_lines).
+///
+/// `data:` lines accumulate, joined with `\n`, until a blank line dispatches
+/// the event, so payloads split across several lines arrive whole.
+#[derive(Default)]
+pub struct SseEventReader {
+    event: Option<String>,
+    data: Option<String>,
+}
+
+impl SseEventReader {
+    pub fn push_line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event = Some(value.trim().to_string()),
+            "data" => match self.data.as_mut() {
+                Some(data) => {
+                    data.push('\n');
+                    data.push_str(value);
+                }
+                None => self.data = Some(value.to_string()),
+            },
+            _ => {}
+        }
+        None
+    }
+
+    /// Ends the current event. Events without data are discarded, as the
+    /// SSE spec requires.
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = self.event.take();
+        let data = self.data.take()?;
+        let event = event
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| MESSAGE_EVENT.to_string());
+        Some(SseEvent { event, data })
+    }
+}
+
+/// Resolves the announced endpoint against the stream URL.
+///
+/// Endpoints on a different origin are rejected so a server cannot redirect
+/// client messages (and their auth headers) elsewhere.
+pub fn resolve_endpoint(stream_url: &str, endpoint: &str) -> Result<String, String> {
+    let base = reqwest::Url::parse(stream_url).map_err(|err| err.to_string())?;
+    let resolved = base
+        .join(endpoint.trim())
+        .map_err(|err| format!("Invalid SSE endpoint '{endpoint}': {err}"))?;
+    if resolved.origin() != base.origin() {
+        return Err(format!(
+            "SSE endpoint '{resolved}' is not on the same origin as '{stream_url}'."
+        ));
+    }
+    Ok(resolved.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reader_pairs_event_names_with_data() {
+        let mut reader = SseEventReader::default();
+        assert_eq!(reader.push_line("event: endpoint"), None);
+        assert_eq!(reader.push_line("data: /messages?sessionId=abc"), None);
+        assert_eq!(
+            reader.push_line(""),
+            Some(SseEvent {
+                event: "endpoint".to_string(),
+                data: "/messages?sessionId=abc".to_string(),
+            })
+        );
+        assert_eq!(reader.push_line(": keep-alive"), None);
+        assert_eq!(reader.push_line(""), None);
+        assert_eq!(reader.push_line("data: {\"jsonrpc\":\"2.0\"}"), None);
+        assert_eq!(reader.push_line("").unwrap().event, "message");
+    }
+
+    #[test]
+    fn reader_joins_multi_line_data_until_blank_line() {
+        let mut reader = SseEventReader::default();
+        assert_eq!(reader.push_line("event: message"), None);
+        assert_eq!(reader.push_line("data: {\"jsonrpc\":\"2.0\","), None);
+        assert_eq!(reader.push_line("data:\"id\":1}"), None);
+        assert_eq!(
+            reader.push_line(""),
+            Some(SseEvent {
+                event: "message".to_string(),
+                data: "{\"jsonrpc\":\"2.0\",\n\"id\":1}".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn endpoint_resolution_stays_on_origin() {
+        assert_eq!(
+            resolve_endpoint("http://127.0.0.1:9000/sse", "/messages?sessionId=1").unwrap(),
+            "http://127.0.0.1:9000/messages?sessionId=1"
+        );
+        assert_eq!(
+            resolve_endpoint("https://mcp.example.com/v1/sse", "messages").unwrap(),
+            "https://mcp.example.com/v1/messages"
+        );
+        assert!(resolve_endpoint("https://mcp.example.com/sse", "https://evil.test/m").is_err());
+    }
+}
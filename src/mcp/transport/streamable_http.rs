@@ -22,9 +22,19 @@ pub fn fetch_list<T>(
 #[derive(Default)]
 pub struct SseLineBuffer {
     buffer: Vec<u8>,
+    keep_blank_lines: bool,
 }
 
 impl SseLineBuffer {
+    /// A buffer that also yields blank lines, which end events in SSE
+    /// framing.
+    pub fn with_blank_lines() -> Self {
+        Self {
+            buffer: Vec::new(),
+            keep_blank_lines: true,
+        }
+    }
+
     pub fn push(&mut self, chunk: &[u8]) -> Vec<String> {
         self.buffer.extend_from_slice(chunk);
         self.drain_lines(false)
@@ -49,7 +59,7 @@ impl SseLineBuffer {
             let line_bytes = &self.buffer[search_index..line_end];
             if let Ok(text) = std::str::from_utf8(line_bytes) {
                 let trimmed = text.trim();
-                if !trimmed.is_empty() {
+                if !trimmed.is_empty() || self.keep_blank_lines {
                     lines.push(trimmed.to_string());
                 }
             }
@@ -28,18 +28,22 @@ const INPUT_BURST_WINDOW_MS: u64 = 200;
 use crate::character::CharacterService;
 use crate::core::app::{
     apply_actions, AppActionContext, AppActionDispatcher, AppActionEnvelope, AppCommand,
-    ComposeAction, InspectAction, InspectMode, StreamingAction,
+    ComposeAction, InspectAction, InspectMode, McpPromptAction, StreamingAction,
 };
 use crate::core::chat_stream::{ChatStreamService, StreamMessage};
+use crate::core::message::Message;
+use crate::mcp::events::McpServerEvent;
 use crate::ui::renderer::ui;
 use ratatui::crossterm::event::{self, Event, KeyEventKind, KeyModifiers};
 use ratatui::prelude::Size;
 use tokio::sync::mpsc;
 
+use super::executors::mcp_health::{spawn_mcp_health_monitor, spawn_mcp_restart};
 use super::executors::mcp_init::spawn_mcp_initializer;
 use super::executors::mcp_tools::{
-    spawn_mcp_prompt_call, spawn_mcp_refresh, spawn_mcp_sampling_call, spawn_mcp_server_error,
-    spawn_mcp_tool_call,
+    spawn_mcp_completion, spawn_mcp_prompt_call, spawn_mcp_refresh, spawn_mcp_resource_attachments,
+    spawn_mcp_resource_unwatch, spawn_mcp_resource_watch, spawn_mcp_resource_watch_read,
+    spawn_mcp_sampling_call, spawn_mcp_server_error, spawn_mcp_set_log_level, spawn_mcp_tool_call,
 };
 use super::executors::model_loader::spawn_model_picker_loader;
 use super::executors::ExecutorContext;
@@ -59,10 +63,14 @@ pub struct RunChatOptions {
     pub provider: Option<String>,
     pub env_only: bool,
     pub character: Option<String>,
+    /// Start without a character even if the provider/model has a default.
+    pub no_default_character: bool,
     pub persona: Option<String>,
     pub preset: Option<String>,
     pub disable_mcp: bool,
     pub character_service: CharacterService,
+    /// Transcript to start from instead of the character greeting.
+    pub history: Vec<Message>,
 }
 
 #[derive(Debug)]
@@ -167,6 +175,13 @@ async fn process_ui_events(
 
     if outcome.events_processed {
         outcome.request_redraw = true;
+        dispatcher.dispatch_many(
+            [McpPromptAction::RefreshCompletions],
+            AppActionContext {
+                term_width: term_size.width,
+                term_height: term_size.height,
+            },
+        );
     }
 
     Ok(outcome)
@@ -281,6 +296,16 @@ async fn route_keyboard_event(
             });
         }
 
+        if app
+            .update(|app| app.complete_mcp_argument(term_size.width))
+            .await
+        {
+            return Ok(KeyboardEventOutcome {
+                request_redraw: true,
+                exit_requested: false,
+            });
+        }
+
         let should_complete = app
             .read(|app| app.ui.is_input_active() && app.ui.get_input_text().starts_with('/'))
             .await;
@@ -295,6 +320,16 @@ async fn route_keyboard_event(
             });
         }
 
+        if app
+            .update(|app| app.complete_mcp_resource_mention(term_size.width))
+            .await
+        {
+            return Ok(KeyboardEventOutcome {
+                request_redraw: true,
+                exit_requested: false,
+            });
+        }
+
         app.update(|app| app.ui.toggle_focus()).await;
         return Ok(KeyboardEventOutcome {
             request_redraw: true,
@@ -503,14 +538,20 @@ async fn drain_action_queue(
             AppCommand::LoadModelPicker(request) => {
                 spawn_model_picker_loader(dispatcher.clone(), request);
             }
-            AppCommand::RunMcpTool(request) => {
+            AppCommand::RunMcpTools(requests) => {
                 let context = ExecutorContext::from_app(app.clone(), dispatcher.clone()).await;
-                spawn_mcp_tool_call(context, request);
+                for request in requests {
+                    spawn_mcp_tool_call(context.clone(), request);
+                }
             }
             AppCommand::RunMcpPrompt(request) => {
                 let context = ExecutorContext::from_app(app.clone(), dispatcher.clone()).await;
                 spawn_mcp_prompt_call(context, request);
             }
+            AppCommand::RequestMcpCompletion(request) => {
+                let context = ExecutorContext::from_app(app.clone(), dispatcher.clone()).await;
+                spawn_mcp_completion(context, request);
+            }
             AppCommand::RunMcpSampling(request) => {
                 let context = ExecutorContext::from_app(app.clone(), dispatcher.clone()).await;
                 spawn_mcp_sampling_call(context, *request);
@@ -527,6 +568,34 @@ async fn drain_action_queue(
                 let context = ExecutorContext::from_app(app.clone(), dispatcher.clone()).await;
                 spawn_mcp_refresh(context, server_id);
             }
+            AppCommand::RestartMcpServer { server_id, attempt } => {
+                let context = ExecutorContext::from_app(app.clone(), dispatcher.clone()).await;
+                spawn_mcp_restart(context, server_id, attempt);
+            }
+            AppCommand::SetMcpLogLevel { server_id, level } => {
+                let context = ExecutorContext::from_app(app.clone(), dispatcher.clone()).await;
+                spawn_mcp_set_log_level(context, server_id, level);
+            }
+            AppCommand::AttachMcpResources(request) => {
+                let context = ExecutorContext::from_app(app.clone(), dispatcher.clone()).await;
+                spawn_mcp_resource_attachments(context, request);
+            }
+            AppCommand::WatchMcpResource {
+                server_id,
+                uri,
+                inject,
+            } => {
+                let context = ExecutorContext::from_app(app.clone(), dispatcher.clone()).await;
+                spawn_mcp_resource_watch(context, server_id, uri, inject);
+            }
+            AppCommand::UnwatchMcpResource { server_id, uri } => {
+                let context = ExecutorContext::from_app(app.clone(), dispatcher.clone()).await;
+                spawn_mcp_resource_unwatch(context, server_id, uri);
+            }
+            AppCommand::RefreshMcpResourceWatch { server_id, uri } => {
+                let context = ExecutorContext::from_app(app.clone(), dispatcher.clone()).await;
+                spawn_mcp_resource_watch_read(context, server_id, uri, true);
+            }
         }
     }
     true
@@ -558,38 +627,49 @@ fn spawn_event_reader(
     })
 }
 
-pub async fn run_chat(options: RunChatOptions) -> Result<(), Box<dyn Error>> {
+pub async fn run_chat(mut options: RunChatOptions) -> Result<(), Box<dyn Error>> {
+    let history = std::mem::take(&mut options.history);
     let app = bootstrap_app(options).await?;
 
     app.update(|app| {
-        app.conversation().show_character_greeting_if_needed();
+        if history.is_empty() {
+            app.conversation().show_character_greeting_if_needed();
+        } else {
+            app.conversation().load_history(history);
+        }
     })
     .await;
 
     let (action_tx, mut action_rx) = mpsc::unbounded_channel::<AppActionEnvelope>();
     let action_dispatcher = AppActionDispatcher::new(action_tx);
-    let (mcp_request_tx, mut mcp_request_rx) =
-        mpsc::unbounded_channel::<crate::mcp::events::McpServerRequest>();
+    let (mcp_event_tx, mut mcp_event_rx) =
+        mpsc::unbounded_channel::<crate::mcp::events::McpServerEvent>();
     app.update(|app| {
-        app.mcp.set_request_sender(mcp_request_tx.clone());
+        app.mcp.set_event_sender(mcp_event_tx.clone());
     })
     .await;
     {
         let app = app.clone();
         let dispatcher = action_dispatcher.clone();
         tokio::spawn(async move {
-            while let Some(request) = mcp_request_rx.recv().await {
+            while let Some(event) = mcp_event_rx.recv().await {
                 let term_size = app.read(|app| app.ui.last_term_size).await;
                 let ctx = AppActionContext {
                     term_width: term_size.width,
                     term_height: term_size.height,
                 };
-                dispatcher.dispatch_many(
-                    [StreamingAction::McpServerRequestReceived {
+                let action = match event {
+                    McpServerEvent::Request(request) => StreamingAction::McpServerRequestReceived {
                         request: Box::new(request),
-                    }],
-                    ctx,
-                );
+                    },
+                    McpServerEvent::Notification(notification) => {
+                        StreamingAction::McpServerNotificationReceived {
+                            notification: Box::new(notification),
+                        }
+                    }
+                    McpServerEvent::Exited(exit) => StreamingAction::McpServerExited { exit },
+                };
+                dispatcher.dispatch_many([action], ctx);
             }
         });
     }
@@ -602,11 +682,8 @@ pub async fn run_chat(options: RunChatOptions) -> Result<(), Box<dyn Error>> {
         })
         .await;
     }
-    spawn_mcp_initializer(
-        app.clone(),
-        action_dispatcher.clone(),
-        mcp_request_tx.clone(),
-    );
+    spawn_mcp_initializer(app.clone(), action_dispatcher.clone(), mcp_event_tx.clone());
+    spawn_mcp_health_monitor(app.clone(), action_dispatcher.clone());
 
     println!(
         "Chabeau is in the public domain, forever. Contribute: https://github.com/permacommons/chabeau"
@@ -133,6 +133,7 @@ async fn tab_does_not_switch_focus_in_edit_select_mode() {
         app.ui.messages.push_back(Message {
             role: TranscriptRole::User,
             content: "hello".into(),
+            id: None,
         });
         app.ui.enter_edit_select_mode(EditSelectTarget::User);
     })
@@ -168,6 +169,7 @@ async fn tab_does_not_switch_focus_in_assistant_edit_select_mode() {
         app.ui.messages.push_back(Message {
             role: TranscriptRole::Assistant,
             content: "response".into(),
+            id: None,
         });
         app.ui.enter_edit_select_mode(EditSelectTarget::Assistant);
     })
@@ -358,6 +360,7 @@ fn process_stream_updates_dispatches_actions() {
     app.ui.messages.push_back(Message {
         role: TranscriptRole::Assistant,
         content: String::new(),
+        id: None,
     });
     app.ui.is_streaming = true;
 
@@ -458,10 +461,12 @@ fn retry_last_message_emits_command_with_history() {
     app.ui.messages.push_back(Message {
         role: TranscriptRole::User,
         content: "Hi".into(),
+        id: None,
     });
     app.ui.messages.push_back(Message {
         role: TranscriptRole::Assistant,
         content: "Hello".into(),
+        id: None,
     });
     app.session.last_retry_time = Instant::now() - Duration::from_millis(500);
 
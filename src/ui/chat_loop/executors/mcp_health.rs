@@ -0,0 +1,108 @@
+use futures_util::future::join_all;
+
+use crate::core::app::{AppActionContext, AppActionDispatcher, StreamingAction};
+use crate::core::mcp_auth::McpTokenStore;
+use crate::mcp::health::{restart_delay, HEALTH_CHECK_INTERVAL, HEALTH_CHECK_TIMEOUT};
+
+use super::super::AppHandle;
+use super::ExecutorContext;
+
+/// Waits out the backoff for `attempt`, then reconnects the server and
+/// refreshes its metadata caches.
+pub fn spawn_mcp_restart(context: ExecutorContext, server_id: String, attempt: u32) {
+    tokio::spawn(async move {
+        tokio::time::sleep(restart_delay(attempt)).await;
+
+        let (mcp_disabled, keyring_enabled) = context
+            .app
+            .read(|app| {
+                (
+                    app.session.mcp_disabled,
+                    !cfg!(test) && !app.session.startup_env_only,
+                )
+            })
+            .await;
+        if mcp_disabled {
+            return;
+        }
+        let token_store = McpTokenStore::new_with_keyring(keyring_enabled);
+
+        let mut mcp = context.app.read(|app| app.mcp.clone()).await;
+        mcp.connect_server(&server_id, &token_store).await;
+        if mcp
+            .server(&server_id)
+            .is_some_and(|server| server.connected)
+        {
+            mcp.refresh_server_metadata_concurrently(&server_id).await;
+        }
+        let Some(state) = mcp.server(&server_id).cloned() else {
+            return;
+        };
+
+        context.dispatcher.dispatch_many(
+            [StreamingAction::McpServerRestarted {
+                server_id,
+                attempt,
+                state: Box::new(state),
+            }],
+            context.action_context(),
+        );
+    });
+}
+
+/// Pings every connected server on a fixed interval for the whole session.
+pub fn spawn_mcp_health_monitor(app: AppHandle, dispatcher: AppActionDispatcher) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+
+            let targets = app
+                .read(|app| {
+                    if app.session.mcp_disabled {
+                        return Vec::new();
+                    }
+                    app.mcp
+                        .servers()
+                        .filter(|server| server.connected && !server.health.is_restarting())
+                        .filter_map(|server| {
+                            let context = app.mcp.tool_call_context(&server.config.id)?;
+                            Some((server.config.id.clone(), server.client_instance(), context))
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .await;
+            if targets.is_empty() {
+                continue;
+            }
+
+            let actions = join_all(targets.into_iter().map(
+                |(server_id, client_instance, mut context)| async move {
+                    let result = match tokio::time::timeout(
+                        HEALTH_CHECK_TIMEOUT,
+                        crate::mcp::client::execute_ping(&mut context),
+                    )
+                    .await
+                    {
+                        Ok(result) => result,
+                        Err(_) => Err("ping timed out".to_string()),
+                    };
+                    StreamingAction::McpHealthChecked {
+                        server_id,
+                        client_instance,
+                        result,
+                    }
+                },
+            ))
+            .await;
+
+            let term_size = app.read(|app| app.ui.last_term_size).await;
+            dispatcher.dispatch_many(
+                actions,
+                AppActionContext {
+                    term_width: term_size.width,
+                    term_height: term_size.height,
+                },
+            );
+        }
+    });
+}
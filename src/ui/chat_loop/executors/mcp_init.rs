@@ -8,7 +8,7 @@ use super::super::AppHandle;
 pub fn spawn_mcp_initializer(
     app: AppHandle,
     dispatcher: AppActionDispatcher,
-    request_tx: mpsc::UnboundedSender<crate::mcp::events::McpServerRequest>,
+    event_tx: mpsc::UnboundedSender<crate::mcp::events::McpServerEvent>,
 ) {
     tokio::spawn(async move {
         let mcp_disabled = app.read(|app| app.session.mcp_disabled).await;
@@ -37,7 +37,7 @@ pub fn spawn_mcp_initializer(
 
         let config = app.read(|app| app.config.clone()).await;
         let mut mcp = crate::mcp::client::McpClientManager::from_config(&config);
-        mcp.set_request_sender(request_tx.clone());
+        mcp.set_event_sender(event_tx.clone());
         mcp.connect_all(&token_store).await;
 
         let server_ids: Vec<String> = mcp
@@ -86,34 +86,18 @@ mod tests {
             crate::core::config::data::McpServerConfig {
                 id: "alpha".to_string(),
                 display_name: "Alpha".to_string(),
-                base_url: None,
                 command: Some("/definitely-missing-command".to_string()),
-                args: None,
-                env: None,
-                headers: None,
                 transport: Some("stdio".to_string()),
-                allowed_tools: None,
-                protocol_version: None,
                 enabled: Some(true),
-                tool_payloads: None,
-                tool_payload_window: None,
-                yolo: None,
+                ..Default::default()
             },
             crate::core::config::data::McpServerConfig {
                 id: "beta".to_string(),
                 display_name: "Beta".to_string(),
-                base_url: None,
                 command: Some("/definitely-missing-command-2".to_string()),
-                args: None,
-                env: None,
-                headers: None,
                 transport: Some("stdio".to_string()),
-                allowed_tools: None,
-                protocol_version: None,
                 enabled: Some(true),
-                tool_payloads: None,
-                tool_payload_window: None,
-                yolo: None,
+                ..Default::default()
             },
         ];
 
@@ -127,9 +111,9 @@ mod tests {
 
         let (action_tx, mut action_rx) = tokio::sync::mpsc::unbounded_channel();
         let dispatcher = AppActionDispatcher::new(action_tx);
-        let (request_tx, _request_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (event_tx, _request_rx) = tokio::sync::mpsc::unbounded_channel();
 
-        spawn_mcp_initializer(app.clone(), dispatcher, request_tx);
+        spawn_mcp_initializer(app.clone(), dispatcher, event_tx);
 
         let action = tokio::time::timeout(Duration::from_secs(5), action_rx.recv())
             .await
@@ -10,12 +10,12 @@ use crate::core::app::StreamingAction;
 use crate::core::mcp_auth::McpTokenStore;
 use crate::core::mcp_sampling::map_finish_reason;
 use crate::core::message::AppMessageKind;
+use crate::mcp::tool_output::McpToolOutput;
 use rust_mcp_schema::schema_utils::ResultFromClient;
 use rust_mcp_schema::{CreateMessageContent, CreateMessageResult, Role, TextContent};
 
 use super::ExecutorContext;
 
-const MCP_SAMPLING_DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
 const MCP_SAMPLING_SEND_TIMEOUT: Duration = Duration::from_secs(10);
 
 pub fn spawn_mcp_tool_call(context: ExecutorContext, request: ToolCallRequest) {
@@ -34,6 +34,7 @@ pub fn spawn_mcp_tool_call(context: ExecutorContext, request: ToolCallRequest) {
                         tool_name: request.tool_name.clone(),
                         tool_call_id: request.tool_call_id.clone(),
                         result: Err("MCP server not available.".to_string()),
+                        output: None,
                     }],
                     ctx,
                 );
@@ -41,90 +42,8 @@ pub fn spawn_mcp_tool_call(context: ExecutorContext, request: ToolCallRequest) {
             }
         };
 
-        let result = if request
-            .tool_name
-            .eq_ignore_ascii_case(crate::mcp::MCP_READ_RESOURCE_TOOL)
-        {
-            let uri = match request
-                .arguments
-                .as_ref()
-                .and_then(|args: &serde_json::Map<String, serde_json::Value>| args.get("uri"))
-                .and_then(|value: &serde_json::Value| value.as_str())
-            {
-                Some(uri) => uri.to_string(),
-                None => {
-                    context.dispatcher.dispatch_many(
-                        [StreamingAction::ToolCallCompleted {
-                            tool_name: request.tool_name.clone(),
-                            tool_call_id: request.tool_call_id.clone(),
-                            result: Err("Resource read requires uri.".to_string()),
-                        }],
-                        ctx,
-                    );
-                    return;
-                }
-            };
-
-            run_cancellable(
-                context.cancel_token.as_ref(),
-                crate::mcp::client::execute_resource_read(&mut call_context, &uri),
-            )
-            .await
-            .map(|result| serialize_mcp_result(&result))
-        } else if request
-            .tool_name
-            .eq_ignore_ascii_case(crate::mcp::MCP_LIST_RESOURCES_TOOL)
-        {
-            let Some(arguments) = request.arguments.as_ref() else {
-                context.dispatcher.dispatch_many(
-                    [StreamingAction::ToolCallCompleted {
-                        tool_name: request.tool_name.clone(),
-                        tool_call_id: request.tool_call_id.clone(),
-                        result: Err("Resource list arguments are required.".to_string()),
-                    }],
-                    ctx,
-                );
-                return;
-            };
-
-            let (kind, cursor) =
-                match crate::core::app::actions::parse_resource_list_kind(arguments) {
-                    Ok(values) => values,
-                    Err(error) => {
-                        context.dispatcher.dispatch_many(
-                            [StreamingAction::ToolCallCompleted {
-                                tool_name: request.tool_name.clone(),
-                                tool_call_id: request.tool_call_id.clone(),
-                                result: Err(error),
-                            }],
-                            ctx,
-                        );
-                        return;
-                    }
-                };
-
-            match kind {
-                crate::core::app::actions::ResourceListKind::Resources => run_cancellable(
-                    context.cancel_token.as_ref(),
-                    crate::mcp::client::execute_resource_list(&mut call_context, cursor),
-                )
-                .await
-                .map(|result| serialize_mcp_result(&result)),
-                crate::core::app::actions::ResourceListKind::Templates => run_cancellable(
-                    context.cancel_token.as_ref(),
-                    crate::mcp::client::execute_resource_template_list(&mut call_context, cursor),
-                )
-                .await
-                .map(|result| serialize_mcp_result(&result)),
-            }
-        } else {
-            run_cancellable(
-                context.cancel_token.as_ref(),
-                crate::mcp::client::execute_tool_call(&mut call_context, &request),
-            )
-            .await
-            .map(|result| serialize_mcp_result(&result))
-        };
+        let (result, output) =
+            execute_tool_request(&mut call_context, &request, context.cancel_token.as_ref()).await;
 
         let session_id = call_context.session_id.clone();
         let error = result.as_ref().err().cloned();
@@ -141,12 +60,89 @@ pub fn spawn_mcp_tool_call(context: ExecutorContext, request: ToolCallRequest) {
                 tool_name: request.tool_name.clone(),
                 tool_call_id: request.tool_call_id.clone(),
                 result,
+                output,
             }],
             ctx,
         );
     });
 }
 
+/// Runs one MCP tool request, including the built-in resource tools, and
+/// returns the serialized payload plus the parsed `tools/call` output.
+pub(crate) async fn execute_tool_request(
+    call_context: &mut crate::mcp::client::McpToolCallContext,
+    request: &ToolCallRequest,
+    cancel_token: Option<&CancellationToken>,
+) -> (Result<String, String>, Option<McpToolOutput>) {
+    if request
+        .tool_name
+        .eq_ignore_ascii_case(crate::mcp::MCP_READ_RESOURCE_TOOL)
+    {
+        let Some(uri) = request
+            .arguments
+            .as_ref()
+            .and_then(|args| args.get("uri"))
+            .and_then(|value| value.as_str())
+        else {
+            return (Err("Resource read requires uri.".to_string()), None);
+        };
+
+        let result = run_cancellable(
+            cancel_token,
+            crate::mcp::client::execute_resource_read(call_context, uri),
+        )
+        .await
+        .map(|result| serialize_mcp_result(&result));
+        return (result, None);
+    }
+
+    if request
+        .tool_name
+        .eq_ignore_ascii_case(crate::mcp::MCP_LIST_RESOURCES_TOOL)
+    {
+        let Some(arguments) = request.arguments.as_ref() else {
+            return (
+                Err("Resource list arguments are required.".to_string()),
+                None,
+            );
+        };
+
+        let (kind, cursor) = match crate::core::app::actions::parse_resource_list_kind(arguments) {
+            Ok(values) => values,
+            Err(error) => return (Err(error), None),
+        };
+
+        let result = match kind {
+            crate::core::app::actions::ResourceListKind::Resources => run_cancellable(
+                cancel_token,
+                crate::mcp::client::execute_resource_list(call_context, cursor),
+            )
+            .await
+            .map(|result| serialize_mcp_result(&result)),
+            crate::core::app::actions::ResourceListKind::Templates => run_cancellable(
+                cancel_token,
+                crate::mcp::client::execute_resource_template_list(call_context, cursor),
+            )
+            .await
+            .map(|result| serialize_mcp_result(&result)),
+        };
+        return (result, None);
+    }
+
+    let mut output = None;
+    let result = run_cancellable(
+        cancel_token,
+        crate::mcp::client::execute_tool_call(call_context, request),
+    )
+    .await
+    .map(|result| {
+        let payload = serialize_mcp_result(&result);
+        output = Some(McpToolOutput::from_result(result));
+        payload
+    });
+    (result, output)
+}
+
 pub fn spawn_mcp_prompt_call(
     context: ExecutorContext,
     request: crate::core::app::session::McpPromptRequest,
@@ -191,6 +187,57 @@ pub fn spawn_mcp_prompt_call(
     });
 }
 
+/// Delay before asking a server for completions so fast typing does not
+/// issue a request per keystroke.
+const MCP_COMPLETION_DEBOUNCE: Duration = Duration::from_millis(150);
+
+pub fn spawn_mcp_completion(
+    context: ExecutorContext,
+    request: crate::core::app::session::McpCompletionRequest,
+) {
+    tokio::spawn(async move {
+        tokio::time::sleep(MCP_COMPLETION_DEBOUNCE).await;
+        let ctx = context.action_context();
+
+        let call_context = context
+            .app
+            .read(|app| {
+                let current = app
+                    .ui
+                    .mcp_completion
+                    .as_ref()
+                    .is_some_and(|completion| completion.request == request);
+                current
+                    .then(|| app.mcp.prompt_call_context(&request.server_id))
+                    .flatten()
+            })
+            .await;
+        let Some(mut call_context) = call_context else {
+            return;
+        };
+
+        let result = crate::mcp::client::execute_completion(&mut call_context, &request).await;
+
+        // Completion failures are advisory; keep the server's last error intact.
+        let session_id = call_context.session_id.clone();
+        context
+            .app
+            .update(|app| {
+                if let Some(server) = app.mcp.server_mut(&call_context.server_id) {
+                    if let Some(session_id) = session_id {
+                        server.session_id = Some(session_id);
+                    }
+                }
+            })
+            .await;
+
+        context.dispatcher.dispatch_many(
+            [crate::core::app::McpPromptAction::CompletionsLoaded { request, result }],
+            ctx,
+        );
+    });
+}
+
 pub fn spawn_mcp_sampling_call(
     context: ExecutorContext,
     request: crate::core::app::session::McpSamplingRequest,
@@ -249,10 +296,20 @@ pub fn spawn_mcp_sampling_call(
             }
         };
 
-        let default_sampling_timeout = MCP_SAMPLING_DEFAULT_TIMEOUT;
-        let sampling_timeout =
-            crate::core::mcp_sampling::sampling_timeout_for_request(&request.request)
-                .unwrap_or(default_sampling_timeout);
+        let configured_timeout = context
+            .app
+            .read(|app| {
+                app.mcp
+                    .server(&request.server_id)
+                    .map(|server| server.limits)
+                    .unwrap_or_default()
+                    .sampling_timeout
+            })
+            .await;
+        let sampling_timeout = crate::core::mcp_sampling::sampling_timeout_for_request(
+            &request.request,
+            configured_timeout,
+        );
 
         let completion = match tokio::time::timeout(
             sampling_timeout,
@@ -486,6 +543,264 @@ pub fn spawn_mcp_refresh(context: ExecutorContext, server_id: String) {
     });
 }
 
+pub fn spawn_mcp_set_log_level(
+    context: ExecutorContext,
+    server_id: String,
+    level: rust_mcp_schema::LoggingLevel,
+) {
+    tokio::spawn(async move {
+        let Some(mut call_context) = context
+            .app
+            .read(|app| app.mcp.tool_call_context(&server_id))
+            .await
+        else {
+            context
+                .app
+                .update(|app| {
+                    app.conversation()
+                        .set_status(format!("MCP server not available: {}", server_id));
+                })
+                .await;
+            return;
+        };
+
+        let result = crate::mcp::client::execute_set_log_level(&mut call_context, level).await;
+
+        let session_id = call_context.session_id.clone();
+        context
+            .app
+            .update(|app| {
+                let status = match &result {
+                    Ok(()) => format!("MCP log level for {} set to {}", server_id, level),
+                    Err(err) => format!("MCP log level update failed for {}: {}", server_id, err),
+                };
+                app.mcp.update_tool_call_session(
+                    &call_context.server_id,
+                    session_id,
+                    result.as_ref().err().cloned(),
+                );
+                if result.is_ok() {
+                    if let Some(server) = app.mcp.server_mut(&server_id) {
+                        server.log_level = Some(level);
+                    }
+                }
+                app.conversation().set_status(status);
+            })
+            .await;
+    });
+}
+
+pub fn spawn_mcp_resource_attachments(
+    context: ExecutorContext,
+    request: crate::core::app::session::McpResourceAttachmentRequest,
+) {
+    tokio::spawn(async move {
+        let ctx = context.action_context();
+        let mut results = Vec::with_capacity(request.mentions.len());
+        for mention in request.mentions {
+            let call_context = context
+                .app
+                .read(|app| app.mcp.tool_call_context(&mention.server_id))
+                .await;
+            let Some(mut call_context) = call_context else {
+                results.push((mention, Err("MCP server not available.".to_string())));
+                continue;
+            };
+
+            let result = run_cancellable(
+                context.cancel_token.as_ref(),
+                crate::mcp::client::execute_resource_read(&mut call_context, &mention.uri),
+            )
+            .await
+            .map(|result| crate::mcp::subscriptions::render_resource_text(&result));
+
+            let session_id = call_context.session_id.clone();
+            let error = result.as_ref().err().cloned();
+            context
+                .app
+                .update(|app| {
+                    app.mcp
+                        .update_tool_call_session(&call_context.server_id, session_id, error);
+                })
+                .await;
+            results.push((mention, result));
+        }
+
+        context.dispatcher.dispatch_many(
+            [StreamingAction::McpResourceAttachmentsRead {
+                message: request.message,
+                results,
+            }],
+            ctx,
+        );
+    });
+}
+
+pub fn spawn_mcp_resource_watch(
+    context: ExecutorContext,
+    server_id: String,
+    uri: String,
+    inject: bool,
+) {
+    tokio::spawn(async move {
+        let Some(mut call_context) = context
+            .app
+            .read(|app| app.mcp.tool_call_context(&server_id))
+            .await
+        else {
+            context
+                .app
+                .update(|app| {
+                    app.conversation()
+                        .set_status(format!("MCP server not available: {}", server_id));
+                })
+                .await;
+            return;
+        };
+
+        let result = crate::mcp::client::execute_resource_subscribe(&mut call_context, &uri).await;
+
+        let session_id = call_context.session_id.clone();
+        let subscribed = result.is_ok();
+        context
+            .app
+            .update(|app| {
+                app.mcp.update_tool_call_session(
+                    &call_context.server_id,
+                    session_id,
+                    result.as_ref().err().cloned(),
+                );
+                match &result {
+                    Ok(()) => {
+                        if let Some(server) = app.mcp.server_mut(&server_id) {
+                            server.resource_watches.insert(
+                                uri.clone(),
+                                crate::mcp::subscriptions::McpResourceWatch::new(
+                                    uri.clone(),
+                                    inject,
+                                ),
+                            );
+                        }
+                        app.conversation()
+                            .set_status(format!("Watching {} on {}", uri, server_id));
+                    }
+                    Err(err) => {
+                        app.conversation().set_status(format!(
+                            "MCP resource subscribe failed for {}: {}",
+                            server_id, err
+                        ));
+                    }
+                }
+            })
+            .await;
+
+        if subscribed {
+            spawn_mcp_resource_watch_read(context, server_id, uri, false);
+        }
+    });
+}
+
+pub fn spawn_mcp_resource_unwatch(context: ExecutorContext, server_id: String, uri: String) {
+    tokio::spawn(async move {
+        let Some(mut call_context) = context
+            .app
+            .read(|app| app.mcp.tool_call_context(&server_id))
+            .await
+        else {
+            context
+                .app
+                .update(|app| {
+                    if let Some(server) = app.mcp.server_mut(&server_id) {
+                        server.resource_watches.remove(&uri);
+                    }
+                    app.conversation()
+                        .set_status(format!("Stopped watching {}", uri));
+                })
+                .await;
+            return;
+        };
+
+        let result =
+            crate::mcp::client::execute_resource_unsubscribe(&mut call_context, &uri).await;
+
+        let session_id = call_context.session_id.clone();
+        context
+            .app
+            .update(|app| {
+                app.mcp.update_tool_call_session(
+                    &call_context.server_id,
+                    session_id,
+                    result.as_ref().err().cloned(),
+                );
+                // Drop the local watch even if the server rejected the request so
+                // stale updates stop being injected.
+                if let Some(server) = app.mcp.server_mut(&server_id) {
+                    server.resource_watches.remove(&uri);
+                }
+                let status = match &result {
+                    Ok(()) => format!("Stopped watching {}", uri),
+                    Err(err) => format!(
+                        "Stopped watching {} (unsubscribe failed for {}: {})",
+                        uri, server_id, err
+                    ),
+                };
+                app.conversation().set_status(status);
+            })
+            .await;
+    });
+}
+
+pub fn spawn_mcp_resource_watch_read(
+    context: ExecutorContext,
+    server_id: String,
+    uri: String,
+    is_update: bool,
+) {
+    tokio::spawn(async move {
+        let ctx = context.action_context();
+        let Some(mut call_context) = context
+            .app
+            .read(|app| app.mcp.tool_call_context(&server_id))
+            .await
+        else {
+            context.dispatcher.dispatch_many(
+                [StreamingAction::McpResourceWatchRead {
+                    server_id,
+                    uri,
+                    result: Err("MCP server not available.".to_string()),
+                    is_update,
+                }],
+                ctx,
+            );
+            return;
+        };
+
+        let result = crate::mcp::client::execute_resource_read(&mut call_context, &uri)
+            .await
+            .map(|result| crate::mcp::subscriptions::render_resource_text(&result));
+
+        let session_id = call_context.session_id.clone();
+        let error = result.as_ref().err().cloned();
+        context
+            .app
+            .update(|app| {
+                app.mcp
+                    .update_tool_call_session(&call_context.server_id, session_id, error);
+            })
+            .await;
+
+        context.dispatcher.dispatch_many(
+            [StreamingAction::McpResourceWatchRead {
+                server_id,
+                uri,
+                result,
+                is_update,
+            }],
+            ctx,
+        );
+    });
+}
+
 fn serialize_mcp_result<T: Serialize>(result: &T) -> String {
     serde_json::to_string_pretty(result)
         .unwrap_or_else(|_| "Unable to serialize MCP result.".to_string())
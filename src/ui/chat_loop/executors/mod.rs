@@ -5,6 +5,7 @@ use crate::core::app::{AppActionContext, AppActionDispatcher};
 
 use super::AppHandle;
 
+pub mod mcp_health;
 pub mod mcp_init;
 pub mod mcp_tools;
 pub mod model_loader;
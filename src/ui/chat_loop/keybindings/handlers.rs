@@ -1156,6 +1156,32 @@ impl KeyHandler for ToolPromptDecisionHandler {
                     );
                     KeyResult::Handled
                 }
+                'f' => {
+                    debug!("Tool prompt decision: allow always (f)");
+                    dispatcher.dispatch_many(
+                        [StreamingAction::ToolPermissionDecision {
+                            decision: ToolPermissionDecision::AllowAlways,
+                        }],
+                        AppActionContext {
+                            term_width,
+                            term_height,
+                        },
+                    );
+                    KeyResult::Handled
+                }
+                'n' => {
+                    debug!("Tool prompt decision: block always (n)");
+                    dispatcher.dispatch_many(
+                        [StreamingAction::ToolPermissionDecision {
+                            decision: ToolPermissionDecision::BlockAlways,
+                        }],
+                        AppActionContext {
+                            term_width,
+                            term_height,
+                        },
+                    );
+                    KeyResult::Handled
+                }
                 'b' => {
                     debug!("Tool prompt decision: block (b)");
                     dispatcher.dispatch_many(
@@ -1211,10 +1237,12 @@ mod tests {
         app.ui.messages.push_back(Message {
             role: TranscriptRole::User,
             content: "Question".to_string(),
+            id: None,
         });
         app.ui.messages.push_back(Message {
             role: TranscriptRole::Assistant,
             content: "Answer".to_string(),
+            id: None,
         });
 
         let app = AppHandle::new(Arc::new(Mutex::new(app)));
@@ -1245,10 +1273,12 @@ mod tests {
         app.ui.messages.push_back(Message {
             role: TranscriptRole::User,
             content: "Question".to_string(),
+            id: None,
         });
         app.ui.messages.push_back(Message {
             role: TranscriptRole::Assistant,
             content: "Answer".to_string(),
+            id: None,
         });
 
         let app = AppHandle::new(Arc::new(Mutex::new(app)));
@@ -1280,6 +1310,7 @@ mod tests {
         app.ui.messages.push_back(Message {
             role: TranscriptRole::User,
             content: "Question".to_string(),
+            id: None,
         });
         // No assistant message added
 
@@ -5,7 +5,7 @@
 //! mode-specific behaviour.
 
 mod event_loop;
-mod executors;
+pub(crate) mod executors;
 pub mod keybindings;
 mod lifecycle;
 pub mod modes;
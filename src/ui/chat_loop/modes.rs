@@ -494,14 +494,13 @@ pub async fn handle_picker_key_event(
                 actions.push(PickerAction::PickerApplySelection { persistent });
             }
             event::KeyCode::Delete => actions.push(PickerAction::PickerUnsetDefault),
+            event::KeyCode::Tab => actions.push(PickerAction::PickerToggleSelection),
             event::KeyCode::Backspace => actions.push(PickerAction::PickerBackspace),
             event::KeyCode::Char('o') if key.modifiers.contains(event::KeyModifiers::CONTROL) => {
                 actions.push(PickerAction::PickerInspectSelection);
             }
-            event::KeyCode::Char(c) => {
-                if !key.modifiers.contains(event::KeyModifiers::CONTROL) {
-                    actions.push(PickerAction::PickerTypeChar { ch: c });
-                }
+            event::KeyCode::Char(c) if !key.modifiers.contains(event::KeyModifiers::CONTROL) => {
+                actions.push(PickerAction::PickerTypeChar { ch: c });
             }
             _ => {}
         }
@@ -926,6 +925,7 @@ mod tests {
                     app.ui.messages.push_back(Message {
                         role: TranscriptRole::User,
                         content: "rewrite me".into(),
+                        id: None,
                     });
                     app.ui.enter_edit_select_mode(EditSelectTarget::User);
                 })
@@ -961,10 +961,12 @@ mod tests {
                     app.ui.messages.push_back(Message {
                         role: TranscriptRole::User,
                         content: "keep".into(),
+                        id: None,
                     });
                     app.ui.messages.push_back(Message {
                         role: TranscriptRole::Assistant,
                         content: "adjust me".into(),
+                        id: None,
                     });
                     app.session.active_assistant_message_index = Some(1);
                     app.ui.enter_edit_select_mode(EditSelectTarget::Assistant);
@@ -1003,10 +1005,12 @@ mod tests {
                     app.ui.messages.push_back(Message {
                         role: TranscriptRole::Assistant,
                         content: "to remove".into(),
+                        id: None,
                     });
                     app.ui.messages.push_back(Message {
                         role: TranscriptRole::User,
                         content: "later".into(),
+                        id: None,
                     });
                     app.session.active_assistant_message_index = Some(0);
                     app.session.tool_pipeline.active_tool_request =
@@ -1078,10 +1082,12 @@ mod tests {
                     app.ui.messages.push_back(Message {
                         role: TranscriptRole::User,
                         content: "keep".into(),
+                        id: None,
                     });
                     app.ui.messages.push_back(Message {
                         role: TranscriptRole::Assistant,
                         content: "to edit".into(),
+                        id: None,
                     });
                     app.ui.enter_edit_select_mode(EditSelectTarget::Assistant);
                 })
@@ -1293,14 +1299,17 @@ mod tests {
                 app.ui.messages.push_back(Message {
                     role: TranscriptRole::Assistant,
                     content: "First block:\n```rust\nfn first() {}\n```".to_string(),
+                    id: None,
                 });
                 app.ui.messages.push_back(Message {
                     role: TranscriptRole::User,
                     content: "Show me more code".to_string(),
+                    id: None,
                 });
                 app.ui.messages.push_back(Message {
                     role: TranscriptRole::Assistant,
                     content: "Second block:\n```python\ndef second():\n    pass\n```".to_string(),
+                    id: None,
                 });
             })
             .await;
@@ -1354,28 +1363,34 @@ mod tests {
                 app.ui.messages.push_back(Message {
                     role: TranscriptRole::User,
                     content: "Show me Rust code".to_string(),
+                    id: None,
                 });
                 app.ui.messages.push_back(Message {
                     role: TranscriptRole::Assistant,
                     content: "Here it is:\n```rust\nfn first() {}\n```".to_string(),
+                    id: None,
                 });
                 // Message 2
                 app.ui.messages.push_back(Message {
                     role: TranscriptRole::User,
                     content: "Thanks, what about Python?".to_string(),
+                    id: None,
                 });
                 app.ui.messages.push_back(Message {
                     role: TranscriptRole::Assistant,
                     content: "Sure, let me explain first...".to_string(),
+                    id: None,
                 });
                 // Message 3
                 app.ui.messages.push_back(Message {
                     role: TranscriptRole::User,
                     content: "Show me the Python code".to_string(),
+                    id: None,
                 });
                 app.ui.messages.push_back(Message {
                     role: TranscriptRole::Assistant,
                     content: "Here you go:\n```python\ndef second():\n    pass\n```".to_string(),
+                    id: None,
                 });
             })
             .await;
@@ -1450,10 +1465,12 @@ mod tests {
                 app.ui.messages.push_back(Message {
                     role: TranscriptRole::User,
                     content: "Show me code".to_string(),
+                    id: None,
                 });
                 app.ui.messages.push_back(Message {
                     role: TranscriptRole::Assistant,
                     content: "```rust\nfn first() {}\n```".to_string(),
+                    id: None,
                 });
             })
             .await;
@@ -1471,10 +1488,12 @@ mod tests {
                 app.ui.messages.push_back(Message {
                     role: TranscriptRole::User,
                     content: "And another".to_string(),
+                    id: None,
                 });
                 app.ui.messages.push_back(Message {
                     role: TranscriptRole::Assistant,
                     content: "```python\ndef second():\n    pass\n```".to_string(),
+                    id: None,
                 });
             })
             .await;
@@ -28,10 +28,12 @@ pub async fn bootstrap_app(
         provider,
         env_only,
         character,
+        no_default_character,
         persona,
         preset,
         disable_mcp,
         character_service,
+        history: _,
     } = options;
     let config = Config::load()?;
     let auth_manager = AuthManager::new()?;
@@ -145,6 +147,7 @@ pub async fn bootstrap_app(
                 env_only,
                 pre_resolved_session,
                 character: character.clone(),
+                no_default_character,
                 persona,
                 preset,
                 disable_mcp,
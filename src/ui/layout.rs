@@ -172,6 +172,7 @@ mod tests {
         messages.push_back(Message {
             role: TranscriptRole::Assistant,
             content: "Testing a [link](https://example.com) span.".into(),
+            id: None,
         });
         let theme = Theme::dark_default();
         let layout = LayoutEngine::layout_messages(&messages, &theme, &LayoutConfig::default());
@@ -193,6 +194,7 @@ mod tests {
         messages.push_back(Message {
             role: TranscriptRole::User,
             content: "Hello there".into(),
+            id: None,
         });
         let theme = Theme::dark_default();
         let layout = LayoutEngine::layout_plain_text(&messages, &theme, Some(10), false);
@@ -216,6 +218,7 @@ mod tests {
         messages.push_back(Message {
             role: TranscriptRole::Assistant,
             content: "[Rust](https://www.rust-lang.org) and [Go](https://go.dev)".into(),
+            id: None,
         });
         let theme = Theme::dark_default();
         let layout = LayoutEngine::layout_messages(&messages, &theme, &LayoutConfig::default());
@@ -237,6 +240,7 @@ mod tests {
         messages.push_back(Message {
             role: TranscriptRole::Assistant,
             content: "[associative trails](https://example.com)".into(),
+            id: None,
         });
         let theme = Theme::dark_default();
         let layout = LayoutEngine::layout_messages(&messages, &theme, &LayoutConfig::default());
@@ -12,6 +12,7 @@ pub fn single_block() -> Message {
     Message {
         role: TranscriptRole::Assistant,
         content: "Here's a function:\n\n```rust\nfn main() {}\n```\n".to_string(),
+        id: None,
     }
 }
 
@@ -31,6 +32,7 @@ pub fn multiple_blocks() -> Message {
             "```\nno language tag\n```\n"
         )
         .to_string(),
+        id: None,
     }
 }
 
@@ -42,18 +44,22 @@ pub fn blocks_across_messages() -> Vec<Message> {
         Message {
             role: TranscriptRole::User,
             content: "Show me Rust code".to_string(),
+            id: None,
         },
         Message {
             role: TranscriptRole::Assistant,
             content: "```rust\nfn first() {}\n```".to_string(),
+            id: None,
         },
         Message {
             role: TranscriptRole::User,
             content: "And Python?".to_string(),
+            id: None,
         },
         Message {
             role: TranscriptRole::Assistant,
             content: "```python\ndef second():\n    pass\n```".to_string(),
+            id: None,
         },
     ]
 }
@@ -76,6 +82,7 @@ pub fn nested_in_list() -> Message {
             "   ```\n"
         )
         .to_string(),
+        id: None,
     }
 }
 
@@ -93,6 +100,7 @@ pub fn wrapped_code() -> Message {
             "```\n"
         )
         .to_string(),
+        id: None,
     }
 }
 
@@ -103,6 +111,7 @@ pub fn empty_block() -> Message {
     Message {
         role: TranscriptRole::Assistant,
         content: "Here's an empty block:\n\n```\n```\n\nDone.".to_string(),
+        id: None,
     }
 }
 
@@ -113,6 +122,7 @@ pub fn no_language_tag() -> Message {
     Message {
         role: TranscriptRole::Assistant,
         content: "```\nplain code\nno language\n```".to_string(),
+        id: None,
     }
 }
 
@@ -123,6 +133,7 @@ pub fn adjacent_to_text() -> Message {
     Message {
         role: TranscriptRole::Assistant,
         content: "Before```rust\nfn adjacent() {}\n```After".to_string(),
+        id: None,
     }
 }
 
@@ -133,6 +144,7 @@ pub fn user_message_with_code() -> Message {
     Message {
         role: TranscriptRole::User,
         content: "Can you explain this?\n\n```python\ndef mystery():\n    pass\n```".to_string(),
+        id: None,
     }
 }
 
@@ -148,6 +160,7 @@ pub fn code_and_links() -> Message {
             "See also [this guide](https://example.org)."
         )
         .to_string(),
+        id: None,
     }
 }
 
@@ -164,5 +177,6 @@ pub fn various_languages() -> Message {
             "```txt\nplain text\n```\n"
         )
         .to_string(),
+        id: None,
     }
 }
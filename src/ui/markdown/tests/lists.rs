@@ -10,6 +10,7 @@ fn wrapped_list_items_align_under_text() {
     let message = Message {
             role: TranscriptRole::Assistant,
             content: "- Parent item that wraps within the width budget and keeps alignment.\n  - Child item that wraps nicely under its parent alignment requirement.\n    - Grandchild entry that wraps and keeps deeper indentation consistent.".into(),
+            id: None,
         };
 
     let rendered = render_markdown_for_test(&message, &theme, true, Some(28));
@@ -72,6 +73,7 @@ fn gfm_callout_blockquotes_render_content() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "> [!NOTE]\n> Always document parser upgrades.".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, true, None);
@@ -95,6 +97,7 @@ fn ordered_list_item_code_block_is_indented_under_marker() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "1. Intro text\n\n   ```\n   fn greet() {}\n   ```\n\n   Follow up text".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -131,6 +134,7 @@ fn multi_item_ordered_list_keeps_code_block_with_correct_item() {
             role: TranscriptRole::Assistant,
             content: "1. **Open a new terminal** on your local machine (keeping your SSH session open) and run `scp` as above.\n2. **Use `scp` in reverse** from the remote side *to* your local machine (if remote can reach your local machine and SSH is accessible), e.g.:\n   ```bash\n   scp /path/to/file you@your_local_IP:/path/to/local/destination/\n   ```\n   But this only works if your local machine is running an SSH server and is network-reachable — rarely the case.\n3. **Use `rsync` over SSH** similarly to `scp`."
                 .into(),
+            id: None,
         };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -181,6 +185,7 @@ fn nested_bullet_lists_render_with_indentation() {
         role: TranscriptRole::Assistant,
         content: "* Item 1\n    * Sub-item 1.1\n    * Sub-item 1.2\n        * Sub-sub-item 1.2.1"
             .into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -227,6 +232,7 @@ fn nested_lists_dont_add_blank_lines_between_same_level_items() {
         content:
             "- Budget tree, branch one\n  - Emergency fund\n    - Sub-sticky note\n  - Groceries"
                 .into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -265,6 +271,7 @@ fn list_with_source_blank_lines_preserves_spacing_between_top_level_items() {
     let message = Message {
             role: TranscriptRole::Assistant,
             content: "- Strategic Foundations\n  - Long-Horizon Thinking\n    - Scenario Branches\n\n- Implementation Patterns\n  - Knowledge Architecture\n    - Modular repositories\n\n- Resilience\n  - Stressors".into(),
+            id: None,
         };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -303,6 +310,7 @@ fn list_without_source_blank_lines_has_no_spacing_between_top_level_items() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "- First section\n  - Nested item\n- Second section\n  - Another nested".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -334,6 +342,7 @@ fn list_preceded_by_paragraph_has_blank_line_before() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "Here is some introductory text.\n\n- First item\n- Second item".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -365,6 +374,7 @@ fn list_followed_by_paragraph_has_blank_line_after() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "- First item\n- Second item\n\nThis is concluding text.".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -396,6 +406,7 @@ fn list_preceded_by_heading_has_blank_line_before() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "## My Section\n\n- First item\n- Second item".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -424,6 +435,7 @@ fn list_followed_by_heading_has_blank_line_after() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "- First item\n- Second item\n\n## Next Section".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -485,6 +497,7 @@ fn complex_nested_lists_with_long_text_preserve_blank_lines() {
        - Define entities, attributes, and relations using logical formalisms.
        - Incorporate context-sensitive nodes for ambiguous linguistic references.
 "#.into(),
+            id: None,
         };
 
     let rendered = render_markdown_for_test(&message, &theme, false, Some(80));
@@ -614,6 +627,7 @@ Paragraph text after first item.
 More paragraph text.
 - Second item (should have NO blank before it)"#
             .into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -670,6 +684,7 @@ fn lists_with_numeric_text_before_them_dont_shift_indices() {
 
 3. Third initiative (after blank line)"#
             .into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -721,6 +736,7 @@ fn lists_with_plus_markers_preserve_blank_lines() {
 + Third item (after blank line)
 + Fourth item"#
             .into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -784,6 +800,7 @@ fn code_blocks_dont_shift_list_item_indices() {
 
 - Third real item (should have blank before)"#
             .into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -835,6 +852,7 @@ fn list_items_with_multiple_paragraphs_preserve_blank_lines() {
 
 - Next item"#
             .into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -903,6 +921,7 @@ fn list_items_preserve_blank_lines_before_all_block_elements() {
 
   > Important quote here"#
             .into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -968,6 +987,7 @@ fn list_paragraphs_keep_indent_after_blank_lines() {
   Once normalized, data should be molded into adaptive knowledge graphs or relational mappings.
 - Next item"#
                 .into(),
+            id: None,
         };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -1004,6 +1024,7 @@ fn list_paragraphs_with_soft_breaks_keep_indent() {
   The architecture should balance **information retrieval efficiency**, **semantic accuracy**, and **human-centered accessibility**.
   Below is a structured decomposition of its design hierarchy:"#
                 .into(),
+            id: None,
         };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -1052,6 +1073,7 @@ fn nested_lists_with_single_blank_line_dont_double_space() {
   - child one
   - child two"#
             .into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -1089,6 +1111,7 @@ fn blockquote_followed_by_list_has_single_blank_line() {
 
 - **Merit:** it funds the dream of four walls and a window box."#
             .into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -1125,6 +1148,7 @@ fn blockquote_followed_by_paragraph_has_single_blank_line() {
 
 This is a paragraph after the quote."#
             .into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -1166,6 +1190,7 @@ fn blockquote_followed_by_heading_has_single_blank_line() {
 
 ## Next Section"#
             .into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
@@ -1209,6 +1234,7 @@ fn blockquote_with_code_block_followed_by_paragraph() {
 
 Next paragraph"#
             .into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, None);
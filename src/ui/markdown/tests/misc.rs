@@ -30,6 +30,7 @@ fn markdown_images_emit_clickable_links() {
         role: TranscriptRole::Assistant,
         content: "Look at this sketch: ![diagram](https://example.com/diagram.png) neat, right?"
             .into(),
+        id: None,
     };
 
     let cfg = MessageRenderConfig::markdown(true, false).with_span_metadata();
@@ -66,6 +67,7 @@ fn horizontal_rules_render_as_centered_lines() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "Above\n\n---\n\nBelow".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, true, Some(50));
@@ -107,6 +109,7 @@ fn superscript_and_subscript_render_without_markers() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "Subscripts: ~abc~ alongside superscripts: ^def^.".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, true, None);
@@ -200,6 +203,7 @@ fn test_extremely_narrow_terminal_no_truncation() {
 | VeryLongUnbreakableWord | AnotherLongWord |
 "
         .into(),
+        id: None,
     });
     let theme = crate::ui::theme::Theme::dark_default();
 
@@ -251,6 +255,7 @@ fn emphasis_with_standalone_paren() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "Space exploration is *fundamentally_x* )".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, Some(36));
@@ -8,6 +8,7 @@ fn markdown_details_metadata_matches_lines_and_tags() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "Testing metadata with a [link](https://example.com) inside.".into(),
+        id: None,
     };
 
     let details = render_message_markdown_details_with_policy_and_user_name(
@@ -57,6 +58,7 @@ fn metadata_marks_user_prefix() {
     let message = Message {
         role: TranscriptRole::User,
         content: "Hello world".into(),
+        id: None,
     };
 
     let details = render_message_markdown_details_with_policy_and_user_name(
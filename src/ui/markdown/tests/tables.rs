@@ -18,6 +18,7 @@ fn metadata_marks_table_links() {
 | Mixed | plain text and [Example](https://example.com) with trailing words |
 "
         .into(),
+        id: None,
     };
 
     let details = render_message_markdown_details_with_policy_and_user_name(
@@ -123,6 +124,7 @@ fn table_rendering_works() {
 
 End of table."###
             .into(),
+        id: None,
     });
     let theme = crate::ui::theme::Theme::dark_default();
     let rendered = render_markdown_for_test(&messages[0], &theme, true, None);
@@ -162,6 +164,7 @@ fn table_renders_emoji_and_br_correctly() {
 | Emoji | 🚀<br/>Hi |
 "
         .into(),
+        id: None,
     });
     let theme = crate::ui::theme::Theme::dark_default();
     let rendered = render_markdown_for_test(&messages[0], &theme, true, None);
@@ -478,6 +481,7 @@ fn test_table_no_content_truncation_wide_terminal() {
 | B     | More content        | Another long piece of text with technical details and specifications that must remain fully visible to be useful |
 "
                 .into(),
+            id: None,
         });
     let theme = crate::ui::theme::Theme::dark_default();
 
@@ -533,6 +537,7 @@ fn test_table_content_wrapping_medium_terminal() {
 | SDK  | Software Development Kit with comprehensive documentation and examples for developers |
 "
                 .into(),
+            id: None,
         });
     let theme = crate::ui::theme::Theme::dark_default();
 
@@ -587,6 +592,7 @@ fn test_table_should_not_wrap_borders() {
 | Democracy | Government by the people, either directly or through elected representatives. | Universal suffrage, free elections, protection of civil liberties. | United States, India, Germany |
 | Republic | A form of government in which power resides with the citizens, who elect representatives to govern on their behalf. | Elected officials, separation of powers, rule of law. | France, Brazil, South Africa |
 | Dictatorship | A form of government in which a single person or a small group holds absolute power. | Lack of free elections, suppression of opposition, centralized control. | North Korea, Cuba, Syria |"#.into(),
+            id: None,
         });
 
     let theme = crate::ui::theme::Theme::dark_default();
@@ -644,6 +650,7 @@ fn test_styled_words_wrap_at_boundaries_in_table() {
 | X | **Dramatically** _improved_ decision-making capabilities with ***real-time*** analytics |
 "#
         .into(),
+        id: None,
     });
 
     let theme = crate::ui::theme::Theme::dark_default();
@@ -707,6 +714,7 @@ fn test_table_wrapping_with_mixed_content() {
 | 3  | WARN   | Warning: deprecated function usage detected |
 "
         .into(),
+        id: None,
     });
     let theme = crate::ui::theme::Theme::dark_default();
 
@@ -763,6 +771,7 @@ fn test_table_with_emoji_and_unicode_no_truncation() {
 | 🚀     | Launch  | System is ready for deployment with full internationalization support |
 "
                 .into(),
+            id: None,
         });
     let theme = crate::ui::theme::Theme::dark_default();
 
@@ -806,6 +815,7 @@ fn table_preserves_words_with_available_space() {
     messages.push_back(Message {
         role: TranscriptRole::Assistant,
         content: markdown.to_string(),
+        id: None,
     });
 
     let theme = crate::ui::theme::Theme::dark_default();
@@ -854,6 +864,7 @@ fn test_government_systems_table_from_testcase() {
 | **Dictatorship** | A system where power is concentrated in the hands of a single person or a small group, often with no meaningful opposition. | - Single-party rule or military rule<br/>- Suppression of political opposition and civil liberties<br/>- Often characterized by censorship and propaganda | - *North Korea*, *Cuba*, *Syria* |
 | **Theocracy** | A system where government is *the rule of God* or a divine being, with religious leaders holding political power. | - Religious law (e.g., Sharia) as the basis for governance<br/>- Religious leaders hold political authority<br/>- Often limited civil liberties for non-believers or dissenters | - *Iran*, *Vatican City* |
 | **Communism** | A system where the means of production are owned and controlled by the state, aiming for a classless society. | - Central planning and state ownership of industry<br/>- Single-party rule and suppression of political opposition<br/>- Emphasis on collective ownership and equality | - *China*, *Cuba*, *North Korea* |"#.into(),
+            id: None,
         });
     let theme = crate::ui::theme::Theme::dark_default();
 
@@ -949,6 +960,7 @@ fn test_table_cell_word_wrapping_regression() {
 | This is a very long sentence that should definitely wrap within the cell when the terminal is narrow | Short | Another moderately long piece of content |
 | Short content | This is another extremely long sentence that contains many words and should wrap properly within the table cell boundaries | More content here |
 "###.to_string(),
+            id: None,
         });
 
     let theme = crate::ui::theme::Theme::dark_default();
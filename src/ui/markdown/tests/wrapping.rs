@@ -18,6 +18,7 @@ fn shared_renderer_with_metadata_matches_details_wrapper() {
         role: TranscriptRole::Assistant,
         content: "A [link](https://example.com) and a code block.\n\n```rust\nfn main() {}\n```"
             .into(),
+        id: None,
     };
 
     let expected = render_message_markdown_details_with_policy_and_user_name(
@@ -58,6 +59,7 @@ fn markdown_links_wrap_at_word_boundaries_with_width() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "abcd efgh [hypertext dreams](https://docs.hypertext.org) and more text".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, true, Some(10));
@@ -95,6 +97,7 @@ fn markdown_links_wrap_in_long_paragraph_without_mid_word_break() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: SAMPLE_HYPERTEXT_PARAGRAPH.to_string(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, true, Some(158));
@@ -213,6 +216,7 @@ fn emphasis_ending_one_after_width() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "Space exploration is *fundamentally* good.".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, Some(34));
@@ -237,6 +241,7 @@ fn strong_emphasis_ending_at_width() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "Space exploration is **fundamentally** useful.".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, Some(35));
@@ -260,6 +265,7 @@ fn inline_code_ending_at_width() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "The function is `very_important_func` today.".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, Some(35));
@@ -300,6 +306,7 @@ fn link_ending_at_width() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "Check out [this important resource](http://example.com) here.".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, Some(35));
@@ -323,6 +330,7 @@ fn strikethrough_ending_at_width() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "This approach is ~~fundamentally~~ useful.".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, Some(30));
@@ -346,6 +354,7 @@ fn emphasis_with_punctuation_at_width() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "Space exploration is *fundamentally*, I think, useful.".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, Some(36));
@@ -371,6 +380,7 @@ fn emphasis_with_paren_inside_at_width() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "Space exploration is *(fundamentally)* useful.".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, Some(36));
@@ -412,6 +422,7 @@ fn emphasis_with_paren_outside_one_past_width() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "Space exploration is *fundamentally_x*) useful.".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, Some(36));
@@ -449,6 +460,7 @@ fn code_with_closing_paren_at_width() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "The function is `very_important_func`) today.".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, Some(36));
@@ -488,6 +500,7 @@ fn emphasis_with_multiple_adjacent_punctuation() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "Space exploration is *fundamentally*))) more.".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, Some(37));
@@ -529,6 +542,7 @@ fn emphasis_preserves_style_during_backtracking() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: "Space exploration is *fundamentally_x*) useful.".into(),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, Some(36));
@@ -579,6 +593,7 @@ fn emphasis_fills_entire_width_with_adjacent_punct() {
     let message = Message {
         role: TranscriptRole::Assistant,
         content: format!("*{}*) more.", word),
+        id: None,
     };
 
     let rendered = render_markdown_for_test(&message, &theme, false, Some(32));
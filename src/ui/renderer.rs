@@ -8,7 +8,7 @@
 //! Scroll state and OSC hyperlink metadata are recomputed only when necessary
 //! to keep redraws responsive.
 
-use crate::core::app::ui_state::{EditSelectTarget, ToolPrompt};
+use crate::core::app::ui_state::{EditSelectTarget, McpPromptInputTarget, ToolPrompt};
 use crate::core::app::App;
 use crate::core::app::InspectMode;
 use crate::core::message::{AppMessageKind, TranscriptRole};
@@ -391,6 +391,10 @@ pub fn ui(f: &mut Frame, app: &mut App) {
         f.render_widget(blank, text_area);
     }
 
+    if app.ui.is_input_active() && app.picker_session().is_none() {
+        render_mcp_completion_dropdown(f, app, area);
+    }
+
     // Render modal picker or inspect overlay if present
     if app.inspect_state().is_some() {
         let theme = app.ui.theme.clone();
@@ -561,6 +565,60 @@ pub fn ui(f: &mut Frame, app: &mut App) {
     }
 }
 
+/// Maximum number of MCP argument suggestions shown above the input box.
+const MCP_COMPLETION_VISIBLE: usize = 8;
+
+fn render_mcp_completion_dropdown(f: &mut Frame, app: &App, input_area: Rect) {
+    let Some(completion) = app.ui.mcp_completion.as_ref() else {
+        return;
+    };
+    if completion.values.is_empty() {
+        return;
+    }
+
+    let truncated = completion.has_more || completion.values.len() > MCP_COMPLETION_VISIBLE;
+    let shown = &completion.values[..completion.values.len().min(MCP_COMPLETION_VISIBLE)];
+    let height = (shown.len() as u16).saturating_add(2).min(input_area.y);
+    if height < 3 {
+        return;
+    }
+    let longest = shown
+        .iter()
+        .map(|value| UnicodeWidthStr::width(value.as_str()))
+        .max()
+        .unwrap_or(0) as u16;
+    let width = longest.saturating_add(4).max(30).min(input_area.width);
+    let area = Rect {
+        x: input_area.x,
+        y: input_area.y - height,
+        width,
+        height,
+    };
+
+    let title = if truncated {
+        "Suggestions (Tab=complete) …"
+    } else {
+        "Suggestions (Tab=complete)"
+    };
+    let items: Vec<ListItem> = shown
+        .iter()
+        .map(|value| {
+            let text = truncate_to_width(value, width.saturating_sub(2) as usize);
+            ListItem::new(Span::styled(text, app.ui.theme.input_text_style))
+        })
+        .collect();
+    let list = List::new(items)
+        .style(Style::default().bg(app.ui.theme.background_color))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(app.ui.theme.input_border_style)
+                .title(Span::styled(title, app.ui.theme.title_style)),
+        );
+    f.render_widget(Clear, area);
+    f.render_widget(list, area);
+}
+
 fn suppress_link_rendering(app: &App) -> bool {
     app.picker_state().is_some() || app.inspect_state().is_some()
 }
@@ -613,6 +671,9 @@ fn input_title_base(app: &App, input_width: u16) -> Cow<'_, str> {
             Some(crate::core::app::PickerMode::Preset) => {
                 Cow::Borrowed("Select a preset (Esc=cancel • Ctrl+C=quit)")
             }
+            Some(crate::core::app::PickerMode::Greeting) => {
+                Cow::Borrowed("Select a greeting (Esc=cancel • Ctrl+C=quit)")
+            }
             _ => Cow::Borrowed("Make a selection (Esc=cancel • Ctrl+C=quit)"),
         }
     } else if let Some(prompt) = app.ui.tool_prompt() {
@@ -639,9 +700,13 @@ fn input_title_base(app: &App, input_width: u16) -> Cow<'_, str> {
                     .map(|arg| arg.name.as_str())
                     .unwrap_or("value")
             });
+        let kind = match prompt.target {
+            McpPromptInputTarget::Prompt => "Prompt",
+            McpPromptInputTarget::ResourceTemplate(_) => "Resource",
+        };
         Cow::Owned(format!(
-            "Prompt {} on {}: {} (Enter=Next • Esc=Cancel)",
-            prompt.prompt_name, prompt.server_name, label
+            "{} {} on {}: {} (Enter=Next • Esc=Cancel)",
+            kind, prompt.prompt_name, prompt.server_name, label
         ))
     } else if app.ui.file_prompt().is_some() {
         Cow::Borrowed("Specify new filename (Esc=Cancel • Alt+Enter=Overwrite)")
@@ -722,18 +787,18 @@ fn tool_prompt_title(prompt: &ToolPrompt, width: u16, frame: &str) -> String {
     if let Some(display_name) = prompt.display_name.as_deref() {
         let mut candidates = Vec::new();
         candidates.push(format!(
-            "❓ {} (A=once • S=session • D/Esc=deny • B=block • Ctrl+O=inspect){}",
+            "❓ {} (A=once • S=session • F=always • D/Esc=deny • B=block • N=never • Ctrl+O=inspect){}",
             display_name, frame_suffix
         ));
         candidates.push(format!(
-            "❓ {} (A=once • S=session • D=deny • B=block • Ctrl+O=inspect){}",
+            "❓ {} (A=once • S=session • F=always • D=deny • B=block • N=never • Ctrl+O=inspect){}",
             display_name, frame_suffix
         ));
         candidates.push(format!(
-            "❓ {} (A/S/D/B • Ctrl+O){}",
+            "❓ {} (A/S/F/D/B/N • Ctrl+O){}",
             display_name, frame_suffix
         ));
-        candidates.push(format!("❓ {} (A/S/D/B){}", display_name, frame_suffix));
+        candidates.push(format!("❓ {} (A/S/F/D/B/N){}", display_name, frame_suffix));
         candidates.push(format!("❓ {}{}", display_name, frame_suffix));
 
         for candidate in candidates {
@@ -752,27 +817,30 @@ fn tool_prompt_title(prompt: &ToolPrompt, width: u16, frame: &str) -> String {
 
     let mut candidates = Vec::new();
     candidates.push(format!(
-        "❓ Allow {} on {}? (A=once • S=session • D/Esc=deny • B=block • Ctrl+O=inspect){}",
+        "❓ Allow {} on {}? (A=once • S=session • F=always • D/Esc=deny • B=block • N=never • Ctrl+O=inspect){}",
         tool, server, frame_suffix
     ));
     candidates.push(format!(
-        "❓ Allow {} on {}? (A=once • S=session • D=deny • B=block • Ctrl+O=inspect){}",
+        "❓ Allow {} on {}? (A=once • S=session • F=always • D=deny • B=block • N=never • Ctrl+O=inspect){}",
         tool, server, frame_suffix
     ));
     candidates.push(format!(
-        "❓ Allow {} on {}? (A/S/D/B • Ctrl+O){}",
+        "❓ Allow {} on {}? (A/S/F/D/B/N • Ctrl+O){}",
         tool, server, frame_suffix
     ));
     candidates.push(format!(
-        "❓ {} on {}? (A/S/D/B • Ctrl+O){}",
+        "❓ {} on {}? (A/S/F/D/B/N • Ctrl+O){}",
         tool, server, frame_suffix
     ));
-    candidates.push(format!("❓ {}? (A/S/D/B • Ctrl+O){}", tool, frame_suffix));
     candidates.push(format!(
-        "❓ Tool permission (A/S/D/B • Ctrl+O){}",
+        "❓ {}? (A/S/F/D/B/N • Ctrl+O){}",
+        tool, frame_suffix
+    ));
+    candidates.push(format!(
+        "❓ Tool permission (A/S/F/D/B/N • Ctrl+O){}",
         frame_suffix
     ));
-    candidates.push("❓ Tool permission (A/S/D/B • Ctrl+O)".to_string());
+    candidates.push("❓ Tool permission (A/S/F/D/B/N • Ctrl+O)".to_string());
     candidates.push("❓ Tool permission".to_string());
 
     for candidate in candidates {
@@ -846,6 +914,10 @@ fn generate_picker_help_text(app: &App) -> String {
             .preset_picker_state()
             .map(|state| state.search_filter.as_str())
             .unwrap_or(""),
+        Some(crate::core::app::PickerMode::Greeting) => app
+            .greeting_picker_state()
+            .map(|state| state.search_filter.as_str())
+            .unwrap_or(""),
         _ => "",
     };
 
@@ -863,8 +935,10 @@ fn generate_picker_help_text(app: &App) -> String {
     };
 
     // Suppress persistent save option during env-only startup model selection
+    // and for greetings, which only apply to the current chat
     let show_persist = !(app.session.startup_env_only
-        && app.current_picker_mode() == Some(crate::core::app::PickerMode::Model));
+        && app.current_picker_mode() == Some(crate::core::app::PickerMode::Model))
+        && app.current_picker_mode() != Some(crate::core::app::PickerMode::Greeting);
     if show_persist {
         format!("{}\nEnter=This session • Alt+Enter=As default", first_line)
     } else {
@@ -1390,6 +1464,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
         app.session.active_character = Some(card);
@@ -1457,6 +1532,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
         app.session.active_character = Some(card);
@@ -1493,6 +1569,7 @@ mod tests {
                 tags: None,
                 creator: None,
                 character_version: None,
+                ..Default::default()
             },
         };
         app.session.active_character = Some(card);
@@ -582,6 +582,7 @@ mod tests {
             role: TranscriptRole::Assistant,
             content: "1. Step one\n\n   ```python\n   def foo():\n       pass\n   ```\n"
                 .to_string(),
+            id: None,
         };
 
         let theme = Theme::dark_default();
@@ -244,15 +244,16 @@ pub fn build_main_title(app: &App, available_width: u16) -> String {
 
     let logging_variant = FieldVariant::new(format!("Logging: {}", app.get_logging_status()));
 
-    let char_variants = app
-        .session
-        .active_character
-        .as_ref()
-        .map(|character| build_variants("Character: ", character.data.name.as_str()));
-    let preset_variants = app
-        .preset_manager
-        .get_active_preset()
-        .map(|preset| build_variants("Preset: ", preset.id.as_str()));
+    let char_variants = match (&app.session.group, &app.session.active_character) {
+        (Some(group), _) => Some(build_variants("Group: ", &group.names().join(", "))),
+        (None, Some(character)) => {
+            Some(build_variants("Character: ", character.data.name.as_str()))
+        }
+        (None, None) => None,
+    };
+    let preset_ids = app.preset_manager.active_preset_ids();
+    let preset_variants =
+        (!preset_ids.is_empty()).then(|| build_variants("Preset: ", &preset_ids.join(", ")));
     let mcp_variant = mcp_field(app);
 
     let separator_width = UnicodeWidthStr::width(SEPARATOR);
@@ -98,3 +98,25 @@ pub async fn launch_external_editor(
 
     // Temp file will be automatically cleaned up when it goes out of scope
 }
+
+/// Edit text in `$EDITOR` from a plain (non-TUI) terminal.
+///
+/// Returns `Ok(None)` when `$EDITOR` is not set so callers can fall back to
+/// another prompt. Trailing newlines added by the editor are trimmed.
+pub fn edit_text_in_editor(initial_text: &str) -> Result<Option<String>, Box<dyn Error>> {
+    let editor = match std::env::var("EDITOR") {
+        Ok(editor) if !editor.trim().is_empty() => editor,
+        _ => return Ok(None),
+    };
+
+    let temp_file = NamedTempFile::new()?;
+    fs::write(temp_file.path(), initial_text)?;
+
+    let status = Command::new(&editor).arg(temp_file.path()).status()?;
+    if !status.success() {
+        return Err(format!("Editor exited with status: {}", status).into());
+    }
+
+    let content = fs::read_to_string(temp_file.path())?;
+    Ok(Some(content.trim_end().to_string()))
+}
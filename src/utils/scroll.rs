@@ -789,6 +789,7 @@ mod tests {
         messages.push_back(Message {
             role: TranscriptRole::Assistant,
             content: content.into(),
+            id: None,
         });
 
         let lines_wide = ScrollCalculator::build_display_lines_with_theme_and_flags_and_width(
@@ -829,6 +830,7 @@ mod tests {
         messages.push_back(Message {
             role: TranscriptRole::Assistant,
             content: long.into(),
+            id: None,
         });
 
         let width = 20usize;
@@ -956,6 +958,7 @@ mod tests {
         messages.push_back(Message {
             role: TranscriptRole::Assistant,
             content: SAMPLE_HYPERTEXT_PARAGRAPH.into(),
+            id: None,
         });
 
         let layout = crate::ui::layout::LayoutEngine::layout_messages(
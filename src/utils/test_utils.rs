@@ -196,6 +196,7 @@ pub fn create_test_message(role: &str, content: &str) -> Message {
         role: crate::core::message::TranscriptRole::try_from(role.to_string())
             .expect("invalid test role"),
         content: content.to_string(),
+        id: None,
     }
 }
 
@@ -207,6 +208,7 @@ pub fn create_test_message_with_role(
     Message {
         role,
         content: content.to_string(),
+        id: None,
     }
 }
 